@@ -1,11 +1,21 @@
 // AIDEV-NOTE: Audio metadata commands
 // Exposes audio file scanning and metadata operations to the frontend
 
-use tauri::State;
+use serde::Serialize;
+use tauri::{Emitter, State};
 use walkdir::WalkDir;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Mutex;
 
-use crate::libs::{Database, Track, Result, extract_metadata, write_metadata, is_supported_extension};
+use crate::libs::album_order::order_tracks_by_album;
+use crate::libs::dirstate::{classify_file, FileChange, FileDirstate};
+use crate::libs::musicbrainz::{apply_fingerprint_match, identify_track};
+use crate::libs::{
+    CancellationToken, CuePoint, Database, Track, Result, extract_metadata, extract_metadata_multi,
+    extract_metadata_multi_with_cues, write_metadata, write_metadata_ext, is_supported_extension,
+    WriteMetadataOptions,
+};
 
 /// Scan a single audio file and extract metadata
 #[tauri::command]
@@ -74,7 +84,8 @@ pub async fn scan_paths(paths: Vec<String>) -> Result<Vec<String>> {
 }
 
 /// Scan multiple audio files and extract metadata (batch operation)
-/// Returns tracks with metadata, skipping files that fail to parse
+/// Returns tracks with metadata, skipping files that fail to parse.
+/// Files accompanied by a `.cue` sheet expand into one track per CUE entry.
 #[tauri::command]
 pub async fn scan_audio_files_batch(file_paths: Vec<String>) -> Result<Vec<Track>> {
     use rayon::prelude::*;
@@ -82,12 +93,12 @@ pub async fn scan_audio_files_batch(file_paths: Vec<String>) -> Result<Vec<Track
     // Use rayon for parallel processing
     let tracks: Vec<Track> = file_paths
         .par_iter()
-        .filter_map(|path| {
-            match extract_metadata(path) {
-                Ok(track) => Some(track),
+        .flat_map(|path| {
+            match extract_metadata_multi(path) {
+                Ok(tracks) => tracks,
                 Err(e) => {
                     log::warn!("Failed to extract metadata from {}: {}", path, e);
-                    None
+                    Vec::new()
                 }
             }
         })
@@ -102,6 +113,16 @@ pub async fn write_track_metadata(track: Track) -> Result<()> {
     write_metadata(&track.path, &track)
 }
 
+/// Write track metadata with explicit options (e.g. ID3v2.3 for compatibility
+/// with players that don't read v2.4)
+#[tauri::command]
+pub async fn write_track_metadata_with_options(
+    track: Track,
+    options: WriteMetadataOptions,
+) -> Result<()> {
+    write_metadata_ext(&track.path, &track, &options)
+}
+
 /// Write metadata for multiple tracks (batch operation)
 #[tauri::command]
 pub async fn write_tracks_metadata_batch(tracks: Vec<Track>) -> Result<BatchResult> {
@@ -121,22 +142,18 @@ pub async fn write_tracks_metadata_batch(tracks: Vec<Track>) -> Result<BatchResu
     Ok(BatchResult { succeeded, failed })
 }
 
-/// Full library import: scan paths, extract metadata, insert into database
-#[tauri::command]
-pub async fn import_library(
-    db: State<'_, Database>,
-    paths: Vec<String>,
-) -> Result<ImportResult> {
-    use rayon::prelude::*;
-    
-    log::info!("Starting library import for {} paths", paths.len());
-
-    // Step 1: Scan all paths to get audio file list
+/// Walk `paths` (files and/or directories) and return every supported audio
+/// file found, calling `on_file_found` with the running count as each file
+/// is discovered. Shared by [`import_library`] (passes a no-op callback)
+/// and [`import_library_with_progress`] (emits a `library-import-scanning`
+/// event from the callback).
+fn collect_import_files(paths: Vec<String>, mut on_file_found: impl FnMut(usize)) -> Vec<String> {
     let mut all_files = Vec::new();
     for path_str in paths {
         let path = Path::new(&path_str);
         if path.is_file() && is_supported_extension(&path_str) {
             all_files.push(path_str);
+            on_file_found(all_files.len());
         } else if path.is_dir() {
             for entry in WalkDir::new(&path_str)
                 .follow_links(true)
@@ -147,47 +164,493 @@ pub async fn import_library(
                     if let Some(file_path) = entry.path().to_str() {
                         if is_supported_extension(file_path) {
                             all_files.push(file_path.to_string());
+                            on_file_found(all_files.len());
                         }
                     }
                 }
             }
         }
     }
+    all_files
+}
+
+/// Full library import: scan paths, extract metadata, insert into database.
+/// Incremental - unchanged files (same size/mtime as their last import) are
+/// skipped entirely, and tracks for files removed from disk are pruned. See
+/// `libs::dirstate`. Blocks until the whole import finishes; for large
+/// libraries prefer [`import_library_with_progress`], which streams progress
+/// and supports cancellation.
+#[tauri::command]
+pub async fn import_library(
+    db: State<'_, Database>,
+    paths: Vec<String>,
+    enrich: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<ImportResult> {
+    use rayon::prelude::*;
+
+    let dry_run = dry_run.unwrap_or(false);
+
+    log::info!(
+        "Starting library import for {} paths{}",
+        paths.len(),
+        if dry_run { " (dry run)" } else { "" }
+    );
+
+    // Step 1: Scan all paths to get audio file list
+    let all_files = collect_import_files(paths, |_| {});
 
     let total_files = all_files.len();
     log::info!("Found {} audio files to import", total_files);
 
-    // Step 2: Extract metadata in parallel
-    let tracks: Vec<Track> = all_files
+    // Step 2: Classify each file against the dirstate recorded on its last
+    // import, so unchanged files skip metadata extraction entirely - see
+    // `libs::dirstate`.
+    let previous_dirstate = db.get_file_dirstate()?;
+    let classified: Vec<(String, FileChange, FileDirstate)> = all_files
         .par_iter()
         .filter_map(|path| {
-            match extract_metadata(path) {
-                Ok(track) => Some(track),
+            match classify_file(path, previous_dirstate.get(path)) {
+                Ok((change, state)) => Some((path.clone(), change, state)),
                 Err(e) => {
-                    log::warn!("Failed to extract metadata from {}: {}", path, e);
+                    log::warn!("Failed to stat {}: {}", path, e);
                     None
                 }
             }
         })
         .collect();
 
+    let added = classified.iter().filter(|(_, c, _)| *c == FileChange::Added).count();
+    let modified = classified.iter().filter(|(_, c, _)| *c == FileChange::Modified).count();
+    let unchanged = classified.iter().filter(|(_, c, _)| *c == FileChange::Unchanged).count();
+
+    let to_extract: Vec<String> = classified
+        .iter()
+        .filter(|(_, change, _)| *change != FileChange::Unchanged)
+        .map(|(path, _, _)| path.clone())
+        .collect();
+
+    log::info!(
+        "{} added, {} modified, {} unchanged - extracting metadata for {} files",
+        added, modified, unchanged, to_extract.len()
+    );
+
+    // Step 3: Extract metadata in parallel for Added/Modified files only
+    // (CUE sheets expand into multiple tracks per file, plus any cue points
+    // their sheet carried for sub-positions within a track)
+    let per_file_results: Vec<Option<(Vec<Track>, Vec<CuePoint>)>> = to_extract
+        .par_iter()
+        .map(|path| match extract_metadata_multi_with_cues(path) {
+            Ok(result) => Some(result),
+            Err(e) => {
+                log::warn!("Failed to extract metadata from {}: {}", path, e);
+                None
+            }
+        })
+        .collect();
+
+    let failed = per_file_results.iter().filter(|r| r.is_none()).count();
+    let mut tracks: Vec<Track> = Vec::new();
+    let mut cue_points: Vec<CuePoint> = Vec::new();
+    for (file_tracks, file_cues) in per_file_results.into_iter().flatten() {
+        tracks.extend(file_tracks);
+        cue_points.extend(file_cues);
+    }
     let processed = tracks.len();
-    let failed = total_files - processed;
 
-    log::info!("Extracted metadata for {} tracks ({} failed)", processed, failed);
+    // Deterministic album/track ordering (artist -> release date -> track
+    // number) so same-year releases by one artist don't collapse together -
+    // see `libs::album_order`.
+    order_tracks_by_album(&mut tracks);
+
+    log::info!("Extracted metadata for {} tracks ({} files failed)", processed, failed);
 
-    // Step 3: Insert into database
-    db.insert_tracks(&tracks)?;
+    // Step 4: Insert into database (skipped for a dry run - `dry_run` only
+    // previews the added/modified/unchanged/removed counts below).
+    if !dry_run {
+        db.insert_tracks(&tracks)?;
+        db.save_cue_points(&cue_points)?;
+    }
+
+    // Step 5: Persist the new dirstate for every file that was actually
+    // extracted (unchanged files already match what's stored), and prune
+    // tracks for files that disappeared from disk since the last import.
+    let scanned_at = chrono::Utc::now().timestamp_millis();
+    let new_states: Vec<(String, FileDirstate)> = classified
+        .iter()
+        .filter(|(_, change, _)| *change != FileChange::Unchanged)
+        .map(|(path, _, state)| (path.clone(), state.clone()))
+        .collect();
+    if !dry_run {
+        db.save_file_dirstate(&new_states, scanned_at)?;
+    }
 
-    log::info!("Library import complete: {} tracks imported", processed);
+    let current_paths: HashSet<&String> = all_files.iter().collect();
+    let removed_paths: Vec<String> = previous_dirstate
+        .keys()
+        .filter(|path| !current_paths.contains(path))
+        .cloned()
+        .collect();
+    let removed = removed_paths.len();
+
+    if !removed_paths.is_empty() {
+        if !dry_run {
+            let removed_ids: Vec<String> = removed_paths
+                .iter()
+                .map(|path| Track::generate_id(path))
+                .collect();
+            db.delete_tracks(&removed_ids)?;
+            db.delete_file_dirstate(&removed_paths)?;
+        }
+        log::info!("Pruned {} tracks for files no longer on disk", removed);
+    }
+
+    // Step 6: Optional AcoustID fingerprint enrichment, one request per track,
+    // so it's opt-in rather than part of every import. Skipped entirely for a
+    // dry run since it both looks up and persists real matches.
+    let enriched = if enrich.unwrap_or(false) && !dry_run {
+        let matches: Vec<(Track, _)> = tracks
+            .into_par_iter()
+            .filter_map(|track| {
+                let matched = identify_track(&track)
+                    .inspect_err(|e| log::warn!("Fingerprint lookup failed for {}: {}", track.path, e))
+                    .ok()
+                    .flatten()?;
+                Some((track, matched))
+            })
+            .collect();
+
+        let enriched_count = matches.len();
+        for (mut track, matched) in matches {
+            apply_fingerprint_match(&mut track, &matched);
+            if let Err(e) = db.update_track(&track) {
+                log::warn!("Failed to persist fingerprint enrichment for {}: {}", track.path, e);
+            }
+        }
+        enriched_count
+    } else {
+        0
+    };
+
+    log::info!(
+        "Library import {}: {} tracks imported, {} fingerprint-enriched",
+        if dry_run { "dry run complete" } else { "complete" },
+        processed,
+        enriched
+    );
 
     Ok(ImportResult {
         total: total_files,
         processed,
         failed,
+        enriched,
+        added,
+        modified,
+        unchanged,
+        removed,
     })
 }
 
+/// In-flight library-import cancellation tokens, keyed by the `batch_id` the
+/// frontend generated when it started the import. Mirrors
+/// `audio_analysis::BatchAnalysisRegistry`. Removed once the import finishes.
+#[derive(Default)]
+pub struct ImportBatchRegistry(Mutex<HashMap<String, CancellationToken>>);
+
+impl ImportBatchRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Payload for the `library-import-scanning` event, emitted with a running
+/// file count while [`import_library_with_progress`] walks `paths`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ScanningPayload {
+    batch_id: String,
+    files_found: usize,
+}
+
+/// Payload for the `library-import-progress` event, emitted once per file as
+/// its metadata extraction completes.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ExtractProgressPayload {
+    batch_id: String,
+    done: usize,
+    total: usize,
+    path: String,
+}
+
+/// Payload for the terminal `library-import-done` event.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ImportDonePayload {
+    batch_id: String,
+    cancelled: bool,
+    result: ImportResult,
+}
+
+/// Only emit a `library-import-scanning` event every this-many files, so
+/// scanning a huge directory doesn't flood the frontend with IPC events.
+const SCAN_EVENT_INTERVAL: usize = 50;
+
+/// Insert extracted tracks in batches of this size so partial results show
+/// up in the database (and therefore the frontend) as soon as they're
+/// available, instead of waiting for the whole import to finish.
+const IMPORT_INSERT_CHUNK_SIZE: usize = 200;
+
+/// Like [`import_library`], but streams `library-import-scanning` and
+/// `library-import-progress` events to `app_handle` instead of blocking
+/// until the whole import finishes, and inserts extracted tracks in chunks
+/// of [`IMPORT_INSERT_CHUNK_SIZE`] so partial results are visible
+/// immediately. Pass the same `batch_id` to [`cancel_library_import`] to
+/// abort a scan in progress - files still in flight are allowed to finish,
+/// and everything extracted so far is kept.
+#[tauri::command]
+pub async fn import_library_with_progress(
+    app_handle: tauri::AppHandle,
+    db: State<'_, Database>,
+    registry: State<'_, ImportBatchRegistry>,
+    batch_id: String,
+    paths: Vec<String>,
+    enrich: Option<bool>,
+) -> Result<ImportResult> {
+    use rayon::prelude::*;
+
+    log::info!(
+        "Command: import_library_with_progress - batch {} - {} paths",
+        batch_id,
+        paths.len()
+    );
+
+    let cancel = CancellationToken::new();
+    registry
+        .0
+        .lock()
+        .unwrap()
+        .insert(batch_id.clone(), cancel.clone());
+
+    let mut cue_point_chunk: Vec<CuePoint> = Vec::new();
+
+    // Step 1: scan, emitting a running file count as files are discovered.
+    let scan_batch_id = batch_id.clone();
+    let scan_app_handle = app_handle.clone();
+    let all_files = collect_import_files(paths, |files_found| {
+        if files_found % SCAN_EVENT_INTERVAL == 0 {
+            let _ = scan_app_handle.emit(
+                "library-import-scanning",
+                ScanningPayload {
+                    batch_id: scan_batch_id.clone(),
+                    files_found,
+                },
+            );
+        }
+    });
+    let total_files = all_files.len();
+    let _ = app_handle.emit(
+        "library-import-scanning",
+        ScanningPayload {
+            batch_id: batch_id.clone(),
+            files_found: total_files,
+        },
+    );
+
+    // Step 2: classify against dirstate, same as `import_library`.
+    let previous_dirstate = db.get_file_dirstate()?;
+    let classified: Vec<(String, FileChange, FileDirstate)> = all_files
+        .par_iter()
+        .filter_map(|path| match classify_file(path, previous_dirstate.get(path)) {
+            Ok((change, state)) => Some((path.clone(), change, state)),
+            Err(e) => {
+                log::warn!("Failed to stat {}: {}", path, e);
+                None
+            }
+        })
+        .collect();
+
+    let added = classified.iter().filter(|(_, c, _)| *c == FileChange::Added).count();
+    let modified = classified.iter().filter(|(_, c, _)| *c == FileChange::Modified).count();
+    let unchanged = classified.iter().filter(|(_, c, _)| *c == FileChange::Unchanged).count();
+    let state_by_path: HashMap<String, FileDirstate> = classified
+        .iter()
+        .filter(|(_, change, _)| *change != FileChange::Unchanged)
+        .map(|(path, _, state)| (path.clone(), state.clone()))
+        .collect();
+    let to_extract: Vec<String> = state_by_path.keys().cloned().collect();
+    let extract_total = to_extract.len();
+
+    // Step 3: extract metadata in the background, streaming a
+    // `library-import-progress` event and a chunked DB insert as each file
+    // completes, checking `cancel` between items.
+    let (tx, rx) = crossbeam_channel::unbounded();
+    let worker_cancel = cancel.clone();
+    rayon::spawn(move || {
+        to_extract.par_iter().for_each(|path| {
+            if worker_cancel.is_cancelled() {
+                return;
+            }
+            let result = extract_metadata_multi_with_cues(path);
+            let _ = tx.send((path.clone(), result));
+        });
+    });
+
+    let mut done = 0usize;
+    let mut failed = 0usize;
+    let mut all_tracks: Vec<Track> = Vec::new();
+    let mut chunk_buffer: Vec<Track> = Vec::new();
+    let mut extracted_paths: Vec<String> = Vec::new();
+
+    for (path, result) in rx {
+        done += 1;
+        match result {
+            Ok((tracks, cues)) => {
+                all_tracks.extend(tracks.iter().cloned());
+                chunk_buffer.extend(tracks);
+                cue_point_chunk.extend(cues);
+                extracted_paths.push(path.clone());
+            }
+            Err(e) => {
+                log::warn!("Failed to extract metadata from {}: {}", path, e);
+                failed += 1;
+            }
+        }
+
+        let _ = app_handle.emit(
+            "library-import-progress",
+            ExtractProgressPayload {
+                batch_id: batch_id.clone(),
+                done,
+                total: extract_total,
+                path,
+            },
+        );
+
+        if chunk_buffer.len() >= IMPORT_INSERT_CHUNK_SIZE {
+            order_tracks_by_album(&mut chunk_buffer);
+            db.insert_tracks(&chunk_buffer)?;
+            chunk_buffer.clear();
+            db.save_cue_points(&cue_point_chunk)?;
+            cue_point_chunk.clear();
+        }
+
+        if cancel.is_cancelled() {
+            break;
+        }
+    }
+
+    if !chunk_buffer.is_empty() {
+        order_tracks_by_album(&mut chunk_buffer);
+        db.insert_tracks(&chunk_buffer)?;
+    }
+    if !cue_point_chunk.is_empty() {
+        db.save_cue_points(&cue_point_chunk)?;
+    }
+
+    let processed = all_tracks.len();
+    log::info!(
+        "Extracted metadata for {} tracks ({} files failed)",
+        processed, failed
+    );
+
+    // Step 4: persist dirstate only for files actually extracted, so a
+    // cancelled run leaves the rest classified as Added/Modified next time.
+    let scanned_at = chrono::Utc::now().timestamp_millis();
+    let new_states: Vec<(String, FileDirstate)> = extracted_paths
+        .iter()
+        .filter_map(|path| state_by_path.get(path).map(|state| (path.clone(), state.clone())))
+        .collect();
+    db.save_file_dirstate(&new_states, scanned_at)?;
+
+    // Step 5: prune tracks for files that disappeared from disk since the
+    // last import - independent of the extraction pass above, so this still
+    // runs even if the import was cancelled midway.
+    let current_paths: HashSet<&String> = all_files.iter().collect();
+    let removed_paths: Vec<String> = previous_dirstate
+        .keys()
+        .filter(|path| !current_paths.contains(path))
+        .cloned()
+        .collect();
+    let removed = removed_paths.len();
+
+    if !removed_paths.is_empty() {
+        let removed_ids: Vec<String> = removed_paths
+            .iter()
+            .map(|path| Track::generate_id(path))
+            .collect();
+        db.delete_tracks(&removed_ids)?;
+        db.delete_file_dirstate(&removed_paths)?;
+        log::info!("Pruned {} tracks for files no longer on disk", removed);
+    }
+
+    // Step 6: optional AcoustID fingerprint enrichment, same as `import_library`.
+    let enriched = if enrich.unwrap_or(false) && !cancel.is_cancelled() {
+        let matches: Vec<(Track, _)> = all_tracks
+            .into_par_iter()
+            .filter_map(|track| {
+                let matched = identify_track(&track)
+                    .inspect_err(|e| log::warn!("Fingerprint lookup failed for {}: {}", track.path, e))
+                    .ok()
+                    .flatten()?;
+                Some((track, matched))
+            })
+            .collect();
+
+        let enriched_count = matches.len();
+        for (mut track, matched) in matches {
+            apply_fingerprint_match(&mut track, &matched);
+            if let Err(e) = db.update_track(&track) {
+                log::warn!("Failed to persist fingerprint enrichment for {}: {}", track.path, e);
+            }
+        }
+        enriched_count
+    } else {
+        0
+    };
+
+    let cancelled = cancel.is_cancelled();
+    registry.0.lock().unwrap().remove(&batch_id);
+
+    let result = ImportResult {
+        total: total_files,
+        processed,
+        failed,
+        enriched,
+        added,
+        modified,
+        unchanged,
+        removed,
+    };
+
+    let _ = app_handle.emit(
+        "library-import-done",
+        ImportDonePayload {
+            batch_id,
+            cancelled,
+            result: result.clone(),
+        },
+    );
+
+    Ok(result)
+}
+
+/// Abort an in-flight import started by [`import_library_with_progress`]. A
+/// no-op if the import has already finished or never existed.
+#[tauri::command]
+pub async fn cancel_library_import(
+    registry: State<'_, ImportBatchRegistry>,
+    batch_id: String,
+) -> Result<()> {
+    log::info!("Command: cancel_library_import - batch {}", batch_id);
+
+    if let Some(cancel) = registry.0.lock().unwrap().get(&batch_id) {
+        cancel.cancel();
+    }
+
+    Ok(())
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchResult {
@@ -195,10 +658,19 @@ pub struct BatchResult {
     pub failed: usize,
 }
 
-#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImportResult {
     pub total: usize,
     pub processed: usize,
     pub failed: usize,
+    pub enriched: usize,
+    /// Files with no prior dirstate entry.
+    pub added: usize,
+    /// Files whose size or mtime differ from their stored dirstate.
+    pub modified: usize,
+    /// Files skipped entirely because their dirstate matched.
+    pub unchanged: usize,
+    /// Previously-imported files no longer found on disk; their tracks were pruned.
+    pub removed: usize,
 }
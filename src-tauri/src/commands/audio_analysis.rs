@@ -2,9 +2,15 @@
 // Provides BPM detection, key detection, and waveform generation
 // Replaces IPCAudioAnalysisModule from Electron
 
-use crate::libs::{analyze_audio, analyze_audio_batch, AudioAnalysisOptions, AudioAnalysisResult};
+use crate::libs::{
+  analyze_audio, analyze_audio_batch, analyze_audio_batch_streaming, AudioAnalysisOptions,
+  AudioAnalysisResult, CancellationToken,
+};
 use log::info;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{Emitter, State};
 
 /// Result wrapper for batch analysis
 #[derive(Debug, Serialize)]
@@ -63,18 +69,105 @@ pub async fn analyze_audio_batch_command(
   Ok(batch_results)
 }
 
-// AIDEV-TODO: Consider adding progress reporting via Tauri events
-// For long-running batch operations, we could emit progress events:
-// app.emit_all("audio-analysis-progress", { processed: i, total: paths.len() })
-//
-// Example implementation:
-// #[tauri::command]
-// pub async fn analyze_audio_batch_with_progress(
-//   app_handle: tauri::AppHandle,
-//   paths: Vec<String>,
-//   options: Option<AudioAnalysisOptions>,
-// ) -> Result<Vec<BatchAnalysisResult>, String> {
-//   // Emit events during processing
-//   app_handle.emit_all("audio-analysis-progress", ProgressPayload { ... })?;
-//   ...
-// }
+/// In-flight batch cancellation tokens, keyed by the `batch_id` the frontend
+/// generated when it started the scan. Removed once the batch finishes.
+#[derive(Default)]
+pub struct BatchAnalysisRegistry(Mutex<HashMap<String, CancellationToken>>);
+
+impl BatchAnalysisRegistry {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+/// Payload for the `audio-analysis-progress` event, emitted once per file as
+/// it completes.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ProgressPayload {
+  batch_id: String,
+  processed: usize,
+  total: usize,
+  path: String,
+  result: Option<AudioAnalysisResult>,
+  error: Option<String>,
+}
+
+/// Payload for the terminal `audio-analysis-done` event.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DonePayload {
+  batch_id: String,
+  cancelled: bool,
+}
+
+/// Analyze multiple audio files, streaming an `audio-analysis-progress`
+/// event to the frontend as each file completes rather than blocking until
+/// the whole batch finishes, followed by a terminal `audio-analysis-done`.
+/// Pass the same `batch_id` to [`cancel_audio_analysis_batch`] to abort a
+/// scan in progress; files already in flight are allowed to finish.
+#[tauri::command]
+pub async fn analyze_audio_batch_with_progress(
+  app_handle: tauri::AppHandle,
+  registry: State<'_, BatchAnalysisRegistry>,
+  batch_id: String,
+  paths: Vec<String>,
+  options: Option<AudioAnalysisOptions>,
+) -> Result<(), String> {
+  info!(
+    "Command: analyze_audio_batch_with_progress - batch {} - {} files",
+    batch_id,
+    paths.len()
+  );
+
+  let cancel = CancellationToken::new();
+  registry
+    .0
+    .lock()
+    .unwrap()
+    .insert(batch_id.clone(), cancel.clone());
+
+  let progress_batch_id = batch_id.clone();
+  let progress_app_handle = app_handle.clone();
+  analyze_audio_batch_streaming(paths, options, cancel.clone(), move |progress| {
+    let (result, error) = match progress.result {
+      Ok(analysis) => (Some(analysis), None),
+      Err(e) => (None, Some(e.to_string())),
+    };
+
+    let _ = progress_app_handle.emit(
+      "audio-analysis-progress",
+      ProgressPayload {
+        batch_id: progress_batch_id.clone(),
+        processed: progress.processed,
+        total: progress.total,
+        path: progress.path,
+        result,
+        error,
+      },
+    );
+  });
+
+  let cancelled = cancel.is_cancelled();
+  registry.0.lock().unwrap().remove(&batch_id);
+
+  let _ = app_handle.emit("audio-analysis-done", DonePayload { batch_id, cancelled });
+
+  Ok(())
+}
+
+/// Abort an in-flight batch started by [`analyze_audio_batch_with_progress`].
+/// A no-op if the batch has already finished or never existed.
+#[tauri::command]
+pub async fn cancel_audio_analysis_batch(
+  registry: State<'_, BatchAnalysisRegistry>,
+  batch_id: String,
+) -> Result<(), String> {
+  info!("Command: cancel_audio_analysis_batch - batch {}", batch_id);
+
+  if let Some(cancel) = registry.0.lock().unwrap().get(&batch_id) {
+    cancel.cancel();
+  }
+
+  Ok(())
+}
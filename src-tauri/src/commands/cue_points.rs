@@ -2,7 +2,9 @@
 // Exposes cue point CRUD operations to the frontend
 
 use tauri::State;
-use crate::libs::{Database, CuePoint, Result};
+use crate::libs::cue_merge::CueMerge3WayResult;
+use crate::libs::{auto_cue, cue_sheet, AutoCueOptions, Database, CuePoint, Result};
+use std::path::Path;
 
 #[tauri::command]
 pub async fn get_cue_points_for_track(db: State<'_, Database>, track_id: String) -> Result<Vec<CuePoint>> {
@@ -37,3 +39,55 @@ pub async fn replace_cue_points_for_track(
 ) -> Result<()> {
     db.replace_cue_points_for_track(&track_id, &cue_points)
 }
+
+/// Three-way merge `cue_points` (freshly re-imported from an external
+/// source) into `track_id`'s existing cue points instead of the destructive
+/// wipe-and-replace `replace_cue_points_for_track` does. `keep_local_on_conflict`
+/// picks which side wins when a matched slot changed differently on both
+/// sides since the last merge; either way the conflicting slots are returned
+/// so the UI can surface them. See `Database::merge_cue_points_for_track`.
+#[tauri::command]
+pub async fn merge_cue_points_for_track(
+    db: State<'_, Database>,
+    track_id: String,
+    cue_points: Vec<CuePoint>,
+    keep_local_on_conflict: bool,
+) -> Result<CueMerge3WayResult> {
+    db.merge_cue_points_for_track(&track_id, &cue_points, keep_local_on_conflict)
+}
+
+/// Import cue points for `track_id` from a standard `.cue` sheet file.
+///
+/// `include_pregap` controls whether each TRACK's `INDEX 00` is imported as
+/// a `CueType::Load` (true) or skipped (false) - see `cue_sheet::import_cue_sheet`.
+#[tauri::command]
+pub async fn import_cue_sheet(cue_path: String, track_id: String, include_pregap: bool) -> Result<Vec<CuePoint>> {
+    cue_sheet::import_cue_sheet(Path::new(&cue_path), &track_id, include_pregap)
+}
+
+/// Export `cue_points` as a standard `.cue` sheet referencing `audio_filename`,
+/// writing the result to `output_path`. See `cue_sheet::export_cue_sheet`.
+#[tauri::command]
+pub async fn export_cue_sheet(
+    output_path: String,
+    audio_filename: String,
+    cue_points: Vec<CuePoint>,
+) -> Result<()> {
+    let sheet = cue_sheet::export_cue_sheet(&cue_points, &audio_filename);
+    std::fs::write(&output_path, sheet)?;
+    Ok(())
+}
+
+/// Analyze `file_path` and generate beatgrid/structural CuePoints for
+/// `track_id`, ready to pass to `save_cue_points`. `existing_cues` is only
+/// consulted to decide whether to skip grid generation when the track
+/// already has one - see `auto_cue::generate_auto_cues`.
+#[tauri::command]
+pub async fn generate_auto_cues(
+    track_id: String,
+    file_path: String,
+    existing_cues: Vec<CuePoint>,
+    options: Option<AutoCueOptions>,
+) -> Result<Vec<CuePoint>> {
+    auto_cue::generate_auto_cues(&track_id, &file_path, &existing_cues, options)
+}
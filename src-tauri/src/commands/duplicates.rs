@@ -0,0 +1,74 @@
+// AIDEV-NOTE: Duplicate detection Tauri commands
+// Exposes acoustic-fingerprint duplicate scanning to the frontend
+
+use log::info;
+use tauri::State;
+
+use crate::libs::duplicate_detection::{find_duplicate_tracks, DuplicateCluster};
+use crate::libs::fuzzy_duplicates::{criteria_from_labels, criteria_labels, find_fuzzy_duplicate_tracks};
+use crate::libs::{extract_metadata, Database, Result, Track};
+
+/// Scan a set of file paths and return clusters of acoustically duplicate tracks.
+/// Files that fail to decode are skipped rather than aborting the whole batch.
+#[tauri::command]
+pub async fn find_duplicate_tracks_cmd(paths: Vec<String>) -> Result<Vec<DuplicateCluster>> {
+  info!("Command: find_duplicate_tracks_cmd - {} paths", paths.len());
+
+  let tracks = paths
+    .iter()
+    .filter_map(|path| match extract_metadata(path) {
+      Ok(track) => Some(track),
+      Err(e) => {
+        log::warn!("Skipping {} for duplicate scan: {}", path, e);
+        None
+      }
+    })
+    .collect::<Vec<_>>();
+
+  Ok(find_duplicate_tracks(&tracks))
+}
+
+/// Group tracks already in the library that share the same ASCII-folded
+/// `(artist, title)` key - a cheap metadata-only complement to
+/// `find_duplicate_tracks_cmd`'s acoustic fingerprint scan, for catching
+/// re-tagged or re-encoded copies without decoding any audio.
+/// See `Database::find_duplicate_tracks_by_key`.
+#[tauri::command]
+pub async fn find_duplicate_tracks_by_key_cmd(db: State<'_, Database>) -> Result<Vec<Vec<Track>>> {
+  db.find_duplicate_tracks_by_key()
+}
+
+/// A candidate duplicate set from `find_fuzzy_duplicate_tracks_cmd`, with the
+/// criteria that were compared reported as labels rather than a raw bitmask.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuzzyDuplicateGroup {
+  pub tracks: Vec<Track>,
+  pub matched_criteria: Vec<String>,
+}
+
+/// Group tracks already loaded by the caller (e.g. the full library, or
+/// tracks newly imported from another source) by metadata similarity rather
+/// than path or exact key - `criteria` picks which of
+/// `title`/`artist`/`year`/`length`/`bitrate`/`genre` must all match, and
+/// `strip_feat_remix` optionally ignores "(feat. ...)"/"(remix)"-style
+/// parentheticals when comparing title/artist. See
+/// `libs::fuzzy_duplicates::find_fuzzy_duplicate_tracks`.
+#[tauri::command]
+pub async fn find_fuzzy_duplicate_tracks_cmd(
+  tracks: Vec<Track>,
+  criteria: Vec<String>,
+  strip_feat_remix: bool,
+) -> Result<Vec<FuzzyDuplicateGroup>> {
+  let criteria = criteria_from_labels(&criteria);
+
+  Ok(
+    find_fuzzy_duplicate_tracks(&tracks, criteria, strip_feat_remix)
+      .into_iter()
+      .map(|group| FuzzyDuplicateGroup {
+        matched_criteria: criteria_labels(group.matched).into_iter().map(String::from).collect(),
+        tracks: group.tracks,
+      })
+      .collect(),
+  )
+}
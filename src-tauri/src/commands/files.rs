@@ -10,7 +10,8 @@ use std::path::Path;
 use tauri::State;
 
 use crate::libs::{
-  check_library_changes, copy_file, delete_file, fetch_cover, move_file, Database, LibraryChanges, Result, Track,
+  check_library_changes, copy_file, delete_file, fetch_cover, fetch_cover_cached, move_file,
+  CoverCacheOptions, Database, LibraryChanges, Result, Track,
 };
 
 /// Response for batch delete operations
@@ -91,6 +92,42 @@ pub async fn get_track_cover(path: String, ignore_tags: bool) -> Result<Option<S
   fetch_cover(&path, ignore_tags, true)
 }
 
+/// Get cover art for a track, consulting an on-disk cache keyed by
+/// `track_id` + source mtime before re-extracting from tags/directory.
+/// `name_patterns` overrides the default directory-cover filename list with
+/// case-insensitive regexes; `max_dimension` downscales large embedded art
+/// before it's cached.
+#[tauri::command]
+pub async fn get_track_cover_cached(
+  app_handle: tauri::AppHandle,
+  path: String,
+  track_id: String,
+  ignore_tags: bool,
+  name_patterns: Option<Vec<String>>,
+  max_dimension: Option<u32>,
+) -> Result<Option<String>> {
+  use tauri::Manager;
+
+  info!(
+    "Command: get_track_cover_cached({}, track_id={}, ignore_tags={})",
+    path, track_id, ignore_tags
+  );
+
+  let cache_dir = app_handle
+    .path()
+    .app_cache_dir()
+    .map_err(|e| crate::libs::HarmonyError::Custom(format!("No app cache directory: {}", e)))?
+    .join("covers");
+
+  let options = CoverCacheOptions {
+    cache_dir,
+    name_patterns,
+    max_dimension,
+  };
+
+  fetch_cover_cached(&path, &track_id, ignore_tags, true, &options)
+}
+
 /// Get cover from file path (convert image file to base64)
 #[tauri::command]
 pub async fn get_cover_from_file(path: String) -> Result<String> {
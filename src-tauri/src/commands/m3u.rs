@@ -0,0 +1,61 @@
+// AIDEV-NOTE: M3U/M3U8 playlist import/export commands
+// Counterpart to commands::rekordbox/commands::serato, for the plain M3U
+// interchange format - see libs::m3u for the format logic itself.
+
+use std::collections::HashMap;
+use tauri::State;
+
+use crate::libs::m3u::{export_m3u, parse_m3u, resolve_m3u_entries};
+use crate::libs::{Database, Result};
+
+/// Export `track_ids` (in order) as an M3U8 playlist file at `output_path`.
+#[tauri::command]
+pub async fn export_m3u_playlist(
+    db: State<'_, Database>,
+    track_ids: Vec<String>,
+    output_path: String,
+) -> Result<()> {
+    let tracks = track_ids
+        .iter()
+        .filter_map(|id| db.get_track_by_id(id).ok().flatten())
+        .collect::<Vec<_>>();
+
+    let m3u = export_m3u(&tracks);
+    std::fs::write(&output_path, m3u)?;
+    Ok(())
+}
+
+/// Result of importing an M3U/M3U8 playlist file.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportM3uResult {
+    pub track_ids: Vec<String>,
+    pub unmatched_paths: Vec<String>,
+}
+
+/// Import an M3U/M3U8 playlist file, matching each entry to an existing
+/// track by path. `rename_map` (old path -> new path) lets callers resolve
+/// entries whose file has moved since the playlist was written - e.g. from
+/// `check_library_changes_cmd`.
+#[tauri::command]
+pub async fn import_m3u_playlist(
+    db: State<'_, Database>,
+    input_path: String,
+    rename_map: HashMap<String, String>,
+) -> Result<ImportM3uResult> {
+    let content = std::fs::read_to_string(&input_path)?;
+    let entries = parse_m3u(&content);
+
+    let tracks_by_path: HashMap<String, String> = db
+        .get_all_tracks()?
+        .into_iter()
+        .map(|t| (t.path, t.id))
+        .collect();
+
+    let resolution = resolve_m3u_entries(&entries, &tracks_by_path, &rename_map);
+
+    Ok(ImportM3uResult {
+        track_ids: resolution.track_ids,
+        unmatched_paths: resolution.unmatched_paths,
+    })
+}
@@ -4,9 +4,15 @@
 pub mod audio;
 pub mod audio_analysis;
 pub mod cue_points;
+pub mod duplicates;
 pub mod files;
 pub mod folders;
+pub mod m3u;
+pub mod musicbrainz;
 pub mod playlists;
+pub mod rekordbox;
+pub mod serato;
+pub mod similarity;
 pub mod tracks;
 pub mod traktor;
 
@@ -14,8 +20,14 @@ pub mod traktor;
 pub use audio::*;
 pub use audio_analysis::*;
 pub use cue_points::*;
+pub use duplicates::*;
 pub use files::*;
 pub use folders::*;
+pub use m3u::*;
+pub use musicbrainz::*;
 pub use playlists::*;
+pub use rekordbox::*;
+pub use serato::*;
+pub use similarity::*;
 pub use tracks::*;
 pub use traktor::*;
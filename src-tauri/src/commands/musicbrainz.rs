@@ -0,0 +1,100 @@
+// AIDEV-NOTE: MusicBrainz enrichment command for Tauri
+// Looks up candidate metadata for tracks with missing/low-confidence fields
+// via `libs::musicbrainz`, leaving the caller free to apply the result
+// through the same `MergeStrategy` machinery used by Traktor sync.
+
+use crate::libs::musicbrainz::{
+  apply_fingerprint_match, enrich_tracks as enrich_tracks_lib, identify_track, TrackEnrichment,
+};
+use crate::libs::{Database, HarmonyError, Result, Track};
+use log::{info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+/// Progress event payload for enrichment, mirroring `SyncProgress` from
+/// `commands::traktor` but reported per-artist rather than per-track since
+/// that's the unit of work `libs::musicbrainz` batches requests by.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrichProgress {
+  pub progress: f64,
+  pub current: usize,
+  pub total: usize,
+  pub message: String,
+}
+
+/// Enrich tracks missing metadata (artist, album, year, catalog number,
+/// ISRC) with candidate matches from MusicBrainz.
+///
+/// When `track_ids` is `None`, every track in the library is considered
+/// (tracks that already have all enrichable fields populated are skipped
+/// internally). Emits `"musicbrainz-enrich-progress"` once per distinct
+/// artist processed.
+#[tauri::command]
+pub async fn enrich_tracks(
+  app: AppHandle,
+  db: State<'_, Database>,
+  track_ids: Option<Vec<String>>,
+) -> Result<Vec<TrackEnrichment>> {
+  let tracks: Vec<Track> = match track_ids {
+    Some(ids) => ids
+      .iter()
+      .filter_map(|id| db.get_track_by_id(id).ok().flatten())
+      .collect(),
+    None => db.get_all_tracks()?,
+  };
+
+  info!("Command: enrich_tracks - {} track(s) in scope", tracks.len());
+
+  let results = enrich_tracks_lib(&tracks, |current, total, artist| {
+    let payload = EnrichProgress {
+      progress: if total == 0 {
+        100.0
+      } else {
+        (current as f64 / total as f64) * 100.0
+      },
+      current,
+      total,
+      message: format!("Looked up '{}' on MusicBrainz", artist),
+    };
+
+    if let Err(e) = app.emit("musicbrainz-enrich-progress", &payload) {
+      warn!("Failed to emit enrichment progress event: {}", e);
+    }
+  })?;
+
+  info!(
+    "MusicBrainz enrichment found candidates for {}/{} track(s)",
+    results.len(),
+    tracks.len()
+  );
+
+  Ok(results)
+}
+
+/// Enrich a single track via AcoustID fingerprint + MusicBrainz recording
+/// lookup, a more precise (but one-request-per-track) alternative to the
+/// artist/title search [`enrich_tracks`] does in bulk. Persists the merged
+/// track and returns it, or `Ok(None)` if no confident fingerprint match was
+/// found.
+#[tauri::command]
+pub async fn enrich_track_metadata(
+  db: State<'_, Database>,
+  track_id: String,
+) -> Result<Option<Track>> {
+  info!("Command: enrich_track_metadata - track {}", track_id);
+
+  let mut track = db
+    .get_track_by_id(&track_id)?
+    .ok_or_else(|| HarmonyError::Custom(format!("Track not found: {}", track_id)))?;
+
+  let Some(matched) = identify_track(&track)? else {
+    info!("No confident AcoustID match for track {}", track_id);
+    return Ok(None);
+  };
+
+  apply_fingerprint_match(&mut track, &matched);
+  db.update_track(&track)?;
+
+  Ok(Some(track))
+}
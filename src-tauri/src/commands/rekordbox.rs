@@ -0,0 +1,105 @@
+// AIDEV-NOTE: Rekordbox collection.xml import/export commands
+// Counterpart to commands::traktor's NML import/export, for the Rekordbox
+// DJ ecosystem.
+
+use log::info;
+use std::collections::HashMap;
+use std::fs;
+use tauri::State;
+
+use crate::libs::cue_point::CuePoint;
+use crate::libs::playlist_tree::insert_playlist_into_tree;
+use crate::libs::rekordbox::{export_rekordbox_xml, parse_rekordbox_xml};
+use crate::libs::{Database, FolderTreeNode, Result};
+
+/// Export the Harmony library to a Rekordbox `collection.xml` file.
+#[tauri::command]
+pub async fn export_rekordbox_collection(
+  db: State<'_, Database>,
+  output_path: String,
+) -> Result<usize> {
+  info!("Command: export_rekordbox_collection - output: {}", output_path);
+
+  let tracks = db.get_all_tracks()?;
+
+  let mut tree = FolderTreeNode::folder("ROOT");
+  for meta in db.get_all_playlists()? {
+    let Some(playlist) = db.get_playlist_by_id(&meta.id)? else {
+      continue;
+    };
+    let track_paths: Vec<String> = playlist.tracks.iter().map(|t| t.path.clone()).collect();
+    insert_playlist_into_tree(
+      &mut tree,
+      crate::libs::ImportedPlaylist {
+        id: playlist.id,
+        name: playlist.name,
+        track_paths,
+        folder_path: playlist.folder_id,
+      },
+    );
+  }
+
+  let track_ids: Vec<String> = tracks.iter().map(|t| t.id.clone()).collect();
+  let cue_points = db.get_cue_points_for_tracks(&track_ids)?;
+  let mut cues_by_track_id: HashMap<String, Vec<CuePoint>> = HashMap::new();
+  for cue in cue_points {
+    cues_by_track_id.entry(cue.track_id.clone()).or_default().push(cue);
+  }
+
+  let xml = export_rekordbox_xml(&tracks, &tree, &cues_by_track_id)?;
+  fs::write(&output_path, xml)?;
+
+  info!("Exported {} tracks to Rekordbox collection.xml", tracks.len());
+  Ok(tracks.len())
+}
+
+/// Result of importing a Rekordbox `collection.xml` file.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRekordboxResult {
+  pub tracks_imported: usize,
+  pub playlists_imported: usize,
+  pub cue_points_imported: usize,
+}
+
+/// Import a Rekordbox `collection.xml` file into the Harmony library.
+#[tauri::command]
+pub async fn import_rekordbox_collection(
+  db: State<'_, Database>,
+  input_path: String,
+) -> Result<ImportRekordboxResult> {
+  info!("Command: import_rekordbox_collection - input: {}", input_path);
+
+  let xml = fs::read_to_string(&input_path)?;
+  let (tracks, tree, cues_by_track_id) = parse_rekordbox_xml(&xml)?;
+
+  db.insert_tracks(&tracks)?;
+
+  let all_cues: Vec<CuePoint> = cues_by_track_id.into_values().flatten().collect();
+  db.save_cue_points(&all_cues)?;
+
+  let playlists = crate::libs::playlist_tree::flatten_playlist_tree(&tree, None);
+  for imported in &playlists {
+    let playlist = crate::libs::playlist_tree::convert_to_harmony_playlist(imported);
+    db.create_playlist(&playlist)?;
+    let track_ids: Vec<String> = imported
+      .track_paths
+      .iter()
+      .map(|path| crate::libs::Track::generate_id(path))
+      .collect();
+    db.set_playlist_tracks(&playlist.id, &track_ids)?;
+  }
+
+  info!(
+    "Imported {} tracks, {} cue points, and {} playlists from Rekordbox collection.xml",
+    tracks.len(),
+    all_cues.len(),
+    playlists.len()
+  );
+
+  Ok(ImportRekordboxResult {
+    tracks_imported: tracks.len(),
+    playlists_imported: playlists.len(),
+    cue_points_imported: all_cues.len(),
+  })
+}
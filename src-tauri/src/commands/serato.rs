@@ -0,0 +1,126 @@
+// AIDEV-NOTE: Serato crate import/export commands
+// Counterpart to commands::traktor/commands::rekordbox, for the Serato DJ
+// ecosystem. Unlike those single-file formats, Serato keeps one `.crate`
+// file per playlist under a `_Serato_/Subcrates` folder, so these commands
+// take a directory rather than a file path.
+
+use log::info;
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+use crate::libs::playlist_tree::insert_playlist_into_tree;
+use crate::libs::serato::{build_serato_crates, parse_serato_crate};
+use crate::libs::{Database, FolderTreeNode, HarmonyError, Result};
+
+/// Export every Harmony playlist to its own Serato `.crate` file under
+/// `output_dir`.
+#[tauri::command]
+pub async fn export_serato_crates(
+  db: State<'_, Database>,
+  output_dir: String,
+) -> Result<usize> {
+  info!("Command: export_serato_crates - output dir: {}", output_dir);
+
+  let mut tree = FolderTreeNode::folder("ROOT");
+  for meta in db.get_all_playlists()? {
+    let Some(playlist) = db.get_playlist_by_id(&meta.id)? else {
+      continue;
+    };
+    let track_paths: Vec<String> = playlist.tracks.iter().map(|t| t.path.clone()).collect();
+    insert_playlist_into_tree(
+      &mut tree,
+      crate::libs::ImportedPlaylist {
+        id: playlist.id,
+        name: playlist.name,
+        track_paths,
+        folder_path: playlist.folder_id,
+      },
+    );
+  }
+
+  let crates = build_serato_crates(&tree);
+  fs::create_dir_all(&output_dir)?;
+  for (crate_name, bytes) in &crates {
+    let file_path = Path::new(&output_dir).join(format!("{}.crate", crate_name));
+    fs::write(file_path, bytes)?;
+  }
+
+  info!("Exported {} Serato crates to {}", crates.len(), output_dir);
+  Ok(crates.len())
+}
+
+/// Result of importing a directory of Serato `.crate` files.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSeratoResult {
+  pub playlists_imported: usize,
+  pub tracks_referenced: usize,
+}
+
+/// Import every `.crate` file in `input_dir` as a Harmony playlist.
+///
+/// AIDEV-NOTE: Unlike Traktor/Rekordbox import, this only links existing
+/// tracks (matched by path) - Serato crates don't carry track metadata, so
+/// there's nothing to insert into the `track` table.
+#[tauri::command]
+pub async fn import_serato_crates(
+  db: State<'_, Database>,
+  input_dir: String,
+) -> Result<ImportSeratoResult> {
+  info!("Command: import_serato_crates - input dir: {}", input_dir);
+
+  let mut tracks_referenced = 0;
+  let mut playlists_imported = 0;
+
+  for entry in fs::read_dir(&input_dir)? {
+    let entry = entry?;
+    let path = entry.path();
+    if path.extension().and_then(|e| e.to_str()) != Some("crate") {
+      continue;
+    }
+
+    let crate_name = path
+      .file_stem()
+      .and_then(|s| s.to_str())
+      .ok_or_else(|| HarmonyError::Custom(format!("Invalid crate filename: {:?}", path)))?
+      .to_string();
+
+    let bytes = fs::read(&path)?;
+    let track_paths = parse_serato_crate(&bytes)?;
+
+    let mut segments: Vec<&str> = crate_name.split("%%").collect();
+    let name = segments.pop().unwrap_or(&crate_name).to_string();
+    let folder_path = if segments.is_empty() {
+      None
+    } else {
+      Some(format!("/ROOT/{}", segments.join("/")))
+    };
+
+    let playlist_id = format!("serato-{}", crate_name);
+    db.create_playlist(&crate::libs::Playlist {
+      id: playlist_id.clone(),
+      name,
+      folder_id: folder_path,
+      tracks: vec![],
+    })?;
+
+    let track_ids: Vec<String> = track_paths
+      .iter()
+      .map(|path| crate::libs::Track::generate_id(path))
+      .collect();
+    tracks_referenced += track_ids.len();
+    db.set_playlist_tracks(&playlist_id, &track_ids)?;
+    playlists_imported += 1;
+  }
+
+  info!(
+    "Imported {} Serato crates referencing {} tracks",
+    playlists_imported, tracks_referenced
+  );
+
+  Ok(ImportSeratoResult {
+    playlists_imported,
+    tracks_referenced,
+  })
+}
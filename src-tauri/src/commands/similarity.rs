@@ -0,0 +1,110 @@
+// AIDEV-NOTE: Acoustic-similarity commands
+// Exposes per-track feature analysis and "sounds-like" playlist generation
+// to the frontend, alongside the tag-based commands in `commands::audio`.
+
+use log::{info, warn};
+use tauri::State;
+
+use crate::libs::similarity::{self, FeatureVector};
+use crate::libs::{Database, Result};
+
+/// Analyze a batch of tracks' audio signal (tempo, spectral centroid,
+/// zero-crossing rate, chroma) into normalized feature vectors and persist
+/// them keyed by path. Runs under the same rayon parallel pipeline as
+/// `scan_audio_files_batch`, skipping/logging files that fail to decode.
+#[tauri::command]
+pub async fn analyze_track_similarity_batch(
+  db: State<'_, Database>,
+  file_paths: Vec<String>,
+) -> Result<SimilarityAnalysisResult> {
+  use rayon::prelude::*;
+
+  info!("Command: analyze_track_similarity_batch - {} files", file_paths.len());
+
+  let mut computed: Vec<(String, FeatureVector)> = file_paths
+    .par_iter()
+    .filter_map(|path| match similarity::compute_feature_vector(path) {
+      Ok(vector) => Some((path.clone(), vector)),
+      Err(e) => {
+        warn!("Skipping {} for similarity analysis: {}", path, e);
+        None
+      }
+    })
+    .collect();
+
+  let failed = file_paths.len() - computed.len();
+
+  let mut vectors: Vec<FeatureVector> = computed.iter().map(|(_, vector)| *vector).collect();
+  similarity::normalize_feature_vectors(&mut vectors);
+  for ((_, slot), normalized) in computed.iter_mut().zip(vectors.iter()) {
+    *slot = *normalized;
+  }
+
+  let analyzed = computed.len();
+  let updated_at = chrono::Utc::now().timestamp_millis();
+  db.save_feature_vectors(&computed, updated_at)?;
+
+  info!("Similarity analysis complete: {} analyzed, {} failed", analyzed, failed);
+
+  Ok(SimilarityAnalysisResult { analyzed, failed })
+}
+
+/// Build a "sounds-like" playlist starting from `seed_path`, greedily
+/// walking to the nearest un-picked track each step so the playlist drifts
+/// smoothly instead of jumping around the whole library.
+#[tauri::command]
+pub async fn generate_similar_playlist(
+  db: State<'_, Database>,
+  seed_path: String,
+  len: usize,
+) -> Result<Vec<String>> {
+  info!("Command: generate_similar_playlist - seed: {}, len: {}", seed_path, len);
+
+  let vectors = db.get_all_feature_vectors()?;
+  Ok(similarity::generate_similar_playlist(&seed_path, &vectors, len))
+}
+
+/// Rank a specific set of candidate tracks by similarity to `seed_path` and
+/// return the `count` closest, nearest-first - for "more like this track"
+/// within one playlist/crate rather than a library-wide walk (see
+/// `generate_similar_playlist` for that).
+#[tauri::command]
+pub async fn build_similarity_playlist(
+  db: State<'_, Database>,
+  seed_path: String,
+  candidate_paths: Vec<String>,
+  count: usize,
+) -> Result<Vec<String>> {
+  info!(
+    "Command: build_similarity_playlist - seed: {}, {} candidates, count {}",
+    seed_path,
+    candidate_paths.len(),
+    count
+  );
+
+  let vectors = db.get_all_feature_vectors()?;
+  Ok(similarity::build_similarity_playlist(&seed_path, &candidate_paths, &vectors, count))
+}
+
+/// Remove near-duplicate consecutive tracks from an ordered path list: any
+/// track whose distance to the previously kept track falls below
+/// `threshold` is dropped.
+#[tauri::command]
+pub async fn dedup_playlist(
+  db: State<'_, Database>,
+  track_paths: Vec<String>,
+  threshold: f64,
+) -> Result<Vec<String>> {
+  info!("Command: dedup_playlist - {} tracks, threshold {}", track_paths.len(), threshold);
+
+  let vectors = db.get_all_feature_vectors()?;
+  Ok(similarity::dedup_playlist(&track_paths, &vectors, threshold))
+}
+
+/// Result of a similarity analysis batch.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarityAnalysisResult {
+  pub analyzed: usize,
+  pub failed: usize,
+}
@@ -2,17 +2,31 @@
 // Provides Traktor NML parsing and synchronization
 // Replaces IPCTraktorModule from Electron
 
+use crate::libs::field_clock::{FieldStamp, SourcePriority};
+use crate::libs::playlist::Playlist;
 use crate::libs::traktor::{
-  conflict_resolver::{merge_cue_points, merge_track, CueMergeStrategy, MergeStrategy},
-  cue_mapper::map_traktor_cues_to_harmony,
-  map_traktor_entry_to_track,
-  playlist_sync::{convert_to_harmony_playlist, extract_playlists_from_traktor},
-  TraktorNMLParser,
+  conflict_resolver::{
+    expand_strategy_to_policy, merge_cue_points, merge_track_with_policy, CueMergeStrategy,
+    FieldMergePolicy, MergeStrategy,
+  },
+  cue_mapper::{map_harmony_cues_to_traktor, map_traktor_cues_to_harmony},
+  compute_entry_content_hash, map_traktor_entry_to_track, map_traktor_modified_at,
+  mapper::map_track_to_traktor_entry,
+  merge_nml,
+  nml_types::{
+    TraktorCollection, TraktorEntry, TraktorHead, TraktorNML, TraktorPlaylists, NML,
+  },
+  match_tracks, MergeConflict,
+  playlist_sync::{
+    build_traktor_playlists_node, convert_to_harmony_playlist, extract_playlists_from_traktor,
+    map_path_to_traktor_playlist_key,
+  },
+  TraktorNMLParser, TraktorNMLWriter, DEFAULT_MATCH_THRESHOLD,
 };
-use crate::libs::{Database, Result, Track};
-use log::{debug, info, warn};
+use crate::libs::{Database, LibraryStore, Result, Track, TraktorSyncWrite};
+use log::{info, warn};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tauri::{AppHandle, Emitter, State};
 
 /// Result of parsing a Traktor NML file
@@ -45,12 +59,20 @@ pub struct EnhancedSyncStats {
   pub tracks_processed: usize,
   /// Tracks matched between Traktor and Harmony
   pub tracks_matched: usize,
+  /// Of `tracks_matched`, the subset matched by fuzzy title/artist/duration
+  /// scoring (see `track_matcher::match_tracks`) rather than exact path
+  /// equality - i.e. tracks whose file moved since the last sync
+  pub tracks_fuzzy_matched: usize,
   /// Tracks imported from Traktor (new)
   pub tracks_imported: usize,
   /// Tracks updated (metadata changed)
   pub tracks_updated: usize,
   /// Tracks skipped (no changes)
   pub tracks_skipped: usize,
+  /// Tracks whose content hash matched the last sync, so DELTA mode skipped
+  /// merging them entirely (not counted in `tracks_matched`/`tracks_skipped`,
+  /// which only cover entries that were actually merged)
+  pub tracks_skipped_unchanged: usize,
   /// Count of each field that was updated
   pub fields_updated: HashMap<String, usize>,
   /// Total cue points added/updated
@@ -77,6 +99,20 @@ pub struct SyncProgress {
   pub message: String,
 }
 
+/// Statistics for a Harmony -> Traktor NML export
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportNMLStats {
+  /// Total tracks written to the exported NML
+  pub tracks_exported: usize,
+  /// Tracks matched against the source NML (their unmodeled fields were preserved)
+  pub tracks_matched_to_source: usize,
+  /// Total cue points written
+  pub cue_points_exported: usize,
+  /// Playlists written
+  pub playlists_exported: usize,
+}
+
 /// Parse a Traktor NML file and return tracks
 #[tauri::command]
 pub async fn parse_traktor_nml(nml_path: String) -> Result<ParseNMLResult> {
@@ -104,12 +140,11 @@ pub async fn parse_traktor_nml(nml_path: String) -> Result<ParseNMLResult> {
 /// Sync Traktor NML tracks with Harmony database (ENHANCED - Phase 4.5)
 ///
 /// This implements advanced sync with merge strategies, cue points, and playlists:
-/// 1. Parse Traktor NML file (tracks, cue points, playlists)
-/// 2. Match tracks by file path
-/// 3. Merge track metadata using specified strategy
-/// 4. Sync cue points for each track
-/// 5. Import playlists with folder hierarchy
-/// 6. Emit progress events during sync
+/// 1. Load existing Harmony tracks for path matching
+/// 2. Stream `<ENTRY>` elements from the NML and merge each against Harmony
+///    as it arrives, flushing writes to the database in `batch_size` chunks
+/// 3. Sync playlists with folder hierarchy
+/// 4. Emit progress events during sync
 ///
 /// AIDEV-NOTE: Enhanced version with full feature parity to Electron
 /// - Supports SMART_MERGE, TRAKTOR_WINS, HARMONY_WINS strategies
@@ -117,13 +152,42 @@ pub async fn parse_traktor_nml(nml_path: String) -> Result<ParseNMLResult> {
 /// - Imports playlists with folder hierarchy preservation
 /// - Emits progress events for UI updates
 ///
+/// AIDEV-NOTE: A Traktor entry that doesn't resolve by exact path falls back
+/// to `track_matcher::match_tracks` against whichever Harmony tracks this
+/// sync hasn't already claimed, so a file moved/renamed on disk since the
+/// last sync still merges into its existing Harmony track instead of being
+/// re-imported as a duplicate (`stats.tracks_fuzzy_matched` counts these).
+///
+/// AIDEV-NOTE: This used to fully materialize `nml.nml.collection.entry`
+/// (every `TraktorEntry`) plus several per-track `HashMap`s, and issued one
+/// `update_track`/`save_field_clock`/`replace_cue_points_for_track` call per
+/// matched track - fine for small collections, but ~150k individual commits
+/// on a 50k-track library. It now pulls entries via
+/// `TraktorNMLParser::stream_entries` (bounded memory regardless of
+/// collection size) and flushes merged writes through
+/// `Database::apply_traktor_sync_batch` in `batch_size` chunks (one
+/// transaction per chunk). Because writes are batched, the per-track
+/// Harmony-origin re-diff that `update_track` used to layer on top of the
+/// merge's own clock is no longer applied here - the CRDT merge clock
+/// (`merge_result.merged_clock`) is the single source of truth for what
+/// changed and why.
+///
 /// # Arguments
 /// * `app` - Tauri app handle for emitting progress events
 /// * `db` - Database state
 /// * `nml_path` - Path to Traktor collection.nml file
 /// * `strategy` - Merge strategy: "SMART_MERGE" (default), "TRAKTOR_WINS", "HARMONY_WINS"
-/// * `cue_strategy` - Cue merge strategy: "SMART_MERGE" (default), "REPLACE"
+/// * `field_policy` - Optional per-field override of `strategy`, keyed by
+///   [`Track`] field name (e.g. `{"bpm": "TRAKTOR_WINS", "rating":
+///   "HARMONY_WINS"}`) with the same strategy strings as `strategy`. Fields
+///   absent from the map fall back to `strategy`. See
+///   [`crate::libs::traktor::conflict_resolver::FieldMergePolicy`].
+/// * `cue_strategy` - Cue merge strategy: "SMART_MERGE" (default), "REPLACE", "COMBINE"
 /// * `sync_playlists` - Whether to import playlists (default: true)
+/// * `batch_size` - Tracks per transaction when flushing writes (default: 500)
+/// * `mode` - "FULL" (default) merges every entry; "DELTA" skips merging
+///   entries whose content hash matches the last sync (see
+///   `compute_entry_content_hash`, `traktorSyncHash` table)
 ///
 /// # Returns
 /// EnhancedSyncStats with detailed sync results
@@ -133,10 +197,17 @@ pub async fn sync_traktor_nml(
   db: State<'_, Database>,
   nml_path: String,
   strategy: Option<String>,
+  field_policy: Option<HashMap<String, String>>,
   cue_strategy: Option<String>,
   sync_playlists: Option<bool>,
+  batch_size: Option<usize>,
+  mode: Option<String>,
 ) -> Result<EnhancedSyncStats> {
   info!("Command: sync_traktor_nml - path: {}", nml_path);
+  // Routed through `LibraryStore` rather than the concrete `Database` from
+  // here on, so this whole sync algorithm can be exercised in tests against
+  // an in-memory store (see `libs::store`) instead of a real DB file.
+  let db: &dyn LibraryStore = db.inner();
   info!(
     "  Strategy: {}, Cue: {}, Playlists: {}",
     strategy.as_ref().unwrap_or(&"SMART_MERGE".to_string()),
@@ -155,148 +226,154 @@ pub async fn sync_traktor_nml(
 
   let cue_merge_strategy = match cue_strategy.as_deref() {
     Some("REPLACE") => CueMergeStrategy::Replace,
+    Some("COMBINE") => CueMergeStrategy::Combine,
     _ => CueMergeStrategy::SmartMerge,
   };
 
-  let should_sync_playlists = sync_playlists.unwrap_or(true);
-
-  // ═══════════════════════════════════════════════════════════════════════════
-  // Phase 1: Parse NML file
-  // ═══════════════════════════════════════════════════════════════════════════
-  emit_progress(
-    &app,
-    "Parsing NML",
-    0.0,
-    0,
-    1,
-    "Reading Traktor collection.nml...",
-  );
-
-  let parser = TraktorNMLParser::new();
-  let nml = parser.parse(&nml_path)?;
+  // Per-field overrides of `merge_strategy`, e.g. "BPM: TraktorWins, Rating:
+  // HarmonyWins, everything else: SmartMerge" - fields the caller doesn't
+  // override fall back to the global strategy above.
+  let merge_policy: FieldMergePolicy = {
+    let mut policy = expand_strategy_to_policy(merge_strategy);
+    for (field, field_strategy_str) in field_policy.into_iter().flatten() {
+      let field_strategy = match field_strategy_str.as_str() {
+        "TRAKTOR_WINS" => MergeStrategy::TraktorWins,
+        "HARMONY_WINS" => MergeStrategy::HarmonyWins,
+        _ => MergeStrategy::SmartMerge,
+      };
+      policy.insert(field, field_strategy);
+    }
+    policy
+  };
 
-  info!("Parsed {} tracks from Traktor NML", nml.nml.collection.entry.len());
+  let should_sync_playlists = sync_playlists.unwrap_or(true);
+  let batch_size = batch_size.unwrap_or(500).max(1);
+  let is_delta = matches!(mode.as_deref(), Some("DELTA"));
+  info!("  Batch size: {}, Mode: {}", batch_size, if is_delta { "DELTA" } else { "FULL" });
 
   // ═══════════════════════════════════════════════════════════════════════════
-  // Phase 2: Convert Traktor entries to Harmony tracks and extract cue points
+  // Phase 1: Load existing Harmony tracks for path matching
   // ═══════════════════════════════════════════════════════════════════════════
-  emit_progress(
-    &app,
-    "Processing Tracks",
-    5.0,
-    0,
-    nml.nml.collection.entry.len(),
-    "Converting Traktor data...",
-  );
-
-  let mut traktor_tracks: Vec<Track> = Vec::new();
-  let mut traktor_cues_by_path: HashMap<String, Vec<crate::libs::cue_point::CuePoint>> =
-    HashMap::new();
+  emit_progress(&app, "Loading Tracks", 0.0, 0, 1, "Loading Harmony library...");
 
-  for (idx, entry) in nml.nml.collection.entry.iter().enumerate() {
-    let track = map_traktor_entry_to_track(entry);
-
-    // Extract cue points for this track (cue_v2 is a Vec, not Option<Vec>)
-    let cue_points = if !entry.cue_v2.is_empty() {
-      map_traktor_cues_to_harmony(Some(&entry.cue_v2), &track.id)
-    } else {
-      Vec::new()
-    };
-
-    if !cue_points.is_empty() {
-      traktor_cues_by_path.insert(track.path.clone(), cue_points);
-    }
+  let existing_tracks = db.get_all_tracks()?;
+  let existing_by_path: HashMap<String, Track> =
+    existing_tracks.iter().map(|t| (t.path.clone(), t.clone())).collect();
 
-    traktor_tracks.push(track);
+  // Harmony tracks already claimed this sync (by path or by fuzzy match),
+  // so a Traktor entry never merges into the same Harmony track twice.
+  let mut matched_existing_ids: HashSet<String> = HashSet::new();
 
-    if idx % 100 == 0 {
-      emit_progress(
-        &app,
-        "Processing Tracks",
-        5.0 + (idx as f64 / nml.nml.collection.entry.len() as f64) * 15.0,
-        idx,
-        nml.nml.collection.entry.len(),
-        format!("Processed {}/{} tracks", idx, nml.nml.collection.entry.len()),
-      );
-    }
-  }
+  info!("Matching against {} existing Harmony tracks", existing_by_path.len());
 
-  debug!(
-    "Extracted {} cue points from {} tracks",
-    traktor_cues_by_path.values().map(|v| v.len()).sum::<usize>(),
-    traktor_cues_by_path.len()
-  );
+  // DELTA mode: load the hash recorded for each path at the end of the
+  // previous sync, so unchanged entries can skip merge below. Also tracks
+  // which of those paths are still present in the current NML, so paths
+  // that disappear can have their stored hash dropped afterwards.
+  let previous_hashes = if is_delta { db.get_traktor_sync_hashes()? } else { HashMap::new() };
+  let mut seen_paths: HashSet<String> = HashSet::new();
+  let synced_at = chrono::Utc::now().timestamp_millis();
+  let mut pending_hashes: Vec<(String, String)> = Vec::with_capacity(batch_size);
 
   // ═══════════════════════════════════════════════════════════════════════════
-  // Phase 3: Match tracks by path
+  // Phase 2: Stream entries, merge, and flush writes in batches
   // ═══════════════════════════════════════════════════════════════════════════
   emit_progress(
     &app,
-    "Matching Tracks",
-    20.0,
+    "Streaming Entries",
+    5.0,
     0,
     1,
-    "Matching tracks by file path...",
-  );
-
-  let existing_tracks = db.get_all_tracks()?;
-  let existing_by_path: HashMap<String, &Track> = existing_tracks
-    .iter()
-    .map(|t| (t.path.clone(), t))
-    .collect();
-
-  info!(
-    "Matching {} Traktor tracks against {} Harmony tracks",
-    traktor_tracks.len(),
-    existing_tracks.len()
-  );
-
-  // ═══════════════════════════════════════════════════════════════════════════
-  // Phase 4: Merge metadata and cue points
-  // ═══════════════════════════════════════════════════════════════════════════
-  emit_progress(
-    &app,
-    "Merging Metadata",
-    25.0,
-    0,
-    traktor_tracks.len(),
-    "Merging track metadata and cue points...",
+    "Streaming Traktor collection.nml...",
   );
 
-  let mut tracks_to_import = Vec::new();
-  let mut tracks_to_update = Vec::new();
-  let mut cues_to_save = Vec::new();
-  let mut fields_updated: HashMap<String, usize> = HashMap::new();
+  let parser = TraktorNMLParser::new();
+  let mut entry_stream = parser.stream_entries(&nml_path)?;
 
   let mut stats = EnhancedSyncStats {
     strategy: format!("{:?}", merge_strategy),
     tracks_processed: 0,
     tracks_matched: 0,
+    tracks_fuzzy_matched: 0,
     tracks_imported: 0,
     tracks_updated: 0,
     tracks_skipped: 0,
+    tracks_skipped_unchanged: 0,
     fields_updated: HashMap::new(),
     cue_points_synced: 0,
     playlists_imported: 0,
     playlist_tracks_linked: 0,
   };
+  let mut pending_writes: Vec<TraktorSyncWrite> = Vec::with_capacity(batch_size);
+
+  while let Some(entry_result) = entry_stream.next() {
+    let entry = entry_result?;
+    let traktor_track = map_traktor_entry_to_track(&entry);
+    let modified_at = map_traktor_modified_at(&entry).unwrap_or(0);
+    let path = traktor_track.path.clone();
 
-  for (idx, traktor_track) in traktor_tracks.iter().enumerate() {
     stats.tracks_processed += 1;
 
-    if let Some(existing_track) = existing_by_path.get(&traktor_track.path) {
+    if is_delta {
+      seen_paths.insert(path.clone());
+
+      let content_hash = compute_entry_content_hash(&entry);
+      let unchanged = existing_by_path.contains_key(&path)
+        && previous_hashes.get(&path) == Some(&content_hash);
+
+      if unchanged {
+        stats.tracks_skipped_unchanged += 1;
+        continue;
+      }
+
+      pending_hashes.push((path.clone(), content_hash));
+    }
+
+    // Extract cue points for this track (cue_v2 is a Vec, not Option<Vec>)
+    let traktor_cues = if !entry.cue_v2.is_empty() {
+      map_traktor_cues_to_harmony(Some(&entry.cue_v2), &traktor_track.id, modified_at)
+    } else {
+      Vec::new()
+    };
+
+    // Path equality is the fast path; Traktor exports often drift from
+    // Harmony's paths after files are moved or renamed, so a path miss falls
+    // back to fuzzy title/artist/duration matching (see `track_matcher`)
+    // against whichever Harmony tracks this sync hasn't already claimed.
+    let matched_track = existing_by_path
+      .get(&traktor_track.path)
+      .filter(|t| !matched_existing_ids.contains(&t.id))
+      .cloned()
+      .or_else(|| {
+        let candidates: Vec<Track> = existing_tracks
+          .iter()
+          .filter(|t| !matched_existing_ids.contains(&t.id))
+          .cloned()
+          .collect();
+        match_tracks(&candidates, std::slice::from_ref(&traktor_track), DEFAULT_MATCH_THRESHOLD)
+          .into_iter()
+          .next()
+          .map(|m| {
+            stats.tracks_fuzzy_matched += 1;
+            candidates[m.harmony_index].clone()
+          })
+      });
+
+    if let Some(existing_track) = matched_track {
+      matched_existing_ids.insert(existing_track.id.clone());
       // Track exists - merge metadata
       stats.tracks_matched += 1;
 
-      let merge_result = merge_track(existing_track, traktor_track, merge_strategy);
+      let harmony_clock = db.get_field_clock(&existing_track.id)?;
+      let traktor_stamp = FieldStamp { updated_at: modified_at, source: SourcePriority::Traktor };
+
+      let merge_result =
+        merge_track_with_policy(&existing_track, &harmony_clock, &traktor_track, traktor_stamp, &merge_policy);
 
       if merge_result.has_changes {
-        tracks_to_update.push(merge_result.merged.clone());
         stats.tracks_updated += 1;
-
-        // Count field updates
         for field in &merge_result.fields_updated {
-          *fields_updated.entry(field.clone()).or_insert(0) += 1;
+          *stats.fields_updated.entry(field.clone()).or_insert(0) += 1;
         }
       } else {
         stats.tracks_skipped += 1;
@@ -304,98 +381,96 @@ pub async fn sync_traktor_nml(
 
       // Merge cue points
       let existing_cues = db.get_cue_points_for_track(&existing_track.id)?;
-      let traktor_cues = traktor_cues_by_path
-        .get(&traktor_track.path)
-        .cloned()
-        .unwrap_or_default();
-
       let cue_merge_result =
         merge_cue_points(&existing_cues, &traktor_cues, &existing_track.id, cue_merge_strategy);
 
       if cue_merge_result.has_changes {
-        cues_to_save.extend(cue_merge_result.merged);
         stats.cue_points_synced += cue_merge_result.added;
       }
+
+      // Persist the merged clock regardless of has_changes - it may have
+      // bootstrapped entries for fields that were compared but didn't move.
+      pending_writes.push(TraktorSyncWrite {
+        track: merge_result.merged,
+        field_clock: Some(merge_result.merged_clock),
+        cues: cue_merge_result.has_changes.then_some(cue_merge_result.merged),
+      });
     } else {
       // New track - import it
-      tracks_to_import.push(traktor_track.clone());
+      stats.tracks_imported += 1;
+      stats.cue_points_synced += traktor_cues.len();
+
+      pending_writes.push(TraktorSyncWrite {
+        track: traktor_track,
+        field_clock: None,
+        cues: (!traktor_cues.is_empty()).then_some(traktor_cues),
+      });
+    }
+
+    if pending_writes.len() >= batch_size {
+      db.apply_traktor_sync_batch(&pending_writes)?;
+      pending_writes.clear();
 
-      // Also prepare cue points for this new track
-      if let Some(cues) = traktor_cues_by_path.get(&traktor_track.path) {
-        cues_to_save.extend(cues.clone());
-        stats.cue_points_synced += cues.len();
+      if !pending_hashes.is_empty() {
+        db.save_traktor_sync_hashes(&pending_hashes, synced_at)?;
+        pending_hashes.clear();
       }
-    }
 
-    if idx % 50 == 0 {
+      let total_hint = entry_stream.total_entries();
+      let progress = match total_hint {
+        Some(total) if total > 0 => 5.0 + (stats.tracks_processed as f64 / total as f64) * 65.0,
+        _ => 5.0,
+      };
       emit_progress(
         &app,
-        "Merging Metadata",
-        25.0 + (idx as f64 / traktor_tracks.len() as f64) * 30.0,
-        idx,
-        traktor_tracks.len(),
-        format!("Merged {}/{} tracks", idx, traktor_tracks.len()),
+        "Streaming Entries",
+        progress,
+        stats.tracks_processed,
+        total_hint.unwrap_or(stats.tracks_processed),
+        format!("Synced {} tracks", stats.tracks_processed),
       );
     }
   }
 
-  stats.fields_updated = fields_updated;
-
-  // ═══════════════════════════════════════════════════════════════════════════
-  // Phase 5: Save tracks to database
-  // ═══════════════════════════════════════════════════════════════════════════
-  emit_progress(
-    &app,
-    "Saving Tracks",
-    55.0,
-    0,
-    tracks_to_import.len() + tracks_to_update.len(),
-    "Saving tracks to database...",
-  );
-
-  if !tracks_to_import.is_empty() {
-    info!("Importing {} new tracks from Traktor", tracks_to_import.len());
-    db.insert_tracks(&tracks_to_import)?;
-    stats.tracks_imported = tracks_to_import.len();
+  if !pending_writes.is_empty() {
+    db.apply_traktor_sync_batch(&pending_writes)?;
+    pending_writes.clear();
+  }
+  if !pending_hashes.is_empty() {
+    db.save_traktor_sync_hashes(&pending_hashes, synced_at)?;
+    pending_hashes.clear();
   }
 
-  for (idx, track) in tracks_to_update.iter().enumerate() {
-    db.update_track(track)?;
-
-    if idx % 50 == 0 {
-      emit_progress(
-        &app,
-        "Saving Tracks",
-        55.0 + (idx as f64 / tracks_to_update.len() as f64) * 10.0,
-        idx,
-        tracks_to_update.len(),
-        format!("Saved {}/{} updated tracks", idx, tracks_to_update.len()),
-      );
+  if is_delta {
+    // Paths recorded in a previous sync but absent from this NML were
+    // removed from the Traktor collection - forget their hash so a later
+    // re-add is treated as new rather than unchanged.
+    let removed_paths: Vec<String> = previous_hashes
+      .keys()
+      .filter(|path| !seen_paths.contains(*path))
+      .cloned()
+      .collect();
+    if !removed_paths.is_empty() {
+      info!("Delta sync: {} entries removed since last sync", removed_paths.len());
+      db.delete_traktor_sync_hashes(&removed_paths)?;
     }
   }
 
-  // ═══════════════════════════════════════════════════════════════════════════
-  // Phase 6: Save cue points
-  // ═══════════════════════════════════════════════════════════════════════════
-  emit_progress(
-    &app,
-    "Syncing Cue Points",
-    65.0,
-    0,
-    cues_to_save.len(),
-    "Saving cue points...",
+  info!(
+    "Streamed {} tracks ({} matched, {} imported, {} updated, {} skipped, {} unchanged)",
+    stats.tracks_processed,
+    stats.tracks_matched,
+    stats.tracks_imported,
+    stats.tracks_updated,
+    stats.tracks_skipped,
+    stats.tracks_skipped_unchanged
   );
 
-  if !cues_to_save.is_empty() {
-    info!("Saving {} cue points", cues_to_save.len());
-    db.save_cue_points(&cues_to_save)?;
-  }
-
   // ═══════════════════════════════════════════════════════════════════════════
-  // Phase 7: Sync playlists (if enabled)
+  // Phase 3: Sync playlists (if enabled)
   // ═══════════════════════════════════════════════════════════════════════════
   if should_sync_playlists {
-    if let Some(playlists_root) = &nml.nml.playlists {
+    if let Some(playlists_root) = parser.parse_playlists(&nml_path)? {
       emit_progress(
         &app,
         "Syncing Playlists",
@@ -466,17 +541,18 @@ pub async fn sync_traktor_nml(
   }
 
   // ═══════════════════════════════════════════════════════════════════════════
-  // Phase 8: Complete
+  // Phase 4: Complete
   // ═══════════════════════════════════════════════════════════════════════════
   emit_progress(&app, "Complete", 100.0, 1, 1, "Sync completed successfully!");
 
   info!(
-    "Sync complete: {} processed, {} matched, {} imported, {} updated, {} skipped",
+    "Sync complete: {} processed, {} matched, {} imported, {} updated, {} skipped, {} unchanged",
     stats.tracks_processed,
     stats.tracks_matched,
     stats.tracks_imported,
     stats.tracks_updated,
-    stats.tracks_skipped
+    stats.tracks_skipped,
+    stats.tracks_skipped_unchanged
   );
   info!(
     "Cue points: {} synced, Playlists: {} imported with {} tracks",
@@ -486,6 +562,296 @@ pub async fn sync_traktor_nml(
   Ok(stats)
 }
 
+/// Export the Harmony library to a Traktor NML file (bidirectional write-back)
+///
+/// This is the inverse of `sync_traktor_nml`:
+/// 1. Optionally re-parse `source_nml_path` so entries Traktor carries but
+///    Harmony never modeled (audio fingerprints, loudness analysis, unknown
+///    attributes, ...) survive the round trip
+/// 2. Load all Harmony tracks and cue points
+/// 3. Build a Traktor entry per track, overlaying Harmony's fields onto the
+///    matching source entry (if any)
+/// 4. Build the PLAYLISTS tree from Harmony playlists
+/// 5. Serialize and write the resulting NML to `output_path`
+///
+/// AIDEV-NOTE: `source_nml_path` lets a re-export stay close to the original
+/// file instead of emitting a minimal NML that drops everything Harmony
+/// doesn't understand. Matching is by system path, same as `sync_traktor_nml`.
+///
+/// # Arguments
+/// * `app` - Tauri app handle for emitting progress events
+/// * `db` - Database state
+/// * `output_path` - Path to write the exported collection.nml file
+/// * `source_nml_path` - Optional existing Traktor NML to preserve unmodeled fields from
+///
+/// # Returns
+/// ExportNMLStats with export counts
+#[tauri::command]
+pub async fn export_traktor_nml(
+  app: AppHandle,
+  db: State<'_, Database>,
+  output_path: String,
+  source_nml_path: Option<String>,
+) -> Result<ExportNMLStats> {
+  info!("Command: export_traktor_nml - output: {}", output_path);
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // Phase 1: Load source NML (if provided) to seed unmodeled fields
+  // ═══════════════════════════════════════════════════════════════════════════
+  emit_progress(
+    &app,
+    "Loading Source NML",
+    0.0,
+    0,
+    1,
+    "Reading existing Traktor collection.nml...",
+  );
+
+  let mut seed_by_path: HashMap<String, TraktorEntry> = HashMap::new();
+  if let Some(path) = &source_nml_path {
+    let parser = TraktorNMLParser::new();
+    let source_nml = parser.parse(path)?;
+    for entry in &source_nml.nml.collection.entry {
+      let track = map_traktor_entry_to_track(entry);
+      seed_by_path.insert(track.path, entry.clone());
+    }
+    info!("Loaded {} entries from source NML for preservation", seed_by_path.len());
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // Phase 2: Load Harmony tracks and cue points
+  // ═══════════════════════════════════════════════════════════════════════════
+  emit_progress(&app, "Loading Tracks", 10.0, 0, 1, "Loading Harmony library...");
+
+  let tracks = db.get_all_tracks()?;
+  let track_ids: Vec<String> = tracks.iter().map(|t| t.id.clone()).collect();
+  let cue_points = db.get_cue_points_for_tracks(&track_ids)?;
+
+  let mut cues_by_track: HashMap<String, Vec<crate::libs::cue_point::CuePoint>> = HashMap::new();
+  for cue in cue_points {
+    cues_by_track.entry(cue.track_id.clone()).or_default().push(cue);
+  }
+
+  info!("Exporting {} tracks from Harmony library", tracks.len());
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // Phase 3: Build Traktor entries
+  // ═══════════════════════════════════════════════════════════════════════════
+  emit_progress(
+    &app,
+    "Building Entries",
+    25.0,
+    0,
+    tracks.len(),
+    "Converting tracks to Traktor format...",
+  );
+
+  let exported_at_ms = chrono::Utc::now().timestamp_millis();
+  let mut tracks_matched_to_source = 0;
+  let mut cue_points_exported = 0;
+  let mut entries = Vec::with_capacity(tracks.len());
+
+  for (idx, track) in tracks.iter().enumerate() {
+    let base = seed_by_path.get(&track.path);
+    if base.is_some() {
+      tracks_matched_to_source += 1;
+    }
+
+    let mut entry = map_track_to_traktor_entry(track, base, exported_at_ms);
+
+    if let Some(cues) = cues_by_track.get(&track.id) {
+      let traktor_cues = map_harmony_cues_to_traktor(cues);
+      cue_points_exported += traktor_cues.len();
+      entry.cue_v2 = traktor_cues;
+    }
+
+    entries.push(entry);
+
+    if idx % 100 == 0 {
+      emit_progress(
+        &app,
+        "Building Entries",
+        25.0 + (idx as f64 / tracks.len().max(1) as f64) * 40.0,
+        idx,
+        tracks.len(),
+        format!("Converted {}/{} tracks", idx, tracks.len()),
+      );
+    }
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // Phase 4: Build playlists
+  // ═══════════════════════════════════════════════════════════════════════════
+  emit_progress(
+    &app,
+    "Building Playlists",
+    70.0,
+    0,
+    1,
+    "Converting playlists to Traktor format...",
+  );
+
+  let playlist_metas = db.get_all_playlists()?;
+  let mut playlists_with_tracks = Vec::with_capacity(playlist_metas.len());
+  for meta in playlist_metas {
+    let Some(playlist) = db.get_playlist_by_id(&meta.id)? else {
+      continue;
+    };
+    let track_keys: Vec<String> = playlist
+      .tracks
+      .iter()
+      .map(|t| map_path_to_traktor_playlist_key(&t.path))
+      .collect();
+    playlists_with_tracks.push((playlist, track_keys));
+  }
+
+  let playlists_exported = playlists_with_tracks.len();
+  let playlists_node = build_traktor_playlists_node(&playlists_with_tracks);
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // Phase 5: Assemble and write the NML
+  // ═══════════════════════════════════════════════════════════════════════════
+  emit_progress(&app, "Writing NML", 85.0, 0, 1, "Serializing collection.nml...");
+
+  let nml = TraktorNML {
+    nml: NML {
+      version: "19".to_string(),
+      head: TraktorHead {
+        company: "www.native-instruments.com".to_string(),
+        program: "Traktor".to_string(),
+      },
+      collection: TraktorCollection {
+        entries: entries.len().to_string(),
+        entry: entries,
+      },
+      playlists: Some(TraktorPlaylists { node: playlists_node }),
+      indexing: None,
+    },
+  };
+
+  let writer = TraktorNMLWriter::new();
+  writer.write(&nml, &output_path)?;
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // Phase 6: Complete
+  // ═══════════════════════════════════════════════════════════════════════════
+  emit_progress(&app, "Complete", 100.0, 1, 1, "Export completed successfully!");
+
+  let stats = ExportNMLStats {
+    tracks_exported: nml.nml.collection.entry.len(),
+    tracks_matched_to_source,
+    cue_points_exported,
+    playlists_exported,
+  };
+
+  info!(
+    "Export complete: {} tracks ({} matched to source), {} cue points, {} playlists",
+    stats.tracks_exported, stats.tracks_matched_to_source, stats.cue_points_exported, stats.playlists_exported
+  );
+
+  Ok(stats)
+}
+
+/// Result of enriching a Traktor NML file's entries via MusicBrainz.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrichNMLResult {
+  pub entries_considered: usize,
+  pub entries_enriched: usize,
+}
+
+/// Enrich a Traktor `collection.nml` file's `@GENRE`/`@LABEL`/`@RELEASE_DATE`
+/// and album title from MusicBrainz, writing the result back to `nml_path`.
+///
+/// AIDEV-NOTE: Operates directly on the parsed NML tree rather than going
+/// through the Harmony `Track` model - see
+/// `libs::traktor::musicbrainz_enrich` for why. Emits
+/// `"traktor-enrich-progress"` once per distinct artist, mirroring how
+/// `commands::musicbrainz::enrich_tracks` reports progress.
+#[tauri::command]
+pub async fn enrich_traktor_nml(app: AppHandle, nml_path: String) -> Result<EnrichNMLResult> {
+  info!("Command: enrich_traktor_nml - {}", nml_path);
+
+  let parser = TraktorNMLParser::new();
+  let mut nml = parser.parse(&nml_path)?;
+  let entries_considered = nml.nml.collection.entry.len();
+
+  let entries_enriched = crate::libs::traktor::enrich_traktor_entries(
+    &mut nml.nml.collection.entry,
+    |current, total, artist| {
+      let payload = SyncProgress {
+        phase: "Enriching".to_string(),
+        progress: if total == 0 { 100.0 } else { (current as f64 / total as f64) * 100.0 },
+        current,
+        total,
+        message: format!("Looked up '{}' on MusicBrainz", artist),
+      };
+
+      if let Err(e) = app.emit("traktor-enrich-progress", &payload) {
+        warn!("Failed to emit enrichment progress event: {}", e);
+      }
+    },
+  )?;
+
+  let writer = TraktorNMLWriter::new();
+  writer.write(&nml, &nml_path)?;
+
+  info!(
+    "MusicBrainz enrichment wrote {}/{} entries in {}",
+    entries_enriched, entries_considered, nml_path
+  );
+
+  Ok(EnrichNMLResult { entries_considered, entries_enriched })
+}
+
+/// Result of merging two Traktor `collection.nml` files.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeNMLResult {
+  pub entries_from_a_only: usize,
+  pub entries_from_b_only: usize,
+  pub entries_merged: usize,
+  pub entries_total: usize,
+  pub cues_added: usize,
+  pub conflicts: Vec<MergeConflict>,
+}
+
+/// Merge two Traktor `collection.nml` files - e.g. one from a laptop and one
+/// from a controller's USB drive - into a single collection written to
+/// `output_path`, and report what was deduplicated or auto-resolved.
+///
+/// AIDEV-NOTE: See `libs::traktor::nml_merge` for the merge rules (identity,
+/// per-field recency, cue union, playlist-tree merge). This command only
+/// parses both inputs, delegates to it, and writes the result.
+#[tauri::command]
+pub async fn merge_traktor_nml(nml_path_a: String, nml_path_b: String, output_path: String) -> Result<MergeNMLResult> {
+  info!("Command: merge_traktor_nml - {} + {} -> {}", nml_path_a, nml_path_b, output_path);
+
+  let parser = TraktorNMLParser::new();
+  let nml_a = parser.parse(&nml_path_a)?;
+  let nml_b = parser.parse(&nml_path_b)?;
+
+  let (merged, report) = merge_nml(nml_a, nml_b);
+
+  let writer = TraktorNMLWriter::new();
+  writer.write(&merged, &output_path)?;
+
+  info!(
+    "Merged Traktor collections: {} total ({} from A only, {} from B only, {} merged, {} conflicts)",
+    report.entries_total, report.entries_from_a_only, report.entries_from_b_only, report.entries_merged,
+    report.conflicts.len()
+  );
+
+  Ok(MergeNMLResult {
+    entries_from_a_only: report.entries_from_a_only,
+    entries_from_b_only: report.entries_from_b_only,
+    entries_merged: report.entries_merged,
+    entries_total: report.entries_total,
+    cues_added: report.cues_added,
+    conflicts: report.conflicts,
+  })
+}
+
 /// Helper function to emit progress events
 ///
 /// AIDEV-NOTE: Emits "traktor-sync-progress" event to frontend
@@ -55,6 +55,8 @@ pub fn run() {
 
       // Register database as managed state
       app.manage(database);
+      app.manage(commands::BatchAnalysisRegistry::new());
+      app.manage(commands::ImportBatchRegistry::new());
 
       info!("Harmony initialized successfully!");
       Ok(())
@@ -90,29 +92,65 @@ pub fn run() {
       commands::delete_cue_points_for_track,
       commands::delete_cue_points,
       commands::replace_cue_points_for_track,
+      commands::merge_cue_points_for_track,
       // Audio metadata commands
       commands::scan_audio_file,
       commands::scan_directory,
       commands::scan_paths,
       commands::scan_audio_files_batch,
       commands::write_track_metadata,
+      commands::write_track_metadata_with_options,
       commands::write_tracks_metadata_batch,
       commands::import_library,
+      commands::import_library_with_progress,
+      commands::cancel_library_import,
       // File operations and cover art commands
       commands::copy_track_file,
       commands::move_track_file,
       commands::delete_track_file,
       commands::delete_tracks_batch,
       commands::get_track_cover,
+      commands::get_track_cover_cached,
       commands::get_cover_from_file,
       commands::replace_track_file,
       commands::check_library_changes_cmd,
       // Audio analysis commands
       commands::analyze_audio_file,
       commands::analyze_audio_batch_command,
+      commands::analyze_audio_batch_with_progress,
+      commands::cancel_audio_analysis_batch,
       // Traktor sync commands
       commands::parse_traktor_nml,
       commands::sync_traktor_nml,
+      commands::export_traktor_nml,
+      commands::enrich_traktor_nml,
+      commands::merge_traktor_nml,
+      // CUE sheet cue point commands
+      commands::import_cue_sheet,
+      commands::export_cue_sheet,
+      // Auto-cue generation commands
+      commands::generate_auto_cues,
+      // Rekordbox collection.xml commands
+      commands::export_rekordbox_collection,
+      commands::import_rekordbox_collection,
+      // Serato crate commands
+      commands::export_serato_crates,
+      commands::import_serato_crates,
+      // M3U/M3U8 playlist commands
+      commands::export_m3u_playlist,
+      commands::import_m3u_playlist,
+      // Duplicate detection commands
+      commands::find_duplicate_tracks_cmd,
+      commands::find_duplicate_tracks_by_key_cmd,
+      commands::find_fuzzy_duplicate_tracks_cmd,
+      // MusicBrainz enrichment commands
+      commands::enrich_tracks,
+      commands::enrich_track_metadata,
+      // Acoustic-similarity commands
+      commands::analyze_track_similarity_batch,
+      commands::generate_similar_playlist,
+      commands::build_similarity_playlist,
+      commands::dedup_playlist,
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
@@ -0,0 +1,207 @@
+// AIDEV-NOTE: Month/sequence-aware album ordering for `import_library`. Tag
+// dates are often year-only (or missing month/day entirely), and two albums
+// by the same artist released in the same year otherwise sort identically -
+// this gives `import_library` a deterministic ordering that groups by
+// artist/date instead of interleaving them arbitrarily.
+
+use crate::libs::track::{AlbumDate, AlbumSeq, Track};
+
+/// Parse a release date string pulled from tags into an [`AlbumDate`],
+/// accepting whatever precision the tag actually carried: `"YYYY"`,
+/// `"YYYY-MM"`, or `"YYYY-MM-DD"` (optionally followed by a time component,
+/// e.g. an ID3v2.4 `TDRC` frame's `"YYYY-MM-DDTHH:MM:SS"`). Falls back to
+/// less precision rather than failing outright - a malformed month or day
+/// is dropped instead of rejecting the whole date.
+pub fn parse_album_date(raw: &str) -> Option<AlbumDate> {
+  let raw = raw.trim();
+  if raw.is_empty() {
+    return None;
+  }
+
+  let mut parts = raw.splitn(3, '-');
+  let year = parts.next()?.parse::<i32>().ok()?;
+
+  let month = parts
+    .next()
+    .and_then(|m| m.parse::<u32>().ok())
+    .filter(|m| (1..=12).contains(m));
+
+  // Only look for a day if the month parsed cleanly - a day without a valid
+  // month isn't a date we can place within the year.
+  let day = month.and_then(|_| {
+    parts
+      .next()
+      .and_then(|d| d.split(|c: char| !c.is_ascii_digit()).next())
+      .and_then(|d| d.parse::<u32>().ok())
+      .filter(|d| (1..=31).contains(d))
+  });
+
+  Some(AlbumDate { year, month, day })
+}
+
+/// Sort `tracks` into a deterministic album/track order: by artist, then by
+/// release date (year, then month, then day - so same-year releases by one
+/// artist order chronologically instead of collapsing together), then by
+/// `album_seq` as a manual tiebreaker for releases that share an identical
+/// date, then by `track_number` as an explicit sequence tiebreaker, then by
+/// album/title so the order is fully deterministic even when tags are
+/// sparse.
+///
+/// Used by `commands::import_library` right before tracks are inserted, so
+/// the frontend can group/sort the library the same way without re-deriving
+/// this ordering itself.
+pub fn order_tracks_by_album(tracks: &mut [Track]) {
+  tracks.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+}
+
+type SortKey<'a> = (
+  Option<&'a str>,
+  Option<i32>,
+  Option<u32>,
+  Option<u32>,
+  Option<AlbumSeq>,
+  Option<i32>,
+  Option<&'a str>,
+  &'a str,
+);
+
+fn sort_key(track: &Track) -> SortKey<'_> {
+  (
+    track.artist.as_deref(),
+    track.album_date.as_ref().map(|d| d.year),
+    track.album_date.as_ref().and_then(|d| d.month),
+    track.album_date.as_ref().and_then(|d| d.day),
+    track.album_seq,
+    track.track_number,
+    track.album.as_deref(),
+    track.title.as_str(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_full_date() {
+    let date = parse_album_date("2020-05-14").unwrap();
+    assert_eq!(date, AlbumDate { year: 2020, month: Some(5), day: Some(14) });
+  }
+
+  #[test]
+  fn test_parse_year_month() {
+    let date = parse_album_date("2020-05").unwrap();
+    assert_eq!(date, AlbumDate { year: 2020, month: Some(5), day: None });
+  }
+
+  #[test]
+  fn test_parse_year_only() {
+    let date = parse_album_date("2020").unwrap();
+    assert_eq!(date, AlbumDate { year: 2020, month: None, day: None });
+  }
+
+  #[test]
+  fn test_parse_falls_back_on_invalid_month() {
+    let date = parse_album_date("2020-99-14").unwrap();
+    assert_eq!(date, AlbumDate { year: 2020, month: None, day: None });
+  }
+
+  #[test]
+  fn test_parse_date_with_trailing_time_component() {
+    let date = parse_album_date("2020-05-14T00:00:00").unwrap();
+    assert_eq!(date, AlbumDate { year: 2020, month: Some(5), day: Some(14) });
+  }
+
+  #[test]
+  fn test_parse_empty_returns_none() {
+    assert!(parse_album_date("").is_none());
+    assert!(parse_album_date("not-a-date").is_none());
+  }
+
+  #[test]
+  fn test_album_date_deserializes_bare_year_for_backward_compatibility() {
+    let date: AlbumDate = serde_json::from_str("2019").unwrap();
+    assert_eq!(date, AlbumDate { year: 2019, month: None, day: None });
+
+    let date: AlbumDate = serde_json::from_str(r#"{"year":2019,"month":3,"day":null}"#).unwrap();
+    assert_eq!(date, AlbumDate { year: 2019, month: Some(3), day: None });
+  }
+
+  fn sample(artist: &str, album_date: Option<AlbumDate>, track_number: Option<i32>, title: &str) -> Track {
+    Track {
+      id: format!("{artist}-{title}"),
+      path: format!("/music/{artist}/{title}.mp3"),
+      title: title.to_string(),
+      artist: Some(artist.to_string()),
+      album: None,
+      genre: None,
+      year: album_date.map(|d| d.year),
+      duration: 0,
+      bitrate: None,
+      comment: None,
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date,
+      track_number,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_same_year_albums_order_by_month_not_collapsed() {
+    let mut tracks = vec![
+      sample("Aphex Twin", Some(AlbumDate { year: 2014, month: Some(9), day: None }), Some(1), "Syro Track"),
+      sample("Aphex Twin", Some(AlbumDate { year: 2014, month: Some(2), day: None }), Some(1), "Earlier EP Track"),
+    ];
+
+    order_tracks_by_album(&mut tracks);
+
+    assert_eq!(tracks[0].title, "Earlier EP Track");
+    assert_eq!(tracks[1].title, "Syro Track");
+  }
+
+  #[test]
+  fn test_album_seq_breaks_tie_for_identical_release_dates() {
+    let same_day = Some(AlbumDate { year: 2020, month: Some(3), day: Some(6) });
+    let mut tracks = vec![
+      sample("Four Tet", same_day, Some(1), "Reissue Track"),
+      sample("Four Tet", same_day, Some(1), "Original Track"),
+    ];
+    tracks[0].album_seq = Some(AlbumSeq(2));
+    tracks[1].album_seq = Some(AlbumSeq(1));
+
+    order_tracks_by_album(&mut tracks);
+
+    assert_eq!(tracks[0].title, "Original Track");
+    assert_eq!(tracks[1].title, "Reissue Track");
+  }
+
+  #[test]
+  fn test_track_number_breaks_tie_within_same_album() {
+    let mut tracks = vec![
+      sample("Boards of Canada", Some(AlbumDate { year: 1998, month: None, day: None }), Some(2), "Track B"),
+      sample("Boards of Canada", Some(AlbumDate { year: 1998, month: None, day: None }), Some(1), "Track A"),
+    ];
+
+    order_tracks_by_album(&mut tracks);
+
+    assert_eq!(tracks[0].title, "Track A");
+    assert_eq!(tracks[1].title, "Track B");
+  }
+}
@@ -2,21 +2,21 @@
 // Provides BPM detection, key detection, and waveform generation
 //
 // Implementation:
+// - Decode: Symphonia, a pure-Rust demuxer/decoder (no ffmpeg subprocess)
 // - Waveform: RMS-based peak detection (pure Rust)
 // - BPM: Autocorrelation-based tempo detection (pure Rust)
-// - Key: essentia CLI wrapper (optional, falls back gracefully)
+// - Key: chroma vector + Krumhansl-Schmuckler correlation (pure Rust)
 //
 // Phase 4.5 Enhancements:
 // ✅ Real BPM detection using autocorrelation
-// ✅ Key detection using essentia CLI (if available)
-// ✅ Graceful fallback when tools are unavailable
+// ✅ Key detection using chroma + Krumhansl-Schmuckler, no external tools
+// ✅ Fully self-contained - no ffmpeg/essentia subprocess dependency
 //
 // Note: aubio-rs was considered but has C library dependency issues.
 // This pure-Rust implementation provides good accuracy for most music.
 
-use log::{debug, info, warn};
+use log::{info, warn};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
 
 use crate::libs::Result;
 
@@ -30,6 +30,13 @@ pub struct AudioAnalysisResult {
   pub scale: Option<String>, // "major" or "minor"
   pub key_confidence: Option<f64>,
   pub waveform_peaks: Option<Vec<f64>>,
+  // AIDEV-NOTE: The same acoustic fingerprint `libs::similarity` persists
+  // for "sounds-like" playlist generation, surfaced here too so a single
+  // `analyze_audio` call returns it alongside BPM/key instead of requiring
+  // a second decode via the separate `analyze_track_similarity_batch`
+  // command. See `libs::similarity::compute_feature_vector` for how it's
+  // derived (tempo, spectral centroid, zero-crossing rate, chroma mean/var).
+  pub features: Option<Vec<f32>>,
 }
 
 /// Audio analysis options
@@ -46,6 +53,13 @@ pub struct AudioAnalysisOptions {
   pub waveform_bins: usize,
   #[serde(default = "default_sample_rate")]
   pub sample_rate: u32,
+  // AIDEV-NOTE: (start_ms, end_ms) of the slice to analyze within the
+  // decoded file, for CUE-split virtual tracks (see `libs::cue_sheet`) that
+  // point at one segment of a longer mix/album-rip file rather than the
+  // whole thing. `None` (the default) analyzes the full decode, matching
+  // prior behavior for non-CUE tracks.
+  #[serde(default)]
+  pub segment: Option<(f64, f64)>,
 }
 
 fn default_true() -> bool {
@@ -66,44 +80,114 @@ impl Default for AudioAnalysisOptions {
       generate_waveform: true,
       waveform_bins: 300,
       sample_rate: 44100,
+      segment: None,
     }
   }
 }
 
-/// Decode audio file to mono samples
-/// Uses ffmpeg to convert any audio format to raw PCM samples
-fn decode_audio_file(file_path: &str, target_sample_rate: u32) -> Result<Vec<f32>> {
+/// Decode audio file to mono samples, resampled to `target_sample_rate`.
+///
+/// AIDEV-NOTE: Demuxes/decodes via Symphonia - a pure-Rust library - instead
+/// of shelling out to an `ffmpeg` binary, so analysis no longer depends on
+/// an external tool being installed on PATH. Symphonia decodes at the
+/// file's native sample rate and channel layout; this downmixes to mono by
+/// averaging channels per frame, then runs `resample_linear` to match
+/// `target_sample_rate` (BPM/waveform/key detection below all assume a
+/// fixed rate).
+pub(crate) fn decode_audio_file(file_path: &str, target_sample_rate: u32) -> Result<Vec<f32>> {
+  use symphonia::core::audio::SampleBuffer;
+  use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+  use symphonia::core::errors::Error as SymphoniaError;
+  use symphonia::core::formats::FormatOptions;
+  use symphonia::core::io::MediaSourceStream;
+  use symphonia::core::meta::MetadataOptions;
+  use symphonia::core::probe::Hint;
+
   info!("Decoding audio file: {}", file_path);
 
-  // Use ffmpeg to decode to raw f32 samples
-  let output = std::process::Command::new("ffmpeg")
-    .args(&[
-      "-i",
-      file_path,
-      "-ac",
-      "1", // Mono
-      "-ar",
-      &target_sample_rate.to_string(),
-      "-f",
-      "f32le", // 32-bit float little-endian
-      "-",     // Output to stdout
-    ])
-    .output()?;
-
-  if !output.status.success() {
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    return Err(crate::libs::HarmonyError::Custom(format!(
-      "ffmpeg failed: {}",
-      stderr
-    )));
-  }
-
-  // Convert bytes to f32 samples
-  let bytes = output.stdout;
-  let samples: Vec<f32> = bytes
-    .chunks_exact(4)
-    .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-    .collect();
+  let file = std::fs::File::open(file_path).map_err(|e| {
+    crate::libs::HarmonyError::Custom(format!("failed to open {}: {}", file_path, e))
+  })?;
+  let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+  let mut hint = Hint::new();
+  if let Some(ext) = std::path::Path::new(file_path)
+    .extension()
+    .and_then(|e| e.to_str())
+  {
+    hint.with_extension(ext);
+  }
+
+  let probed = symphonia::default::get_probe()
+    .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+    .map_err(|e| {
+      crate::libs::HarmonyError::Custom(format!("failed to probe {}: {}", file_path, e))
+    })?;
+  let mut format = probed.format;
+
+  let track = format
+    .tracks()
+    .iter()
+    .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+    .ok_or_else(|| {
+      crate::libs::HarmonyError::Custom(format!("no decodable audio track in {}", file_path))
+    })?;
+  let track_id = track.id;
+  let native_sample_rate = track.codec_params.sample_rate.ok_or_else(|| {
+    crate::libs::HarmonyError::Custom(format!("unknown sample rate in {}", file_path))
+  })?;
+
+  let mut decoder = symphonia::default::get_codecs()
+    .make(&track.codec_params, &DecoderOptions::default())
+    .map_err(|e| {
+      crate::libs::HarmonyError::Custom(format!("failed to create decoder for {}: {}", file_path, e))
+    })?;
+
+  let mut mono_samples: Vec<f32> = Vec::new();
+  let mut sample_buf: Option<SampleBuffer<f32>> = None;
+
+  loop {
+    let packet = match format.next_packet() {
+      Ok(packet) => packet,
+      Err(SymphoniaError::IoError(_)) => break, // end of stream
+      Err(e) => {
+        return Err(crate::libs::HarmonyError::Custom(format!(
+          "error reading packet from {}: {}",
+          file_path, e
+        )))
+      }
+    };
+
+    if packet.track_id() != track_id {
+      continue;
+    }
+
+    match decoder.decode(&packet) {
+      Ok(decoded) => {
+        let buf = sample_buf.get_or_insert_with(|| {
+          SampleBuffer::new(decoded.capacity() as u64, *decoded.spec())
+        });
+        buf.copy_interleaved_ref(decoded);
+
+        let channels = buf.spec().channels.count().max(1);
+        for frame in buf.samples().chunks_exact(channels) {
+          mono_samples.push(frame.iter().sum::<f32>() / channels as f32);
+        }
+      }
+      // A single corrupt packet shouldn't abort decoding the whole file -
+      // skip it and keep going, same tolerance ffmpeg's CLI gave us for
+      // free on slightly-damaged files.
+      Err(SymphoniaError::DecodeError(_)) => continue,
+      Err(e) => {
+        return Err(crate::libs::HarmonyError::Custom(format!(
+          "decode error in {}: {}",
+          file_path, e
+        )))
+      }
+    }
+  }
+
+  let samples = resample_linear(&mono_samples, native_sample_rate, target_sample_rate);
 
   info!(
     "Decoded {} samples ({:.2}s)",
@@ -114,6 +198,45 @@ fn decode_audio_file(file_path: &str, target_sample_rate: u32) -> Result<Vec<f32
   Ok(samples)
 }
 
+/// Linear-interpolation resampler from `from_rate` to `to_rate`. Good enough
+/// to align Symphonia's native-rate decode with the fixed rate the
+/// BPM/waveform/key analysis below expect - not meant for audible playback,
+/// where a proper sinc resampler would be worth the extra cost.
+fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+  if from_rate == to_rate || samples.is_empty() {
+    return samples.to_vec();
+  }
+
+  let ratio = to_rate as f64 / from_rate as f64;
+  let out_len = ((samples.len() as f64) * ratio).round() as usize;
+  let mut out = Vec::with_capacity(out_len);
+
+  for i in 0..out_len {
+    let src_pos = i as f64 / ratio;
+    let src_idx = src_pos.floor() as usize;
+    let frac = (src_pos - src_idx as f64) as f32;
+
+    let a = samples[src_idx.min(samples.len() - 1)];
+    let b = samples[(src_idx + 1).min(samples.len() - 1)];
+    out.push(a + (b - a) * frac);
+  }
+
+  out
+}
+
+/// Convert an `AudioAnalysisOptions::segment` (start_ms, end_ms) into a
+/// `[start, end)` sample index range into a decode of `total_samples`
+/// samples at `sample_rate`, clamped so it never panics on an
+/// out-of-bounds or inverted segment (a CUE sheet's last track may run a
+/// few samples past what Symphonia actually decoded, for instance).
+fn segment_sample_range(start_ms: f64, end_ms: f64, sample_rate: u32, total_samples: usize) -> (usize, usize) {
+  let start = ((start_ms / 1000.0) * sample_rate as f64).round() as usize;
+  let end = ((end_ms / 1000.0) * sample_rate as f64).round() as usize;
+  let start = start.min(total_samples);
+  let end = end.clamp(start, total_samples);
+  (start, end)
+}
+
 /// Generate waveform peaks using RMS (Root Mean Square)
 /// Returns normalized peaks (0.0 to 1.0) for visualization
 fn generate_waveform_peaks(samples: &[f32], num_bins: usize) -> Vec<f64> {
@@ -236,7 +359,7 @@ fn detect_bpm(samples: &[f32], sample_rate: u32) -> Option<(i32, f64)> {
 }
 
 /// Calculate energy envelope of audio signal
-fn calculate_energy_envelope(samples: &[f32], frame_size: usize, hop_size: usize) -> Vec<f64> {
+pub(crate) fn calculate_energy_envelope(samples: &[f32], frame_size: usize, hop_size: usize) -> Vec<f64> {
   let num_frames = (samples.len() - frame_size) / hop_size + 1;
   let mut energy = Vec::with_capacity(num_frames);
 
@@ -255,7 +378,7 @@ fn calculate_energy_envelope(samples: &[f32], frame_size: usize, hop_size: usize
 }
 
 /// Calculate autocorrelation of a signal
-fn autocorrelate(signal: &[f64]) -> Vec<f64> {
+pub(crate) fn autocorrelate(signal: &[f64]) -> Vec<f64> {
   let n = signal.len();
   let mut autocorr = vec![0.0; n];
 
@@ -275,146 +398,136 @@ fn autocorrelate(signal: &[f64]) -> Vec<f64> {
   autocorr
 }
 
-/// Detect musical key from audio samples using essentia CLI
-/// Returns (key, scale, confidence) or None on failure
+const NOTE_NAMES: [&str; 12] = [
+  "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+// AIDEV-NOTE: Krumhansl-Schmuckler key profiles - relative perceived
+// stability of each of the 12 chromatic pitch classes within a major/minor
+// key, from Krumhansl & Kessler's probe-tone experiments. Correlating a
+// track's chroma vector (rotated through all 12 roots) against these picks
+// the best-fitting key without needing a trained model or external tool.
+const MAJOR_PROFILE: [f64; 12] = [
+  6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+const MINOR_PROFILE: [f64; 12] = [
+  6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+/// Detect musical key from audio samples using a pure-Rust chroma vector +
+/// Krumhansl-Schmuckler key-profile correlation.
+/// Returns (key, scale, confidence) or None on failure.
 ///
-/// AIDEV-NOTE: This uses essentia_streaming_extractor_music if available
-/// Falls back gracefully if essentia is not installed
+/// AIDEV-NOTE: Builds a 12-bin chroma profile via `chroma_vector`, which sums
+/// the DFT energy at every semitone probe of each window onto its pitch
+/// class, then correlates
+/// it against the `MAJOR_PROFILE`/`MINOR_PROFILE` key templates (rotated
+/// through all 12 roots) and keeps the best match. Replaces the previous
+/// essentia CLI subprocess - no external tool dependency.
 fn detect_key(samples: &[f32], sample_rate: u32) -> Option<(String, String, f64)> {
-  info!("Detecting key with essentia CLI");
+  info!("Detecting key via chroma + Krumhansl-Schmuckler");
 
-  // Check if essentia is available
-  if !is_essentia_available() {
-    warn!("essentia_streaming_extractor_music not found - key detection disabled");
-    debug!("Install essentia: https://essentia.upf.edu/installing.html");
+  if samples.is_empty() {
     return None;
   }
 
-  // Create temporary WAV file for essentia
-  use tempfile::TempDir;
+  let chroma = chroma_vector(samples, sample_rate);
 
-  let temp_dir = match TempDir::new() {
-    Ok(dir) => dir,
-    Err(e) => {
-      warn!("Failed to create temp directory: {}", e);
-      return None;
+  let mut best: Option<(usize, bool, f64)> = None;
+  for root in 0..12 {
+    for (is_minor, profile) in [(false, &MAJOR_PROFILE), (true, &MINOR_PROFILE)] {
+      let rotated: [f64; 12] = std::array::from_fn(|i| profile[(i + 12 - root) % 12]);
+      let score = pearson_correlation(&chroma, &rotated);
+      if best.map_or(true, |(_, _, best_score)| score > best_score) {
+        best = Some((root, is_minor, score));
+      }
     }
+  }
+
+  let (root, is_minor, confidence) = best?;
+  let key = NOTE_NAMES[root];
+  let scale = if is_minor { "minor" } else { "major" };
+  let formatted_key = if is_minor {
+    format!("{}m", key)
+  } else {
+    key.to_string()
   };
 
-  let wav_path = temp_dir.path().join("temp.wav");
-  let json_path = temp_dir.path().join("temp.json");
+  info!(
+    "Key detected: {} {} (confidence: {:.2})",
+    formatted_key, scale, confidence
+  );
+  Some((formatted_key, scale.to_string(), confidence))
+}
 
-  // Write WAV file (simple 32-bit float PCM)
-  if let Err(e) = write_wav_file(&wav_path, samples, sample_rate) {
-    warn!("Failed to write temp WAV: {}", e);
-    return None;
-  }
+/// Build a 12-bin chroma vector (energy per pitch class, summed across
+/// octaves and normalized to sum to 1.0) from mono PCM samples, via a
+/// straightforward DFT over fixed-size windows - no FFT crate dependency,
+/// just the Goertzel-style per-pitch-class energy this needs.
+fn chroma_vector(samples: &[f32], sample_rate: u32) -> [f64; 12] {
+  const WINDOW_SIZE: usize = 4096;
+  const MIN_FREQ: f64 = 55.0; // A1, the lowest semitone probed
+  const MAX_FREQ: f64 = 2000.0; // a few octaves up is plenty for key detection
 
-  // Run essentia
-  let output = match Command::new("essentia_streaming_extractor_music")
-    .arg(wav_path.to_string_lossy().as_ref())
-    .arg(json_path.to_string_lossy().as_ref())
-    .output()
-  {
-    Ok(out) => out,
-    Err(e) => {
-      warn!("Failed to run essentia: {}", e);
-      return None;
+  let mut chroma = [0.0f64; 12];
+
+  for window in samples.chunks(WINDOW_SIZE) {
+    if window.len() < WINDOW_SIZE / 4 {
+      continue; // trailing partial window too short to be meaningful
     }
-  };
 
-  if !output.status.success() {
-    warn!(
-      "Essentia failed: {}",
-      String::from_utf8_lossy(&output.stderr)
-    );
-    return None;
+    let mut freq = MIN_FREQ;
+    while freq <= MAX_FREQ {
+      let (mut re, mut im) = (0.0f64, 0.0f64);
+      let omega = 2.0 * std::f64::consts::PI * freq / sample_rate as f64;
+      for (n, &sample) in window.iter().enumerate() {
+        let phase = omega * n as f64;
+        re += sample as f64 * phase.cos();
+        im += sample as f64 * phase.sin();
+      }
+      let energy = (re * re + im * im).sqrt();
+
+      // MIDI-style pitch class: how many semitones above C is this frequency?
+      let semitones_from_c = 12.0 * (freq / 16.3516).log2(); // C0 = 16.3516 Hz
+      let pitch_class = semitones_from_c.round().rem_euclid(12.0) as usize;
+      chroma[pitch_class.min(11)] += energy;
+
+      freq *= 2f64.powf(1.0 / 12.0); // next semitone up
+    }
   }
 
-  // Parse JSON output
-  let json_content = match std::fs::read_to_string(&json_path) {
-    Ok(content) => content,
-    Err(e) => {
-      warn!("Failed to read essentia output: {}", e);
-      return None;
+  let total: f64 = chroma.iter().sum();
+  if total > 0.0 {
+    for bin in chroma.iter_mut() {
+      *bin /= total;
     }
-  };
-
-  parse_essentia_key(&json_content)
-}
-
-/// Check if essentia CLI is available
-fn is_essentia_available() -> bool {
-  Command::new("essentia_streaming_extractor_music")
-    .arg("--help")
-    .output()
-    .is_ok()
-}
+  }
 
-/// Write samples to WAV file
-fn write_wav_file(
-  path: &std::path::Path,
-  samples: &[f32],
-  sample_rate: u32,
-) -> std::io::Result<()> {
-  use std::fs::File;
-  use std::io::Write;
-
-  let mut file = File::create(path)?;
-
-  // WAV header for 32-bit float PCM, mono
-  let data_size = (samples.len() * 4) as u32;
-  let file_size = 36 + data_size;
-
-  // RIFF header
-  file.write_all(b"RIFF")?;
-  file.write_all(&file_size.to_le_bytes())?;
-  file.write_all(b"WAVE")?;
-
-  // fmt chunk
-  file.write_all(b"fmt ")?;
-  file.write_all(&16u32.to_le_bytes())?; // chunk size
-  file.write_all(&3u16.to_le_bytes())?; // format: IEEE float
-  file.write_all(&1u16.to_le_bytes())?; // channels: mono
-  file.write_all(&sample_rate.to_le_bytes())?;
-  file.write_all(&(sample_rate * 4).to_le_bytes())?; // byte rate
-  file.write_all(&4u16.to_le_bytes())?; // block align
-  file.write_all(&32u16.to_le_bytes())?; // bits per sample
-
-  // data chunk
-  file.write_all(b"data")?;
-  file.write_all(&data_size.to_le_bytes())?;
-
-  // Write samples
-  for &sample in samples {
-    file.write_all(&sample.to_le_bytes())?;
-  }
-
-  Ok(())
+  chroma
 }
 
-/// Parse essentia JSON output to extract key information
-fn parse_essentia_key(json: &str) -> Option<(String, String, f64)> {
-  use serde_json::Value;
-
-  let v: Value = serde_json::from_str(json).ok()?;
-
-  let key = v["tonal"]["key_key"].as_str()?.to_string();
-  let scale = v["tonal"]["key_scale"].as_str()?.to_string();
-  let strength = v["tonal"]["key_strength"].as_f64()?;
+/// Pearson correlation coefficient between two equal-length vectors, used to
+/// score how well a chroma profile fits a rotated key template.
+fn pearson_correlation(a: &[f64; 12], b: &[f64; 12]) -> f64 {
+  let mean_a = a.iter().sum::<f64>() / 12.0;
+  let mean_b = b.iter().sum::<f64>() / 12.0;
+
+  let mut cov = 0.0;
+  let mut var_a = 0.0;
+  let mut var_b = 0.0;
+  for i in 0..12 {
+    let da = a[i] - mean_a;
+    let db = b[i] - mean_b;
+    cov += da * db;
+    var_a += da * da;
+    var_b += db * db;
+  }
 
-  // Format key like Traktor: "Am", "C", "F#m", etc.
-  let formatted_key = if scale == "minor" {
-    format!("{}m", key)
-  } else {
-    key.to_string()
-  };
+  if var_a <= 0.0 || var_b <= 0.0 {
+    return 0.0;
+  }
 
-  info!(
-    "Key detected: {} {} (strength: {:.2})",
-    formatted_key, scale, strength
-  );
-  Some((formatted_key, scale, strength))
+  cov / (var_a.sqrt() * var_b.sqrt())
 }
 
 /// Analyze an audio file
@@ -430,22 +543,34 @@ pub fn analyze_audio(
     scale: None,
     key_confidence: None,
     waveform_peaks: None,
+    features: None,
   };
 
   info!("Starting audio analysis: {}", file_path);
 
   // Decode audio file
-  let samples = decode_audio_file(file_path, opts.sample_rate)?;
+  let decoded = decode_audio_file(file_path, opts.sample_rate)?;
+
+  // Restrict analysis to a CUE-split virtual track's segment, if given (see
+  // `AudioAnalysisOptions::segment`), rather than the whole decoded file.
+  let samples: &[f32] = match opts.segment {
+    Some((start_ms, end_ms)) => {
+      let (start, end) = segment_sample_range(start_ms, end_ms, opts.sample_rate, decoded.len());
+      info!("Analyzing segment [{}, {}) of {} decoded samples", start, end, decoded.len());
+      &decoded[start..end]
+    }
+    None => &decoded,
+  };
 
   // Generate waveform
   if opts.generate_waveform {
     info!("Generating waveform with {} bins", opts.waveform_bins);
-    result.waveform_peaks = Some(generate_waveform_peaks(&samples, opts.waveform_bins));
+    result.waveform_peaks = Some(generate_waveform_peaks(samples, opts.waveform_bins));
   }
 
   // Detect BPM
   if opts.detect_bpm {
-    if let Some((bpm, confidence)) = detect_bpm(&samples, opts.sample_rate) {
+    if let Some((bpm, confidence)) = detect_bpm(samples, opts.sample_rate) {
       result.bpm = Some(bpm);
       result.bpm_confidence = Some(confidence);
       info!("BPM detected: {} (confidence: {:.2})", bpm, confidence);
@@ -454,7 +579,7 @@ pub fn analyze_audio(
 
   // Detect key
   if opts.detect_key {
-    if let Some((key, scale, confidence)) = detect_key(&samples, opts.sample_rate) {
+    if let Some((key, scale, confidence)) = detect_key(samples, opts.sample_rate) {
       result.key = Some(key.clone());
       result.scale = Some(scale.clone());
       result.key_confidence = Some(confidence);
@@ -465,6 +590,15 @@ pub fn analyze_audio(
     }
   }
 
+  // Compute the acoustic fingerprint used for "sounds-like" similarity
+  // (see `libs::similarity`). Re-decodes the file at its native sample
+  // rate rather than reusing `samples` above, since the feature extractor
+  // needs its own frame-based analysis independent of `opts.sample_rate`.
+  match crate::libs::similarity::compute_feature_vector(file_path) {
+    Ok(vector) => result.features = Some(vector.iter().map(|&v| v as f32).collect()),
+    Err(e) => warn!("Skipping similarity features for {}: {}", file_path, e),
+  }
+
   info!("Audio analysis complete");
   Ok(result)
 }
@@ -495,6 +629,145 @@ pub fn analyze_audio_batch(
   results
 }
 
+/// Like [`analyze_audio_batch`], but runs on a scoped pool capped at
+/// `num_cores` threads instead of saturating rayon's global pool -
+/// analogous to bliss-rs's `analyze_paths_with_cores`. `0` means "use the
+/// global pool", matching `ScanOptions::extractor_threads`'s convention in
+/// `libs::scan_pipeline`. Falls back to the global pool (with a warning) if
+/// building the scoped pool fails.
+pub fn analyze_audio_batch_with_cores(
+  file_paths: Vec<String>,
+  options: Option<AudioAnalysisOptions>,
+  num_cores: usize,
+) -> Vec<Result<AudioAnalysisResult>> {
+  let run = || analyze_audio_batch(file_paths, options);
+
+  match num_cores {
+    0 => run(),
+    n => match rayon::ThreadPoolBuilder::new().num_threads(n).build() {
+      Ok(pool) => pool.install(run),
+      Err(err) => {
+        warn!("Failed to build {}-thread analysis pool, using the global pool: {}", n, err);
+        run()
+      }
+    },
+  }
+}
+
+/// Like [`analyze_audio_batch`], but invokes `on_done(index, result)` as
+/// soon as each file's analysis completes (in completion order, not input
+/// order) so a caller can render a live progress bar - while still
+/// returning the full `Vec` in the same order as `file_paths`, since
+/// `rayon`'s `map`/`collect` reassembles by index regardless of which
+/// thread finished first.
+pub fn analyze_audio_batch_with_callback(
+  file_paths: Vec<String>,
+  options: Option<AudioAnalysisOptions>,
+  on_done: impl Fn(usize, &Result<AudioAnalysisResult>) + Sync,
+) -> Vec<Result<AudioAnalysisResult>> {
+  use rayon::prelude::*;
+
+  let total = file_paths.len();
+  info!("Starting batch analysis with progress for {} files", total);
+
+  let results: Vec<Result<AudioAnalysisResult>> = file_paths
+    .par_iter()
+    .enumerate()
+    .map(|(index, path)| {
+      let result = analyze_audio(path, options.clone());
+      on_done(index, &result);
+      result
+    })
+    .collect();
+
+  let succeeded = results.iter().filter(|r| r.is_ok()).count();
+  info!(
+    "Batch analysis with progress complete: {} succeeded, {} failed",
+    succeeded,
+    results.len() - succeeded
+  );
+
+  results
+}
+
+/// Shared, cheaply-cloneable flag that lets a long-running batch be aborted
+/// from outside the worker pool (e.g. a Tauri command handling a "cancel"
+/// button click).
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(std::sync::Arc<std::sync::atomic::AtomicBool>);
+
+impl CancellationToken {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn cancel(&self) {
+    self.0.store(true, std::sync::atomic::Ordering::Relaxed);
+  }
+
+  pub fn is_cancelled(&self) -> bool {
+    self.0.load(std::sync::atomic::Ordering::Relaxed)
+  }
+}
+
+/// One file's worth of progress from [`analyze_audio_batch_streaming`].
+pub struct BatchProgress {
+  pub processed: usize,
+  pub total: usize,
+  pub path: String,
+  pub result: Result<AudioAnalysisResult>,
+}
+
+/// Like [`analyze_audio_batch`], but streams results back through
+/// `on_progress` as each file finishes instead of blocking until the whole
+/// batch completes, and checks `cancel` between files so a caller can abort
+/// a long scan. Files still in flight when cancellation is observed are
+/// allowed to finish; no new ones are started.
+pub fn analyze_audio_batch_streaming(
+  file_paths: Vec<String>,
+  options: Option<AudioAnalysisOptions>,
+  cancel: CancellationToken,
+  mut on_progress: impl FnMut(BatchProgress) + Send,
+) {
+  use rayon::prelude::*;
+
+  let total = file_paths.len();
+  info!("Starting streaming batch analysis for {} files", total);
+
+  let (tx, rx) = crossbeam_channel::unbounded();
+
+  let worker_cancel = cancel.clone();
+  rayon::spawn(move || {
+    file_paths.par_iter().for_each(|path| {
+      if worker_cancel.is_cancelled() {
+        return;
+      }
+      let result = analyze_audio(path, options.clone());
+      // The receiver is dropped when the call returns, which after a cancel
+      // can happen while workers are still finishing a file; such a send
+      // fails and is ignored.
+      let _ = tx.send((path.clone(), result));
+    });
+  });
+
+  let mut processed = 0;
+  for (path, result) in rx {
+    processed += 1;
+    on_progress(BatchProgress {
+      processed,
+      total,
+      path,
+      result,
+    });
+
+    if cancel.is_cancelled() {
+      break;
+    }
+  }
+
+  info!("Streaming batch analysis finished ({}/{})", processed, total);
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -524,4 +797,73 @@ mod tests {
     assert_eq!(opts.waveform_bins, 300);
     assert_eq!(opts.sample_rate, 44100);
   }
+
+  #[test]
+  fn test_resample_linear_same_rate_is_noop() {
+    let samples = vec![0.1, 0.2, 0.3, 0.4];
+    let resampled = resample_linear(&samples, 44100, 44100);
+    assert_eq!(resampled, samples);
+  }
+
+  #[test]
+  fn test_resample_linear_downsamples_by_half() {
+    let samples: Vec<f32> = (0..100).map(|i| i as f32).collect();
+    let resampled = resample_linear(&samples, 44100, 22050);
+    assert_eq!(resampled.len(), 50);
+    // Interpolated values should stay within the original range.
+    assert!(resampled.iter().all(|&s| (0.0..=99.0).contains(&s)));
+  }
+
+  #[test]
+  fn test_resample_linear_handles_empty_input() {
+    let resampled = resample_linear(&[], 44100, 48000);
+    assert!(resampled.is_empty());
+  }
+
+  #[test]
+  fn test_detect_key_on_pure_c_tone() {
+    let sample_rate = 44100;
+    // A pure 261.63 Hz tone (C4) should produce a chroma vector dominated by
+    // the C pitch class. A single pitch class fits the C major and C minor
+    // templates almost equally well (both weight their tonic highest), so
+    // accept either mode as long as the tonic is C.
+    let samples: Vec<f32> = (0..sample_rate * 2)
+      .map(|i| (i as f32 * 261.63 * 2.0 * std::f32::consts::PI / sample_rate as f32).sin() * 0.5)
+      .collect();
+
+    let (key, _scale, confidence) =
+      detect_key(&samples, sample_rate).expect("key detection should succeed on a pure tone");
+
+    assert!(key == "C" || key == "Cm", "unexpected key: {}", key);
+    assert!(confidence > 0.0);
+  }
+
+  #[test]
+  fn test_detect_key_on_empty_samples_returns_none() {
+    assert!(detect_key(&[], 44100).is_none());
+  }
+
+  #[test]
+  fn test_pearson_correlation_identical_vectors_is_one() {
+    let v = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0];
+    assert!((pearson_correlation(&v, &v) - 1.0).abs() < 1e-9);
+  }
+
+  #[test]
+  fn test_segment_sample_range_converts_ms_to_sample_indices() {
+    // 1.0s to 2.0s at 44100 Hz.
+    assert_eq!(segment_sample_range(1000.0, 2000.0, 44100, 1_000_000), (44100, 88200));
+  }
+
+  #[test]
+  fn test_segment_sample_range_clamps_to_total_samples() {
+    // A CUE sheet's last track may claim an end_ms past what was decoded.
+    assert_eq!(segment_sample_range(0.0, 10_000.0, 44100, 100), (0, 100));
+  }
+
+  #[test]
+  fn test_segment_sample_range_clamps_inverted_range() {
+    // end_ms before start_ms should yield an empty, non-panicking range.
+    assert_eq!(segment_sample_range(2000.0, 1000.0, 44100, 1_000_000), (88200, 88200));
+  }
 }
@@ -6,10 +6,154 @@ use lofty::config::WriteOptions;
 use lofty::file::{AudioFile, TaggedFileExt};
 use lofty::prelude::{Accessor, ItemKey};
 use lofty::tag::TagExt;
-use log::info;
+use log::{info, warn};
 use std::path::Path;
 
-use crate::libs::{HarmonyError, Result, Track, TrackRating};
+use crate::libs::album_order::parse_album_date;
+use crate::libs::cue_sheet::{cue_points_for_virtual_track, find_accompanying_cue_sheet, parse_cue_sheet};
+use crate::libs::{AlbumDate, CuePoint, HarmonyError, Result, Track, TrackRating};
+
+/// Custom tag item keys used to round-trip `Track::chapters` and
+/// `Track::synced_lyrics` (see the AIDEV-NOTE on those fields).
+const CHAPTERS_ITEM_KEY: &str = "HARMONY_CHAPTERS";
+const SYNCED_LYRICS_ITEM_KEY: &str = "HARMONY_SYNCED_LYRICS";
+
+/// Controls how [`write_with_lofty`] persists tags.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteMetadataOptions {
+  #[serde(default)]
+  pub id3v2_version: Id3v2Version,
+  /// When `true`, [`write_metadata_ext`] refuses to write a track that
+  /// fails [`validate_track`] and returns the validation errors instead.
+  /// When `false` (the default), validation failures are logged as
+  /// warnings and the write proceeds anyway.
+  #[serde(default)]
+  pub strict: bool,
+}
+
+/// A single field that failed [`validate_track`], identifying the
+/// offending field and why it was rejected.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagValidationError {
+  pub field: String,
+  pub message: String,
+}
+
+impl std::fmt::Display for TagValidationError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}", self.field, self.message)
+  }
+}
+
+/// Sane bounds for BPM and release year — anything outside these is almost
+/// certainly a parsing/tagging mistake rather than a real value.
+const MIN_BPM: i32 = 20;
+const MAX_BPM: i32 = 300;
+const MIN_YEAR: i32 = 1860; // earliest commercially recorded music
+const MAX_YEAR: i32 = 2100;
+
+/// Validate a [`Track`] against the set of fields DJ software/hardware
+/// expects to be present and well-formed before writing tags to disk,
+/// mirroring how transcode pipelines refuse to process releases whose tags
+/// fail a validity check.
+///
+/// Returns every failing field rather than stopping at the first one, so
+/// the frontend can show exactly what's wrong per file.
+pub fn validate_track(track: &Track) -> std::result::Result<(), Vec<TagValidationError>> {
+  let mut errors = Vec::new();
+
+  if track.title.trim().is_empty() {
+    errors.push(TagValidationError {
+      field: "title".to_string(),
+      message: "title is empty".to_string(),
+    });
+  }
+
+  if track.artist.as_deref().unwrap_or("").trim().is_empty() {
+    errors.push(TagValidationError {
+      field: "artist".to_string(),
+      message: "artist is missing".to_string(),
+    });
+  }
+
+  if let Some(bpm) = track.bpm {
+    if !(MIN_BPM..=MAX_BPM).contains(&bpm) {
+      errors.push(TagValidationError {
+        field: "bpm".to_string(),
+        message: format!("bpm {} is outside the plausible range {}-{}", bpm, MIN_BPM, MAX_BPM),
+      });
+    }
+  }
+
+  if let Some(key) = &track.initial_key {
+    if !is_valid_key_notation(key) {
+      errors.push(TagValidationError {
+        field: "initial_key".to_string(),
+        message: format!("'{}' is not a recognised Camelot or musical key notation", key),
+      });
+    }
+  }
+
+  if let Some(year) = track.year {
+    if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+      errors.push(TagValidationError {
+        field: "year".to_string(),
+        message: format!("year {} is outside the plausible range {}-{}", year, MIN_YEAR, MAX_YEAR),
+      });
+    }
+  }
+
+  if errors.is_empty() {
+    Ok(())
+  } else {
+    Err(errors)
+  }
+}
+
+/// Accepts Camelot notation (`8A`, `12B`) and standard musical notation
+/// (`C`, `C#`, `Db`, `Am`, `F#m`, ...).
+fn is_valid_key_notation(key: &str) -> bool {
+  let key = key.trim();
+  if key.is_empty() {
+    return false;
+  }
+
+  let camelot = key.len() <= 3
+    && key
+      .strip_suffix('A')
+      .or_else(|| key.strip_suffix('B'))
+      .map(|digits| {
+        digits
+          .parse::<u32>()
+          .is_ok_and(|n| (1..=12).contains(&n))
+      })
+      .unwrap_or(false);
+
+  let musical = {
+    let mut chars = key.chars();
+    match chars.next() {
+      Some(letter) if ('A'..='G').contains(&letter) => {
+        let rest: String = chars.collect();
+        matches!(rest.as_str(), "" | "#" | "b" | "m" | "#m" | "bm")
+      }
+      _ => false,
+    }
+  };
+
+  camelot || musical
+}
+
+/// ID3v2 minor version to write for MP3 files. Lofty defaults to v2.4, but
+/// some DJ hardware/older players only read v2.3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Id3v2Version {
+  V3,
+  #[default]
+  V4,
+}
 
 /// Supported audio file extensions
 pub const SUPPORTED_EXTENSIONS: &[&str] = &[
@@ -31,7 +175,19 @@ pub fn is_supported_extension(path: &str) -> bool {
 }
 
 /// Extract audio metadata from a file
+///
+/// Dispatches through [`crate::libs::tag_handler`]: tries the `lofty` backend
+/// first and falls back to `ffprobe` for containers lofty can't fully parse.
 pub fn extract_metadata(file_path: &str) -> Result<Track> {
+  crate::libs::tag_handler::read_track(Path::new(file_path))
+}
+
+/// Read metadata using the `lofty` backend. Used directly by
+/// [`crate::libs::tag_handler::LoftyTagHandler`] and by `extract_metadata`'s
+/// default path.
+pub(crate) fn read_with_lofty(path: &Path) -> Result<Track> {
+  let file_path = path.to_string_lossy().to_string();
+  let file_path = file_path.as_str();
   info!("Extracting metadata from: {}", file_path);
 
   // Parse audio file with lofty
@@ -66,6 +222,21 @@ pub fn extract_metadata(file_path: &str) -> Result<Track> {
 
   let year = tag.and_then(|t| t.year()).map(|y| y as i32);
 
+  // AIDEV-NOTE: Structured release date (year/month/day) for album
+  // grouping/ordering - see `libs::album_order`. Prefers the full recording
+  // date tag over the bare `year` above, falling back to year-only when no
+  // more precise date is present (or doesn't parse).
+  let album_date = tag
+    .and_then(|t| {
+      t.items()
+        .find(|item| matches!(item.key(), &ItemKey::RecordingDate))
+        .and_then(|item| item.value().text())
+        .and_then(parse_album_date)
+    })
+    .or_else(|| year.map(|y| AlbumDate { year: y, month: None, day: None }));
+
+  let track_number = tag.and_then(|t| t.track()).map(|n| n as i32);
+
   // Duration in seconds from lofty, convert to milliseconds for Track
   let duration_seconds = properties.duration().as_secs_f64();
   let duration_ms = (duration_seconds * 1000.0) as i64;
@@ -99,6 +270,18 @@ pub fn extract_metadata(file_path: &str) -> Result<Track> {
       .and_then(|item| item.value().text().map(|s| s.to_string()))
   });
 
+  let catalog_number = tag.and_then(|t| {
+    t.items()
+      .find(|item| matches!(item.key(), &ItemKey::CatalogNumber))
+      .and_then(|item| item.value().text().map(|s| s.to_string()))
+  });
+
+  let isrc = tag.and_then(|t| {
+    t.items()
+      .find(|item| matches!(item.key(), &ItemKey::Isrc))
+      .and_then(|item| item.value().text().map(|s| s.to_string()))
+  });
+
   // AIDEV-NOTE: URL field - lofty doesn't have a generic Url key
   // Using AudioFileUrl as the most appropriate variant for DJ music files
   let url = tag.and_then(|t| {
@@ -122,6 +305,26 @@ pub fn extract_metadata(file_path: &str) -> Result<Track> {
       })
   });
 
+  // Chapters and synchronised lyrics are round-tripped as JSON through a
+  // custom tag item (see module doc on `Track::chapters`)
+  let chapters = tag
+    .and_then(|t| {
+      t.items()
+        .find(|item| matches!(item.key(), ItemKey::Unknown(k) if k == CHAPTERS_ITEM_KEY))
+        .and_then(|item| item.value().text())
+        .and_then(|s| serde_json::from_str(s).ok())
+    })
+    .unwrap_or_default();
+
+  let synced_lyrics = tag
+    .and_then(|t| {
+      t.items()
+        .find(|item| matches!(item.key(), ItemKey::Unknown(k) if k == SYNCED_LYRICS_ITEM_KEY))
+        .and_then(|item| item.value().text())
+        .and_then(|s| serde_json::from_str(s).ok())
+    })
+    .unwrap_or_default();
+
   // Generate track ID from path
   let track_id = Track::generate_id(file_path);
 
@@ -140,9 +343,23 @@ pub fn extract_metadata(file_path: &str) -> Result<Track> {
     initial_key,
     rating,
     label,
+    catalog_number,
+    isrc,
+    musicbrainz_id: None,
+    release_group_id: None,
     waveform_peaks: None,
     added_at: Some(chrono::Utc::now().timestamp_millis()),
     url,
+    start_ms: None,
+    end_ms: None,
+    chapters,
+    album_date,
+    track_number,
+    album_seq: None,
+    artist_sort: None,
+    album_sort: None,
+    title_sort: None,
+    synced_lyrics,
   };
 
   info!(
@@ -155,8 +372,114 @@ pub fn extract_metadata(file_path: &str) -> Result<Track> {
   Ok(track)
 }
 
-/// Write metadata back to audio file
+/// Extract metadata from a file, splitting it into one `Track` per CUE sheet
+/// entry when a `.cue` file accompanies it (continuous mixes, album rips).
+///
+/// Falls back to a single `Track` (identical to `extract_metadata`) when no
+/// CUE sheet is found.
+pub fn extract_metadata_multi(file_path: &str) -> Result<Vec<Track>> {
+  Ok(extract_metadata_multi_with_cues(file_path)?.0)
+}
+
+/// Like [`extract_metadata_multi`], but also returns the `CuePoint`s carried
+/// by the CUE sheet's `INDEX 02`+ entries (sub-positions within a track,
+/// e.g. a drop marked inside a continuous-mix segment), rewritten relative
+/// to each virtual track's own start - see
+/// `cue_sheet::cue_points_for_virtual_track`. Empty when the file has no CUE
+/// sheet, or none of its tracks carry extra indices.
+pub fn extract_metadata_multi_with_cues(file_path: &str) -> Result<(Vec<Track>, Vec<CuePoint>)> {
+  let base_track = extract_metadata(file_path)?;
+
+  let cue_path = match find_accompanying_cue_sheet(file_path) {
+    Some(path) => path,
+    None => return Ok((vec![base_track], Vec::new())),
+  };
+
+  let cue_tracks = parse_cue_sheet(&cue_path)?;
+  if cue_tracks.is_empty() {
+    return Ok((vec![base_track], Vec::new()));
+  }
+
+  info!(
+    "Splitting {} into {} virtual track(s) using {:?}",
+    file_path,
+    cue_tracks.len(),
+    cue_path
+  );
+
+  let mut tracks = Vec::with_capacity(cue_tracks.len());
+  let mut cues = Vec::new();
+  for (idx, cue_track) in cue_tracks.iter().enumerate() {
+    let start_ms = cue_track.start_ms;
+    let end_ms = cue_tracks.get(idx + 1).map(|next| next.start_ms);
+    let duration = end_ms.unwrap_or(base_track.duration) - start_ms;
+
+    let id_source = format!("{}#{}", file_path, start_ms);
+    let id = Track::generate_id(&id_source);
+
+    cues.extend(cue_points_for_virtual_track(cue_track, &id));
+
+    tracks.push(Track {
+      id,
+      title: cue_track
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("{} (Track {})", base_track.title, idx + 1)),
+      artist: cue_track.performer.clone().or_else(|| base_track.artist.clone()),
+      duration,
+      start_ms: Some(start_ms),
+      end_ms,
+      ..base_track.clone()
+    });
+  }
+
+  Ok((tracks, cues))
+}
+
+/// Write metadata back to audio file, using the default write options
+/// (ID3v2.4 where applicable).
+///
+/// Writing is only supported through the `lofty` backend — the `ffprobe`
+/// fallback is read-only, since it exists solely for containers lofty
+/// can't parse in the first place.
 pub fn write_metadata(file_path: &str, track: &Track) -> Result<()> {
+  write_metadata_ext(file_path, track, &WriteMetadataOptions::default())
+}
+
+/// Write metadata back to audio file with explicit [`WriteMetadataOptions`]
+/// (e.g. to target ID3v2.3 for players that don't read v2.4).
+pub fn write_metadata_ext(
+  file_path: &str,
+  track: &Track,
+  options: &WriteMetadataOptions,
+) -> Result<()> {
+  if let Err(errors) = validate_track(track) {
+    let summary = errors
+      .iter()
+      .map(|e| e.to_string())
+      .collect::<Vec<_>>()
+      .join("; ");
+
+    if options.strict {
+      return Err(HarmonyError::Custom(format!(
+        "tag validation failed for {}: {}",
+        file_path, summary
+      )));
+    }
+
+    warn!("tag validation warnings for {}: {}", file_path, summary);
+  }
+
+  write_with_lofty(Path::new(file_path), track, options)
+}
+
+pub(crate) fn write_with_lofty(
+  path: &Path,
+  track: &Track,
+  options: &WriteMetadataOptions,
+) -> Result<()> {
+  let file_path = path.to_string_lossy().to_string();
+  let file_path = file_path.as_str();
   info!("Writing metadata to: {}", file_path);
 
   let mut tagged_file = lofty::read_from_path(file_path)?;
@@ -202,6 +525,14 @@ pub fn write_metadata(file_path: &str, track: &Track) -> Result<()> {
     tag.insert_text(ItemKey::Label, label.clone());
   }
 
+  if let Some(catalog_number) = &track.catalog_number {
+    tag.insert_text(ItemKey::CatalogNumber, catalog_number.clone());
+  }
+
+  if let Some(isrc) = &track.isrc {
+    tag.insert_text(ItemKey::Isrc, isrc.clone());
+  }
+
   if let Some(url) = &track.url {
     tag.insert_text(ItemKey::AudioFileUrl, url.clone());
   }
@@ -212,8 +543,24 @@ pub fn write_metadata(file_path: &str, track: &Track) -> Result<()> {
     tag.insert_text(ItemKey::Popularimeter, popm_value.to_string());
   }
 
-  // Save changes to file with default write options
-  tag.save_to_path(file_path, WriteOptions::default())?;
+  // Chapters / synchronised lyrics - round-tripped as JSON (see
+  // CHAPTERS_ITEM_KEY / SYNCED_LYRICS_ITEM_KEY doc comment above)
+  if !track.chapters.is_empty() {
+    if let Ok(json) = serde_json::to_string(&track.chapters) {
+      tag.insert_text(ItemKey::Unknown(CHAPTERS_ITEM_KEY.to_string()), json);
+    }
+  }
+
+  if !track.synced_lyrics.is_empty() {
+    if let Ok(json) = serde_json::to_string(&track.synced_lyrics) {
+      tag.insert_text(ItemKey::Unknown(SYNCED_LYRICS_ITEM_KEY.to_string()), json);
+    }
+  }
+
+  // Save changes to file, honoring the requested ID3v2 minor version
+  let write_options =
+    WriteOptions::default().use_id3v23(options.id3v2_version == Id3v2Version::V3);
+  tag.save_to_path(file_path, write_options)?;
 
   info!("Metadata written successfully to: {}", file_path);
 
@@ -232,4 +579,70 @@ mod tests {
     assert!(!is_supported_extension("/path/to/song.txt"));
     assert!(!is_supported_extension("/path/to/song"));
   }
+
+  fn sample_track() -> Track {
+    Track {
+      id: "id".to_string(),
+      path: "/tmp/song.mp3".to_string(),
+      title: "Song".to_string(),
+      artist: Some("Artist".to_string()),
+      album: None,
+      genre: None,
+      year: Some(2020),
+      duration: 1000,
+      bitrate: None,
+      comment: None,
+      bpm: Some(128),
+      initial_key: Some("8A".to_string()),
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_validate_track_valid() {
+    assert!(validate_track(&sample_track()).is_ok());
+  }
+
+  #[test]
+  fn test_validate_track_reports_every_bad_field() {
+    let mut track = sample_track();
+    track.title = "  ".to_string();
+    track.artist = None;
+    track.bpm = Some(500);
+    track.initial_key = Some("H#".to_string());
+    track.year = Some(1066);
+
+    let errors = validate_track(&track).unwrap_err();
+    let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(fields, vec!["title", "artist", "bpm", "initial_key", "year"]);
+  }
+
+  #[test]
+  fn test_key_notation() {
+    assert!(is_valid_key_notation("8A"));
+    assert!(is_valid_key_notation("12B"));
+    assert!(is_valid_key_notation("Am"));
+    assert!(is_valid_key_notation("F#m"));
+    assert!(is_valid_key_notation("Db"));
+    assert!(!is_valid_key_notation("13A"));
+    assert!(!is_valid_key_notation("H"));
+  }
 }
@@ -0,0 +1,429 @@
+// AIDEV-NOTE: Auto-cue generation module
+// Turns a decoded audio signal into CuePoints: a single CueType::Grid marker
+// anchoring the beatgrid, plus CueType::HotCue markers at detected
+// structural boundaries (intro/drop/breakdown-style transitions).
+//
+// Reuses the onset-envelope/autocorrelation machinery from `audio_analysis`
+// rather than duplicating it - the BPM search range is narrower here
+// (70-180, the range a DJ actually beatgrids against) and we additionally
+// need the *phase* of the best-fit lag, not just its value.
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::libs::audio_analysis::{autocorrelate, calculate_energy_envelope, decode_audio_file};
+use crate::libs::cue_point::{CuePoint, CueType};
+use crate::libs::traktor::cue_mapper::generate_cue_id;
+use crate::libs::Result;
+
+/// Beatgrid onset-envelope frame size, chosen for the same reason as
+/// `audio_analysis::detect_bpm`'s: fine enough to resolve onsets, coarse
+/// enough that autocorrelation over a whole track is cheap.
+const GRID_FRAME_SIZE: usize = 2048;
+const GRID_HOP_SIZE: usize = 512;
+const GRID_MIN_BPM: u32 = 70;
+const GRID_MAX_BPM: u32 = 180;
+
+/// Structural-boundary analysis window. Wide enough that the feature vector
+/// characterizes a section of the track (not a single transient), narrow
+/// enough to catch an 8-bar-ish structural change.
+const STRUCTURE_WINDOW_SECS: f64 = 0.5;
+
+/// Options for [`generate_auto_cues`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoCueOptions {
+  #[serde(default = "default_sample_rate")]
+  pub sample_rate: u32,
+  /// Skip emitting a new Grid cue if `existing_cues` already has a
+  /// non-deleted one.
+  #[serde(default = "default_true")]
+  pub skip_grid_if_present: bool,
+}
+
+fn default_sample_rate() -> u32 {
+  44100
+}
+fn default_true() -> bool {
+  true
+}
+
+impl Default for AutoCueOptions {
+  fn default() -> Self {
+    Self {
+      sample_rate: default_sample_rate(),
+      skip_grid_if_present: default_true(),
+    }
+  }
+}
+
+/// Analyze `file_path` and return auto-generated CuePoints for `track_id`,
+/// ready to pass to `Database::save_cue_points`.
+///
+/// AIDEV-NOTE: Never touches `existing_cues` - it's only consulted to decide
+/// whether to skip grid generation (per `options.skip_grid_if_present`).
+/// Callers are responsible for merging/deduping against it, same as any
+/// other cue source (see `cue_sheet::import_cue_sheet`).
+pub fn generate_auto_cues(
+  track_id: &str,
+  file_path: &str,
+  existing_cues: &[CuePoint],
+  options: Option<AutoCueOptions>,
+) -> Result<Vec<CuePoint>> {
+  let opts = options.unwrap_or_default();
+  let samples = decode_audio_file(file_path, opts.sample_rate)?;
+
+  let mut cues = Vec::new();
+
+  let grid = if should_skip_grid(existing_cues, opts.skip_grid_if_present) {
+    info!("Skipping grid generation for {}: grid cue already present", track_id);
+    None
+  } else {
+    detect_beatgrid(&samples, opts.sample_rate)
+  };
+
+  if let Some((bpm, anchor_ms)) = grid {
+    info!("Auto-detected grid for {}: {:.2} BPM at {:.1}ms", track_id, bpm, anchor_ms);
+    cues.push(make_cue(
+      track_id,
+      CueType::Grid,
+      anchor_ms,
+      None,
+      Some(bpm),
+    ));
+  }
+
+  let boundaries = detect_structural_boundaries(&samples, opts.sample_rate, grid);
+  for position_ms in boundaries {
+    cues.push(make_cue(track_id, CueType::HotCue, position_ms, None, None));
+  }
+
+  Ok(cues)
+}
+
+/// Whether grid generation should be skipped because `existing_cues`
+/// already has a live (non-deleted) `CueType::Grid` marker.
+fn should_skip_grid(existing_cues: &[CuePoint], skip_if_present: bool) -> bool {
+  skip_if_present
+    && existing_cues
+      .iter()
+      .any(|c| c.cue_type == CueType::Grid && !c.deleted)
+}
+
+fn make_cue(
+  track_id: &str,
+  cue_type: CueType,
+  position_ms: f64,
+  hotcue_slot: Option<i32>,
+  grid_bpm: Option<f64>,
+) -> CuePoint {
+  let id = generate_cue_id(track_id, position_ms, cue_type, hotcue_slot, grid_bpm);
+
+  CuePoint {
+    id,
+    track_id: track_id.to_string(),
+    cue_type,
+    position_ms,
+    length_ms: None,
+    hotcue_slot,
+    name: None,
+    color: None,
+    grid_bpm,
+    order: None,
+    updated_at: chrono::Utc::now().timestamp_millis(),
+    deleted: false,
+  }
+}
+
+/// Estimate tempo and beatgrid anchor from an onset envelope.
+///
+/// Returns `(bpm, anchor_ms)` where `anchor_ms` is the offset of the beat
+/// phase that best aligns with strong onsets, or `None` if the track is too
+/// short or the `GRID_MIN_BPM..=GRID_MAX_BPM` lag range is empty. Otherwise
+/// the strongest lag in that range is used; no confidence threshold applies.
+fn detect_beatgrid(samples: &[f32], sample_rate: u32) -> Option<(f64, f64)> {
+  if samples.is_empty() {
+    return None;
+  }
+
+  let envelope = onset_envelope(samples, GRID_FRAME_SIZE, GRID_HOP_SIZE);
+  if envelope.len() < 100 {
+    warn!("Audio too short for beatgrid detection");
+    return None;
+  }
+
+  let autocorr = autocorrelate(&envelope);
+
+  let min_lag = (60.0 * sample_rate as f64 / (GRID_MAX_BPM as f64 * GRID_HOP_SIZE as f64)) as usize;
+  let max_lag = ((60.0 * sample_rate as f64 / (GRID_MIN_BPM as f64 * GRID_HOP_SIZE as f64)) as usize)
+    .min(autocorr.len().saturating_sub(1));
+
+  if min_lag == 0 || min_lag >= max_lag {
+    warn!("Invalid lag range for beatgrid detection");
+    return None;
+  }
+
+  let (best_lag, _) = (min_lag..=max_lag)
+    .map(|lag| (lag, autocorr[lag]))
+    .fold((min_lag, autocorr[min_lag]), |best, candidate| {
+      if candidate.1 > best.1 {
+        candidate
+      } else {
+        best
+      }
+    });
+
+  let bpm = 60.0 * sample_rate as f64 / (best_lag as f64 * GRID_HOP_SIZE as f64);
+  let phase_frame = best_phase(&envelope, best_lag);
+  let anchor_ms = phase_frame as f64 * GRID_HOP_SIZE as f64 / sample_rate as f64 * 1000.0;
+
+  Some((bpm, anchor_ms))
+}
+
+/// Short-time spectral-flux-style onset envelope: the half-wave-rectified
+/// frame-to-frame energy increase, which peaks at transients (onsets)
+/// instead of just tracking loudness the way the raw energy envelope does.
+fn onset_envelope(samples: &[f32], frame_size: usize, hop_size: usize) -> Vec<f64> {
+  let energy = calculate_energy_envelope(samples, frame_size, hop_size);
+
+  let mut onset = Vec::with_capacity(energy.len());
+  let mut prev = 0.0;
+  for e in energy {
+    onset.push((e - prev).max(0.0));
+    prev = e;
+  }
+  onset
+}
+
+/// Find the phase (0..period) of the beat grid that maximizes the sum of
+/// onset-envelope strength at beat-spaced positions - the offset a real
+/// beatgrid anchor would sit at, rather than an arbitrary autocorrelation
+/// lag.
+fn best_phase(envelope: &[f64], period: usize) -> usize {
+  if period == 0 {
+    return 0;
+  }
+
+  let mut best_phase = 0;
+  let mut best_sum = f64::MIN;
+
+  for phase in 0..period {
+    let mut sum = 0.0;
+    let mut i = phase;
+    while i < envelope.len() {
+      sum += envelope[i];
+      i += period;
+    }
+
+    if sum > best_sum {
+      best_sum = sum;
+      best_phase = phase;
+    }
+  }
+
+  best_phase
+}
+
+/// Per-window timbre feature used for structural segmentation: RMS energy
+/// and zero-crossing rate. Cheap proxies for "loudness" and "brightness"
+/// that don't require an FFT, in keeping with the rest of this module.
+fn window_feature(window: &[f32]) -> [f64; 2] {
+  if window.is_empty() {
+    return [0.0, 0.0];
+  }
+
+  let sum_squares: f64 = window.iter().map(|&s| (s as f64).powi(2)).sum();
+  let rms = (sum_squares / window.len() as f64).sqrt();
+
+  let zero_crossings = window
+    .windows(2)
+    .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+    .count();
+  let zcr = zero_crossings as f64 / window.len() as f64;
+
+  [rms, zcr]
+}
+
+fn euclidean_distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+  ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+/// Segment the track into `STRUCTURE_WINDOW_SECS` windows, compute a
+/// novelty curve from frame-to-frame timbre distance, and return the
+/// positions (ms) of novelty peaks that clear a statistical threshold -
+/// i.e. structural boundaries. When `grid` is known, each position is
+/// quantized to the nearest beat so the resulting hot cues land on-grid.
+fn detect_structural_boundaries(
+  samples: &[f32],
+  sample_rate: u32,
+  grid: Option<(f64, f64)>,
+) -> Vec<f64> {
+  let window_len = (sample_rate as f64 * STRUCTURE_WINDOW_SECS) as usize;
+  if window_len == 0 || samples.len() < window_len * 4 {
+    return Vec::new();
+  }
+
+  let features: Vec<[f64; 2]> = samples
+    .chunks(window_len)
+    .filter(|w| w.len() == window_len)
+    .map(window_feature)
+    .collect();
+
+  if features.len() < 3 {
+    return Vec::new();
+  }
+
+  let novelty: Vec<f64> = features
+    .windows(2)
+    .map(|pair| euclidean_distance(pair[0], pair[1]))
+    .collect();
+
+  let mean = novelty.iter().sum::<f64>() / novelty.len() as f64;
+  let variance = novelty.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / novelty.len() as f64;
+  let threshold = mean + variance.sqrt();
+
+  let mut boundaries = Vec::new();
+  for (i, &value) in novelty.iter().enumerate() {
+    if value <= threshold {
+      continue;
+    }
+    // `novelty[i]` is the distance between window i and i+1 - place the
+    // boundary at the start of the later window.
+    let window_index = i + 1;
+    let position_ms = window_index as f64 * STRUCTURE_WINDOW_SECS * 1000.0;
+
+    let position_ms = match grid {
+      Some((bpm, anchor_ms)) if bpm > 0.0 => quantize_to_beat(position_ms, bpm, anchor_ms),
+      _ => position_ms,
+    };
+
+    boundaries.push(position_ms);
+  }
+
+  boundaries
+}
+
+/// Snap `position_ms` to the nearest beat of a grid anchored at `anchor_ms`
+/// with period implied by `bpm`.
+fn quantize_to_beat(position_ms: f64, bpm: f64, anchor_ms: f64) -> f64 {
+  let period_ms = 60_000.0 / bpm;
+  let beats_from_anchor = (position_ms - anchor_ms) / period_ms;
+  anchor_ms + beats_from_anchor.round() * period_ms
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// A click track: short bursts every `period_samples`, silence between.
+  fn click_track(sample_rate: u32, bpm: f64, duration_secs: f64) -> Vec<f32> {
+    let period_samples = (60.0 / bpm * sample_rate as f64) as usize;
+    let total_samples = (duration_secs * sample_rate as f64) as usize;
+    let click_len = 64;
+
+    let mut samples = vec![0.0f32; total_samples];
+    let mut pos = 0;
+    while pos + click_len < total_samples {
+      for i in 0..click_len {
+        samples[pos + i] = 1.0;
+      }
+      pos += period_samples;
+    }
+    samples
+  }
+
+  #[test]
+  fn test_detect_beatgrid_finds_click_tempo() {
+    let samples = click_track(44100, 128.0, 10.0);
+    let (bpm, _anchor_ms) = detect_beatgrid(&samples, 44100).expect("grid should be detected");
+    assert!((bpm - 128.0).abs() < 2.0, "expected ~128 BPM, got {}", bpm);
+  }
+
+  #[test]
+  fn test_detect_beatgrid_anchor_near_first_click() {
+    let samples = click_track(44100, 120.0, 10.0);
+    let (_bpm, anchor_ms) = detect_beatgrid(&samples, 44100).expect("grid should be detected");
+    // First click is at t=0; the anchor should land within one beat of it.
+    let period_ms = 60_000.0 / 120.0;
+    assert!(anchor_ms < period_ms);
+  }
+
+  #[test]
+  fn test_detect_beatgrid_empty_samples() {
+    assert_eq!(detect_beatgrid(&[], 44100), None);
+  }
+
+  #[test]
+  fn test_detect_structural_boundaries_finds_step_change() {
+    let sample_rate = 44100;
+    let window_len = sample_rate / 2;
+    let mut samples = vec![0.1f32; window_len * 6];
+    // Loud section in the back half - a clear RMS step.
+    for s in samples.iter_mut().skip(window_len * 3) {
+      *s = 0.9;
+    }
+
+    let boundaries = detect_structural_boundaries(&samples, sample_rate, None);
+    assert!(!boundaries.is_empty());
+    // The step happens at window index 3 -> 1.5s in.
+    assert!(boundaries.iter().any(|&ms| (ms - 1500.0).abs() < 500.0));
+  }
+
+  #[test]
+  fn test_detect_structural_boundaries_quantizes_to_grid() {
+    let sample_rate = 44100;
+    let window_len = sample_rate / 2;
+    let mut samples = vec![0.1f32; window_len * 6];
+    for s in samples.iter_mut().skip(window_len * 3) {
+      *s = 0.9;
+    }
+
+    let grid = Some((120.0, 0.0));
+    let boundaries = detect_structural_boundaries(&samples, sample_rate, grid);
+    let period_ms = 60_000.0 / 120.0;
+    for ms in boundaries {
+      let beats = ms / period_ms;
+      assert!((beats - beats.round()).abs() < 1e-6);
+    }
+  }
+
+  #[test]
+  fn test_quantize_to_beat() {
+    // 120 BPM -> 500ms/beat, anchor at 0
+    assert_eq!(quantize_to_beat(510.0, 120.0, 0.0), 500.0);
+    assert_eq!(quantize_to_beat(740.0, 120.0, 0.0), 500.0);
+    assert_eq!(quantize_to_beat(760.0, 120.0, 0.0), 1000.0);
+  }
+
+  fn grid_cue(deleted: bool) -> CuePoint {
+    CuePoint {
+      id: "existing-grid".to_string(),
+      track_id: "track-1".to_string(),
+      cue_type: CueType::Grid,
+      position_ms: 0.0,
+      length_ms: None,
+      hotcue_slot: None,
+      name: None,
+      color: None,
+      grid_bpm: Some(128.0),
+      order: None,
+      updated_at: 0,
+      deleted,
+    }
+  }
+
+  #[test]
+  fn test_should_skip_grid_when_present() {
+    assert!(should_skip_grid(&[grid_cue(false)], true));
+  }
+
+  #[test]
+  fn test_should_skip_grid_ignores_tombstoned() {
+    assert!(!should_skip_grid(&[grid_cue(true)], true));
+  }
+
+  #[test]
+  fn test_should_skip_grid_respects_option() {
+    assert!(!should_skip_grid(&[grid_cue(false)], false));
+  }
+}
@@ -5,7 +5,8 @@
 
 use base64::{engine::general_purpose, Engine as _};
 use lofty::file::TaggedFileExt;
-use log::info;
+use log::{info, warn};
+use regex::Regex;
 use std::path::{Path, PathBuf};
 
 use crate::libs::Result;
@@ -13,9 +14,30 @@ use crate::libs::Result;
 /// Supported cover file extensions
 const COVER_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "gif", "webp"];
 
-/// Common cover file names (case-insensitive)
+/// Common cover file names (case-insensitive), used when no
+/// [`CoverCacheOptions::name_patterns`] are configured.
 const COVER_NAMES: &[&str] = &["album", "albumart", "folder", "cover", "front"];
 
+/// Settings for [`fetch_cover_cached`]: where to keep the on-disk cache, what
+/// directory-cover filenames to look for, and whether to downscale large
+/// embedded art before returning it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverCacheOptions {
+  /// Directory the resolved cover art is cached in, keyed by track ID and
+  /// source file mtime. Created on first use if it doesn't exist.
+  pub cache_dir: PathBuf,
+  /// Case-insensitive regex patterns matched against a directory cover's
+  /// file stem; a file matching any of them qualifies. `None` falls back to
+  /// [`COVER_NAMES`].
+  #[serde(default)]
+  pub name_patterns: Option<Vec<String>>,
+  /// Maximum width/height (in pixels) to downscale embedded art to before
+  /// caching it. `None` keeps the original size.
+  #[serde(default)]
+  pub max_dimension: Option<u32>,
+}
+
 /// Cover art data with format information
 #[derive(Debug, Clone)]
 pub struct CoverArt {
@@ -46,9 +68,20 @@ impl CoverArt {
   }
 }
 
-/// Extract cover art from audio file ID3 tags
-/// Returns the first embedded picture found
+/// Extract cover art from audio file tags
+///
+/// Reads through the `lofty` backend of [`crate::libs::tag_handler`]
+/// (`LoftyTagHandler::read_cover`) only; the `ffprobe` fallback backend is
+/// not consulted, since it never yields cover art (see
+/// `FfprobeTagHandler::read_cover`).
 pub fn extract_cover_from_tags(file_path: &str) -> Result<Option<CoverArt>> {
+  use crate::libs::tag_handler::{LoftyTagHandler, TagHandler};
+  LoftyTagHandler.read_cover(Path::new(file_path))
+}
+
+pub(crate) fn read_cover_with_lofty(path: &Path) -> Result<Option<CoverArt>> {
+  let file_path = path.to_string_lossy().to_string();
+  let file_path = file_path.as_str();
   info!("Extracting cover from tags: {}", file_path);
 
   let tagged_file = lofty::read_from_path(file_path)?;
@@ -90,6 +123,16 @@ pub fn extract_cover_from_tags(file_path: &str) -> Result<Option<CoverArt>> {
 /// Search for cover image files in the directory containing the audio file
 /// Looks for common names: cover.jpg, folder.png, albumart.jpg, etc.
 pub fn find_cover_in_directory(file_path: &str) -> Result<Option<PathBuf>> {
+  find_cover_in_directory_matching(file_path, None)
+}
+
+/// Like [`find_cover_in_directory`], but matches filenames against
+/// `name_patterns` (case-insensitive regexes) instead of the hard-coded
+/// [`COVER_NAMES`] list when patterns are supplied.
+pub fn find_cover_in_directory_matching(
+  file_path: &str,
+  name_patterns: Option<&[String]>,
+) -> Result<Option<PathBuf>> {
   let file_path = Path::new(file_path);
 
   let dir = file_path.parent().ok_or_else(|| {
@@ -98,6 +141,8 @@ pub fn find_cover_in_directory(file_path: &str) -> Result<Option<PathBuf>> {
 
   info!("Searching for cover in directory: {}", dir.display());
 
+  let patterns = compile_name_patterns(name_patterns);
+
   // Read directory entries
   let entries = std::fs::read_dir(dir)?;
 
@@ -110,7 +155,7 @@ pub fn find_cover_in_directory(file_path: &str) -> Result<Option<PathBuf>> {
     }
 
     // Check if it's a valid cover file
-    if is_valid_cover_file(&path) {
+    if is_valid_cover_file(&path, &patterns) {
       info!("Cover found in directory: {}", path.display());
       return Ok(Some(path));
     }
@@ -120,8 +165,39 @@ pub fn find_cover_in_directory(file_path: &str) -> Result<Option<PathBuf>> {
   Ok(None)
 }
 
+/// Compiled filename matcher, either the user-supplied regex patterns or the
+/// default [`COVER_NAMES`] substring list.
+enum NamePatterns {
+  Default,
+  Regex(Vec<Regex>),
+}
+
+fn compile_name_patterns(patterns: Option<&[String]>) -> NamePatterns {
+  match patterns {
+    None => NamePatterns::Default,
+    Some(patterns) => {
+      let compiled = patterns
+        .iter()
+        .filter_map(|pattern| {
+          regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| warn!("Invalid cover name pattern '{}': {}", pattern, e))
+            .ok()
+        })
+        .collect::<Vec<_>>();
+
+      if compiled.is_empty() {
+        NamePatterns::Default
+      } else {
+        NamePatterns::Regex(compiled)
+      }
+    }
+  }
+}
+
 /// Check if a file is a valid cover image based on name and extension
-fn is_valid_cover_file(path: &Path) -> bool {
+fn is_valid_cover_file(path: &Path, patterns: &NamePatterns) -> bool {
   // Check extension
   let extension_valid = path
     .extension()
@@ -134,18 +210,19 @@ fn is_valid_cover_file(path: &Path) -> bool {
   }
 
   // Check filename
-  let name_valid = path
+  path
     .file_stem()
     .and_then(|stem| stem.to_str())
-    .map(|name| {
-      let name_lower = name.to_lowercase();
-      COVER_NAMES
-        .iter()
-        .any(|cover_name| name_lower.contains(cover_name))
+    .map(|name| match patterns {
+      NamePatterns::Default => {
+        let name_lower = name.to_lowercase();
+        COVER_NAMES
+          .iter()
+          .any(|cover_name| name_lower.contains(cover_name))
+      }
+      NamePatterns::Regex(regexes) => regexes.iter().any(|re| re.is_match(name)),
     })
-    .unwrap_or(false);
-
-  name_valid
+    .unwrap_or(false)
 }
 
 /// Smart cover fetch: tries ID3 tags first, then falls back to directory search
@@ -182,6 +259,147 @@ pub fn fetch_cover(file_path: &str, ignore_tags: bool, as_base64: bool) -> Resul
   Ok(None)
 }
 
+/// Like [`fetch_cover`], but consults an on-disk cache before touching tags
+/// or the filesystem, and (when [`CoverCacheOptions::max_dimension`] is set)
+/// downscales large embedded art before caching it. Cache entries are keyed
+/// by `track_id` and the audio file's mtime, so editing or replacing the
+/// audio file invalidates the old entry; replacing only a directory cover
+/// image does not.
+///
+/// Intended for grid/list views that would otherwise re-decode multi-megabyte
+/// embedded JPEGs on every render.
+pub fn fetch_cover_cached(
+  file_path: &str,
+  track_id: &str,
+  ignore_tags: bool,
+  as_base64: bool,
+  options: &CoverCacheOptions,
+) -> Result<Option<String>> {
+  let cache_path = cache_entry_path(file_path, track_id, options)?;
+
+  if let Some(path) = &cache_path {
+    if path.exists() {
+      info!("Cover cache hit for {}: {}", file_path, path.display());
+      return Ok(Some(cover_path_to_url(path, as_base64)?));
+    }
+  }
+
+  let resolved = fetch_cover_uncached(file_path, ignore_tags, options.name_patterns.as_deref())?;
+  let Some((data, mime_type)) = resolved else {
+    return Ok(None);
+  };
+
+  let (data, mime_type) = match options.max_dimension {
+    Some(max_dim) => resize_if_needed(data, &mime_type, max_dim),
+    None => (data, mime_type),
+  };
+
+  if let Some(path) = &cache_path {
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, &data)?;
+    info!("Cached cover for {} at {}", file_path, path.display());
+    return Ok(Some(cover_path_to_url(path, as_base64)?));
+  }
+
+  // No cache path (the source file's mtime couldn't be read) - return inline.
+  if as_base64 {
+    let base64_data = general_purpose::STANDARD.encode(&data);
+    Ok(Some(format!("data:{};base64,{}", mime_type, base64_data)))
+  } else {
+    Ok(None)
+  }
+}
+
+/// Resolve the on-disk path a cache entry for this track/file would live at,
+/// without checking whether it exists yet. Returns `None` if the source
+/// file's mtime can't be read (e.g. it was deleted between calls).
+fn cache_entry_path(
+  file_path: &str,
+  track_id: &str,
+  options: &CoverCacheOptions,
+) -> Result<Option<PathBuf>> {
+  let mtime = match std::fs::metadata(file_path).and_then(|m| m.modified()) {
+    Ok(mtime) => mtime,
+    Err(_) => return Ok(None),
+  };
+  let mtime_secs = mtime
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|d| d.as_secs())
+    .unwrap_or(0);
+
+  Ok(Some(
+    options
+      .cache_dir
+      .join(format!("{}-{}.bin", track_id, mtime_secs)),
+  ))
+}
+
+fn cover_path_to_url(path: &Path, as_base64: bool) -> Result<String> {
+  if as_base64 {
+    let data = std::fs::read(path)?;
+    let mime_type = mime_guess::from_path(path).first_or_octet_stream().to_string();
+    let base64_data = general_purpose::STANDARD.encode(&data);
+    Ok(format!("data:{};base64,{}", mime_type, base64_data))
+  } else {
+    Ok(format!("file://{}", path.display()))
+  }
+}
+
+/// Tag/directory lookup behind [`fetch_cover_cached`], returning the raw
+/// bytes and MIME type instead of a formatted URL. `name_patterns` is passed
+/// to [`find_cover_in_directory_matching`].
+fn fetch_cover_uncached(
+  file_path: &str,
+  ignore_tags: bool,
+  name_patterns: Option<&[String]>,
+) -> Result<Option<(Vec<u8>, String)>> {
+  if !ignore_tags {
+    if let Some(cover) = extract_cover_from_tags(file_path)? {
+      return Ok(Some((cover.data, cover.mime_type)));
+    }
+  }
+
+  if let Some(cover_path) = find_cover_in_directory_matching(file_path, name_patterns)? {
+    let data = std::fs::read(&cover_path)?;
+    let mime_type = mime_guess::from_path(&cover_path)
+      .first_or_octet_stream()
+      .to_string();
+    return Ok(Some((data, mime_type)));
+  }
+
+  Ok(None)
+}
+
+/// Downscale `data` to fit within `max_dim` on its longest side, re-encoding
+/// to JPEG. Falls back to the original bytes/MIME type if decoding fails or
+/// the image is already small enough.
+fn resize_if_needed(data: Vec<u8>, mime_type: &str, max_dim: u32) -> (Vec<u8>, String) {
+  let img = match image::load_from_memory(&data) {
+    Ok(img) => img,
+    Err(e) => {
+      warn!("Could not decode cover art for resizing, caching as-is: {}", e);
+      return (data, mime_type.to_string());
+    }
+  };
+
+  if img.width() <= max_dim && img.height() <= max_dim {
+    return (data, mime_type.to_string());
+  }
+
+  let resized = img.resize(max_dim, max_dim, image::imageops::FilterType::Lanczos3);
+
+  let mut out = Vec::new();
+  match resized.write_to(&mut std::io::Cursor::new(&mut out), image::ImageFormat::Jpeg) {
+    Ok(()) => (out, "image/jpeg".to_string()),
+    Err(e) => {
+      warn!("Could not re-encode resized cover art, caching original: {}", e);
+      (data, mime_type.to_string())
+    }
+  }
+}
+
 /// Read an image file and return as base64 data URL
 pub fn file_to_base64(file_path: &str) -> Result<String> {
   info!("Converting file to base64: {}", file_path);
@@ -201,15 +419,25 @@ mod tests {
 
   #[test]
   fn test_is_valid_cover_file() {
-    assert!(is_valid_cover_file(Path::new("/path/to/cover.jpg")));
-    assert!(is_valid_cover_file(Path::new("/path/to/folder.png")));
-    assert!(is_valid_cover_file(Path::new("/path/to/albumart.jpeg")));
-    assert!(is_valid_cover_file(Path::new("/path/to/front.bmp")));
-    assert!(is_valid_cover_file(Path::new("/path/to/Album.PNG"))); // Case insensitive
-
-    assert!(!is_valid_cover_file(Path::new("/path/to/song.mp3")));
-    assert!(!is_valid_cover_file(Path::new("/path/to/random.jpg")));
-    assert!(!is_valid_cover_file(Path::new("/path/to/cover.txt")));
+    let default_patterns = compile_name_patterns(None);
+
+    assert!(is_valid_cover_file(Path::new("/path/to/cover.jpg"), &default_patterns));
+    assert!(is_valid_cover_file(Path::new("/path/to/folder.png"), &default_patterns));
+    assert!(is_valid_cover_file(Path::new("/path/to/albumart.jpeg"), &default_patterns));
+    assert!(is_valid_cover_file(Path::new("/path/to/front.bmp"), &default_patterns));
+    assert!(is_valid_cover_file(Path::new("/path/to/Album.PNG"), &default_patterns)); // Case insensitive
+
+    assert!(!is_valid_cover_file(Path::new("/path/to/song.mp3"), &default_patterns));
+    assert!(!is_valid_cover_file(Path::new("/path/to/random.jpg"), &default_patterns));
+    assert!(!is_valid_cover_file(Path::new("/path/to/cover.txt"), &default_patterns));
+  }
+
+  #[test]
+  fn test_is_valid_cover_file_with_custom_patterns() {
+    let patterns = compile_name_patterns(Some(&["^art$".to_string()]));
+
+    assert!(is_valid_cover_file(Path::new("/path/to/art.jpg"), &patterns));
+    assert!(!is_valid_cover_file(Path::new("/path/to/cover.jpg"), &patterns));
   }
 
   #[test]
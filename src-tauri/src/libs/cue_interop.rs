@@ -0,0 +1,105 @@
+// AIDEV-NOTE: Hot-cue interchange with other DJ software, alongside
+// `Database::replace_cue_points_for_track` (this produces/consumes
+// `CuePoint`s - callers still persist the result through that method).
+// Rekordbox nests hotcues inside each `collection.xml` `TRACK` entry as
+// `POSITION_MARK` elements (see `libs::rekordbox`); Serato embeds them
+// directly in the audio file's own tags as a `Markers2` GEOB blob (see
+// `libs::serato`). This module is just the format enum + dispatch between
+// the two - the actual encode/decode logic lives in those modules.
+
+use crate::libs::cue_point::CuePoint;
+use crate::libs::rekordbox::{build_position_marks, parse_position_marks, RekordboxPositionMark};
+use crate::libs::serato::{build_markers2_geob, parse_markers2_geob};
+use crate::libs::{HarmonyError, Result};
+
+/// Host DJ software whose hotcue format a `CuePoint` set should round-trip
+/// through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CueInteropFormat {
+  Rekordbox,
+  Serato,
+}
+
+#[derive(serde::Deserialize)]
+struct PositionMarkList {
+  #[serde(rename = "POSITION_MARK", default)]
+  marks: Vec<RekordboxPositionMark>,
+}
+
+/// Serialize `cues` to `format`'s on-disk representation:
+/// - `Rekordbox`: an XML fragment of `<POSITION_MARK>` elements, ready to
+///   nest inside a `collection.xml` `TRACK` entry.
+/// - `Serato`: a base64-encoded `Markers2` GEOB tag payload.
+pub fn export_cue_points(cues: &[CuePoint], format: CueInteropFormat) -> Result<Vec<u8>> {
+  match format {
+    CueInteropFormat::Rekordbox => {
+      let marks = build_position_marks(cues);
+      let fragments = marks
+        .iter()
+        .map(quick_xml::se::to_string)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| HarmonyError::Xml(format!("Failed to serialize POSITION_MARK: {}", e)))?;
+      Ok(fragments.join("\n").into_bytes())
+    }
+    CueInteropFormat::Serato => Ok(build_markers2_geob(cues)),
+  }
+}
+
+/// Parse a hotcue blob produced by `export_cue_points` (or read directly
+/// from an existing Rekordbox/Serato file) back into `CuePoint` rows for
+/// `track_id`.
+pub fn import_cue_points(data: &[u8], format: CueInteropFormat, track_id: &str) -> Result<Vec<CuePoint>> {
+  match format {
+    CueInteropFormat::Rekordbox => {
+      let xml = std::str::from_utf8(data)
+        .map_err(|e| HarmonyError::Xml(format!("Invalid UTF-8 in POSITION_MARK XML: {}", e)))?;
+      let wrapped = format!("<MARKS>{}</MARKS>", xml);
+      let parsed: PositionMarkList = quick_xml::de::from_str(&wrapped)
+        .map_err(|e| HarmonyError::Xml(format!("Failed to parse POSITION_MARK XML: {}", e)))?;
+      Ok(parse_position_marks(&parsed.marks, track_id))
+    }
+    CueInteropFormat::Serato => parse_markers2_geob(data, track_id),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::libs::cue_point::CueType;
+
+  fn sample_cue() -> CuePoint {
+    CuePoint {
+      id: "cue1".to_string(),
+      track_id: "track1".to_string(),
+      cue_type: CueType::HotCue,
+      position_ms: 5_000.0,
+      length_ms: None,
+      hotcue_slot: Some(0),
+      name: Some("Drop".to_string()),
+      color: Some("#00ff00".to_string()),
+      grid_bpm: None,
+      order: Some(0),
+      updated_at: 0,
+      deleted: false,
+    }
+  }
+
+  #[test]
+  fn test_rekordbox_round_trip() {
+    let cues = vec![sample_cue()];
+    let bytes = export_cue_points(&cues, CueInteropFormat::Rekordbox).unwrap();
+    let parsed = import_cue_points(&bytes, CueInteropFormat::Rekordbox, "track1").unwrap();
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].hotcue_slot, Some(0));
+    assert_eq!(parsed[0].color, Some("#00ff00".to_string()));
+  }
+
+  #[test]
+  fn test_serato_round_trip() {
+    let cues = vec![sample_cue()];
+    let bytes = export_cue_points(&cues, CueInteropFormat::Serato).unwrap();
+    let parsed = import_cue_points(&bytes, CueInteropFormat::Serato, "track1").unwrap();
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].hotcue_slot, Some(0));
+  }
+}
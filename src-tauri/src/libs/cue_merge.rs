@@ -0,0 +1,245 @@
+// AIDEV-NOTE: General-purpose three-way cue point merge, used when
+// re-importing cue points from any external source (CUE sheet, Rekordbox,
+// Serato, manual re-scan) into a track that may already carry hand-edited
+// cues. Unlike `libs::traktor::conflict_resolver::merge_cue_points`, which
+// resolves per-cue via an LWW clock (`CuePoint::updated_at`), this compares
+// against a stored snapshot of the last-imported set (the "base") the way
+// `conflict_resolver::merge_track_3way` compares Track fields against a
+// base Track - so a user's local edit is distinguished from a value that
+// simply never changed, without needing every cue to carry its own
+// timestamp.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::libs::cue_point::{CueKey, CuePoint};
+
+/// A matched cue where local and remote both changed away from `base`, but
+/// landed on different results. `base` is `None` when the slot has no prior
+/// snapshot entry (e.g. the track was never merged before).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CueMergeConflict {
+  pub key: CueKey,
+  pub base: Option<CuePoint>,
+  pub local: CuePoint,
+  pub remote: CuePoint,
+}
+
+/// Result of [`merge_cue_points_3way`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CueMerge3WayResult {
+  /// The reconciled cue set, ready to persist as both the track's cue
+  /// points and the new base snapshot.
+  pub merged: Vec<CuePoint>,
+  /// Slots where local and remote disagreed and neither matched `base`.
+  pub conflicts: Vec<CueMergeConflict>,
+}
+
+/// Whether two cues carry the same content, ignoring `id`/`track_id` (which
+/// may legitimately differ between the local and remote copy of "the same"
+/// cue) and `updated_at`/`deleted` (this merge doesn't use the LWW
+/// tombstone scheme).
+fn cue_content_eq(a: &CuePoint, b: &CuePoint) -> bool {
+  a.cue_type == b.cue_type
+    && a.position_ms == b.position_ms
+    && a.length_ms == b.length_ms
+    && a.hotcue_slot == b.hotcue_slot
+    && a.name == b.name
+    && a.color == b.color
+    && a.grid_bpm == b.grid_bpm
+}
+
+fn index_by_key(cues: &[CuePoint]) -> HashMap<CueKey, CuePoint> {
+  cues.iter().map(|cue| (cue.lww_key(), cue.clone())).collect()
+}
+
+/// Three-way reconcile `local` (the current DB rows) against `remote` (a
+/// freshly re-imported set), using `base` (the set captured the last time
+/// this track was merged) to tell genuine edits apart on each side. Cues are
+/// matched across the three sides by [`CuePoint::lww_key`] - hotcue slot
+/// when present, otherwise `(cue_type, position_ms)` quantized to
+/// [`crate::libs::cue_point`]'s position tolerance.
+///
+/// For each key present in `local` or `remote`:
+/// - only in remote -> inserted
+/// - only in local -> preserved
+/// - in both, local unchanged from base -> take remote
+/// - in both, remote unchanged from base (or identical to local) -> keep local
+/// - in both, both changed from base to different values -> conflict;
+///   `keep_local_on_conflict` picks which side survives in `merged` (the
+///   losing side is still reported in [`CueMergeConflict`] so the UI can
+///   offer to flip it)
+///
+/// The caller is expected to persist `merged` both as the track's cue
+/// points and as the new base snapshot for the next merge.
+pub fn merge_cue_points_3way(
+  local: &[CuePoint],
+  remote: &[CuePoint],
+  base: &[CuePoint],
+  track_id: &str,
+  keep_local_on_conflict: bool,
+) -> CueMerge3WayResult {
+  let local_by_key = index_by_key(local);
+  let remote_by_key = index_by_key(remote);
+  let base_by_key = index_by_key(base);
+
+  let mut merged: Vec<CuePoint> = Vec::new();
+  let mut conflicts: Vec<CueMergeConflict> = Vec::new();
+  let mut seen_remote_keys: std::collections::HashSet<CueKey> = std::collections::HashSet::new();
+
+  for local_cue in local {
+    let key = local_cue.lww_key();
+    let stamped_local = CuePoint {
+      track_id: track_id.to_string(),
+      ..local_cue.clone()
+    };
+
+    let Some(remote_cue) = remote_by_key.get(&key) else {
+      // Unmatched local entry - preserved untouched.
+      merged.push(stamped_local);
+      continue;
+    };
+    seen_remote_keys.insert(key);
+
+    let stamped_remote = CuePoint {
+      track_id: track_id.to_string(),
+      ..remote_cue.clone()
+    };
+
+    if cue_content_eq(local_cue, remote_cue) {
+      merged.push(stamped_local);
+      continue;
+    }
+
+    let base_cue = base_by_key.get(&key);
+    let local_unchanged = base_cue.is_some_and(|b| cue_content_eq(local_cue, b));
+    let remote_unchanged = base_cue.is_some_and(|b| cue_content_eq(remote_cue, b));
+
+    if local_unchanged && !remote_unchanged {
+      // Only remote changed since the base - take it.
+      merged.push(stamped_remote);
+    } else if remote_unchanged {
+      // Only local changed since the base - keep local.
+      merged.push(stamped_local);
+    } else {
+      // Both changed from base to different values - a true conflict.
+      conflicts.push(CueMergeConflict {
+        key,
+        base: base_cue.cloned(),
+        local: local_cue.clone(),
+        remote: remote_cue.clone(),
+      });
+      merged.push(if keep_local_on_conflict { stamped_local } else { stamped_remote });
+    }
+  }
+
+  // Remote entries with no local match at all - inserted as new.
+  for remote_cue in remote {
+    let key = remote_cue.lww_key();
+    if seen_remote_keys.contains(&key) {
+      continue;
+    }
+    merged.push(CuePoint {
+      track_id: track_id.to_string(),
+      ..remote_cue.clone()
+    });
+  }
+
+  merged.sort_by(|a, b| {
+    a.position_ms
+      .partial_cmp(&b.position_ms)
+      .unwrap_or(std::cmp::Ordering::Equal)
+  });
+
+  CueMerge3WayResult { merged, conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::libs::cue_point::CueType;
+
+  fn hotcue(slot: i32, position_ms: f64, name: &str) -> CuePoint {
+    CuePoint {
+      id: format!("cue-{}", slot),
+      track_id: "track-1".to_string(),
+      cue_type: CueType::HotCue,
+      position_ms,
+      length_ms: None,
+      hotcue_slot: Some(slot),
+      name: Some(name.to_string()),
+      color: None,
+      grid_bpm: None,
+      order: None,
+      updated_at: 0,
+      deleted: false,
+    }
+  }
+
+  #[test]
+  fn test_remote_only_change_is_taken() {
+    let base = vec![hotcue(1, 1000.0, "Intro")];
+    let local = vec![hotcue(1, 1000.0, "Intro")];
+    let remote = vec![hotcue(1, 1000.0, "Drop")];
+
+    let result = merge_cue_points_3way(&local, &remote, &base, "track-1", false);
+
+    assert!(result.conflicts.is_empty());
+    assert_eq!(result.merged.len(), 1);
+    assert_eq!(result.merged[0].name.as_deref(), Some("Drop"));
+  }
+
+  #[test]
+  fn test_local_only_change_is_kept() {
+    let base = vec![hotcue(1, 1000.0, "Intro")];
+    let local = vec![hotcue(1, 1000.0, "Renamed by user")];
+    let remote = vec![hotcue(1, 1000.0, "Intro")];
+
+    let result = merge_cue_points_3way(&local, &remote, &base, "track-1", false);
+
+    assert!(result.conflicts.is_empty());
+    assert_eq!(result.merged[0].name.as_deref(), Some("Renamed by user"));
+  }
+
+  #[test]
+  fn test_divergent_change_is_a_conflict_and_can_keep_local() {
+    let base = vec![hotcue(1, 1000.0, "Intro")];
+    let local = vec![hotcue(1, 1000.0, "User Name")];
+    let remote = vec![hotcue(1, 1000.0, "Remote Name")];
+
+    let result = merge_cue_points_3way(&local, &remote, &base, "track-1", true);
+
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].local.name.as_deref(), Some("User Name"));
+    assert_eq!(result.conflicts[0].remote.name.as_deref(), Some("Remote Name"));
+    assert_eq!(result.merged[0].name.as_deref(), Some("User Name"));
+  }
+
+  #[test]
+  fn test_divergent_change_can_prefer_remote() {
+    let base = vec![hotcue(1, 1000.0, "Intro")];
+    let local = vec![hotcue(1, 1000.0, "User Name")];
+    let remote = vec![hotcue(1, 1000.0, "Remote Name")];
+
+    let result = merge_cue_points_3way(&local, &remote, &base, "track-1", false);
+
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.merged[0].name.as_deref(), Some("Remote Name"));
+  }
+
+  #[test]
+  fn test_unmatched_remote_is_inserted_and_unmatched_local_is_preserved() {
+    let base: Vec<CuePoint> = vec![];
+    let local = vec![hotcue(1, 1000.0, "Local only")];
+    let remote = vec![hotcue(2, 5000.0, "Remote only")];
+
+    let result = merge_cue_points_3way(&local, &remote, &base, "track-1", false);
+
+    assert!(result.conflicts.is_empty());
+    let names: Vec<_> = result.merged.iter().filter_map(|c| c.name.clone()).collect();
+    assert_eq!(names, vec!["Local only", "Remote only"]);
+  }
+}
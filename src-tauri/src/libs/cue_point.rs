@@ -56,5 +56,40 @@ pub struct CuePoint {
   pub hotcue_slot: Option<i32>,
   pub name: Option<String>,
   pub color: Option<String>,
+  /// Beatgrid tempo for `CueType::Grid` markers, in BPM.
+  pub grid_bpm: Option<f64>,
   pub order: Option<i32>,
+  // AIDEV-NOTE: LWW-map bookkeeping for `conflict_resolver::merge_cue_points`.
+  // `updated_at` is this cue's own last-write timestamp (not the track's),
+  // so re-syncing the same NML twice is a no-op. `deleted` is a tombstone
+  // rather than a row removal, so a delete propagates through merges instead
+  // of looking like "Traktor just doesn't know about this cue yet".
+  #[serde(default)]
+  pub updated_at: i64,
+  #[serde(default)]
+  pub deleted: bool,
+}
+
+/// Stable identity for a cue point across re-syncs, used as the LWW-map key.
+/// Hot cues/loads/etc. with a slot are identified by `(type, slot)`; anything
+/// without one (fades, grid markers) falls back to its position, quantized
+/// so that sub-quarter-second jitter between exports doesn't mint a new key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CueKey {
+  Slot(CueType, i32),
+  Position(CueType, i64),
+}
+
+const CUE_POSITION_QUANTUM_MS: f64 = 250.0;
+
+impl CuePoint {
+  pub fn lww_key(&self) -> CueKey {
+    match self.hotcue_slot {
+      Some(slot) => CueKey::Slot(self.cue_type, slot),
+      None => CueKey::Position(
+        self.cue_type,
+        (self.position_ms / CUE_POSITION_QUANTUM_MS).round() as i64,
+      ),
+    }
+  }
 }
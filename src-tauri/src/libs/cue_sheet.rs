@@ -0,0 +1,585 @@
+// AIDEV-NOTE: CUE sheet parsing for single-file DJ mixes and album rips, plus
+// import/export of CuePoints to/from the same plain-text CUE format (a
+// different concern: here a sheet's INDEX lines *are* the cues, rather than
+// markers splitting one file into several virtual tracks). See
+// `import_cue_sheet`/`export_cue_sheet` below, and `traktor::cue_mapper` for
+// the analogous Traktor NML <-> CuePoint conversion.
+
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+
+use crate::libs::cue_point::{CuePoint, CueType};
+use crate::libs::Result;
+
+/// One `TRACK` entry parsed out of a CUE sheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueSheetTrack {
+  pub title: Option<String>,
+  pub performer: Option<String>,
+  /// Start offset into the referenced audio file, in milliseconds (this
+  /// track's `INDEX 01`).
+  pub start_ms: i64,
+  /// Every `INDEX` line seen for this track, as `(index_number, absolute_ms)`
+  /// into the referenced audio file - including `INDEX 01` itself. Redbook
+  /// CUE sheets allow `INDEX 02` and beyond to mark sub-positions within a
+  /// track (used here to carry hot cues through a continuous-mix split, see
+  /// `cue_points_for_virtual_track`).
+  pub indices: Vec<(u32, i64)>,
+}
+
+/// Look for a `.cue` file accompanying an audio file (same stem, same directory).
+pub fn find_accompanying_cue_sheet(audio_path: &str) -> Option<PathBuf> {
+  let path = Path::new(audio_path);
+  let cue_path = path.with_extension("cue");
+  if cue_path.is_file() {
+    return Some(cue_path);
+  }
+  None
+}
+
+/// Parse MM:SS:FF (75 frames/sec) into milliseconds.
+fn parse_cue_timestamp(timestamp: &str) -> Option<i64> {
+  let parts: Vec<&str> = timestamp.trim().split(':').collect();
+  if parts.len() != 3 {
+    return None;
+  }
+
+  let minutes = parts[0].parse::<i64>().ok()?;
+  let seconds = parts[1].parse::<i64>().ok()?;
+  let frames = parts[2].parse::<i64>().ok()?;
+
+  Some((minutes * 60 + seconds) * 1000 + (frames * 1000) / 75)
+}
+
+/// Strip the surrounding quotes CUE sheets wrap string values in.
+fn unquote(value: &str) -> String {
+  value.trim().trim_matches('"').to_string()
+}
+
+/// Parse a CUE sheet's `TRACK`/`INDEX 01` entries into virtual tracks.
+///
+/// `FILE` lines are not read: every audio `TRACK` in the sheet, including
+/// any under a second `FILE` block, is taken as an offset into the one
+/// audio file the sheet accompanies.
+pub fn parse_cue_sheet(cue_path: &Path) -> Result<Vec<CueSheetTrack>> {
+  info!("Parsing CUE sheet: {:?}", cue_path);
+
+  let contents = std::fs::read_to_string(cue_path)?;
+
+  let mut tracks = Vec::new();
+  let mut current_title: Option<String> = None;
+  let mut current_performer: Option<String> = None;
+  let mut current_start: Option<i64> = None;
+  let mut current_indices: Vec<(u32, i64)> = Vec::new();
+  let mut in_track = false;
+
+  let flush = |tracks: &mut Vec<CueSheetTrack>,
+               title: &mut Option<String>,
+               performer: &mut Option<String>,
+               start: &mut Option<i64>,
+               indices: &mut Vec<(u32, i64)>| {
+    if let Some(start_ms) = start.take() {
+      tracks.push(CueSheetTrack {
+        title: title.take(),
+        performer: performer.take(),
+        start_ms,
+        indices: std::mem::take(indices),
+      });
+    } else {
+      *title = None;
+      *performer = None;
+      indices.clear();
+    }
+  };
+
+  for line in contents.lines() {
+    let trimmed = line.trim();
+
+    if let Some(rest) = trimmed.strip_prefix("TRACK ") {
+      if in_track {
+        flush(
+          &mut tracks,
+          &mut current_title,
+          &mut current_performer,
+          &mut current_start,
+          &mut current_indices,
+        );
+      }
+      in_track = rest.to_uppercase().contains("AUDIO");
+    } else if let Some(rest) = trimmed.strip_prefix("TITLE ") {
+      let title = unquote(rest);
+      if in_track {
+        current_title = Some(title);
+      }
+      // Disc-level TITLE (before any TRACK) is ignored here; the container's
+      // own tags already supply an album-level title via extract_metadata.
+    } else if let Some(rest) = trimmed.strip_prefix("PERFORMER ") {
+      if in_track {
+        current_performer = Some(unquote(rest));
+      }
+    } else if let Some(rest) = trimmed.strip_prefix("INDEX ") {
+      if in_track {
+        let mut parts = rest.splitn(2, char::is_whitespace);
+        let index_number = parts.next().and_then(|n| n.parse::<u32>().ok());
+        let timestamp = parts.next();
+        match (index_number, timestamp.and_then(parse_cue_timestamp)) {
+          (Some(number), Some(ms)) => {
+            if number == 1 {
+              current_start = Some(ms);
+            }
+            current_indices.push((number, ms));
+          }
+          _ => warn!("Unparseable INDEX line in {:?}: {}", cue_path, rest),
+        }
+      }
+    }
+  }
+
+  if in_track {
+    flush(
+      &mut tracks,
+      &mut current_title,
+      &mut current_performer,
+      &mut current_start,
+      &mut current_indices,
+    );
+  }
+
+  info!("Parsed {} virtual track(s) from CUE sheet", tracks.len());
+  Ok(tracks)
+}
+
+/// Derive hot cues for one virtual track produced by [`parse_cue_sheet`],
+/// from any `INDEX` beyond `01` it carried (`INDEX 01` itself is the split
+/// point, not a cue within the track).
+///
+/// Positions are rewritten relative to `cue_track.start_ms` - the virtual
+/// track plays from 0, not from its offset into the parent file, so its own
+/// cues must start from 0 too. `hotcue_slot` is `index_number - 2` so the
+/// first extra index (`02`) lands in slot 0, matching the
+/// `hotcue_slot = track_number - 1` convention `import_cue_sheet` uses for a
+/// sheet's primary `INDEX 01`.
+pub fn cue_points_for_virtual_track(cue_track: &CueSheetTrack, track_id: &str) -> Vec<CuePoint> {
+  let updated_at = chrono::Utc::now().timestamp_millis();
+
+  cue_track
+    .indices
+    .iter()
+    .filter(|(number, _)| *number > 1)
+    .map(|(number, absolute_ms)| {
+      let position_ms = (*absolute_ms - cue_track.start_ms) as f64;
+      let hotcue_slot = Some(*number as i32 - 2);
+
+      CuePoint {
+        id: crate::libs::traktor::cue_mapper::generate_cue_id(
+          track_id,
+          position_ms,
+          CueType::HotCue,
+          hotcue_slot,
+          None,
+        ),
+        track_id: track_id.to_string(),
+        cue_type: CueType::HotCue,
+        position_ms,
+        length_ms: None,
+        hotcue_slot,
+        name: cue_track.title.clone(),
+        color: None,
+        grid_bpm: None,
+        order: None,
+        updated_at,
+        deleted: false,
+      }
+    })
+    .collect()
+}
+
+/// Parse MM:SS:FF (75 frames/sec) into milliseconds, at `f64` precision so
+/// the result can round-trip through [`CuePoint::position_ms`] without the
+/// integer-division rounding `parse_cue_timestamp` accepts for virtual-track
+/// offsets. Frames are clamped to `0..=74` - some sheets carry `75` from an
+/// off-by-one encoder - rather than rejecting the whole line.
+fn parse_index_timestamp(timestamp: &str) -> Option<f64> {
+  let parts: Vec<&str> = timestamp.trim().split(':').collect();
+  if parts.len() != 3 {
+    return None;
+  }
+
+  let minutes = parts[0].parse::<f64>().ok()?;
+  let seconds = parts[1].parse::<f64>().ok()?;
+  let frames = parts[2].parse::<f64>().ok()?.clamp(0.0, 74.0);
+
+  Some(((minutes * 60.0 + seconds) + frames / 75.0) * 1000.0)
+}
+
+/// Format a position in milliseconds back into MM:SS:FF, the inverse of
+/// [`parse_index_timestamp`].
+fn format_index_timestamp(position_ms: f64) -> String {
+  let total_seconds = (position_ms / 1000.0).floor();
+  let minutes = (total_seconds / 60.0) as i64;
+  let seconds = (total_seconds % 60.0) as i64;
+  let frames = (((position_ms / 1000.0) % 1.0) * 75.0).round().clamp(0.0, 74.0) as i64;
+  format!("{:02}:{:02}:{:02}", minutes, seconds, frames)
+}
+
+/// Parse a standard CUE sheet's `INDEX` lines into `CuePoint`s for `track_id`.
+///
+/// `INDEX 01` of each `TRACK` becomes a `CueType::HotCue` with
+/// `hotcue_slot = track_number - 1` and `name` taken from that TRACK's
+/// `TITLE` (if any). `INDEX 00` (the pre-gap) becomes a `CueType::Load` when
+/// `include_pregap` is true, and is otherwise skipped - most CUE sheets in
+/// the wild omit it entirely, so its absence is not itself a warning.
+/// Malformed timecodes are logged and skipped rather than aborting the
+/// whole file, matching `parse_cue_sheet`'s tolerance.
+pub fn import_cue_sheet(cue_path: &Path, track_id: &str, include_pregap: bool) -> Result<Vec<CuePoint>> {
+  info!("Importing cue points from CUE sheet: {:?}", cue_path);
+
+  let contents = std::fs::read_to_string(cue_path)?;
+  let updated_at = chrono::Utc::now().timestamp_millis();
+
+  let mut cues = Vec::new();
+  let mut track_number: Option<i32> = None;
+  let mut current_title: Option<String> = None;
+
+  for line in contents.lines() {
+    let trimmed = line.trim();
+
+    if let Some(rest) = trimmed.strip_prefix("TRACK ") {
+      track_number = rest
+        .split_whitespace()
+        .next()
+        .and_then(|n| n.parse::<i32>().ok());
+      current_title = None;
+    } else if let Some(rest) = trimmed.strip_prefix("TITLE ") {
+      if track_number.is_some() {
+        current_title = Some(unquote(rest));
+      }
+    } else if let Some(rest) = trimmed.strip_prefix("INDEX ") {
+      let Some(track_number) = track_number else {
+        continue;
+      };
+      let mut parts = rest.splitn(2, char::is_whitespace);
+      let Some(index_number) = parts.next().and_then(|n| n.parse::<u32>().ok()) else {
+        warn!("Unparseable INDEX line in {:?}: {}", cue_path, rest);
+        continue;
+      };
+      let Some(timestamp) = parts.next() else {
+        warn!("Missing timecode on INDEX {:02} in {:?}", index_number, cue_path);
+        continue;
+      };
+
+      let Some(position_ms) = parse_index_timestamp(timestamp) else {
+        warn!(
+          "Unparseable INDEX {:02} timestamp in {:?}: {}",
+          index_number, cue_path, timestamp
+        );
+        continue;
+      };
+
+      let (cue_type, hotcue_slot) = match index_number {
+        1 => (CueType::HotCue, Some(track_number - 1)),
+        0 if include_pregap => (CueType::Load, None),
+        _ => continue,
+      };
+
+      let id = crate::libs::traktor::cue_mapper::generate_cue_id(
+        track_id,
+        position_ms,
+        cue_type,
+        hotcue_slot,
+        None,
+      );
+
+      cues.push(CuePoint {
+        id,
+        track_id: track_id.to_string(),
+        cue_type,
+        position_ms,
+        length_ms: None,
+        hotcue_slot,
+        name: current_title.clone(),
+        color: None,
+        grid_bpm: None,
+        order: Some(track_number),
+        updated_at,
+        deleted: false,
+      });
+    }
+  }
+
+  info!("Parsed {} cue point(s) from CUE sheet", cues.len());
+  Ok(cues)
+}
+
+/// Write `cues` out as a standard CUE sheet referencing `audio_filename`.
+///
+/// Only `CueType::HotCue` cues are written, each as its own ascending
+/// `TRACK nn AUDIO` block with an `INDEX 01` line - the CUE format has no
+/// equivalent for loops, fades, or grid markers. Tracks are numbered in
+/// ascending order of `position_ms` regardless of the cues' input order or
+/// `hotcue_slot`, and tombstoned cues (`deleted: true`) are dropped, mirroring
+/// `cue_mapper::map_harmony_cues_to_traktor`.
+pub fn export_cue_sheet(cues: &[CuePoint], audio_filename: &str) -> String {
+  let mut hot_cues: Vec<&CuePoint> = cues
+    .iter()
+    .filter(|cue| !cue.deleted && cue.cue_type == CueType::HotCue)
+    .collect();
+  hot_cues.sort_by(|a, b| a.position_ms.partial_cmp(&b.position_ms).unwrap());
+
+  let mut sheet = format!("FILE \"{}\" WAVE\n", audio_filename);
+  for (idx, cue) in hot_cues.iter().enumerate() {
+    let track_number = idx + 1;
+    sheet.push_str(&format!("  TRACK {:02} AUDIO\n", track_number));
+    if let Some(name) = &cue.name {
+      sheet.push_str(&format!("    TITLE \"{}\"\n", name));
+    }
+    sheet.push_str(&format!(
+      "    INDEX 01 {}\n",
+      format_index_timestamp(cue.position_ms)
+    ));
+  }
+
+  sheet
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_cue_timestamp() {
+    assert_eq!(parse_cue_timestamp("00:00:00"), Some(0));
+    assert_eq!(parse_cue_timestamp("03:45:12"), Some(225160));
+    assert_eq!(parse_cue_timestamp("bogus"), None);
+  }
+
+  #[test]
+  fn test_unquote() {
+    assert_eq!(unquote("\"Track One\""), "Track One");
+    assert_eq!(unquote("Track One"), "Track One");
+  }
+
+  #[test]
+  fn test_parse_cue_sheet_two_tracks() {
+    let dir = tempfile::tempdir().unwrap();
+    let cue_path = dir.path().join("mix.cue");
+    std::fs::write(
+      &cue_path,
+      r#"PERFORMER "Various"
+TITLE "Continuous Mix"
+FILE "mix.flac" WAVE
+  TRACK 01 AUDIO
+    TITLE "Track One"
+    PERFORMER "Artist One"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Track Two"
+    PERFORMER "Artist Two"
+    INDEX 01 03:45:12
+"#,
+    )
+    .unwrap();
+
+    let tracks = parse_cue_sheet(&cue_path).unwrap();
+    assert_eq!(tracks.len(), 2);
+    assert_eq!(tracks[0].title, Some("Track One".to_string()));
+    assert_eq!(tracks[0].start_ms, 0);
+    assert_eq!(tracks[1].title, Some("Track Two".to_string()));
+    assert_eq!(tracks[1].start_ms, 225160);
+  }
+
+  #[test]
+  fn test_parse_index_timestamp() {
+    assert_eq!(parse_index_timestamp("00:00:00"), Some(0.0));
+    assert_eq!(parse_index_timestamp("00:01:37"), Some((1.0 + 37.0 / 75.0) * 1000.0));
+    assert_eq!(parse_index_timestamp("bogus"), None);
+  }
+
+  #[test]
+  fn test_format_index_timestamp_roundtrip() {
+    let ms = parse_index_timestamp("03:45:12").unwrap();
+    assert_eq!(format_index_timestamp(ms), "03:45:12");
+  }
+
+  #[test]
+  fn test_import_cue_sheet_maps_index_01_to_hotcue() {
+    let dir = tempfile::tempdir().unwrap();
+    let cue_path = dir.path().join("mix.cue");
+    std::fs::write(
+      &cue_path,
+      r#"FILE "mix.flac" WAVE
+  TRACK 01 AUDIO
+    TITLE "Intro"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Drop"
+    INDEX 01 01:30:00
+"#,
+    )
+    .unwrap();
+
+    let cues = import_cue_sheet(&cue_path, "track-1", false).unwrap();
+    assert_eq!(cues.len(), 2);
+    assert_eq!(cues[0].cue_type, CueType::HotCue);
+    assert_eq!(cues[0].hotcue_slot, Some(0));
+    assert_eq!(cues[0].name, Some("Intro".to_string()));
+    assert_eq!(cues[0].position_ms, 0.0);
+    assert_eq!(cues[1].hotcue_slot, Some(1));
+    assert_eq!(cues[1].position_ms, 90000.0);
+  }
+
+  #[test]
+  fn test_import_cue_sheet_pregap_flag() {
+    let dir = tempfile::tempdir().unwrap();
+    let cue_path = dir.path().join("mix.cue");
+    std::fs::write(
+      &cue_path,
+      r#"FILE "mix.flac" WAVE
+  TRACK 01 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:02:00
+"#,
+    )
+    .unwrap();
+
+    let skipped = import_cue_sheet(&cue_path, "track-1", false).unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].cue_type, CueType::HotCue);
+
+    let with_pregap = import_cue_sheet(&cue_path, "track-1", true).unwrap();
+    assert_eq!(with_pregap.len(), 2);
+    assert_eq!(with_pregap[0].cue_type, CueType::Load);
+    assert_eq!(with_pregap[1].cue_type, CueType::HotCue);
+  }
+
+  #[test]
+  fn test_import_cue_sheet_skips_malformed_timecode() {
+    let dir = tempfile::tempdir().unwrap();
+    let cue_path = dir.path().join("mix.cue");
+    std::fs::write(
+      &cue_path,
+      r#"FILE "mix.flac" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 garbage
+  TRACK 02 AUDIO
+    INDEX 01 00:01:00
+"#,
+    )
+    .unwrap();
+
+    let cues = import_cue_sheet(&cue_path, "track-1", false).unwrap();
+    assert_eq!(cues.len(), 1);
+    assert_eq!(cues[0].hotcue_slot, Some(1));
+  }
+
+  #[test]
+  fn test_export_cue_sheet_orders_by_position_and_drops_tombstones() {
+    let late = CuePoint {
+      id: "cue-a".to_string(),
+      track_id: "track-1".to_string(),
+      cue_type: CueType::HotCue,
+      position_ms: 90000.0,
+      length_ms: None,
+      hotcue_slot: Some(1),
+      name: Some("Drop".to_string()),
+      color: None,
+      grid_bpm: None,
+      order: None,
+      updated_at: 0,
+      deleted: false,
+    };
+    let early = CuePoint {
+      id: "cue-b".to_string(),
+      track_id: "track-1".to_string(),
+      cue_type: CueType::HotCue,
+      position_ms: 0.0,
+      length_ms: None,
+      hotcue_slot: Some(0),
+      name: Some("Intro".to_string()),
+      color: None,
+      grid_bpm: None,
+      order: None,
+      updated_at: 0,
+      deleted: false,
+    };
+    let mut deleted = early.clone();
+    deleted.id = "cue-c".to_string();
+    deleted.deleted = true;
+    let loop_cue = CuePoint {
+      id: "cue-d".to_string(),
+      track_id: "track-1".to_string(),
+      cue_type: CueType::Loop,
+      position_ms: 45000.0,
+      length_ms: Some(8000.0),
+      hotcue_slot: None,
+      name: None,
+      color: None,
+      grid_bpm: None,
+      order: None,
+      updated_at: 0,
+      deleted: false,
+    };
+
+    let sheet = export_cue_sheet(&[late, early, deleted, loop_cue], "mix.flac");
+
+    assert_eq!(
+      sheet,
+      "FILE \"mix.flac\" WAVE\n  TRACK 01 AUDIO\n    TITLE \"Intro\"\n    INDEX 01 00:00:00\n  TRACK 02 AUDIO\n    TITLE \"Drop\"\n    INDEX 01 01:30:00\n"
+    );
+  }
+
+  #[test]
+  fn test_parse_cue_sheet_captures_extra_indices() {
+    let dir = tempfile::tempdir().unwrap();
+    let cue_path = dir.path().join("mix.cue");
+    std::fs::write(
+      &cue_path,
+      r#"FILE "mix.flac" WAVE
+  TRACK 01 AUDIO
+    TITLE "Track One"
+    INDEX 00 00:58:00
+    INDEX 01 01:00:00
+    INDEX 02 01:30:00
+"#,
+    )
+    .unwrap();
+
+    let tracks = parse_cue_sheet(&cue_path).unwrap();
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(
+      tracks[0].indices,
+      vec![(0, 58000), (1, 60000), (2, 90000)]
+    );
+  }
+
+  #[test]
+  fn test_cue_points_for_virtual_track_rebases_to_split_start() {
+    let cue_track = CueSheetTrack {
+      title: Some("Track One".to_string()),
+      performer: None,
+      start_ms: 60000,
+      indices: vec![(0, 58000), (1, 60000), (2, 90000), (3, 120000)],
+    };
+
+    let cues = cue_points_for_virtual_track(&cue_track, "track-1");
+
+    assert_eq!(cues.len(), 2);
+    assert_eq!(cues[0].position_ms, 30000.0);
+    assert_eq!(cues[0].hotcue_slot, Some(0));
+    assert_eq!(cues[0].cue_type, CueType::HotCue);
+    assert_eq!(cues[1].position_ms, 60000.0);
+    assert_eq!(cues[1].hotcue_slot, Some(1));
+  }
+
+  #[test]
+  fn test_cue_points_for_virtual_track_empty_without_extra_indices() {
+    let cue_track = CueSheetTrack {
+      title: None,
+      performer: None,
+      start_ms: 0,
+      indices: vec![(1, 0)],
+    };
+
+    assert!(cue_points_for_virtual_track(&cue_track, "track-1").is_empty());
+  }
+}
@@ -4,19 +4,55 @@
 
 use log::info;
 use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Mutex;
 
 use crate::libs::cue_point::{CuePoint, CueType};
+use crate::libs::dirstate::FileDirstate;
+use crate::libs::field_clock::{FieldClock, FieldStamp, SourcePriority};
 use crate::libs::folder::Folder;
+use crate::libs::normalize::normalize_key;
 use crate::libs::playlist::Playlist;
+use crate::libs::scan_pipeline::scan_paths;
 use crate::libs::track::Track;
-use crate::libs::Result;
+use crate::libs::traktor::conflict_resolver::changed_mergeable_fields;
+use crate::libs::{CancellationToken, HarmonyError, Result, ScanOptions};
+
+/// Rows per transaction when bulk-upserting tracks - keeps a large library
+/// import from committing once per row, without holding one giant
+/// transaction open for the whole import. Tunable.
+const INSERT_BATCH_SIZE: usize = 1000;
+
+/// Rows per transaction when deleting tracks whose file has vanished from
+/// disk - see `prune_missing_tracks`. Tunable.
+const PRUNE_BATCH_SIZE: usize = 500;
+
+/// Current version of the (external) audio-analysis pipeline that produces
+/// the vectors stored via `Database::save_analysis`. Bump this when the
+/// pipeline changes in a way that makes old vectors incomparable to new
+/// ones; `generate_similar_playlist` skips any row whose stored version
+/// doesn't match, so stale vectors get recomputed rather than compared.
+pub const ANALYSIS_VERSION: i32 = 1;
+
+/// A track's stored acoustic analysis vector plus the pipeline version that
+/// produced it. See `Database::save_analysis`/`get_analysis`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackAnalysis {
+  pub features: Vec<f32>,
+  pub version: i32,
+}
+
+/// Default minimum trigram-similarity score for `Database::search_tracks` to
+/// return a track - below this a match is noise rather than signal.
+pub const DEFAULT_SEARCH_THRESHOLD: f64 = 0.3;
 
 // AIDEV-NOTE: Database wrapper with Mutex for thread-safe access
 // Used as Tauri managed state
 pub struct Database {
   conn: Mutex<Connection>,
+  db_path: PathBuf,
+  reindex_tx: CommandSender,
 }
 
 impl Database {
@@ -36,8 +72,13 @@ impl Database {
     // Enable foreign keys
     conn.pragma_update(None, "foreign_keys", true)?;
 
+    let (reindex_tx, reindex_rx) = crossbeam_channel::unbounded();
+    spawn_reindex_worker(db_path.clone(), CommandReceiver(reindex_rx));
+
     let db = Database {
       conn: Mutex::new(conn),
+      db_path,
+      reindex_tx: CommandSender(reindex_tx),
     };
 
     db.init_schema()?;
@@ -46,6 +87,12 @@ impl Database {
     Ok(db)
   }
 
+  /// Path of the SQLite file backing this database, e.g. so a caller can
+  /// locate it for a backup/export.
+  pub fn db_path(&self) -> &std::path::Path {
+    &self.db_path
+  }
+
   /// Create all tables and indexes
   fn init_schema(&self) -> Result<()> {
     let conn = self.conn.lock().unwrap();
@@ -67,9 +114,19 @@ impl Database {
                 initialKey TEXT,
                 rating TEXT,
                 label TEXT,
+                catalogNumber TEXT,
+                isrc TEXT,
+                musicbrainzId TEXT,
+                releaseGroupId TEXT,
                 waveformPeaks TEXT,
                 addedAt INTEGER,
-                url TEXT
+                url TEXT,
+                startMs INTEGER,
+                endMs INTEGER,
+                chapters TEXT,
+                syncedLyrics TEXT,
+                albumDate TEXT,
+                trackNumber INTEGER
             )",
       [],
     )?;
@@ -89,6 +146,45 @@ impl Database {
     )?;
     conn.execute("CREATE INDEX IF NOT EXISTS IDX_track_bpm ON track(bpm)", [])?;
 
+    // AIDEV-NOTE: Added after the initial `track` table shipped, so existing
+    // databases need a migration rather than just a `CREATE TABLE` column -
+    // `column_exists` guards each `ALTER TABLE ... ADD COLUMN` so re-running
+    // `init_schema` against an already-migrated database is a no-op instead
+    // of an error. `artistSort`/`albumSort`/`titleSort` hold a derived
+    // sort-friendly form of the matching field (see `derive_sort_name`),
+    // unless `track.artist_sort`/`album_sort`/`title_sort` carries an
+    // explicit override, in which case that's stored verbatim instead;
+    // `releaseMonth` mirrors `track.album_date.month` as its own column so
+    // it can be used in `ORDER BY` without a JSON extract; `albumSeq`
+    // mirrors `track.album_seq` (see `AlbumSeq`) for the same reason. All
+    // populated by `upsert_tracks_tx`.
+    for (column, ddl) in [
+      ("artistSort", "ALTER TABLE track ADD COLUMN artistSort TEXT"),
+      ("albumSort", "ALTER TABLE track ADD COLUMN albumSort TEXT"),
+      ("releaseMonth", "ALTER TABLE track ADD COLUMN releaseMonth INTEGER"),
+      ("titleNormalized", "ALTER TABLE track ADD COLUMN titleNormalized TEXT"),
+      ("artistNormalized", "ALTER TABLE track ADD COLUMN artistNormalized TEXT"),
+      ("albumNormalized", "ALTER TABLE track ADD COLUMN albumNormalized TEXT"),
+      ("albumSeq", "ALTER TABLE track ADD COLUMN albumSeq INTEGER"),
+      ("titleSort", "ALTER TABLE track ADD COLUMN titleSort TEXT"),
+    ] {
+      if !column_exists(&conn, "track", column)? {
+        conn.execute(ddl, [])?;
+      }
+    }
+
+    // AIDEV-NOTE: `*Normalized` columns hold the ASCII-folded, lowercased
+    // form of title/artist/album (see `libs::normalize::normalize_key`), so
+    // diacritic/case variants of the same metadata ("Björk" vs "bjork")
+    // compare equal without a JSON extract or runtime fold on every query.
+    // Indexed as a pair since `find_duplicate_tracks_by_key` groups on both
+    // together; populated by `upsert_tracks_tx`/`update_track`.
+    conn.execute(
+      "CREATE INDEX IF NOT EXISTS IDX_track_normalized_artist_title
+             ON track(artistNormalized, titleNormalized)",
+      [],
+    )?;
+
     // Folder table (for Traktor playlist hierarchy)
     conn.execute(
       "CREATE TABLE IF NOT EXISTS folder (
@@ -141,7 +237,10 @@ impl Database {
                 hotcueSlot INTEGER,
                 name TEXT,
                 color TEXT,
-                \"order\" INTEGER
+                gridBpm REAL,
+                \"order\" INTEGER,
+                updatedAt INTEGER NOT NULL DEFAULT 0,
+                deleted INTEGER NOT NULL DEFAULT 0
             )",
       [],
     )?;
@@ -155,6 +254,91 @@ impl Database {
       [],
     )?;
 
+    // AIDEV-NOTE: Snapshot of the cue point set as of the last
+    // `merge_cue_points_for_track` call, the "base" side of
+    // `libs::cue_merge::merge_cue_points_3way`. Stored as JSON rather than
+    // normalized rows since it's never queried, only round-tripped whole.
+    conn.execute(
+      "CREATE TABLE IF NOT EXISTS cuePointBaseSnapshot (
+                trackId TEXT PRIMARY KEY NOT NULL,
+                snapshot TEXT NOT NULL,
+                updatedAt INTEGER NOT NULL
+            )",
+      [],
+    )?;
+
+    // AIDEV-NOTE: LWW clock for the CRDT-based track merge in
+    // `libs::traktor::conflict_resolver`. One row per (track, field) holding
+    // when that field was last written and by whom, so repeated/out-of-order
+    // syncs converge instead of flip-flopping. See `libs::field_clock`.
+    conn.execute(
+      "CREATE TABLE IF NOT EXISTS trackFieldClock (
+                trackId TEXT NOT NULL,
+                fieldName TEXT NOT NULL,
+                updatedAt INTEGER NOT NULL,
+                source TEXT NOT NULL,
+                PRIMARY KEY (trackId, fieldName)
+            )",
+      [],
+    )?;
+
+    // AIDEV-NOTE: Keyed by Traktor path (not trackId) because a delta sync
+    // needs to recognize an entry before it knows whether it matches an
+    // existing track - see `compute_entry_content_hash` and DELTA mode in
+    // `commands::sync_traktor_nml`.
+    conn.execute(
+      "CREATE TABLE IF NOT EXISTS traktorSyncHash (
+                path TEXT PRIMARY KEY,
+                contentHash TEXT NOT NULL,
+                lastSyncedAt INTEGER NOT NULL
+            )",
+      [],
+    )?;
+
+    // AIDEV-NOTE: Keyed by path like `traktorSyncHash` - records each
+    // imported file's size/mtime so `import_library` can classify a rescan
+    // candidate as Added/Modified/Unchanged without re-extracting metadata.
+    // See `libs::dirstate`.
+    conn.execute(
+      "CREATE TABLE IF NOT EXISTS fileDirstate (
+                path TEXT PRIMARY KEY,
+                size INTEGER NOT NULL,
+                mtimeMillis INTEGER NOT NULL,
+                contentHash TEXT,
+                lastScannedAt INTEGER NOT NULL
+            )",
+      [],
+    )?;
+
+    // AIDEV-NOTE: Keyed by path like `traktorSyncHash` - the acoustic feature
+    // vector describes the audio file on disk, not a particular track row,
+    // so it survives a track being re-imported/re-matched. `features` is a
+    // JSON-encoded array of `similarity::FEATURE_DIM` floats; see
+    // `libs::similarity::compute_feature_vector`.
+    conn.execute(
+      "CREATE TABLE IF NOT EXISTS trackFeatureVector (
+                path TEXT PRIMARY KEY,
+                features TEXT NOT NULL,
+                updatedAt INTEGER NOT NULL
+            )",
+      [],
+    )?;
+
+    // AIDEV-NOTE: Keyed by trackId (unlike `trackFeatureVector`, which is
+    // keyed by path) - this stores a bliss-audio-style analysis vector tied
+    // to a specific track row, versioned so `generate_similar_playlist` can
+    // skip vectors left behind by an older analysis pipeline instead of
+    // comparing them as if they were in the same feature space. `features`
+    // is a raw little-endian `f32` BLOB; see `save_analysis`/`get_analysis`.
+    conn.execute(
+      "CREATE TABLE IF NOT EXISTS trackAnalysis (
+                trackId TEXT PRIMARY KEY NOT NULL,
+                features BLOB NOT NULL,
+                version INTEGER NOT NULL
+            )",
+      [],
+    )?;
+
     Ok(())
   }
 
@@ -166,8 +350,10 @@ impl Database {
   pub fn get_all_tracks(&self) -> Result<Vec<Track>> {
     let conn = self.conn.lock().unwrap();
     let mut stmt = conn.prepare(
-      "SELECT id, path, title, artist, album, genre, year, duration, bitrate, 
-                    comment, bpm, initialKey, rating, label, waveformPeaks, addedAt, url 
+      "SELECT id, path, title, artist, album, genre, year, duration, bitrate,
+                    comment, bpm, initialKey, rating, label, catalogNumber, isrc, musicbrainzId, releaseGroupId, waveformPeaks, addedAt, url,
+                    startMs, endMs, chapters, syncedLyrics, albumDate, trackNumber, albumSeq,
+                    artistSort, albumSort, titleSort
              FROM track",
     )?;
 
@@ -190,11 +376,108 @@ impl Database {
             .get::<_, Option<String>>(12)?
             .and_then(|s| serde_json::from_str(&s).ok()),
           label: row.get(13)?,
+          catalog_number: row.get(14)?,
+          isrc: row.get(15)?,
+          musicbrainz_id: row.get(16)?,
+          release_group_id: row.get(17)?,
           waveform_peaks: row
-            .get::<_, Option<String>>(14)?
+            .get::<_, Option<String>>(18)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+          added_at: row.get(19)?,
+          url: row.get(20)?,
+          start_ms: row.get(21)?,
+          end_ms: row.get(22)?,
+          chapters: row
+            .get::<_, Option<String>>(23)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+          synced_lyrics: row
+            .get::<_, Option<String>>(24)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+          album_date: row
+            .get::<_, Option<String>>(25)?
             .and_then(|s| serde_json::from_str(&s).ok()),
-          added_at: row.get(15)?,
-          url: row.get(16)?,
+          track_number: row.get(26)?,
+          album_seq: row.get::<_, Option<i64>>(27)?.map(|seq| crate::libs::track::AlbumSeq(seq as u8)),
+          artist_sort: row.get(28)?,
+          album_sort: row.get(29)?,
+          title_sort: row.get(30)?,
+        })
+      })?
+      .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(tracks)
+  }
+
+  /// Get all tracks in deterministic display order: by `artistSort` (falling
+  /// back to `artist` when unset), then `year`, then `releaseMonth` (nulls
+  /// sort last, not first - an unknown month shouldn't jump a reissue ahead
+  /// of ones with a known month), then `albumSort`, then `titleSort`
+  /// (falling back to `title`). Mirrors how collection managers fall back
+  /// to `<field>_sort` tags and break year ties by month; see
+  /// `derive_sort_name` for how the sort columns are populated, and
+  /// `Track::artist_sort`/`album_sort`/`title_sort` for how a track can
+  /// override the derived default.
+  pub fn get_all_tracks_sorted(&self) -> Result<Vec<Track>> {
+    let conn = self.conn.lock().unwrap();
+    let mut stmt = conn.prepare(
+      "SELECT id, path, title, artist, album, genre, year, duration, bitrate,
+                    comment, bpm, initialKey, rating, label, catalogNumber, isrc, musicbrainzId, releaseGroupId, waveformPeaks, addedAt, url,
+                    startMs, endMs, chapters, syncedLyrics, albumDate, trackNumber, albumSeq,
+                    artistSort, albumSort, titleSort
+             FROM track
+             ORDER BY COALESCE(artistSort, artist), year,
+                      releaseMonth IS NULL, releaseMonth,
+                      albumSort, COALESCE(titleSort, title)",
+    )?;
+
+    let tracks = stmt
+      .query_map([], |row| {
+        Ok(Track {
+          id: row.get(0)?,
+          path: row.get(1)?,
+          title: row.get(2)?,
+          artist: row.get(3)?,
+          album: row.get(4)?,
+          genre: row.get(5)?,
+          year: row.get(6)?,
+          duration: row.get(7)?,
+          bitrate: row.get(8)?,
+          comment: row.get(9)?,
+          bpm: row.get(10)?,
+          initial_key: row.get(11)?,
+          rating: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+          label: row.get(13)?,
+          catalog_number: row.get(14)?,
+          isrc: row.get(15)?,
+          musicbrainz_id: row.get(16)?,
+          release_group_id: row.get(17)?,
+          waveform_peaks: row
+            .get::<_, Option<String>>(18)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+          added_at: row.get(19)?,
+          url: row.get(20)?,
+          start_ms: row.get(21)?,
+          end_ms: row.get(22)?,
+          chapters: row
+            .get::<_, Option<String>>(23)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+          synced_lyrics: row
+            .get::<_, Option<String>>(24)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+          album_date: row
+            .get::<_, Option<String>>(25)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+          track_number: row.get(26)?,
+          album_seq: row.get::<_, Option<i64>>(27)?.map(|seq| crate::libs::track::AlbumSeq(seq as u8)),
+          artist_sort: row.get(28)?,
+          album_sort: row.get(29)?,
+          title_sort: row.get(30)?,
         })
       })?
       .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -206,8 +489,10 @@ impl Database {
   pub fn get_track_by_id(&self, track_id: &str) -> Result<Option<Track>> {
     let conn = self.conn.lock().unwrap();
     let mut stmt = conn.prepare(
-      "SELECT id, path, title, artist, album, genre, year, duration, bitrate, 
-                    comment, bpm, initialKey, rating, label, waveformPeaks, addedAt, url 
+      "SELECT id, path, title, artist, album, genre, year, duration, bitrate,
+                    comment, bpm, initialKey, rating, label, catalogNumber, isrc, musicbrainzId, releaseGroupId, waveformPeaks, addedAt, url,
+                    startMs, endMs, chapters, syncedLyrics, albumDate, trackNumber, albumSeq,
+                    artistSort, albumSort, titleSort
              FROM track WHERE id = ?1",
     )?;
 
@@ -230,11 +515,33 @@ impl Database {
             .get::<_, Option<String>>(12)?
             .and_then(|s| serde_json::from_str(&s).ok()),
           label: row.get(13)?,
+          catalog_number: row.get(14)?,
+          isrc: row.get(15)?,
+          musicbrainz_id: row.get(16)?,
+          release_group_id: row.get(17)?,
           waveform_peaks: row
-            .get::<_, Option<String>>(14)?
+            .get::<_, Option<String>>(18)?
             .and_then(|s| serde_json::from_str(&s).ok()),
-          added_at: row.get(15)?,
-          url: row.get(16)?,
+          added_at: row.get(19)?,
+          url: row.get(20)?,
+          start_ms: row.get(21)?,
+          end_ms: row.get(22)?,
+          chapters: row
+            .get::<_, Option<String>>(23)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+          synced_lyrics: row
+            .get::<_, Option<String>>(24)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+          album_date: row
+            .get::<_, Option<String>>(25)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+          track_number: row.get(26)?,
+          album_seq: row.get::<_, Option<i64>>(27)?.map(|seq| crate::libs::track::AlbumSeq(seq as u8)),
+          artist_sort: row.get(28)?,
+          album_sort: row.get(29)?,
+          title_sort: row.get(30)?,
         })
       })
       .optional()?;
@@ -242,66 +549,203 @@ impl Database {
     Ok(track)
   }
 
-  /// Insert multiple tracks (used during library import)
-  pub fn insert_tracks(&self, tracks: &[Track]) -> Result<()> {
+  /// Fuzzy-search the library by title/artist/album using trigram
+  /// (3-character shingle) similarity instead of an exact substring match,
+  /// so typos and partial words ("daft pnk") still surface "Daft Punk".
+  /// Both the query and each field are folded through `normalize_key`
+  /// before shingling, so diacritic/case variants ("Björk" vs "bjork")
+  /// score identically to an exact match rather than merely a close one.
+  /// Scores each field independently with Jaccard similarity over its
+  /// shingle set, takes the max across fields as the track's score,
+  /// discards anything below `DEFAULT_SEARCH_THRESHOLD`, and returns the
+  /// top `limit` tracks sorted by descending score.
+  pub fn search_tracks(&self, query: &str, limit: usize) -> Result<Vec<Track>> {
+    let query_shingles = trigram_shingles(&normalize_key(query));
+    if query_shingles.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let mut scored: Vec<(f64, Track)> = self
+      .get_all_tracks()?
+      .into_iter()
+      .filter_map(|track| {
+        let score = [Some(track.title.as_str()), track.artist.as_deref(), track.album.as_deref()]
+          .into_iter()
+          .flatten()
+          .map(|field| trigram_similarity(&query_shingles, &trigram_shingles(&normalize_key(field))))
+          .fold(0.0_f64, f64::max);
+
+        (score >= DEFAULT_SEARCH_THRESHOLD).then_some((score, track))
+      })
+      .collect();
+
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(limit);
+
+    Ok(scored.into_iter().map(|(_, track)| track).collect())
+  }
+
+  /// Group tracks that share the same normalized `(artist, title)` key -
+  /// see `libs::normalize::normalize_key` - so re-encoded or re-tagged
+  /// copies of the same recording ("Björk - Venus as a Boy" vs "BJORK -
+  /// VENUS AS A BOY") surface as a duplicate candidate even though their
+  /// raw metadata doesn't match exactly. Unlike
+  /// `duplicate_detection::find_duplicate_tracks`, which fingerprints audio
+  /// content, this is a cheap metadata-only pass driven entirely by the
+  /// indexed `artistNormalized`/`titleNormalized` columns. Tracks with an
+  /// empty normalized title (no title at all) are excluded, since an empty
+  /// key would otherwise group every untitled track together.
+  pub fn find_duplicate_tracks_by_key(&self) -> Result<Vec<Vec<Track>>> {
     let conn = self.conn.lock().unwrap();
     let mut stmt = conn.prepare(
-      "INSERT INTO track (id, path, title, artist, album, genre, year, duration, bitrate,
-                               comment, bpm, initialKey, rating, label, waveformPeaks, addedAt, url)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
-             ON CONFLICT(id) DO UPDATE SET
-                title = excluded.title,
-                artist = excluded.artist,
-                album = excluded.album,
-                genre = excluded.genre,
-                year = excluded.year,
-                duration = excluded.duration,
-                bitrate = excluded.bitrate,
-                comment = excluded.comment,
-                bpm = excluded.bpm,
-                initialKey = excluded.initialKey,
-                rating = excluded.rating,
-                label = excluded.label,
-                waveformPeaks = excluded.waveformPeaks,
-                url = excluded.url",
+      "SELECT id, path, title, artist, album, genre, year, duration, bitrate,
+                    comment, bpm, initialKey, rating, label, catalogNumber, isrc, musicbrainzId, releaseGroupId, waveformPeaks, addedAt, url,
+                    startMs, endMs, chapters, syncedLyrics, albumDate, trackNumber, albumSeq,
+                    artistSort, albumSort, titleSort
+             FROM track
+             WHERE titleNormalized IS NOT NULL AND titleNormalized != ''
+             ORDER BY COALESCE(artistNormalized, ''), titleNormalized",
     )?;
 
+    let tracks = stmt
+      .query_map([], |row| {
+        Ok(Track {
+          id: row.get(0)?,
+          path: row.get(1)?,
+          title: row.get(2)?,
+          artist: row.get(3)?,
+          album: row.get(4)?,
+          genre: row.get(5)?,
+          year: row.get(6)?,
+          duration: row.get(7)?,
+          bitrate: row.get(8)?,
+          comment: row.get(9)?,
+          bpm: row.get(10)?,
+          initial_key: row.get(11)?,
+          rating: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+          label: row.get(13)?,
+          catalog_number: row.get(14)?,
+          isrc: row.get(15)?,
+          musicbrainz_id: row.get(16)?,
+          release_group_id: row.get(17)?,
+          waveform_peaks: row
+            .get::<_, Option<String>>(18)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+          added_at: row.get(19)?,
+          url: row.get(20)?,
+          start_ms: row.get(21)?,
+          end_ms: row.get(22)?,
+          chapters: row
+            .get::<_, Option<String>>(23)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+          synced_lyrics: row
+            .get::<_, Option<String>>(24)?
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+          album_date: row
+            .get::<_, Option<String>>(25)?
+            .and_then(|s| serde_json::from_str(&s).ok()),
+          track_number: row.get(26)?,
+          album_seq: row.get::<_, Option<i64>>(27)?.map(|seq| crate::libs::track::AlbumSeq(seq as u8)),
+          artist_sort: row.get(28)?,
+          album_sort: row.get(29)?,
+          title_sort: row.get(30)?,
+        })
+      })?
+      .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut groups: Vec<Vec<Track>> = Vec::new();
     for track in tracks {
-      let rating_json = track
-        .rating
-        .as_ref()
-        .and_then(|r| serde_json::to_string(r).ok());
-      let waveform_json = track
-        .waveform_peaks
-        .as_ref()
-        .and_then(|w| serde_json::to_string(w).ok());
+      let key = (normalize_key(track.artist.as_deref().unwrap_or("")), normalize_key(&track.title));
+      match groups.last_mut() {
+        Some(group)
+          if (normalize_key(group[0].artist.as_deref().unwrap_or("")), normalize_key(&group[0].title)) == key =>
+        {
+          group.push(track);
+        }
+        _ => groups.push(vec![track]),
+      }
+    }
 
-      stmt.execute(params![
-        track.id,
-        track.path,
-        track.title,
-        track.artist,
-        track.album,
-        track.genre,
-        track.year,
-        track.duration,
-        track.bitrate,
-        track.comment,
-        track.bpm,
-        track.initial_key,
-        rating_json,
-        track.label,
-        waveform_json,
-        track.added_at,
-        track.url,
-      ])?;
+    groups.retain(|group| group.len() > 1);
+    Ok(groups)
+  }
+
+  /// Insert multiple tracks (used during library import).
+  ///
+  /// AIDEV-NOTE: Batches upserts into `INSERT_BATCH_SIZE`-row transactions
+  /// instead of auto-committing every row - a multi-thousand-track import
+  /// used to mean one fsync per row, which dominated import time. See
+  /// `upsert_tracks_tx`, shared with the background reindex worker.
+  pub fn insert_tracks(&self, tracks: &[Track]) -> Result<()> {
+    let mut conn = self.conn.lock().unwrap();
+
+    for batch in tracks.chunks(INSERT_BATCH_SIZE) {
+      let tx = conn.transaction()?;
+      upsert_tracks_tx(&tx, batch)?;
+      tx.commit()?;
     }
 
     Ok(())
   }
 
+  /// Delete `track` rows whose `path` no longer exists on disk, in batches
+  /// of `PRUNE_BATCH_SIZE` per transaction. Returns the number of tracks
+  /// removed. This is the second pass of a background reindex (see
+  /// `trigger_reindex`), run after every root has been walked so a file that
+  /// simply moved within a root isn't pruned before its new path is seen.
+  pub fn prune_missing_tracks(&self) -> Result<usize> {
+    let mut conn = self.conn.lock().unwrap();
+    prune_missing_tracks_conn(&mut conn)
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // ── Background Reindex (Polaris-style worker thread) ──
+  // ═══════════════════════════════════════════════════════════════════════════
+
+  /// Kick off a background reindex of `roots` on the long-lived worker
+  /// thread spawned in [`Database::new`], and return immediately. The
+  /// worker walks `roots` through `libs::scan_pipeline`'s parallel
+  /// traverser/extractor pipeline (tuned by `options`), upserting batches of
+  /// `options.writer_batch_size` tracks, then prunes tracks whose file has
+  /// vanished (see `prune_missing_tracks`), streaming a [`ReindexProgress`]
+  /// update through `on_progress` after every file and pass so the UI can
+  /// show import status. Returns an error only if the worker thread itself
+  /// has died.
+  pub fn trigger_reindex(
+    &self,
+    roots: Vec<PathBuf>,
+    options: ScanOptions,
+    on_progress: impl FnMut(ReindexProgress) + Send + 'static,
+  ) -> Result<()> {
+    self
+      .reindex_tx
+      .0
+      .send(IndexCommand::Reindex {
+        roots,
+        options,
+        on_progress: Box::new(on_progress),
+      })
+      .map_err(|_| HarmonyError::Custom("reindex worker thread has stopped".to_string()))?;
+
+    Ok(())
+  }
+
   /// Update a single track
+  ///
+  /// AIDEV-NOTE: This is the single universal mutation path for track edits,
+  /// so it doubles as the place we bump the CRDT `FieldClock` (see
+  /// `libs::field_clock`, `libs::traktor::conflict_resolver`): whichever
+  /// mergeable fields changed get stamped "now, Harmony" so a later Traktor
+  /// sync knows this edit is newer than whatever Traktor last saw.
+  /// `get_track_by_id` must run before the connection lock is taken, since
+  /// `Mutex<Connection>` isn't reentrant.
   pub fn update_track(&self, track: &Track) -> Result<()> {
+    let previous = self.get_track_by_id(&track.id)?;
+
     let conn = self.conn.lock().unwrap();
     let rating_json = track
       .rating
@@ -311,12 +755,35 @@ impl Database {
       .waveform_peaks
       .as_ref()
       .and_then(|w| serde_json::to_string(w).ok());
+    let chapters_json = (!track.chapters.is_empty())
+      .then(|| serde_json::to_string(&track.chapters).ok())
+      .flatten();
+    let synced_lyrics_json = (!track.synced_lyrics.is_empty())
+      .then(|| serde_json::to_string(&track.synced_lyrics).ok())
+      .flatten();
+    let album_date_json = track
+      .album_date
+      .as_ref()
+      .and_then(|d| serde_json::to_string(d).ok());
+    let artist_sort = track.artist_sort.clone().or_else(|| track.artist.as_deref().map(derive_sort_name));
+    let album_sort = track.album_sort.clone().or_else(|| track.album.as_deref().map(derive_sort_name));
+    let title_sort = Some(track.title_sort.clone().unwrap_or_else(|| derive_sort_name(&track.title)));
+    let release_month = track.album_date.as_ref().and_then(|d| d.month);
+    let title_normalized = normalize_key(&track.title);
+    let artist_normalized = track.artist.as_deref().map(normalize_key);
+    let album_normalized = track.album.as_deref().map(normalize_key);
+    let album_seq = track.album_seq.map(|seq| seq.0 as i64);
 
     conn.execute(
-      "UPDATE track SET 
+      "UPDATE track SET
                 path = ?2, title = ?3, artist = ?4, album = ?5, genre = ?6, year = ?7,
                 duration = ?8, bitrate = ?9, comment = ?10, bpm = ?11, initialKey = ?12,
-                rating = ?13, label = ?14, waveformPeaks = ?15, url = ?16
+                rating = ?13, label = ?14, catalogNumber = ?15, isrc = ?16,
+                musicbrainzId = ?17, releaseGroupId = ?18, waveformPeaks = ?19,
+                url = ?20, startMs = ?21, endMs = ?22, chapters = ?23, syncedLyrics = ?24,
+                albumDate = ?25, trackNumber = ?26, artistSort = ?27, albumSort = ?28,
+                releaseMonth = ?29, titleNormalized = ?30, artistNormalized = ?31,
+                albumNormalized = ?32, albumSeq = ?33, titleSort = ?34
              WHERE id = ?1",
       params![
         track.id,
@@ -333,21 +800,68 @@ impl Database {
         track.initial_key,
         rating_json,
         track.label,
+        track.catalog_number,
+        track.isrc,
+        track.musicbrainz_id,
+        track.release_group_id,
         waveform_json,
         track.url,
+        track.start_ms,
+        track.end_ms,
+        chapters_json,
+        synced_lyrics_json,
+        album_date_json,
+        track.track_number,
+        artist_sort,
+        album_sort,
+        release_month,
+        title_normalized,
+        artist_normalized,
+        album_normalized,
+        album_seq,
+        title_sort,
       ],
     )?;
 
+    drop(conn);
+
+    if let Some(previous) = previous {
+      let changed = changed_mergeable_fields(&previous, track);
+      if !changed.is_empty() {
+        let mut clock = self.get_field_clock(&track.id)?;
+        let stamp = FieldStamp {
+          updated_at: chrono::Utc::now().timestamp_millis(),
+          source: SourcePriority::Harmony,
+        };
+        for field in changed {
+          clock.insert(field.to_string(), stamp);
+        }
+        self.save_field_clock(&track.id, &clock)?;
+      }
+    }
+
     Ok(())
   }
 
-  /// Delete tracks by IDs
+  /// Delete tracks by IDs, cascading to their cue points in the same
+  /// transaction - `cuePoint` has no `FOREIGN KEY ... ON DELETE CASCADE` (it
+  /// can't: it's keyed to Traktor `CueKey` merges that survive a track being
+  /// re-imported), so this mirrors the `DELETE FROM cuePoint WHERE trackId =
+  /// ?` pattern by hand instead of relying on one.
   pub fn delete_tracks(&self, track_ids: &[String]) -> Result<()> {
-    let conn = self.conn.lock().unwrap();
-    let mut stmt = conn.prepare("DELETE FROM track WHERE id = ?1")?;
-
-    for id in track_ids {
-      stmt.execute([id])?;
+    let mut conn = self.conn.lock().unwrap();
+
+    for batch in track_ids.chunks(PRUNE_BATCH_SIZE) {
+      let tx = conn.transaction()?;
+      {
+        let mut delete_cues = tx.prepare("DELETE FROM cuePoint WHERE trackId = ?1")?;
+        let mut delete_track = tx.prepare("DELETE FROM track WHERE id = ?1")?;
+        for id in batch {
+          delete_cues.execute([id])?;
+          delete_track.execute([id])?;
+        }
+      }
+      tx.commit()?;
     }
 
     Ok(())
@@ -394,7 +908,10 @@ impl Database {
       let mut stmt = conn.prepare(
         "SELECT t.id, t.path, t.title, t.artist, t.album, t.genre, t.year, t.duration,
                        t.bitrate, t.comment, t.bpm, t.initialKey, t.rating, t.label,
-                       t.waveformPeaks, t.addedAt, t.url
+                       t.catalogNumber, t.isrc, t.musicbrainzId, t.releaseGroupId,
+                       t.waveformPeaks, t.addedAt, t.url, t.startMs, t.endMs,
+                       t.chapters, t.syncedLyrics, t.albumDate, t.trackNumber, t.albumSeq,
+                       t.artistSort, t.albumSort, t.titleSort
                 FROM track t
                 INNER JOIN playlistTrack pt ON t.id = pt.trackId
                 WHERE pt.playlistId = ?1
@@ -420,11 +937,33 @@ impl Database {
               .get::<_, Option<String>>(12)?
               .and_then(|s| serde_json::from_str(&s).ok()),
             label: row.get(13)?,
+            catalog_number: row.get(14)?,
+            isrc: row.get(15)?,
+            musicbrainz_id: row.get(16)?,
+            release_group_id: row.get(17)?,
             waveform_peaks: row
-              .get::<_, Option<String>>(14)?
+              .get::<_, Option<String>>(18)?
+              .and_then(|s| serde_json::from_str(&s).ok()),
+            added_at: row.get(19)?,
+            url: row.get(20)?,
+            start_ms: row.get(21)?,
+            end_ms: row.get(22)?,
+            chapters: row
+              .get::<_, Option<String>>(23)?
+              .and_then(|s| serde_json::from_str(&s).ok())
+              .unwrap_or_default(),
+            synced_lyrics: row
+              .get::<_, Option<String>>(24)?
+              .and_then(|s| serde_json::from_str(&s).ok())
+              .unwrap_or_default(),
+            album_date: row
+              .get::<_, Option<String>>(25)?
               .and_then(|s| serde_json::from_str(&s).ok()),
-            added_at: row.get(15)?,
-            url: row.get(16)?,
+            track_number: row.get(26)?,
+            album_seq: row.get::<_, Option<i64>>(27)?.map(|seq| crate::libs::track::AlbumSeq(seq as u8)),
+            artist_sort: row.get(28)?,
+            album_sort: row.get(29)?,
+            title_sort: row.get(30)?,
           })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -650,7 +1189,7 @@ impl Database {
   pub fn get_cue_points_for_track(&self, track_id: &str) -> Result<Vec<CuePoint>> {
     let conn = self.conn.lock().unwrap();
     let mut stmt = conn.prepare(
-      "SELECT id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, \"order\"
+      "SELECT id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, gridBpm, \"order\", updatedAt, deleted
              FROM cuePoint WHERE trackId = ?1 ORDER BY positionMs",
     )?;
 
@@ -677,7 +1216,10 @@ impl Database {
           hotcue_slot: row.get(5)?,
           name: row.get(6)?,
           color: row.get(7)?,
-          order: row.get(8)?,
+          grid_bpm: row.get(8)?,
+          order: row.get(9)?,
+          updated_at: row.get(10)?,
+          deleted: row.get(11)?,
         })
       })?
       .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -694,7 +1236,7 @@ impl Database {
     let conn = self.conn.lock().unwrap();
     let placeholders = track_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
     let query = format!(
-      "SELECT id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, \"order\"
+      "SELECT id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, gridBpm, \"order\", updatedAt, deleted
              FROM cuePoint WHERE trackId IN ({}) ORDER BY trackId, positionMs",
       placeholders
     );
@@ -728,7 +1270,10 @@ impl Database {
           hotcue_slot: row.get(5)?,
           name: row.get(6)?,
           color: row.get(7)?,
-          order: row.get(8)?,
+          grid_bpm: row.get(8)?,
+          order: row.get(9)?,
+          updated_at: row.get(10)?,
+          deleted: row.get(11)?,
         })
       })?
       .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -744,8 +1289,8 @@ impl Database {
 
     let conn = self.conn.lock().unwrap();
     let mut stmt = conn.prepare(
-      "INSERT INTO cuePoint (id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, \"order\")
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+      "INSERT INTO cuePoint (id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, gridBpm, \"order\", updatedAt, deleted)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
              ON CONFLICT(id) DO UPDATE SET
                 trackId = excluded.trackId,
                 type = excluded.type,
@@ -754,7 +1299,10 @@ impl Database {
                 hotcueSlot = excluded.hotcueSlot,
                 name = excluded.name,
                 color = excluded.color,
-                \"order\" = excluded.\"order\"",
+                gridBpm = excluded.gridBpm,
+                \"order\" = excluded.\"order\",
+                updatedAt = excluded.updatedAt,
+                deleted = excluded.deleted",
     )?;
 
     for cue in cue_points {
@@ -767,7 +1315,10 @@ impl Database {
         cue.hotcue_slot,
         cue.name,
         cue.color,
+        cue.grid_bpm,
         cue.order,
+        cue.updated_at,
+        cue.deleted,
       ])?;
     }
 
@@ -814,8 +1365,8 @@ impl Database {
     // Insert new if any
     if !cue_points.is_empty() {
       let mut stmt = conn.prepare(
-        "INSERT INTO cuePoint (id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, \"order\")
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        "INSERT INTO cuePoint (id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, gridBpm, \"order\", updatedAt, deleted)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
       )?;
 
       for cue in cue_points {
@@ -828,7 +1379,10 @@ impl Database {
           cue.hotcue_slot,
           cue.name,
           cue.color,
+          cue.grid_bpm,
           cue.order,
+          cue.updated_at,
+          cue.deleted,
         ])?;
       }
     }
@@ -840,4 +1394,1081 @@ impl Database {
     );
     Ok(())
   }
+
+  /// Read the stored base snapshot for `track_id` (see
+  /// `merge_cue_points_for_track`), or an empty set if this track has never
+  /// been merged before.
+  fn get_cue_point_base_snapshot(&self, track_id: &str) -> Result<Vec<CuePoint>> {
+    let conn = self.conn.lock().unwrap();
+    let snapshot: Option<String> = conn
+      .query_row(
+        "SELECT snapshot FROM cuePointBaseSnapshot WHERE trackId = ?1",
+        [track_id],
+        |row| row.get(0),
+      )
+      .optional()?;
+
+    Ok(snapshot
+      .and_then(|s| serde_json::from_str(&s).ok())
+      .unwrap_or_default())
+  }
+
+  fn save_cue_point_base_snapshot(&self, track_id: &str, cues: &[CuePoint]) -> Result<()> {
+    let conn = self.conn.lock().unwrap();
+    let snapshot = serde_json::to_string(cues)?;
+    conn.execute(
+      "INSERT INTO cuePointBaseSnapshot (trackId, snapshot, updatedAt)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(trackId) DO UPDATE SET snapshot = excluded.snapshot, updatedAt = excluded.updatedAt",
+      params![track_id, snapshot, chrono::Utc::now().timestamp_millis()],
+    )?;
+    Ok(())
+  }
+
+  /// Three-way merge `remote` cue points (freshly re-imported from an
+  /// external source) into `track_id`'s existing cue points instead of
+  /// destructively replacing them like `replace_cue_points_for_track` does.
+  /// See `libs::cue_merge::merge_cue_points_3way` for the reconciliation
+  /// rules; this just supplies the local rows and stored base snapshot, then
+  /// persists the result as both the track's new cue points and the next
+  /// base snapshot. Returns the conflicting slots so the caller can surface
+  /// them to the user.
+  pub fn merge_cue_points_for_track(
+    &self,
+    track_id: &str,
+    remote: &[CuePoint],
+    keep_local_on_conflict: bool,
+  ) -> Result<crate::libs::cue_merge::CueMerge3WayResult> {
+    let local = self.get_cue_points_for_track(track_id)?;
+    let base = self.get_cue_point_base_snapshot(track_id)?;
+
+    let result =
+      crate::libs::cue_merge::merge_cue_points_3way(&local, remote, &base, track_id, keep_local_on_conflict);
+
+    self.replace_cue_points_for_track(track_id, &result.merged)?;
+    self.save_cue_point_base_snapshot(track_id, &result.merged)?;
+
+    Ok(result)
+  }
+
+  /// Parse a `.cue` sidecar file (see `libs::cue_sheet::import_cue_sheet`)
+  /// and persist its `TRACK`/`INDEX 01` entries as `CuePoint`s for
+  /// `track_id`, replacing whatever was previously imported for that track.
+  /// Re-importing the same sheet is idempotent since it always fully
+  /// replaces the prior set rather than appending to it. Returns the number
+  /// of cue points written.
+  pub fn import_cue_sheet(&self, track_id: &str, cue_path: &std::path::Path) -> Result<usize> {
+    let cues = crate::libs::cue_sheet::import_cue_sheet(cue_path, track_id, false)?;
+    let count = cues.len();
+    self.replace_cue_points_for_track(track_id, &cues)?;
+    Ok(count)
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // ── Track Field Clock Operations (CRDT merge support) ──
+  // ═══════════════════════════════════════════════════════════════════════════
+
+  /// Load the persisted LWW clock for a track's mergeable fields. Fields
+  /// never written return no entry; callers treat that as `updated_at: 0`.
+  pub fn get_field_clock(&self, track_id: &str) -> Result<FieldClock> {
+    let conn = self.conn.lock().unwrap();
+    let mut stmt = conn.prepare(
+      "SELECT fieldName, updatedAt, source FROM trackFieldClock WHERE trackId = ?1",
+    )?;
+
+    let mut clock = FieldClock::new();
+    let rows = stmt.query_map([track_id], |row| {
+      let field_name: String = row.get(0)?;
+      let updated_at: i64 = row.get(1)?;
+      let source_str: String = row.get(2)?;
+      Ok((field_name, updated_at, source_str))
+    })?;
+
+    for row in rows {
+      let (field_name, updated_at, source_str) = row?;
+      let source = match source_str.as_str() {
+        "TRAKTOR" => SourcePriority::Traktor,
+        _ => SourcePriority::Harmony,
+      };
+      clock.insert(field_name, FieldStamp { updated_at, source });
+    }
+
+    Ok(clock)
+  }
+
+  /// Persist a track's LWW clock, upserting one row per field.
+  pub fn save_field_clock(&self, track_id: &str, clock: &FieldClock) -> Result<()> {
+    if clock.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.conn.lock().unwrap();
+    let mut stmt = conn.prepare(
+      "INSERT INTO trackFieldClock (trackId, fieldName, updatedAt, source)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(trackId, fieldName) DO UPDATE SET
+                updatedAt = excluded.updatedAt,
+                source = excluded.source",
+    )?;
+
+    for (field_name, stamp) in clock {
+      let source_str = match stamp.source {
+        SourcePriority::Traktor => "TRAKTOR",
+        SourcePriority::Harmony => "HARMONY",
+      };
+      stmt.execute(params![track_id, field_name, stamp.updated_at, source_str])?;
+    }
+
+    Ok(())
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // ── Traktor Delta Sync Hashes ──
+  // ═══════════════════════════════════════════════════════════════════════════
+
+  /// Load every stored `(path, contentHash)` pair from the last Traktor sync,
+  /// used by DELTA mode in `commands::sync_traktor_nml` to skip merging
+  /// entries whose content hasn't changed, and to detect paths that were
+  /// synced before but are absent from the current NML (removed entries).
+  pub fn get_traktor_sync_hashes(&self) -> Result<HashMap<String, String>> {
+    let conn = self.conn.lock().unwrap();
+    let mut stmt = conn.prepare("SELECT path, contentHash FROM traktorSyncHash")?;
+
+    let hashes = stmt
+      .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+      .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+
+    Ok(hashes)
+  }
+
+  /// Upsert `(path, contentHash)` pairs after a sync pass, inside a single
+  /// transaction. Called once per batch by `sync_traktor_nml`, alongside
+  /// `apply_traktor_sync_batch`.
+  pub fn save_traktor_sync_hashes(&self, hashes: &[(String, String)], synced_at: i64) -> Result<()> {
+    if hashes.is_empty() {
+      return Ok(());
+    }
+
+    let mut conn = self.conn.lock().unwrap();
+    let tx = conn.transaction()?;
+
+    {
+      let mut stmt = tx.prepare(
+        "INSERT INTO traktorSyncHash (path, contentHash, lastSyncedAt)
+               VALUES (?1, ?2, ?3)
+               ON CONFLICT(path) DO UPDATE SET
+                  contentHash = excluded.contentHash,
+                  lastSyncedAt = excluded.lastSyncedAt",
+      )?;
+
+      for (path, hash) in hashes {
+        stmt.execute(params![path, hash, synced_at])?;
+      }
+    }
+
+    tx.commit()?;
+    Ok(())
+  }
+
+  /// Drop stored sync hashes for paths no longer present in the NML, so a
+  /// later re-add of the same path is treated as new rather than unchanged.
+  pub fn delete_traktor_sync_hashes(&self, paths: &[String]) -> Result<()> {
+    if paths.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.conn.lock().unwrap();
+    let mut stmt = conn.prepare("DELETE FROM traktorSyncHash WHERE path = ?1")?;
+    for path in paths {
+      stmt.execute([path])?;
+    }
+
+    Ok(())
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // ── Batched Sync Writes (bulk upsert for large NML syncs) ──
+  // ═══════════════════════════════════════════════════════════════════════════
+
+  /// Apply a batch of Traktor-sync writes - track upsert plus optional field
+  /// clock and cue replacement - inside a single transaction.
+  ///
+  /// AIDEV-NOTE: `sync_traktor_nml` used to call `insert_tracks`/`update_track`,
+  /// `save_field_clock`, and `replace_cue_points_for_track` once per matched
+  /// track, each auto-committing on its own - fine for a few hundred tracks,
+  /// but a 50k-track collection meant ~150k individual commits. The caller
+  /// chunks its per-track writes (see `batch_size` on `sync_traktor_nml`) and
+  /// hands each chunk to this method, so a sync becomes one commit per chunk
+  /// instead of one per row.
+  pub fn apply_traktor_sync_batch(&self, writes: &[TraktorSyncWrite]) -> Result<()> {
+    if writes.is_empty() {
+      return Ok(());
+    }
+
+    let mut conn = self.conn.lock().unwrap();
+    let tx = conn.transaction()?;
+
+    {
+      let mut upsert_track = tx.prepare(
+        "INSERT INTO track (id, path, title, artist, album, genre, year, duration, bitrate,
+                                 comment, bpm, initialKey, rating, label, catalogNumber, isrc,
+                                 musicbrainzId, releaseGroupId,
+                                 waveformPeaks, addedAt, url, startMs, endMs, chapters, syncedLyrics,
+                                 albumDate, trackNumber, albumSeq, artistSort, albumSort, titleSort)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31)
+               ON CONFLICT(id) DO UPDATE SET
+                  path = excluded.path,
+                  title = excluded.title,
+                  artist = excluded.artist,
+                  album = excluded.album,
+                  genre = excluded.genre,
+                  year = excluded.year,
+                  duration = excluded.duration,
+                  bitrate = excluded.bitrate,
+                  comment = excluded.comment,
+                  bpm = excluded.bpm,
+                  initialKey = excluded.initialKey,
+                  rating = excluded.rating,
+                  label = excluded.label,
+                  catalogNumber = excluded.catalogNumber,
+                  isrc = excluded.isrc,
+                  musicbrainzId = excluded.musicbrainzId,
+                  releaseGroupId = excluded.releaseGroupId,
+                  waveformPeaks = excluded.waveformPeaks,
+                  url = excluded.url,
+                  startMs = excluded.startMs,
+                  endMs = excluded.endMs,
+                  chapters = excluded.chapters,
+                  syncedLyrics = excluded.syncedLyrics,
+                  albumDate = excluded.albumDate,
+                  trackNumber = excluded.trackNumber,
+                  albumSeq = excluded.albumSeq,
+                  artistSort = excluded.artistSort,
+                  albumSort = excluded.albumSort,
+                  titleSort = excluded.titleSort",
+      )?;
+
+      let mut upsert_clock = tx.prepare(
+        "INSERT INTO trackFieldClock (trackId, fieldName, updatedAt, source)
+               VALUES (?1, ?2, ?3, ?4)
+               ON CONFLICT(trackId, fieldName) DO UPDATE SET
+                  updatedAt = excluded.updatedAt,
+                  source = excluded.source",
+      )?;
+
+      for write in writes {
+        let track = &write.track;
+        let rating_json = track
+          .rating
+          .as_ref()
+          .and_then(|r| serde_json::to_string(r).ok());
+        let waveform_json = track
+          .waveform_peaks
+          .as_ref()
+          .and_then(|w| serde_json::to_string(w).ok());
+        let chapters_json = (!track.chapters.is_empty())
+          .then(|| serde_json::to_string(&track.chapters).ok())
+          .flatten();
+        let synced_lyrics_json = (!track.synced_lyrics.is_empty())
+          .then(|| serde_json::to_string(&track.synced_lyrics).ok())
+          .flatten();
+        let album_date_json = track
+          .album_date
+          .as_ref()
+          .and_then(|d| serde_json::to_string(d).ok());
+        let artist_sort = track.artist_sort.clone().or_else(|| track.artist.as_deref().map(derive_sort_name));
+        let album_sort = track.album_sort.clone().or_else(|| track.album.as_deref().map(derive_sort_name));
+        let title_sort = Some(track.title_sort.clone().unwrap_or_else(|| derive_sort_name(&track.title)));
+
+        upsert_track.execute(params![
+          track.id,
+          track.path,
+          track.title,
+          track.artist,
+          track.album,
+          track.genre,
+          track.year,
+          track.duration,
+          track.bitrate,
+          track.comment,
+          track.bpm,
+          track.initial_key,
+          rating_json,
+          track.label,
+          track.catalog_number,
+          track.isrc,
+          track.musicbrainz_id,
+          track.release_group_id,
+          waveform_json,
+          track.added_at,
+          track.url,
+          track.start_ms,
+          track.end_ms,
+          chapters_json,
+          synced_lyrics_json,
+          album_date_json,
+          track.track_number,
+          track.album_seq.map(|seq| seq.0 as i64),
+          artist_sort,
+          album_sort,
+          title_sort,
+        ])?;
+
+        if let Some(clock) = &write.field_clock {
+          for (field_name, stamp) in clock {
+            let source_str = match stamp.source {
+              SourcePriority::Traktor => "TRAKTOR",
+              SourcePriority::Harmony => "HARMONY",
+            };
+            upsert_clock.execute(params![track.id, field_name, stamp.updated_at, source_str])?;
+          }
+        }
+
+        if let Some(cues) = &write.cues {
+          tx.execute("DELETE FROM cuePoint WHERE trackId = ?1", [&track.id])?;
+          for cue in cues {
+            tx.execute(
+              "INSERT INTO cuePoint (id, trackId, type, positionMs, lengthMs, hotcueSlot, name, color, gridBpm, \"order\", updatedAt, deleted)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+              params![
+                cue.id,
+                cue.track_id,
+                cue.cue_type.to_string(),
+                cue.position_ms,
+                cue.length_ms,
+                cue.hotcue_slot,
+                cue.name,
+                cue.color,
+                cue.grid_bpm,
+                cue.order,
+                cue.updated_at,
+                cue.deleted,
+              ],
+            )?;
+          }
+        }
+      }
+    }
+
+    tx.commit()?;
+    Ok(())
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // ── File Import Dirstate (incremental rescan) ──
+  // ═══════════════════════════════════════════════════════════════════════════
+
+  /// Load every stored per-file dirstate, keyed by path - used by
+  /// `import_library` to classify each candidate file as Added/Modified/
+  /// Unchanged before deciding whether to re-extract its metadata.
+  pub fn get_file_dirstate(&self) -> Result<HashMap<String, FileDirstate>> {
+    let conn = self.conn.lock().unwrap();
+    let mut stmt =
+      conn.prepare("SELECT path, size, mtimeMillis, contentHash FROM fileDirstate")?;
+
+    let rows = stmt.query_map([], |row| {
+      Ok((
+        row.get::<_, String>(0)?,
+        FileDirstate {
+          size: row.get::<_, i64>(1)? as u64,
+          mtime_millis: row.get(2)?,
+          content_hash: row.get(3)?,
+        },
+      ))
+    })?;
+
+    Ok(rows.collect::<rusqlite::Result<HashMap<_, _>>>()?)
+  }
+
+  /// Upsert `(path, dirstate)` pairs after a rescan, inside a single
+  /// transaction.
+  pub fn save_file_dirstate(
+    &self,
+    entries: &[(String, FileDirstate)],
+    scanned_at: i64,
+  ) -> Result<()> {
+    if entries.is_empty() {
+      return Ok(());
+    }
+
+    let mut conn = self.conn.lock().unwrap();
+    let tx = conn.transaction()?;
+
+    {
+      let mut stmt = tx.prepare(
+        "INSERT INTO fileDirstate (path, size, mtimeMillis, contentHash, lastScannedAt)
+               VALUES (?1, ?2, ?3, ?4, ?5)
+               ON CONFLICT(path) DO UPDATE SET
+                  size = excluded.size,
+                  mtimeMillis = excluded.mtimeMillis,
+                  contentHash = excluded.contentHash,
+                  lastScannedAt = excluded.lastScannedAt",
+      )?;
+
+      for (path, state) in entries {
+        stmt.execute(params![
+          path,
+          state.size as i64,
+          state.mtime_millis,
+          state.content_hash,
+          scanned_at
+        ])?;
+      }
+    }
+
+    tx.commit()?;
+    Ok(())
+  }
+
+  /// Drop stored dirstate for paths no longer present on disk, so a later
+  /// re-add of the same path is treated as new rather than unchanged.
+  pub fn delete_file_dirstate(&self, paths: &[String]) -> Result<()> {
+    if paths.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.conn.lock().unwrap();
+    let mut stmt = conn.prepare("DELETE FROM fileDirstate WHERE path = ?1")?;
+    for path in paths {
+      stmt.execute([path])?;
+    }
+
+    Ok(())
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // ── Acoustic-Similarity Feature Vectors ──
+  // ═══════════════════════════════════════════════════════════════════════════
+
+  /// Upsert a batch of `(path, feature vector)` pairs from a similarity
+  /// analysis pass, inside a single transaction.
+  pub fn save_feature_vectors(
+    &self,
+    vectors: &[(String, crate::libs::similarity::FeatureVector)],
+    updated_at: i64,
+  ) -> Result<()> {
+    if vectors.is_empty() {
+      return Ok(());
+    }
+
+    let mut conn = self.conn.lock().unwrap();
+    let tx = conn.transaction()?;
+
+    {
+      let mut stmt = tx.prepare(
+        "INSERT INTO trackFeatureVector (path, features, updatedAt)
+               VALUES (?1, ?2, ?3)
+               ON CONFLICT(path) DO UPDATE SET
+                  features = excluded.features,
+                  updatedAt = excluded.updatedAt",
+      )?;
+
+      for (path, vector) in vectors {
+        let features_json = serde_json::to_string(vector.as_slice())?;
+        stmt.execute(params![path, features_json, updated_at])?;
+      }
+    }
+
+    tx.commit()?;
+    Ok(())
+  }
+
+  /// Load the persisted feature vector for a single path, if any.
+  pub fn get_feature_vector(&self, path: &str) -> Result<Option<crate::libs::similarity::FeatureVector>> {
+    let conn = self.conn.lock().unwrap();
+    let features_json: Option<String> = conn
+      .query_row(
+        "SELECT features FROM trackFeatureVector WHERE path = ?1",
+        [path],
+        |row| row.get(0),
+      )
+      .optional()?;
+
+    features_json.map(|json| parse_feature_vector(&json)).transpose()
+  }
+
+  /// Load every persisted feature vector, keyed by path - used by
+  /// `generate_similar_playlist`/`dedup_playlist` to compare a seed or
+  /// ordered list against the rest of the library.
+  pub fn get_all_feature_vectors(
+    &self,
+  ) -> Result<HashMap<String, crate::libs::similarity::FeatureVector>> {
+    let conn = self.conn.lock().unwrap();
+    let mut stmt = conn.prepare("SELECT path, features FROM trackFeatureVector")?;
+
+    let rows = stmt.query_map([], |row| {
+      Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut vectors = HashMap::new();
+    for row in rows {
+      let (path, features_json) = row?;
+      vectors.insert(path, parse_feature_vector(&features_json)?);
+    }
+
+    Ok(vectors)
+  }
+
+  // ═══════════════════════════════════════════════════════════════════════════
+  // ── Track Analysis Vectors (nearest-neighbor "more like this") ──
+  // ═══════════════════════════════════════════════════════════════════════════
+
+  /// Upsert a track's analysis vector and the pipeline version that
+  /// produced it.
+  pub fn save_analysis(&self, track_id: &str, features: &[f32], version: i32) -> Result<()> {
+    let conn = self.conn.lock().unwrap();
+    let blob = encode_analysis_features(features);
+
+    conn.execute(
+      "INSERT INTO trackAnalysis (trackId, features, version)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(trackId) DO UPDATE SET
+                features = excluded.features,
+                version = excluded.version",
+      params![track_id, blob, version],
+    )?;
+
+    Ok(())
+  }
+
+  /// Load the persisted analysis for a single track, if any.
+  pub fn get_analysis(&self, track_id: &str) -> Result<Option<TrackAnalysis>> {
+    let conn = self.conn.lock().unwrap();
+    let row: Option<(Vec<u8>, i32)> = conn
+      .query_row(
+        "SELECT features, version FROM trackAnalysis WHERE trackId = ?1",
+        [track_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+      )
+      .optional()?;
+
+    Ok(row.map(|(blob, version)| TrackAnalysis {
+      features: decode_analysis_features(&blob),
+      version,
+    }))
+  }
+
+  /// Build a "more like this" playlist from `seed_track_id`: load every
+  /// stored analysis in one `SELECT`, skip any row (including the seed)
+  /// whose `version` doesn't match [`ANALYSIS_VERSION`], and return the `n`
+  /// other tracks closest to the seed by squared Euclidean distance,
+  /// ascending. Returns an empty list if the seed has no current analysis -
+  /// there's nothing meaningful to compare a stale or missing vector
+  /// against.
+  pub fn generate_similar_playlist(&self, seed_track_id: &str, n: usize) -> Result<Vec<(String, f32)>> {
+    let conn = self.conn.lock().unwrap();
+    let mut stmt = conn.prepare("SELECT trackId, features, version FROM trackAnalysis")?;
+    let rows = stmt
+      .query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?, row.get::<_, i32>(2)?))
+      })?
+      .collect::<std::result::Result<Vec<_>, _>>()?;
+    drop(stmt);
+    drop(conn);
+
+    let current: HashMap<String, Vec<f32>> = rows
+      .into_iter()
+      .filter(|(_, _, version)| *version == ANALYSIS_VERSION)
+      .map(|(track_id, blob, _)| (track_id, decode_analysis_features(&blob)))
+      .collect();
+
+    let Some(seed) = current.get(seed_track_id) else {
+      return Ok(Vec::new());
+    };
+
+    let mut distances: Vec<(String, f32)> = current
+      .iter()
+      .filter(|(track_id, _)| track_id.as_str() != seed_track_id)
+      .map(|(track_id, features)| (track_id.clone(), squared_euclidean_distance(seed, features)))
+      .collect();
+
+    distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    distances.truncate(n);
+
+    Ok(distances)
+  }
+}
+
+/// Encode an analysis feature vector as raw little-endian `f32` bytes, for
+/// the `trackAnalysis.features` BLOB column.
+fn encode_analysis_features(features: &[f32]) -> Vec<u8> {
+  features.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+/// Inverse of `encode_analysis_features`.
+fn decode_analysis_features(blob: &[u8]) -> Vec<f32> {
+  blob
+    .chunks_exact(4)
+    .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+    .collect()
+}
+
+/// Break `s` into its set of overlapping, lowercased 3-character shingles,
+/// padding both ends with a space so short strings (and a query's
+/// prefix/suffix) still contribute a trigram instead of scoring as empty.
+/// Used by `Database::search_tracks`.
+fn trigram_shingles(s: &str) -> HashSet<String> {
+  let padded = format!(" {} ", s.to_lowercase());
+  let chars: Vec<char> = padded.chars().collect();
+
+  if chars.len() < 3 {
+    return HashSet::new();
+  }
+
+  (0..=chars.len() - 3)
+    .map(|i| chars[i..i + 3].iter().collect())
+    .collect()
+}
+
+/// Jaccard similarity `|A∩B| / |A∪B|` between two shingle sets.
+fn trigram_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+  if a.is_empty() || b.is_empty() {
+    return 0.0;
+  }
+
+  let intersection = a.intersection(b).count();
+  let union = a.union(b).count();
+
+  intersection as f64 / union as f64
+}
+
+/// Squared Euclidean distance between two feature vectors, over however
+/// many dimensions they have in common (vectors are always produced at the
+/// same fixed length by one pipeline version, so a length mismatch would
+/// only happen if `ANALYSIS_VERSION` filtering let incomparable vectors
+/// through).
+fn squared_euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+  a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+/// Upsert `tracks` within an already-open transaction. Shared by
+/// `Database::insert_tracks` (batching against `self.conn`) and the
+/// background reindex worker (batching against its own connection), so the
+/// 35-column upsert only has to be kept in sync with the schema in one
+/// place for both batched callers.
+fn upsert_tracks_tx(tx: &rusqlite::Transaction, tracks: &[Track]) -> Result<()> {
+  let mut stmt = tx.prepare(
+    "INSERT INTO track (id, path, title, artist, album, genre, year, duration, bitrate,
+                             comment, bpm, initialKey, rating, label, catalogNumber, isrc,
+                             musicbrainzId, releaseGroupId,
+                             waveformPeaks, addedAt, url, startMs, endMs, chapters, syncedLyrics,
+                             albumDate, trackNumber, artistSort, albumSort, releaseMonth,
+                             titleNormalized, artistNormalized, albumNormalized, albumSeq, titleSort)
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35)
+           ON CONFLICT(id) DO UPDATE SET
+              title = excluded.title,
+              artist = excluded.artist,
+              album = excluded.album,
+              genre = excluded.genre,
+              year = excluded.year,
+              duration = excluded.duration,
+              bitrate = excluded.bitrate,
+              comment = excluded.comment,
+              bpm = excluded.bpm,
+              initialKey = excluded.initialKey,
+              rating = excluded.rating,
+              label = excluded.label,
+              catalogNumber = excluded.catalogNumber,
+              isrc = excluded.isrc,
+              musicbrainzId = excluded.musicbrainzId,
+              releaseGroupId = excluded.releaseGroupId,
+              waveformPeaks = excluded.waveformPeaks,
+              url = excluded.url,
+              startMs = excluded.startMs,
+              endMs = excluded.endMs,
+              chapters = excluded.chapters,
+              syncedLyrics = excluded.syncedLyrics,
+              albumDate = excluded.albumDate,
+              trackNumber = excluded.trackNumber,
+              artistSort = excluded.artistSort,
+              albumSort = excluded.albumSort,
+              releaseMonth = excluded.releaseMonth,
+              titleNormalized = excluded.titleNormalized,
+              artistNormalized = excluded.artistNormalized,
+              albumNormalized = excluded.albumNormalized,
+              albumSeq = excluded.albumSeq,
+              titleSort = excluded.titleSort",
+  )?;
+
+  for track in tracks {
+    let rating_json = track
+      .rating
+      .as_ref()
+      .and_then(|r| serde_json::to_string(r).ok());
+    let waveform_json = track
+      .waveform_peaks
+      .as_ref()
+      .and_then(|w| serde_json::to_string(w).ok());
+    let chapters_json = (!track.chapters.is_empty())
+      .then(|| serde_json::to_string(&track.chapters).ok())
+      .flatten();
+    let synced_lyrics_json = (!track.synced_lyrics.is_empty())
+      .then(|| serde_json::to_string(&track.synced_lyrics).ok())
+      .flatten();
+    let album_date_json = track
+      .album_date
+      .as_ref()
+      .and_then(|d| serde_json::to_string(d).ok());
+    let artist_sort = track.artist_sort.clone().or_else(|| track.artist.as_deref().map(derive_sort_name));
+    let album_sort = track.album_sort.clone().or_else(|| track.album.as_deref().map(derive_sort_name));
+    let title_sort = Some(track.title_sort.clone().unwrap_or_else(|| derive_sort_name(&track.title)));
+    let release_month = track.album_date.as_ref().and_then(|d| d.month);
+    let title_normalized = normalize_key(&track.title);
+    let artist_normalized = track.artist.as_deref().map(normalize_key);
+    let album_normalized = track.album.as_deref().map(normalize_key);
+    let album_seq = track.album_seq.map(|seq| seq.0 as i64);
+
+    stmt.execute(params![
+      track.id,
+      track.path,
+      track.title,
+      track.artist,
+      track.album,
+      track.genre,
+      track.year,
+      track.duration,
+      track.bitrate,
+      track.comment,
+      track.bpm,
+      track.initial_key,
+      rating_json,
+      track.label,
+      track.catalog_number,
+      track.isrc,
+      track.musicbrainz_id,
+      track.release_group_id,
+      waveform_json,
+      track.added_at,
+      track.url,
+      track.start_ms,
+      track.end_ms,
+      chapters_json,
+      synced_lyrics_json,
+      album_date_json,
+      track.track_number,
+      artist_sort,
+      album_sort,
+      release_month,
+      title_normalized,
+      artist_normalized,
+      album_normalized,
+      album_seq,
+      title_sort,
+    ])?;
+  }
+
+  Ok(())
+}
+
+/// Which pass of a [`Database::trigger_reindex`] run a [`ReindexProgress`]
+/// update belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReindexPhase {
+  /// Walking the library roots and upserting discovered tracks.
+  Scanning,
+  /// Removing tracks whose file no longer exists on disk.
+  Pruning,
+  /// The reindex has finished (successfully or not).
+  Done,
+}
+
+/// One step of progress from a [`Database::trigger_reindex`] run, streamed
+/// back through the caller's `on_progress` callback so the UI can show
+/// import status.
+pub struct ReindexProgress {
+  pub phase: ReindexPhase,
+  pub processed: usize,
+  pub total: usize,
+}
+
+/// Commands accepted by the long-lived reindex worker thread spawned in
+/// [`Database::new`], modeled on Polaris's indexer: `Reindex` walks `roots`
+/// and upserts/prunes tracks, `Exit` stops the worker. Nothing sends `Exit`
+/// at present; the worker also stops once the `Database`, and with it the
+/// channel's sending half, is dropped.
+enum IndexCommand {
+  Reindex {
+    roots: Vec<PathBuf>,
+    options: ScanOptions,
+    on_progress: Box<dyn FnMut(ReindexProgress) + Send>,
+  },
+  #[allow(dead_code)] // AIDEV-NOTE: no caller sends this; see the IndexCommand doc above.
+  Exit,
+}
+
+/// Sending half of the reindex worker's command channel.
+struct CommandSender(crossbeam_channel::Sender<IndexCommand>);
+
+/// Receiving half of the reindex worker's command channel.
+struct CommandReceiver(crossbeam_channel::Receiver<IndexCommand>);
+
+/// Spawn the long-lived worker thread backing `Database::trigger_reindex`.
+/// The worker owns its own SQLite connection (opened from `db_path`)
+/// rather than touching `Database::conn` directly, so a reindex never
+/// competes with the main connection's mutex for the whole scan - WAL mode
+/// (enabled in `Database::new`) lets both connections write concurrently.
+fn spawn_reindex_worker(db_path: PathBuf, commands: CommandReceiver) {
+  std::thread::spawn(move || {
+    for command in commands.0.iter() {
+      match command {
+        IndexCommand::Reindex { roots, options, mut on_progress } => {
+          if let Err(err) = run_reindex(&db_path, &roots, options, &mut on_progress) {
+            log::error!("Background reindex failed: {}", err);
+          }
+        }
+        IndexCommand::Exit => break,
+      }
+    }
+  });
+}
+
+/// One full `Reindex` pass: walk `roots` for supported audio files via the
+/// parallel, channel-driven pipeline in `libs::scan_pipeline` (traverser
+/// threads -> rayon extraction pool -> this thread as the writer, batching
+/// `options.writer_batch_size` rows per transaction), then prune any track
+/// whose path no longer exists on disk (see `Database::prune_missing_tracks`).
+/// `ReindexProgress::total` tracks files *seen* so far during the `Scanning`
+/// phase rather than a fixed count known up front - traversal and writing
+/// happen concurrently, so there's no "total" to report until scanning
+/// itself finishes.
+fn run_reindex(
+  db_path: &std::path::Path,
+  roots: &[PathBuf],
+  options: ScanOptions,
+  on_progress: &mut dyn FnMut(ReindexProgress),
+) -> Result<()> {
+  let mut conn = Connection::open(db_path)?;
+  conn.pragma_update(None, "foreign_keys", true)?;
+
+  let handle = scan_paths(roots.to_vec(), options, CancellationToken::new());
+  let mut buffer = Vec::with_capacity(options.writer_batch_size.max(1));
+  let mut processed = 0;
+
+  for (path, result) in handle.results.iter() {
+    match result {
+      Ok(track) => buffer.push(track),
+      Err(err) => log::warn!("Skipping {} during reindex: {}", path.display(), err),
+    }
+
+    if buffer.len() >= options.writer_batch_size.max(1) {
+      let tx = conn.transaction()?;
+      upsert_tracks_tx(&tx, &buffer)?;
+      tx.commit()?;
+      processed += buffer.len();
+      buffer.clear();
+    }
+
+    on_progress(ReindexProgress {
+      phase: ReindexPhase::Scanning,
+      processed,
+      total: handle.files_seen(),
+    });
+  }
+
+  if !buffer.is_empty() {
+    let tx = conn.transaction()?;
+    upsert_tracks_tx(&tx, &buffer)?;
+    tx.commit()?;
+    processed += buffer.len();
+  }
+
+  on_progress(ReindexProgress {
+    phase: ReindexPhase::Pruning,
+    processed: 0,
+    total: 0,
+  });
+
+  prune_missing_tracks_conn(&mut conn)?;
+
+  on_progress(ReindexProgress {
+    phase: ReindexPhase::Done,
+    processed,
+    total: processed,
+  });
+
+  Ok(())
+}
+
+/// Delete `track` rows whose `path` no longer exists on disk, in batches of
+/// `PRUNE_BATCH_SIZE` per transaction. Returns the number of tracks removed.
+/// Shared by `Database::prune_missing_tracks` and the background reindex
+/// worker's pruning pass (see `run_reindex`), each against their own
+/// connection.
+fn prune_missing_tracks_conn(conn: &mut Connection) -> Result<usize> {
+  let rows: Vec<(String, String)> = {
+    let mut stmt = conn.prepare("SELECT id, path FROM track")?;
+    stmt
+      .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+      .collect::<std::result::Result<Vec<_>, _>>()?
+  };
+
+  let missing_ids: Vec<String> = rows
+    .into_iter()
+    .filter(|(_, path)| !std::path::Path::new(path).exists())
+    .map(|(id, _)| id)
+    .collect();
+
+  for batch in missing_ids.chunks(PRUNE_BATCH_SIZE) {
+    let tx = conn.transaction()?;
+    {
+      let mut delete_cues = tx.prepare("DELETE FROM cuePoint WHERE trackId = ?1")?;
+      let mut delete_track = tx.prepare("DELETE FROM track WHERE id = ?1")?;
+      for id in batch {
+        delete_cues.execute([id])?;
+        delete_track.execute([id])?;
+      }
+    }
+    tx.commit()?;
+  }
+
+  Ok(missing_ids.len())
+}
+
+/// Decode a JSON-encoded feature vector back into a fixed-size array,
+/// rejecting rows whose length doesn't match `similarity::FEATURE_DIM`
+/// (shouldn't happen short of manual DB edits or a schema change).
+fn parse_feature_vector(json: &str) -> Result<crate::libs::similarity::FeatureVector> {
+  use crate::libs::similarity::FEATURE_DIM;
+
+  let values: Vec<f64> = serde_json::from_str(json)?;
+  values.try_into().map_err(|values: Vec<f64>| {
+    crate::libs::HarmonyError::Custom(format!(
+      "Expected {} feature dimensions, found {}",
+      FEATURE_DIM,
+      values.len()
+    ))
+  })
+}
+
+/// One track's worth of writes produced by a single `sync_traktor_nml` batch.
+///
+/// `field_clock`/`cues` are `None` when that part of the track didn't change,
+/// so `apply_traktor_sync_batch` can skip the clock upsert / cue replacement
+/// entirely instead of writing a no-op.
+pub struct TraktorSyncWrite {
+  pub track: Track,
+  pub field_clock: Option<FieldClock>,
+  pub cues: Option<Vec<CuePoint>>,
+}
+
+// AIDEV-NOTE: Delegates straight to the inherent methods above. Exists so
+// callers (namely `commands::sync_traktor_nml`) can depend on `&dyn
+// LibraryStore` instead of the concrete SQLite-backed `Database`, which is
+// what makes that sync algorithm unit-testable against an in-memory store -
+// see `libs::store`.
+impl crate::libs::store::LibraryStore for Database {
+  fn get_all_tracks(&self) -> Result<Vec<Track>> {
+    Database::get_all_tracks(self)
+  }
+  fn get_track_by_id(&self, track_id: &str) -> Result<Option<Track>> {
+    Database::get_track_by_id(self, track_id)
+  }
+  fn insert_tracks(&self, tracks: &[Track]) -> Result<()> {
+    Database::insert_tracks(self, tracks)
+  }
+  fn update_track(&self, track: &Track) -> Result<()> {
+    Database::update_track(self, track)
+  }
+  fn delete_tracks(&self, track_ids: &[String]) -> Result<()> {
+    Database::delete_tracks(self, track_ids)
+  }
+
+  fn get_all_playlists(&self) -> Result<Vec<Playlist>> {
+    Database::get_all_playlists(self)
+  }
+  fn get_playlist_by_id(&self, playlist_id: &str) -> Result<Option<Playlist>> {
+    Database::get_playlist_by_id(self, playlist_id)
+  }
+  fn create_playlist(&self, playlist: &Playlist) -> Result<()> {
+    Database::create_playlist(self, playlist)
+  }
+  fn update_playlist(&self, playlist: &Playlist) -> Result<()> {
+    Database::update_playlist(self, playlist)
+  }
+  fn delete_playlist(&self, playlist_id: &str) -> Result<()> {
+    Database::delete_playlist(self, playlist_id)
+  }
+  fn set_playlist_tracks(&self, playlist_id: &str, track_ids: &[String]) -> Result<()> {
+    Database::set_playlist_tracks(self, playlist_id, track_ids)
+  }
+
+  fn get_all_folders(&self) -> Result<Vec<Folder>> {
+    Database::get_all_folders(self)
+  }
+  fn create_folder(&self, folder: &Folder) -> Result<()> {
+    Database::create_folder(self, folder)
+  }
+  fn update_folder(&self, folder: &Folder) -> Result<()> {
+    Database::update_folder(self, folder)
+  }
+  fn delete_folder(&self, folder_id: &str) -> Result<()> {
+    Database::delete_folder(self, folder_id)
+  }
+
+  fn get_cue_points_for_track(&self, track_id: &str) -> Result<Vec<CuePoint>> {
+    Database::get_cue_points_for_track(self, track_id)
+  }
+  fn get_cue_points_for_tracks(&self, track_ids: &[String]) -> Result<Vec<CuePoint>> {
+    Database::get_cue_points_for_tracks(self, track_ids)
+  }
+  fn save_cue_points(&self, cue_points: &[CuePoint]) -> Result<()> {
+    Database::save_cue_points(self, cue_points)
+  }
+  fn replace_cue_points_for_track(&self, track_id: &str, cue_points: &[CuePoint]) -> Result<()> {
+    Database::replace_cue_points_for_track(self, track_id, cue_points)
+  }
+
+  fn get_field_clock(&self, track_id: &str) -> Result<FieldClock> {
+    Database::get_field_clock(self, track_id)
+  }
+  fn save_field_clock(&self, track_id: &str, clock: &FieldClock) -> Result<()> {
+    Database::save_field_clock(self, track_id, clock)
+  }
+
+  fn get_traktor_sync_hashes(&self) -> Result<HashMap<String, String>> {
+    Database::get_traktor_sync_hashes(self)
+  }
+  fn save_traktor_sync_hashes(&self, hashes: &[(String, String)], synced_at: i64) -> Result<()> {
+    Database::save_traktor_sync_hashes(self, hashes, synced_at)
+  }
+  fn delete_traktor_sync_hashes(&self, paths: &[String]) -> Result<()> {
+    Database::delete_traktor_sync_hashes(self, paths)
+  }
+  fn apply_traktor_sync_batch(&self, writes: &[TraktorSyncWrite]) -> Result<()> {
+    Database::apply_traktor_sync_batch(self, writes)
+  }
+}
+
+/// Whether `table` already has a column named `column`, via `PRAGMA
+/// table_info` - lets `init_schema` guard an `ALTER TABLE ... ADD COLUMN`
+/// migration so re-running it against an already-migrated database doesn't
+/// error on a duplicate column.
+fn column_exists(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+  let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+  let exists = stmt
+    .query_map([], |row| row.get::<_, String>(1))?
+    .collect::<std::result::Result<Vec<_>, _>>()?
+    .iter()
+    .any(|name| name.eq_ignore_ascii_case(column));
+  Ok(exists)
+}
+
+/// Derive a sort-friendly key for an artist/album/title name: ASCII-folded
+/// and lowercased via `normalize::normalize_key` (so diacritic/case variants
+/// like "Björk" and "BJORK" sort together), then stripped of a leading
+/// English article ("the ", "a ", "an "), so e.g. "The Prodigy" sorts under
+/// P instead of T. Used as the default for `track.artist_sort`/`album_sort`/
+/// `title_sort` whenever a track doesn't carry an explicit override (see
+/// `upsert_tracks_tx`/`Database::update_track`).
+fn derive_sort_name(name: &str) -> String {
+  const ARTICLES: [&str; 3] = ["the ", "a ", "an "];
+  let normalized = normalize_key(name);
+
+  for article in ARTICLES {
+    if let Some(rest) = normalized.strip_prefix(article) {
+      return rest.to_string();
+    }
+  }
+
+  normalized
 }
@@ -0,0 +1,106 @@
+// AIDEV-NOTE: Per-file import dirstate, so a rescan can skip re-extracting
+// metadata for files that haven't changed since the last import. Mirrors the
+// path-keyed approach of `traktorSyncHash`/`trackFeatureVector` (see
+// `libs::database`) and the mtime-based cache in `duplicate_detection`, but
+// persisted so `import_library` can turn a full re-scan into an O(changed)
+// operation.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::UNIX_EPOCH;
+
+use crate::libs::Result;
+
+/// Size/mtime (and optionally a content hash) recorded for a file at its
+/// last successful import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileDirstate {
+  pub size: u64,
+  pub mtime_millis: i64,
+  /// Reserved for callers that want a stronger-than-mtime change signal
+  /// (e.g. files whose mtime a sync tool resets on every touch). Not
+  /// computed by `classify_file` itself - size/mtime alone is enough to
+  /// turn a full rescan into an incremental one.
+  pub content_hash: Option<String>,
+}
+
+/// How a file compares against its previously stored [`FileDirstate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+  /// Not present in the stored dirstate at all.
+  Added,
+  /// Present, but size and/or mtime differ.
+  Modified,
+  /// Present and unchanged - safe to skip metadata extraction entirely.
+  Unchanged,
+}
+
+/// Stat `path` and classify it against `previous`, returning the new
+/// dirstate to persist alongside the classification. Returns `Err` if the
+/// file can no longer be read - callers should treat that like a removal.
+pub fn classify_file(path: &str, previous: Option<&FileDirstate>) -> Result<(FileChange, FileDirstate)> {
+  let metadata = fs::metadata(path)?;
+  let size = metadata.len();
+  let mtime_millis = metadata
+    .modified()?
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_millis() as i64;
+
+  let change = match previous {
+    Some(prev) if prev.size == size && prev.mtime_millis == mtime_millis => FileChange::Unchanged,
+    Some(_) => FileChange::Modified,
+    None => FileChange::Added,
+  };
+
+  Ok((
+    change,
+    FileDirstate {
+      size,
+      mtime_millis,
+      content_hash: None,
+    },
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io::Write;
+
+  #[test]
+  fn classify_file_detects_added_unchanged_and_modified() {
+    let mut file = tempfile_like_file();
+    write!(file.1, "hello").unwrap();
+    drop(file.1);
+
+    let (change, state) = classify_file(&file.0, None).unwrap();
+    assert_eq!(change, FileChange::Added);
+
+    let (change, _) = classify_file(&file.0, Some(&state)).unwrap();
+    assert_eq!(change, FileChange::Unchanged);
+
+    // Move the stored mtime back a millisecond so the file looks newer than
+    // its recorded state, without waiting out a coarse filesystem mtime
+    // resolution.
+    let newer = FileDirstate {
+      size: state.size,
+      mtime_millis: state.mtime_millis - 1,
+      content_hash: None,
+    };
+    let (change, _) = classify_file(&file.0, Some(&newer)).unwrap();
+    assert_eq!(change, FileChange::Modified);
+
+    std::fs::remove_file(&file.0).unwrap();
+  }
+
+  fn tempfile_like_file() -> (String, std::fs::File) {
+    let path = std::env::temp_dir().join(format!(
+      "harmony_dirstate_test_{:?}",
+      std::thread::current().id()
+    ));
+    let path_str = path.to_string_lossy().to_string();
+    let file = std::fs::File::create(&path).unwrap();
+    (path_str, file)
+  }
+}
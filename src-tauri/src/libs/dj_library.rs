@@ -0,0 +1,26 @@
+// AIDEV-NOTE: Format-agnostic DJ library abstraction.
+// Every DJ software export (Traktor NML, and eventually Rekordbox XML /
+// Serato crates) gets parsed into the same Harmony `Track`/`Playlist` model
+// before anything else in the app touches it. This trait is the seam: a
+// caller that only needs "give me the tracks/playlists in this library" or
+// "write these tracks back out" can depend on `&dyn DjLibrary` instead of a
+// concrete parser, so a new backend slots in without touching callers.
+//
+// `TraktorLibrary` (`libs::traktor::library`) is the first implementor.
+
+use crate::libs::playlist::Playlist;
+use crate::libs::track::Track;
+use crate::libs::Result;
+
+/// A parsed DJ-software library export, normalized to Harmony's own
+/// `Track`/`Playlist` model regardless of source format.
+pub trait DjLibrary {
+  /// All tracks in this library, converted to Harmony's `Track` model.
+  fn tracks(&self) -> Vec<Track>;
+
+  /// All playlists in this library, converted to Harmony's `Playlist` model.
+  fn playlists(&self) -> Vec<Playlist>;
+
+  /// Write `tracks` back out in this library's native format.
+  fn export(&self, tracks: &[Track]) -> Result<()>;
+}
@@ -0,0 +1,327 @@
+// AIDEV-NOTE: Acoustic-fingerprint duplicate detection module
+// Finds perceptually-identical tracks even when tags, bitrate, or container differ.
+// Decodes audio with symphonia, fingerprints with rusty_chromaprint, then clusters
+// tracks whose fingerprints overlap for most of the shorter track's duration.
+
+use log::{info, warn};
+use rusty_chromaprint::{match_fingerprints, Configuration, Fingerprinter};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::CODEC_TYPE_NULL;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::libs::{HarmonyError, Result, Track};
+
+/// Fraction of the shorter track's duration that must match for two tracks
+/// to be considered duplicates.
+const DUPLICATE_OVERLAP_THRESHOLD: f64 = 0.85;
+
+/// A cluster of track IDs that are believed to be duplicates of each other.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCluster {
+  pub track_ids: Vec<String>,
+  pub pairs: Vec<DuplicatePair>,
+}
+
+/// Similarity score between a single pair of tracks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePair {
+  pub track_id_a: String,
+  pub track_id_b: String,
+  /// Fraction (0.0-1.0) of the shorter track's duration that matched.
+  pub similarity: f64,
+}
+
+/// A cached fingerprint, invalidated when the source file's mtime changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+  mtime_millis: i64,
+  fingerprint: Vec<u32>,
+}
+
+type FingerprintCache = HashMap<String, CacheEntry>;
+
+// AIDEV-NOTE: Cache lives in the OS temp dir keyed by Track::generate_id so
+// repeated scans of an unchanged library skip the expensive decode+fingerprint step.
+static FINGERPRINT_CACHE: Mutex<Option<FingerprintCache>> = Mutex::new(None);
+
+fn cache_file_path() -> PathBuf {
+  std::env::temp_dir().join("harmony_fingerprint_cache.json")
+}
+
+fn load_cache() -> FingerprintCache {
+  fs::read_to_string(cache_file_path())
+    .ok()
+    .and_then(|contents| serde_json::from_str(&contents).ok())
+    .unwrap_or_default()
+}
+
+fn persist_cache(cache: &FingerprintCache) {
+  if let Ok(json) = serde_json::to_string(cache) {
+    if let Err(e) = fs::write(cache_file_path(), json) {
+      warn!("Failed to persist fingerprint cache: {}", e);
+    }
+  }
+}
+
+fn file_mtime_millis(path: &str) -> i64 {
+  fs::metadata(path)
+    .and_then(|m| m.modified())
+    .ok()
+    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+    .map(|d| d.as_millis() as i64)
+    .unwrap_or(0)
+}
+
+/// Decode an audio file to mono 16-bit PCM samples using symphonia.
+/// Downmixes multi-channel audio by averaging channels.
+fn decode_to_mono_pcm(path: &str) -> Result<(Vec<i16>, u32)> {
+  let file = fs::File::open(path)?;
+  let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+  let mut hint = Hint::new();
+  if let Some(ext) = std::path::Path::new(path).extension().and_then(|e| e.to_str()) {
+    hint.with_extension(ext);
+  }
+
+  let probed = symphonia::default::get_probe()
+    .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+    .map_err(|e| HarmonyError::Custom(format!("Failed to probe {}: {}", path, e)))?;
+
+  let mut format = probed.format;
+  let track = format
+    .tracks()
+    .iter()
+    .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+    .ok_or_else(|| HarmonyError::Custom(format!("No decodable audio track in {}", path)))?;
+
+  let track_id = track.id;
+  let sample_rate = track.codec_params.sample_rate.unwrap_or(44100);
+
+  let mut decoder = symphonia::default::get_codecs()
+    .make(&track.codec_params, &Default::default())
+    .map_err(|e| HarmonyError::Custom(format!("Failed to create decoder for {}: {}", path, e)))?;
+
+  let mut samples = Vec::new();
+
+  loop {
+    let packet = match format.next_packet() {
+      Ok(packet) => packet,
+      Err(SymphoniaError::IoError(_)) => break, // end of stream
+      Err(e) => return Err(HarmonyError::Custom(format!("Decode error in {}: {}", path, e))),
+    };
+
+    if packet.track_id() != track_id {
+      continue;
+    }
+
+    match decoder.decode(&packet) {
+      Ok(decoded) => {
+        let spec = *decoded.spec();
+        let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buf.copy_interleaved_ref(decoded);
+
+        let channels = spec.channels.count().max(1);
+        for frame in buf.samples().chunks_exact(channels) {
+          let mixed = frame.iter().sum::<f32>() / channels as f32;
+          samples.push((mixed.clamp(-1.0, 1.0) * i16::MAX as f32) as i16);
+        }
+      }
+      Err(SymphoniaError::DecodeError(e)) => {
+        warn!("Skipping corrupt packet in {}: {}", path, e);
+        continue;
+      }
+      Err(e) => return Err(HarmonyError::Custom(format!("Decode error in {}: {}", path, e))),
+    }
+  }
+
+  Ok((samples, sample_rate))
+}
+
+/// Compute (or fetch from cache) the chromaprint fingerprint for a track.
+///
+/// AIDEV-NOTE: `pub(crate)` rather than private - `libs::musicbrainz` reuses
+/// this (and its mtime-keyed cache) for AcoustID-based enrichment instead of
+/// decoding the file a second time.
+pub(crate) fn fingerprint_track(track: &Track) -> Result<Vec<u32>> {
+  let mtime = file_mtime_millis(&track.path);
+
+  {
+    let mut guard = FINGERPRINT_CACHE.lock().unwrap();
+    let cache = guard.get_or_insert_with(load_cache);
+    if let Some(entry) = cache.get(&track.id) {
+      if entry.mtime_millis == mtime {
+        return Ok(entry.fingerprint.clone());
+      }
+    }
+  }
+
+  let (samples, sample_rate) = decode_to_mono_pcm(&track.path)?;
+  if samples.is_empty() {
+    return Err(HarmonyError::Custom(format!(
+      "Decoded zero samples from {}, cannot fingerprint",
+      track.path
+    )));
+  }
+
+  let config = Configuration::preset_test2();
+  let mut printer = Fingerprinter::new(&config);
+  printer
+    .start(sample_rate, 1)
+    .map_err(|e| HarmonyError::Custom(format!("Failed to start fingerprinter: {}", e)))?;
+  printer.consume(&samples);
+  printer.finish();
+
+  let fingerprint = printer.fingerprint().to_vec();
+  if fingerprint.is_empty() {
+    return Err(HarmonyError::Custom(format!(
+      "Empty fingerprint produced for {}",
+      track.path
+    )));
+  }
+
+  let mut guard = FINGERPRINT_CACHE.lock().unwrap();
+  let cache = guard.get_or_insert_with(load_cache);
+  cache.insert(
+    track.id.clone(),
+    CacheEntry {
+      mtime_millis: mtime,
+      fingerprint: fingerprint.clone(),
+    },
+  );
+  persist_cache(cache);
+
+  Ok(fingerprint)
+}
+
+/// Compare two fingerprints and return the matched fraction of the shorter
+/// track's duration (0.0 if they don't overlap meaningfully).
+fn fingerprint_similarity(fp_a: &[u32], fp_b: &[u32], shorter_duration_ms: i64) -> f64 {
+  if shorter_duration_ms <= 0 {
+    return 0.0;
+  }
+
+  let config = Configuration::preset_test2();
+  let segments = match match_fingerprints(fp_a, fp_b, &config) {
+    Ok(segments) => segments,
+    Err(e) => {
+      warn!("Fingerprint comparison failed: {:?}", e);
+      return 0.0;
+    }
+  };
+
+  let matched_ms: f64 = segments.iter().map(|s| s.duration(&config) * 1000.0).sum();
+  (matched_ms / shorter_duration_ms as f64).min(1.0)
+}
+
+/// Find clusters of duplicate tracks among the given paths.
+/// Files that fail to decode or fingerprint are skipped, not fatal.
+pub fn find_duplicate_tracks(tracks: &[Track]) -> Vec<DuplicateCluster> {
+  info!("Scanning {} tracks for acoustic duplicates", tracks.len());
+
+  let mut fingerprints: Vec<(&Track, Vec<u32>)> = Vec::new();
+  for track in tracks {
+    match fingerprint_track(track) {
+      Ok(fp) => fingerprints.push((track, fp)),
+      Err(e) => warn!("Skipping {} ({}): {}", track.id, track.path, e),
+    }
+  }
+
+  let mut pairs = Vec::new();
+  for i in 0..fingerprints.len() {
+    for j in (i + 1)..fingerprints.len() {
+      let (track_a, fp_a) = &fingerprints[i];
+      let (track_b, fp_b) = &fingerprints[j];
+      let shorter_duration = track_a.duration.min(track_b.duration);
+
+      let similarity = fingerprint_similarity(fp_a, fp_b, shorter_duration);
+      if similarity >= DUPLICATE_OVERLAP_THRESHOLD {
+        pairs.push(DuplicatePair {
+          track_id_a: track_a.id.clone(),
+          track_id_b: track_b.id.clone(),
+          similarity,
+        });
+      }
+    }
+  }
+
+  group_pairs_into_clusters(pairs)
+}
+
+/// Union-find style grouping of pairwise matches into clusters.
+fn group_pairs_into_clusters(pairs: Vec<DuplicatePair>) -> Vec<DuplicateCluster> {
+  let mut clusters: Vec<DuplicateCluster> = Vec::new();
+
+  for pair in pairs {
+    let mut hits: Vec<usize> = (0..clusters.len())
+      .filter(|&i| clusters[i].track_ids.contains(&pair.track_id_a) || clusters[i].track_ids.contains(&pair.track_id_b))
+      .collect();
+
+    // A pair linking two existing clusters merges the later one into the first.
+    while hits.len() > 1 {
+      let other = clusters.remove(hits.pop().unwrap());
+      clusters[hits[0]].track_ids.extend(other.track_ids);
+      clusters[hits[0]].pairs.extend(other.pairs);
+    }
+
+    match hits.first().map(|&i| &mut clusters[i]) {
+      Some(cluster) => {
+        if !cluster.track_ids.contains(&pair.track_id_a) {
+          cluster.track_ids.push(pair.track_id_a.clone());
+        }
+        if !cluster.track_ids.contains(&pair.track_id_b) {
+          cluster.track_ids.push(pair.track_id_b.clone());
+        }
+        cluster.pairs.push(pair);
+      }
+      None => clusters.push(DuplicateCluster {
+        track_ids: vec![pair.track_id_a.clone(), pair.track_id_b.clone()],
+        pairs: vec![pair],
+      }),
+    }
+  }
+
+  info!("Found {} duplicate cluster(s)", clusters.len());
+  clusters
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_group_pairs_into_clusters_merges_overlapping_pairs() {
+    let pairs = vec![
+      DuplicatePair {
+        track_id_a: "a".to_string(),
+        track_id_b: "b".to_string(),
+        similarity: 0.9,
+      },
+      DuplicatePair {
+        track_id_a: "b".to_string(),
+        track_id_b: "c".to_string(),
+        similarity: 0.95,
+      },
+    ];
+
+    let clusters = group_pairs_into_clusters(pairs);
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].track_ids.len(), 3);
+  }
+
+  #[test]
+  fn test_fingerprint_similarity_zero_duration() {
+    assert_eq!(fingerprint_similarity(&[1, 2, 3], &[1, 2, 3], 0), 0.0);
+  }
+}
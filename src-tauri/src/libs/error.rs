@@ -23,6 +23,9 @@ pub enum HarmonyError {
   #[error("XML parsing error: {0}")]
   Xml(String),
 
+  #[error("Unsupported Traktor NML schema version: {0}")]
+  UnsupportedNmlVersion(String),
+
   #[allow(dead_code)]
   #[error("Track not found: {0}")]
   TrackNotFound(String),
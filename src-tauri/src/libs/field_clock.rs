@@ -0,0 +1,87 @@
+// AIDEV-NOTE: CRDT last-writer-wins clock for Track field merges
+// Each mergeable Track field gets its own (timestamp, source) pair, persisted
+// per-track so that merging the same NML twice, or merging after Harmony and
+// Traktor were edited independently between syncs, converges to the same
+// result instead of flip-flopping. See `libs::traktor::conflict_resolver`
+// for how this clock is applied during a sync, and `Database::get_field_clock`/
+// `save_field_clock` for persistence.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Which side last wrote a field, used to break exact-timestamp ties
+/// deterministically. Variant order is the tie-break order: the higher
+/// variant wins, so `Harmony > Traktor` here matches the legacy SMART_MERGE
+/// tie-break (Harmony wins on conflict).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SourcePriority {
+  Traktor,
+  Harmony,
+}
+
+/// A single field's LWW register: when it was last written, and by whom.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldStamp {
+  pub updated_at: i64,
+  pub source: SourcePriority,
+}
+
+impl FieldStamp {
+  /// True if `self` should overwrite a field currently at `other`: a
+  /// strictly newer timestamp, or an exact tie broken by `source`.
+  pub fn wins_over(&self, other: &FieldStamp) -> bool {
+    if self.updated_at != other.updated_at {
+      self.updated_at > other.updated_at
+    } else {
+      self.source > other.source
+    }
+  }
+}
+
+/// Per-track map of field name -> its LWW stamp. Fields with no entry yet
+/// (never persisted) are treated as stamped at `updated_at: 0`.
+pub type FieldClock = HashMap<String, FieldStamp>;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_newer_timestamp_wins() {
+    let older = FieldStamp {
+      updated_at: 100,
+      source: SourcePriority::Harmony,
+    };
+    let newer = FieldStamp {
+      updated_at: 200,
+      source: SourcePriority::Traktor,
+    };
+    assert!(newer.wins_over(&older));
+    assert!(!older.wins_over(&newer));
+  }
+
+  #[test]
+  fn test_tie_broken_by_source_priority() {
+    let traktor = FieldStamp {
+      updated_at: 100,
+      source: SourcePriority::Traktor,
+    };
+    let harmony = FieldStamp {
+      updated_at: 100,
+      source: SourcePriority::Harmony,
+    };
+    assert!(harmony.wins_over(&traktor));
+    assert!(!traktor.wins_over(&harmony));
+  }
+
+  #[test]
+  fn test_identical_stamp_does_not_win_over_itself() {
+    let stamp = FieldStamp {
+      updated_at: 100,
+      source: SourcePriority::Traktor,
+    };
+    assert!(!stamp.wins_over(&stamp));
+  }
+}
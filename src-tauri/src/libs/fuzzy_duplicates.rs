@@ -0,0 +1,376 @@
+// AIDEV-NOTE: Cross-library fuzzy duplicate detection by metadata similarity
+// Track::generate_id hashes only the lowercased path, so the same recording
+// stored in two folders (or imported once from disk and once from Traktor)
+// mints two unrelated IDs and never lines up with `libs::database`'s
+// by-path/by-id lookups. This module groups by content similarity instead,
+// as a caller-chosen set of criteria (a track missing `bitrate`/`year` tags
+// shouldn't be excluded from every comparison just because one field is
+// absent) - distinct from `libs::duplicate_detection`'s acoustic fingerprint
+// match (decodes the audio, expensive, format/transcode-proof) and
+// `Database::find_duplicate_tracks_by_key`'s fixed exact-normalized
+// `(artist, title)` key (cheap, SQL-indexed, but all-or-nothing on two fields).
+
+use crate::libs::normalize::normalize_key;
+use crate::libs::track::Track;
+
+/// Which metadata fields must all match for two tracks to be grouped as
+/// candidate duplicates. A plain bitmask rather than the `bitflags` crate,
+/// since nothing else in this codebase depends on it and six fixed bits
+/// don't need a macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DuplicateCriteria(u8);
+
+impl DuplicateCriteria {
+  pub const TITLE: Self = Self(1 << 0);
+  pub const ARTIST: Self = Self(1 << 1);
+  pub const YEAR: Self = Self(1 << 2);
+  pub const LENGTH: Self = Self(1 << 3);
+  pub const BITRATE: Self = Self(1 << 4);
+  pub const GENRE: Self = Self(1 << 5);
+
+  pub const fn empty() -> Self {
+    Self(0)
+  }
+
+  pub const fn all() -> Self {
+    Self(
+      Self::TITLE.0 | Self::ARTIST.0 | Self::YEAR.0 | Self::LENGTH.0 | Self::BITRATE.0 | Self::GENRE.0,
+    )
+  }
+
+  pub const fn contains(self, other: Self) -> bool {
+    self.0 & other.0 == other.0
+  }
+
+  pub const fn is_empty(self) -> bool {
+    self.0 == 0
+  }
+}
+
+impl std::ops::BitOr for DuplicateCriteria {
+  type Output = Self;
+
+  fn bitor(self, rhs: Self) -> Self {
+    Self(self.0 | rhs.0)
+  }
+}
+
+impl std::ops::BitOrAssign for DuplicateCriteria {
+  fn bitor_assign(&mut self, rhs: Self) {
+    self.0 |= rhs.0;
+  }
+}
+
+/// A group of tracks considered duplicates under `matched`, the criteria
+/// that were actually compared to form this group.
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+  pub tracks: Vec<Track>,
+  pub matched: DuplicateCriteria,
+}
+
+/// Two tracks' `duration` (milliseconds) must fall in the same bucket of
+/// this width to count as matching under `DuplicateCriteria::LENGTH` -
+/// mirrors the quantized-bucket approach `CuePoint::lww_key` uses for
+/// position matching, rather than requiring an exact millisecond match.
+const DURATION_TOLERANCE_MS: i64 = 2000;
+
+/// Parenthesized/bracketed segments containing one of these (case-insensitive)
+/// are dropped when `strip_feat_remix` is set, so "Song (feat. Other Artist)"
+/// and "Song" group together instead of being treated as different titles.
+const FEAT_REMIX_MARKERS: [&str; 6] = ["feat", "ft.", "ft ", "remix", "edit", "mix"];
+
+fn strip_feat_remix_parentheticals(input: &str) -> String {
+  let mut out = String::with_capacity(input.len());
+  let mut i = 0;
+
+  while i < input.len() {
+    let ch = input[i..].chars().next().unwrap();
+    let close = match ch {
+      '(' => Some(')'),
+      '[' => Some(']'),
+      _ => None,
+    };
+
+    if let Some(close) = close {
+      if let Some(rel_close) = input[i..].find(close) {
+        let inner_start = i + ch.len_utf8();
+        let inner_end = i + rel_close;
+        let end = inner_end + close.len_utf8();
+        let inner = input[inner_start..inner_end].to_lowercase();
+
+        if FEAT_REMIX_MARKERS.iter().any(|marker| inner.contains(marker)) {
+          i = end;
+          continue;
+        }
+      }
+    }
+
+    out.push(ch);
+    i += ch.len_utf8();
+  }
+
+  out
+}
+
+/// Normalize a title/artist for fuzzy comparison: optionally strip
+/// feat./remix parentheticals, then ASCII-fold/lowercase/collapse
+/// whitespace via the same key used for search and exact-key dedup.
+fn normalize_text_for_match(value: &str, strip_feat_remix: bool) -> String {
+  if strip_feat_remix {
+    normalize_key(&strip_feat_remix_parentheticals(value))
+  } else {
+    normalize_key(value)
+  }
+}
+
+fn duration_bucket(duration_ms: i64) -> i64 {
+  duration_ms / DURATION_TOLERANCE_MS
+}
+
+/// Build the composite sort/grouping key for `track` under `criteria`,
+/// fields joined by a control character that can't appear in tag text so
+/// two tracks never collide across criterion boundaries.
+fn match_key(track: &Track, criteria: DuplicateCriteria, strip_feat_remix: bool) -> String {
+  let mut parts: Vec<String> = Vec::new();
+
+  if criteria.contains(DuplicateCriteria::TITLE) {
+    parts.push(normalize_text_for_match(&track.title, strip_feat_remix));
+  }
+  if criteria.contains(DuplicateCriteria::ARTIST) {
+    parts.push(
+      track
+        .artist
+        .as_deref()
+        .map(|artist| normalize_text_for_match(artist, strip_feat_remix))
+        .unwrap_or_default(),
+    );
+  }
+  if criteria.contains(DuplicateCriteria::YEAR) {
+    parts.push(track.year.map(|year| year.to_string()).unwrap_or_default());
+  }
+  if criteria.contains(DuplicateCriteria::LENGTH) {
+    parts.push(duration_bucket(track.duration).to_string());
+  }
+  if criteria.contains(DuplicateCriteria::BITRATE) {
+    parts.push(track.bitrate.map(|bitrate| bitrate.to_string()).unwrap_or_default());
+  }
+  if criteria.contains(DuplicateCriteria::GENRE) {
+    parts.push(track.genre.as_deref().map(normalize_key).unwrap_or_default());
+  }
+
+  parts.join("\u{1f}")
+}
+
+/// Human-readable labels for the criteria bits set in `criteria`, in a fixed
+/// order - used to report which fields matched without exposing the raw
+/// bitmask across the Tauri IPC boundary.
+pub fn criteria_labels(criteria: DuplicateCriteria) -> Vec<&'static str> {
+  let mut labels = Vec::new();
+  if criteria.contains(DuplicateCriteria::TITLE) {
+    labels.push("title");
+  }
+  if criteria.contains(DuplicateCriteria::ARTIST) {
+    labels.push("artist");
+  }
+  if criteria.contains(DuplicateCriteria::YEAR) {
+    labels.push("year");
+  }
+  if criteria.contains(DuplicateCriteria::LENGTH) {
+    labels.push("length");
+  }
+  if criteria.contains(DuplicateCriteria::BITRATE) {
+    labels.push("bitrate");
+  }
+  if criteria.contains(DuplicateCriteria::GENRE) {
+    labels.push("genre");
+  }
+  labels
+}
+
+/// Parse criteria labels (as produced by `criteria_labels`, case-insensitive)
+/// back into a `DuplicateCriteria`, e.g. for decoding a Tauri command
+/// argument. Unrecognized labels are ignored.
+pub fn criteria_from_labels<S: AsRef<str>>(labels: &[S]) -> DuplicateCriteria {
+  labels.iter().fold(DuplicateCriteria::empty(), |acc, label| {
+    let bit = match label.as_ref().to_lowercase().as_str() {
+      "title" => DuplicateCriteria::TITLE,
+      "artist" => DuplicateCriteria::ARTIST,
+      "year" => DuplicateCriteria::YEAR,
+      "length" => DuplicateCriteria::LENGTH,
+      "bitrate" => DuplicateCriteria::BITRATE,
+      "genre" => DuplicateCriteria::GENRE,
+      _ => DuplicateCriteria::empty(),
+    };
+    acc | bit
+  })
+}
+
+/// Group `tracks` into candidate duplicate sets by sorting on the key built
+/// from `criteria` and collapsing adjacent equal-key runs. Returns only
+/// groups with 2+ tracks; each group reports the criteria it was matched on.
+/// `strip_feat_remix` controls whether title/artist comparison ignores
+/// "(feat. ...)"/"(remix)"-style parentheticals.
+pub fn find_fuzzy_duplicate_tracks(
+  tracks: &[Track],
+  criteria: DuplicateCriteria,
+  strip_feat_remix: bool,
+) -> Vec<DuplicateGroup> {
+  if criteria.is_empty() {
+    return Vec::new();
+  }
+
+  let mut keyed: Vec<(String, Track)> = tracks
+    .iter()
+    .cloned()
+    .map(|track| (match_key(&track, criteria, strip_feat_remix), track))
+    .collect();
+
+  keyed.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+
+  let mut groups: Vec<DuplicateGroup> = Vec::new();
+  let mut last_key: Option<String> = None;
+
+  for (key, track) in keyed {
+    if last_key.as_ref() == Some(&key) {
+      groups.last_mut().unwrap().tracks.push(track);
+    } else {
+      groups.push(DuplicateGroup { tracks: vec![track], matched: criteria });
+      last_key = Some(key);
+    }
+  }
+
+  groups.retain(|group| group.tracks.len() > 1);
+  groups
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn track(title: &str, artist: &str, year: Option<i32>, duration_ms: i64, bitrate: Option<i32>) -> Track {
+    Track {
+      id: Track::generate_id(&format!("{title}-{artist}")),
+      path: format!("/music/{title}.mp3"),
+      title: title.to_string(),
+      artist: Some(artist.to_string()),
+      album: None,
+      genre: None,
+      year,
+      duration: duration_ms,
+      bitrate,
+      comment: None,
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn groups_by_title_and_artist_ignoring_case_and_diacritics() {
+    let tracks = vec![
+      track("Björk - Army of Me", "Björk", None, 240_000, None),
+      track("BJORK - ARMY OF ME", "bjork", None, 240_000, None),
+      track("Unrelated", "Someone Else", None, 180_000, None),
+    ];
+
+    let groups = find_fuzzy_duplicate_tracks(
+      &tracks,
+      DuplicateCriteria::TITLE | DuplicateCriteria::ARTIST,
+      false,
+    );
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].tracks.len(), 2);
+  }
+
+  #[test]
+  fn strips_feat_parenthetical_when_enabled() {
+    let tracks = vec![
+      track("Titanium (feat. Sia)", "David Guetta", None, 245_000, None),
+      track("Titanium", "David Guetta", None, 245_000, None),
+    ];
+
+    let without_strip = find_fuzzy_duplicate_tracks(&tracks, DuplicateCriteria::TITLE, false);
+    assert_eq!(without_strip.len(), 0);
+
+    let with_strip = find_fuzzy_duplicate_tracks(&tracks, DuplicateCriteria::TITLE, true);
+    assert_eq!(with_strip.len(), 1);
+  }
+
+  #[test]
+  fn length_matches_within_tolerance_bucket() {
+    let tracks = vec![
+      track("Same Name", "Same Artist", None, 200_000, None),
+      track("Same Name", "Same Artist", None, 201_500, None),
+    ];
+
+    let groups = find_fuzzy_duplicate_tracks(
+      &tracks,
+      DuplicateCriteria::TITLE | DuplicateCriteria::LENGTH,
+      false,
+    );
+
+    assert_eq!(groups.len(), 1);
+  }
+
+  #[test]
+  fn year_and_bitrate_mismatch_splits_groups() {
+    let tracks = vec![
+      track("Remaster Test", "Artist", Some(1999), 200_000, Some(320)),
+      track("Remaster Test", "Artist", Some(2021), 200_000, Some(128)),
+    ];
+
+    let groups = find_fuzzy_duplicate_tracks(
+      &tracks,
+      DuplicateCriteria::TITLE | DuplicateCriteria::YEAR | DuplicateCriteria::BITRATE,
+      false,
+    );
+
+    assert_eq!(groups.len(), 0);
+  }
+
+  #[test]
+  fn empty_criteria_returns_no_groups() {
+    let tracks = vec![
+      track("A", "X", None, 100_000, None),
+      track("A", "X", None, 100_000, None),
+    ];
+
+    assert!(find_fuzzy_duplicate_tracks(&tracks, DuplicateCriteria::empty(), false).is_empty());
+  }
+
+  #[test]
+  fn singleton_tracks_are_not_returned_as_groups() {
+    let tracks = vec![track("Only One", "Artist", None, 100_000, None)];
+
+    let groups = find_fuzzy_duplicate_tracks(&tracks, DuplicateCriteria::TITLE, false);
+    assert!(groups.is_empty());
+  }
+
+  #[test]
+  fn criteria_labels_round_trip_through_strings() {
+    let criteria = DuplicateCriteria::TITLE | DuplicateCriteria::YEAR | DuplicateCriteria::BITRATE;
+    let labels = criteria_labels(criteria);
+    assert_eq!(labels, vec!["title", "year", "bitrate"]);
+    assert_eq!(criteria_from_labels(&labels), criteria);
+  }
+}
@@ -2,6 +2,14 @@
 // Scans filesystem and compares with database to detect:
 // - New audio files added by user
 // - Missing files (deleted or moved outside app)
+//
+// `.cue` sidecar files are never themselves added/removed entries - they
+// aren't a `SUPPORTED_EXTENSIONS` audio format, so `scan_library_directory`
+// simply never picks them up. A CUE sheet's own audio file is still one
+// filesystem path here; it only becomes several `Track` rows (see
+// `libs::audio_metadata::extract_metadata_multi_with_cues`) once imported,
+// which is why `check_library_changes` below groups DB tracks by path
+// instead of assuming one-path-one-track.
 
 use log::info;
 use serde::{Deserialize, Serialize};
@@ -92,11 +100,16 @@ pub fn check_library_changes(db: &Database, library_paths: &[String]) -> Result<
   let tracks_in_db = db.get_all_tracks()?;
   info!("Found {} tracks in database", tracks_in_db.len());
 
-  // 3. Build map of database tracks by normalized path
-  let mut db_tracks_map: HashMap<String, Track> = HashMap::new();
+  // 3. Build map of database tracks by normalized path. A CUE sheet splits
+  // one physical file into several virtual `Track`s sharing the same
+  // `path` (see `libs::audio_metadata::extract_metadata_multi_with_cues`),
+  // so this must collect all of them per path rather than keeping only the
+  // last insert - otherwise a removed mix file would only surface one of
+  // its virtual tracks below.
+  let mut db_tracks_map: HashMap<String, Vec<Track>> = HashMap::new();
   for track in tracks_in_db {
     let normalized = normalize_path(&track.path);
-    db_tracks_map.insert(normalized, track);
+    db_tracks_map.entry(normalized).or_default().push(track);
   }
 
   // 4. Find added files (in filesystem but not in DB)
@@ -110,16 +123,18 @@ pub fn check_library_changes(db: &Database, library_paths: &[String]) -> Result<
 
   // 5. Find removed files (in DB but not in filesystem)
   let mut removed = Vec::new();
-  for (normalized_path, track) in db_tracks_map {
+  for (normalized_path, tracks) in db_tracks_map {
     if !files_in_filesystem.contains(&normalized_path) {
-      // Double-check that file really doesn't exist
-      if !std::fs::metadata(&track.path).is_ok() {
-        removed.push(RemovedTrack {
-          id: track.id,
-          path: track.path,
-          title: track.title,
-          artist: track.artist,
-        });
+      for track in tracks {
+        // Double-check that file really doesn't exist
+        if !std::fs::metadata(&track.path).is_ok() {
+          removed.push(RemovedTrack {
+            id: track.id,
+            path: track.path,
+            title: track.title,
+            artist: track.artist,
+          });
+        }
       }
     }
   }
@@ -0,0 +1,196 @@
+// AIDEV-NOTE: M3U/M3U8 playlist import/export, keyed to the track table by
+// absolute path rather than a format-specific ID (plain M3U has none). This
+// is the one interchange format with no folder hierarchy of its own - a
+// `.m3u` file is just one ordered list of paths - so unlike
+// `libs::rekordbox`/`libs::serato` there's no `FolderTreeNode` involved.
+
+use std::collections::HashMap;
+
+use crate::libs::track::Track;
+
+/// One parsed M3U entry: the path as written in the file, plus whatever
+/// `#EXTINF` duration/title preceded it. Not yet resolved to a track - see
+/// `resolve_m3u_entries`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct M3uEntry {
+  pub path: String,
+  pub duration_secs: Option<i64>,
+  pub title: Option<String>,
+}
+
+/// Render `tracks` as an M3U8 playlist: an `#EXTM3U` header, then one
+/// `#EXTINF:<seconds>,<artist> - <title>` line followed by the file path,
+/// per track.
+pub fn export_m3u(tracks: &[Track]) -> String {
+  let mut out = String::from("#EXTM3U\n");
+
+  for track in tracks {
+    let label = match &track.artist {
+      Some(artist) => format!("{} - {}", artist, track.title),
+      None => track.title.clone(),
+    };
+    out.push_str(&format!("#EXTINF:{},{}\n", track.duration / 1000, label));
+    out.push_str(&track.path);
+    out.push('\n');
+  }
+
+  out
+}
+
+/// Parse an M3U/M3U8 document into its ordered list of entries. Blank lines
+/// and comments other than `#EXTINF` are ignored; an `#EXTINF` line's
+/// duration/title describe whichever path line follows it.
+pub fn parse_m3u(content: &str) -> Vec<M3uEntry> {
+  let mut entries = Vec::new();
+  let mut pending: Option<(Option<i64>, Option<String>)> = None;
+
+  for raw_line in content.lines() {
+    let line = raw_line.trim();
+    if line.is_empty() {
+      continue;
+    }
+
+    if let Some(rest) = line.strip_prefix("#EXTINF:") {
+      let (duration_part, title_part) = rest.split_once(',').unwrap_or((rest, ""));
+      let duration_secs = duration_part.trim().parse::<i64>().ok().filter(|d| *d >= 0);
+      let title = (!title_part.is_empty()).then(|| title_part.trim().to_string());
+      pending = Some((duration_secs, title));
+      continue;
+    }
+
+    if line.starts_with('#') {
+      continue;
+    }
+
+    let (duration_secs, title) = pending.take().unwrap_or((None, None));
+    entries.push(M3uEntry { path: line.to_string(), duration_secs, title });
+  }
+
+  entries
+}
+
+/// Result of matching parsed `M3uEntry`s to the library by path.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct M3uResolution {
+  /// Track IDs resolved, in playlist order.
+  pub track_ids: Vec<String>,
+  /// Entry paths that couldn't be matched to any known track, even via the
+  /// rename map.
+  pub unmatched_paths: Vec<String>,
+}
+
+/// Match `entries` to known tracks by absolute path, falling back to
+/// `rename_map` (old path -> new path) when a file has moved since the
+/// playlist was written - e.g. after a library-relocation reconciliation.
+///
+/// # Arguments
+/// * `tracks_by_path` - path -> track ID for every track currently in the library
+/// * `rename_map` - old path -> new path
+pub fn resolve_m3u_entries(
+  entries: &[M3uEntry],
+  tracks_by_path: &HashMap<String, String>,
+  rename_map: &HashMap<String, String>,
+) -> M3uResolution {
+  let mut resolution = M3uResolution::default();
+
+  for entry in entries {
+    let track_id = tracks_by_path
+      .get(&entry.path)
+      .or_else(|| rename_map.get(&entry.path).and_then(|new_path| tracks_by_path.get(new_path)));
+
+    match track_id {
+      Some(id) => resolution.track_ids.push(id.clone()),
+      None => resolution.unmatched_paths.push(entry.path.clone()),
+    }
+  }
+
+  resolution
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample_track(path: &str, title: &str, artist: Option<&str>) -> Track {
+    Track {
+      id: Track::generate_id(path),
+      path: path.to_string(),
+      title: title.to_string(),
+      artist: artist.map(|a| a.to_string()),
+      album: None,
+      genre: None,
+      year: None,
+      duration: 215_000,
+      bitrate: None,
+      comment: None,
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_export_then_parse_round_trip() {
+    let tracks = vec![sample_track("/Music/a.mp3", "Track A", Some("Artist A"))];
+    let m3u = export_m3u(&tracks);
+    assert!(m3u.starts_with("#EXTM3U\n"));
+
+    let entries = parse_m3u(&m3u);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].path, "/Music/a.mp3");
+    assert_eq!(entries[0].duration_secs, Some(215));
+    assert_eq!(entries[0].title, Some("Artist A - Track A".to_string()));
+  }
+
+  #[test]
+  fn test_parse_ignores_blank_lines_and_plain_comments() {
+    let content = "#EXTM3U\n\n# just a comment\n/Music/a.mp3\n";
+    let entries = parse_m3u(content);
+    assert_eq!(entries, vec![M3uEntry { path: "/Music/a.mp3".to_string(), duration_secs: None, title: None }]);
+  }
+
+  #[test]
+  fn test_resolve_matches_by_path() {
+    let entries = vec![M3uEntry { path: "/Music/a.mp3".to_string(), duration_secs: None, title: None }];
+    let tracks_by_path = HashMap::from([("/Music/a.mp3".to_string(), "id1".to_string())]);
+
+    let resolution = resolve_m3u_entries(&entries, &tracks_by_path, &HashMap::new());
+    assert_eq!(resolution.track_ids, vec!["id1".to_string()]);
+    assert!(resolution.unmatched_paths.is_empty());
+  }
+
+  #[test]
+  fn test_resolve_falls_back_to_rename_map() {
+    let entries = vec![M3uEntry { path: "/Old/a.mp3".to_string(), duration_secs: None, title: None }];
+    let tracks_by_path = HashMap::from([("/New/a.mp3".to_string(), "id1".to_string())]);
+    let rename_map = HashMap::from([("/Old/a.mp3".to_string(), "/New/a.mp3".to_string())]);
+
+    let resolution = resolve_m3u_entries(&entries, &tracks_by_path, &rename_map);
+    assert_eq!(resolution.track_ids, vec!["id1".to_string()]);
+  }
+
+  #[test]
+  fn test_resolve_reports_unmatched_paths() {
+    let entries = vec![M3uEntry { path: "/Missing/a.mp3".to_string(), duration_secs: None, title: None }];
+    let resolution = resolve_m3u_entries(&entries, &HashMap::new(), &HashMap::new());
+    assert_eq!(resolution.unmatched_paths, vec!["/Missing/a.mp3".to_string()]);
+  }
+}
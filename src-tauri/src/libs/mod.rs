@@ -1,30 +1,71 @@
 // AIDEV-NOTE: Library modules for Harmony backend
 // Contains all domain models and database logic
 
+pub mod album_order;
 pub mod audio_analysis;
 pub mod audio_metadata;
+pub mod auto_cue;
 pub mod cover;
+pub mod cue_interop;
+pub mod cue_merge;
 pub mod cue_point;
+pub mod cue_sheet;
 pub mod database;
+pub mod dirstate;
+pub mod dj_library;
+pub mod duplicate_detection;
 pub mod error;
+pub mod field_clock;
 pub mod file_ops;
 pub mod folder;
+pub mod fuzzy_duplicates;
 pub mod library_changes;
+pub mod m3u;
+pub mod musicbrainz;
+pub mod normalize;
 pub mod playlist;
+pub mod playlist_tree;
+pub mod rekordbox;
+pub mod scan_pipeline;
+pub mod serato;
+pub mod similarity;
+pub mod store;
+pub mod tag_handler;
 pub mod track;
 pub mod traktor;
 
 // Re-export commonly used types
+pub use album_order::{order_tracks_by_album, parse_album_date};
 pub use audio_analysis::{
-  analyze_audio, analyze_audio_batch, AudioAnalysisOptions, AudioAnalysisResult,
+  analyze_audio, analyze_audio_batch, analyze_audio_batch_streaming, analyze_audio_batch_with_callback,
+  analyze_audio_batch_with_cores, AudioAnalysisOptions, AudioAnalysisResult, BatchProgress,
+  CancellationToken,
 };
-pub use audio_metadata::{extract_metadata, is_supported_extension, write_metadata};
-pub use cover::fetch_cover;
+pub use audio_metadata::{
+  extract_metadata, extract_metadata_multi, extract_metadata_multi_with_cues,
+  is_supported_extension, validate_track, write_metadata, write_metadata_ext, Id3v2Version,
+  TagValidationError, WriteMetadataOptions,
+};
+pub use auto_cue::{generate_auto_cues, AutoCueOptions};
+pub use cover::{fetch_cover, fetch_cover_cached, CoverCacheOptions};
 pub use cue_point::CuePoint;
-pub use database::Database;
+pub use database::{
+  Database, ReindexPhase, ReindexProgress, TraktorSyncWrite, TrackAnalysis, ANALYSIS_VERSION,
+  DEFAULT_SEARCH_THRESHOLD,
+};
+pub use dirstate::{classify_file, FileChange, FileDirstate};
+pub use dj_library::DjLibrary;
 pub use error::{HarmonyError, Result};
+pub use field_clock::{FieldClock, FieldStamp, SourcePriority};
 pub use file_ops::{copy_file, delete_file, move_file};
 pub use folder::Folder;
 pub use library_changes::{check_library_changes, LibraryChanges};
+pub use musicbrainz::{enrich_tracks, EnrichmentCandidate, TrackEnrichment};
+pub use normalize::normalize_key;
 pub use playlist::Playlist;
-pub use track::{Track, TrackRating};
+pub use playlist_tree::{FolderTreeNode, ImportedPlaylist};
+pub use scan_pipeline::{
+  run_parallel_scan, scan_paths, Inserter, ScanHandle, ScanOptions, ScanProgress, ScanSummary,
+};
+pub use store::LibraryStore;
+pub use track::{AlbumDate, AlbumSeq, Chapter, Track, TrackRating};
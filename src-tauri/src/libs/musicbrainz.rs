@@ -0,0 +1,701 @@
+// AIDEV-NOTE: MusicBrainz metadata enrichment module
+//
+// Looks up authoritative metadata (artist, album, release date, catalog
+// number, ISRC) for tracks with missing or low-confidence tags, to
+// complement (not replace) the Traktor sync in `libs::traktor`.
+//
+// Rather than issuing one lookup per track, tracks are grouped by artist
+// name; each distinct artist costs one search request (to resolve an
+// artist MBID) plus a *paged* Browse (`/recording?artist=...`,
+// `offset`/`limit`, 25 per page, one request per page) that collects every
+// recording for that artist. All tracks sharing that artist are then matched
+// against the browsed recordings locally, with no further network calls.
+// A shared [`RateLimiter`] keeps every request at least 1 second apart,
+// per MusicBrainz's API etiquette guidelines.
+
+use base64::{engine::general_purpose, Engine as _};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::libs::duplicate_detection::fingerprint_track;
+use crate::libs::{Result, Track};
+
+const USER_AGENT: &str = "Harmony/1.0 (+https://github.com/jvegaf/harmony)";
+const BASE_URL: &str = "https://musicbrainz.org/ws/2";
+const PAGE_SIZE: u32 = 25;
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+/// Candidates below this confidence aren't worth surfacing to the user.
+const MIN_CONFIDENCE: f64 = 0.3;
+/// Cap on candidates returned per track, best match first.
+const MAX_CANDIDATES: usize = 5;
+
+const ACOUSTID_BASE_URL: &str = "https://api.acoustid.org/v2/lookup";
+/// AcoustID requires a free client API key; read from the environment rather
+/// than hard-coding one, since keys are tied to the registering application.
+const ACOUSTID_API_KEY_ENV: &str = "ACOUSTID_API_KEY";
+/// Fingerprint matches below this AcoustID score are too unreliable to trust
+/// without a human confirming the candidate.
+const MIN_FINGERPRINT_CONFIDENCE: f64 = 0.5;
+
+/// One MusicBrainz recording match candidate for a track, with a confidence
+/// score so the caller (or the existing [`crate::libs::traktor::conflict_resolver::MergeStrategy`]
+/// machinery) can decide whether to apply it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrichmentCandidate {
+  pub recording_mbid: String,
+  pub artist: Option<String>,
+  pub album: Option<String>,
+  pub year: Option<i32>,
+  pub catalog_number: Option<String>,
+  pub isrc: Option<String>,
+  /// Primary MusicBrainz genre tag, if any (`inc=genres`).
+  pub genre: Option<String>,
+  /// Release label name, distinct from `catalog_number`.
+  pub label: Option<String>,
+  /// Full MusicBrainz release date string (e.g. `"2009-06-01"`), as opposed
+  /// to `year` which is just the parsed first 4 digits.
+  pub release_date: Option<String>,
+  /// 0.0-1.0 confidence that this candidate matches the source track.
+  pub confidence: f64,
+}
+
+/// Per-track enrichment result returned by [`enrich_tracks`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackEnrichment {
+  pub track_id: String,
+  pub candidates: Vec<EnrichmentCandidate>,
+}
+
+/// Raw MusicBrainz JSON shapes, trimmed to the fields we actually read.
+#[derive(Debug, Deserialize)]
+struct MbArtistCredit {
+  name: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct MbLabelRef {
+  name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct MbLabelInfo {
+  #[serde(rename = "catalog-number")]
+  catalog_number: Option<String>,
+  label: Option<MbLabelRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbGenre {
+  name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbRelease {
+  title: Option<String>,
+  date: Option<String>,
+  #[serde(rename = "label-info", default)]
+  label_info: Vec<MbLabelInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MbRecording {
+  id: String,
+  title: String,
+  length: Option<i64>,
+  #[serde(rename = "first-release-date")]
+  first_release_date: Option<String>,
+  #[serde(default)]
+  isrcs: Vec<String>,
+  #[serde(rename = "artist-credit", default)]
+  artist_credit: Vec<MbArtistCredit>,
+  #[serde(default)]
+  releases: Vec<MbRelease>,
+  #[serde(default)]
+  genres: Vec<MbGenre>,
+}
+
+impl MbRecording {
+  /// This recording's length in milliseconds, if MusicBrainz reported one.
+  pub(crate) fn length_ms(&self) -> Option<i64> {
+    self.length
+  }
+}
+
+#[derive(Debug, Deserialize)]
+struct MbRecordingList {
+  recordings: Vec<MbRecording>,
+  #[serde(rename = "recording-count")]
+  recording_count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct MbArtist {
+  id: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct MbArtistList {
+  #[serde(default)]
+  artists: Vec<MbArtist>,
+}
+
+/// Raw AcoustID JSON shapes, trimmed to the fields we actually read.
+#[derive(Debug, Deserialize)]
+struct AcoustIdReleaseGroup {
+  id: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct AcoustIdRecording {
+  id: String,
+  title: Option<String>,
+  #[serde(default)]
+  artists: Vec<MbArtistCredit>,
+  #[serde(default)]
+  releasegroups: Vec<AcoustIdReleaseGroup>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct AcoustIdResult {
+  score: f64,
+  #[serde(default)]
+  recordings: Vec<AcoustIdRecording>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AcoustIdResponse {
+  status: String,
+  #[serde(default)]
+  results: Vec<AcoustIdResult>,
+}
+
+/// A single, high-confidence fingerprint match: a MusicBrainz recording that
+/// AcoustID is confident is acoustically the same audio as the local file,
+/// as opposed to [`EnrichmentCandidate`]'s title/duration-based guesses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FingerprintMatch {
+  pub recording_mbid: String,
+  pub release_group_id: Option<String>,
+  pub artist: Option<String>,
+  pub album: Option<String>,
+  pub year: Option<i32>,
+  pub catalog_number: Option<String>,
+  pub isrc: Option<String>,
+  /// AcoustID's own match score, 0.0-1.0.
+  pub confidence: f64,
+}
+
+/// Enforces MusicBrainz's "no more than 1 request/sec" rate limit across
+/// every call an [`enrich_tracks`] run makes, regardless of how many
+/// artists/recordings it ends up touching.
+pub(crate) struct RateLimiter {
+  last_request: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+  pub(crate) fn new() -> Self {
+    Self {
+      last_request: Mutex::new(None),
+    }
+  }
+
+  pub(crate) fn throttle(&self) {
+    let mut last = self.last_request.lock().unwrap();
+    if let Some(prev) = *last {
+      let elapsed = prev.elapsed();
+      if elapsed < MIN_REQUEST_INTERVAL {
+        std::thread::sleep(MIN_REQUEST_INTERVAL - elapsed);
+      }
+    }
+    *last = Some(Instant::now());
+  }
+}
+
+pub(crate) fn http_client() -> Result<reqwest::blocking::Client> {
+  Ok(
+    reqwest::blocking::Client::builder()
+      .user_agent(USER_AGENT)
+      .build()?,
+  )
+}
+
+/// A track is worth enriching if any field MusicBrainz could supply is
+/// missing: artist, album, release year, catalog number, or ISRC.
+fn needs_enrichment(track: &Track) -> bool {
+  track.artist.as_deref().unwrap_or("").trim().is_empty()
+    || track.album.as_deref().unwrap_or("").trim().is_empty()
+    || track.year.is_none()
+    || track.catalog_number.is_none()
+    || track.isrc.is_none()
+}
+
+/// Resolve an artist name to its best-matching MusicBrainz artist MBID.
+pub(crate) fn search_artist_mbid(
+  client: &reqwest::blocking::Client,
+  limiter: &RateLimiter,
+  artist_name: &str,
+) -> Result<Option<String>> {
+  if artist_name.trim().is_empty() {
+    return Ok(None);
+  }
+
+  limiter.throttle();
+  let response = client
+    .get(format!("{}/artist", BASE_URL))
+    .query(&[("query", artist_name), ("fmt", "json"), ("limit", "1")])
+    .send()?;
+
+  if !response.status().is_success() {
+    warn!(
+      "MusicBrainz artist search for '{}' returned {}",
+      artist_name,
+      response.status()
+    );
+    return Ok(None);
+  }
+
+  let list: MbArtistList = response.json().unwrap_or_default();
+  Ok(list.artists.into_iter().next().map(|a| a.id))
+}
+
+/// Fetch every recording MusicBrainz has for `artist_mbid`, paging through
+/// the Browse API ([`PAGE_SIZE`] entries at a time) instead of issuing one
+/// lookup per track.
+pub(crate) fn browse_recordings_by_artist(
+  client: &reqwest::blocking::Client,
+  limiter: &RateLimiter,
+  artist_mbid: &str,
+) -> Result<Vec<MbRecording>> {
+  let mut all = Vec::new();
+  let mut offset: u32 = 0;
+
+  loop {
+    limiter.throttle();
+    let response = client
+      .get(format!("{}/recording", BASE_URL))
+      .query(&[
+        ("artist", artist_mbid),
+        ("fmt", "json"),
+        ("inc", "releases+isrcs+genres"),
+        ("limit", &PAGE_SIZE.to_string()),
+        ("offset", &offset.to_string()),
+      ])
+      .send()?;
+
+    if !response.status().is_success() {
+      warn!(
+        "MusicBrainz recording browse for artist {} returned {}",
+        artist_mbid,
+        response.status()
+      );
+      break;
+    }
+
+    let page: MbRecordingList = response.json()?;
+    let fetched = page.recordings.len();
+    all.extend(page.recordings);
+
+    offset += PAGE_SIZE;
+    if fetched == 0 || (offset as usize) >= page.recording_count {
+      break;
+    }
+  }
+
+  Ok(all)
+}
+
+/// Base64-encode a chromaprint fingerprint for the AcoustID `fingerprint`
+/// query parameter. AcoustID's own client libraries compress the raw
+/// subfingerprints before encoding; `rusty_chromaprint` doesn't expose that
+/// compression step, so this sends the raw subfingerprints as little-endian
+/// bytes instead - good enough for the lookups this module makes, but not
+/// byte-compatible with fpcalc output.
+fn encode_fingerprint(fingerprint: &[u32]) -> String {
+  let bytes: Vec<u8> = fingerprint.iter().flat_map(|v| v.to_le_bytes()).collect();
+  general_purpose::STANDARD.encode(bytes)
+}
+
+/// Identify `fingerprint` against AcoustID's database, returning its single
+/// best-scoring recording match (if any). Requires [`ACOUSTID_API_KEY_ENV`]
+/// to be set; silently skips (not an error) when it isn't, since fingerprint
+/// enrichment is an opt-in enhancement on top of the artist/title search in
+/// [`enrich_tracks`].
+fn lookup_acoustid(
+  client: &reqwest::blocking::Client,
+  limiter: &RateLimiter,
+  fingerprint: &[u32],
+  duration_secs: u32,
+) -> Result<Option<(String, Option<String>, Option<String>, Option<String>, f64)>> {
+  let api_key = match std::env::var(ACOUSTID_API_KEY_ENV) {
+    Ok(key) if !key.trim().is_empty() => key,
+    _ => {
+      debug!(
+        "{} not set, skipping AcoustID fingerprint lookup",
+        ACOUSTID_API_KEY_ENV
+      );
+      return Ok(None);
+    }
+  };
+
+  limiter.throttle();
+  let response = client
+    .get(ACOUSTID_BASE_URL)
+    .query(&[
+      ("client", api_key.as_str()),
+      ("meta", "recordings+releasegroups"),
+      ("duration", &duration_secs.to_string()),
+      ("fingerprint", &encode_fingerprint(fingerprint)),
+    ])
+    .send()?;
+
+  if !response.status().is_success() {
+    warn!("AcoustID lookup returned {}", response.status());
+    return Ok(None);
+  }
+
+  let parsed: AcoustIdResponse = response.json()?;
+  if parsed.status != "ok" {
+    return Ok(None);
+  }
+
+  let best = parsed
+    .results
+    .into_iter()
+    .filter(|r| r.score >= MIN_FINGERPRINT_CONFIDENCE)
+    .max_by(|a, b| a.score.total_cmp(&b.score));
+
+  Ok(best.and_then(|result| {
+    let recording = result.recordings.into_iter().next()?;
+    Some((
+      recording.id,
+      recording.title,
+      recording.artists.first().map(|a| a.name.clone()),
+      recording.releasegroups.first().map(|rg| rg.id.clone()),
+      result.score,
+    ))
+  }))
+}
+
+/// Fetch a single MusicBrainz recording by MBID, with the same `inc`
+/// parameters [`browse_recordings_by_artist`] uses, so it can be fed through
+/// the same [`to_candidate`] mapping.
+fn lookup_recording_by_mbid(
+  client: &reqwest::blocking::Client,
+  limiter: &RateLimiter,
+  recording_mbid: &str,
+) -> Result<Option<MbRecording>> {
+  limiter.throttle();
+  let response = client
+    .get(format!("{}/recording/{}", BASE_URL, recording_mbid))
+    .query(&[("fmt", "json"), ("inc", "releases+isrcs+artist-credits+genres")])
+    .send()?;
+
+  if !response.status().is_success() {
+    warn!(
+      "MusicBrainz recording lookup for {} returned {}",
+      recording_mbid,
+      response.status()
+    );
+    return Ok(None);
+  }
+
+  Ok(Some(response.json()?))
+}
+
+/// Identify `track`'s audio via AcoustID fingerprint and resolve the match
+/// to full MusicBrainz recording metadata. Returns `None` (not an error)
+/// when no AcoustID API key is set or AcoustID has no confident match -
+/// "nothing to enrich with", same as an empty [`TrackEnrichment::candidates`].
+/// A fingerprinting or network failure is returned as an error. If the
+/// matched recording can't be fetched from MusicBrainz, the match is still
+/// returned, carrying only AcoustID's artist and release group.
+pub fn identify_track(track: &Track) -> Result<Option<FingerprintMatch>> {
+  let fingerprint = fingerprint_track(track)?;
+  let duration_secs = (track.duration / 1000).max(0) as u32;
+
+  let client = http_client()?;
+  let limiter = RateLimiter::new();
+
+  let Some((recording_mbid, _title, acoustid_artist, release_group_id, confidence)) =
+    lookup_acoustid(&client, &limiter, &fingerprint, duration_secs)?
+  else {
+    return Ok(None);
+  };
+
+  let recording = lookup_recording_by_mbid(&client, &limiter, &recording_mbid)?;
+  let candidate = recording.as_ref().map(|r| to_candidate(r, confidence));
+
+  Ok(Some(FingerprintMatch {
+    recording_mbid,
+    release_group_id,
+    artist: candidate
+      .as_ref()
+      .and_then(|c| c.artist.clone())
+      .or(acoustid_artist),
+    album: candidate.as_ref().and_then(|c| c.album.clone()),
+    year: candidate.as_ref().and_then(|c| c.year),
+    catalog_number: candidate.as_ref().and_then(|c| c.catalog_number.clone()),
+    isrc: candidate.as_ref().and_then(|c| c.isrc.clone()),
+    confidence,
+  }))
+}
+
+/// Merge a [`FingerprintMatch`] onto `track`, filling only fields that are
+/// currently empty so a confirmed user edit is never clobbered by a lower-
+/// confidence automated guess (same "don't overwrite what's already there"
+/// rule [`needs_enrichment`] uses to decide what to enrich in the first
+/// place). `musicbrainz_id`/`release_group_id` are always stamped once
+/// resolved, since they're identifiers rather than display fields and a
+/// later sync can use them to skip re-querying this track.
+pub fn apply_fingerprint_match(track: &mut Track, matched: &FingerprintMatch) {
+  track.musicbrainz_id = Some(matched.recording_mbid.clone());
+  if matched.release_group_id.is_some() {
+    track.release_group_id = matched.release_group_id.clone();
+  }
+  if track.artist.as_deref().unwrap_or("").trim().is_empty() {
+    track.artist = matched.artist.clone();
+  }
+  if track.album.as_deref().unwrap_or("").trim().is_empty() {
+    track.album = matched.album.clone();
+  }
+  if track.year.is_none() {
+    track.year = matched.year;
+  }
+  if track.catalog_number.is_none() {
+    track.catalog_number = matched.catalog_number.clone();
+  }
+  if track.isrc.is_none() {
+    track.isrc = matched.isrc.clone();
+  }
+}
+
+pub(crate) fn to_candidate(recording: &MbRecording, confidence: f64) -> EnrichmentCandidate {
+  let release = recording.releases.first();
+
+  let release_date = release
+    .and_then(|r| r.date.clone())
+    .or_else(|| recording.first_release_date.clone());
+
+  let year = release_date
+    .as_deref()
+    .and_then(|d| d.get(0..4))
+    .and_then(|y| y.parse::<i32>().ok());
+
+  let label_info = release.and_then(|r| r.label_info.first());
+  let catalog_number = label_info.and_then(|l| l.catalog_number.clone());
+  let label = label_info.and_then(|l| l.label.as_ref()).and_then(|l| l.name.clone());
+
+  EnrichmentCandidate {
+    recording_mbid: recording.id.clone(),
+    artist: recording.artist_credit.first().map(|a| a.name.clone()),
+    album: release.and_then(|r| r.title.clone()),
+    year,
+    catalog_number,
+    isrc: recording.isrcs.first().cloned(),
+    genre: recording.genres.first().map(|g| g.name.clone()),
+    label,
+    release_date,
+    confidence,
+  }
+}
+
+/// Confidence that `recording` is the same song as `track`: title
+/// similarity weighted most heavily, with duration proximity as a
+/// tie-breaker (MusicBrainz recording lengths are in milliseconds, same as
+/// [`Track::duration`]).
+fn score_match(track: &Track, recording: &MbRecording) -> f64 {
+  score_title_duration(&track.title, Some(track.duration), recording)
+}
+
+/// Confidence that `recording` matches a `title`/`duration_ms` pair, the
+/// same weighting [`score_match`] uses for a `Track` - shared so
+/// `libs::traktor::musicbrainz_enrich` can match against `TraktorEntry`
+/// fields directly instead of going through the `Track` model.
+pub(crate) fn score_title_duration(title: &str, duration_ms: Option<i64>, recording: &MbRecording) -> f64 {
+  let title_score = title_similarity(title, &recording.title);
+  let duration_score = match (duration_ms, recording.length) {
+    (Some(duration_ms), Some(length_ms)) => {
+      let diff_ms = (duration_ms - length_ms).unsigned_abs() as f64;
+      (1.0 - diff_ms / 5000.0).clamp(0.0, 1.0)
+    }
+    _ => 0.5,
+  };
+
+  title_score * 0.7 + duration_score * 0.3
+}
+
+/// Case-insensitive title similarity in [0.0, 1.0], based on normalized
+/// Levenshtein distance.
+fn title_similarity(a: &str, b: &str) -> f64 {
+  let a = a.trim().to_lowercase();
+  let b = b.trim().to_lowercase();
+
+  let max_len = a.chars().count().max(b.chars().count());
+  if max_len == 0 {
+    return 1.0;
+  }
+
+  1.0 - (levenshtein(&a, &b) as f64 / max_len as f64).min(1.0)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+  let a: Vec<char> = a.chars().collect();
+  let b: Vec<char> = b.chars().collect();
+
+  let mut row: Vec<usize> = (0..=b.len()).collect();
+  for (i, &ca) in a.iter().enumerate() {
+    let mut prev_diag = row[0];
+    row[0] = i + 1;
+    for (j, &cb) in b.iter().enumerate() {
+      let cost = if ca == cb { 0 } else { 1 };
+      let deletion = row[j] + 1;
+      let insertion = row[j + 1] + 1;
+      let substitution = prev_diag + cost;
+      prev_diag = row[j + 1];
+      row[j + 1] = deletion.min(insertion).min(substitution);
+    }
+  }
+
+  row[b.len()]
+}
+
+/// Enrich `tracks` with candidate MusicBrainz matches, skipping tracks that
+/// already have all five enrichable fields populated. `on_progress` is
+/// called once per distinct artist processed (not per track), mirroring how
+/// `sync_traktor_nml` reports progress by batch of work rather than by item.
+pub fn enrich_tracks(
+  tracks: &[Track],
+  mut on_progress: impl FnMut(usize, usize, &str),
+) -> Result<Vec<TrackEnrichment>> {
+  let client = http_client()?;
+  let limiter = RateLimiter::new();
+
+  let mut tracks_by_artist: HashMap<String, Vec<&Track>> = HashMap::new();
+  for track in tracks {
+    if needs_enrichment(track) {
+      let artist = track.artist.clone().unwrap_or_default();
+      tracks_by_artist.entry(artist).or_default().push(track);
+    }
+  }
+
+  info!(
+    "Enriching {} track(s) across {} distinct artist(s) via MusicBrainz",
+    tracks.iter().filter(|t| needs_enrichment(t)).count(),
+    tracks_by_artist.len()
+  );
+
+  let total_artists = tracks_by_artist.len();
+  let mut candidates_by_track_id: HashMap<String, Vec<EnrichmentCandidate>> = HashMap::new();
+
+  for (idx, (artist_name, artist_tracks)) in tracks_by_artist.into_iter().enumerate() {
+    on_progress(idx + 1, total_artists, &artist_name);
+
+    let artist_mbid = match search_artist_mbid(&client, &limiter, &artist_name) {
+      Ok(Some(mbid)) => mbid,
+      Ok(None) => {
+        debug!("No MusicBrainz artist match for '{}'", artist_name);
+        continue;
+      }
+      Err(e) => {
+        warn!("MusicBrainz artist search failed for '{}': {}", artist_name, e);
+        continue;
+      }
+    };
+
+    let recordings = match browse_recordings_by_artist(&client, &limiter, &artist_mbid) {
+      Ok(recordings) => recordings,
+      Err(e) => {
+        warn!("MusicBrainz recording browse failed for '{}': {}", artist_name, e);
+        continue;
+      }
+    };
+
+    for track in artist_tracks {
+      let mut candidates: Vec<EnrichmentCandidate> = recordings
+        .iter()
+        .map(|recording| to_candidate(recording, score_match(track, recording)))
+        .filter(|candidate| candidate.confidence >= MIN_CONFIDENCE)
+        .collect();
+
+      candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+      candidates.truncate(MAX_CANDIDATES);
+
+      candidates_by_track_id.insert(track.id.clone(), candidates);
+    }
+  }
+
+  Ok(
+    tracks
+      .iter()
+      .filter_map(|track| {
+        candidates_by_track_id
+          .remove(&track.id)
+          .map(|candidates| TrackEnrichment {
+            track_id: track.id.clone(),
+            candidates,
+          })
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_title_similarity_exact_match() {
+    assert_eq!(title_similarity("Strobe", "strobe"), 1.0);
+  }
+
+  #[test]
+  fn test_title_similarity_partial_match() {
+    let score = title_similarity("Strobe (Original Mix)", "Strobe");
+    assert!(score > 0.3 && score < 1.0);
+  }
+
+  #[test]
+  fn test_needs_enrichment_missing_catalog_and_isrc() {
+    let track = Track {
+      id: "1".to_string(),
+      path: "/music/track.mp3".to_string(),
+      title: "Strobe".to_string(),
+      artist: Some("deadmau5".to_string()),
+      album: Some("For Lack of a Better Name".to_string()),
+      genre: None,
+      year: Some(2009),
+      duration: 450_000,
+      bitrate: None,
+      comment: None,
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    };
+
+    assert!(needs_enrichment(&track));
+  }
+}
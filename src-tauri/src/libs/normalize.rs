@@ -0,0 +1,119 @@
+// AIDEV-NOTE: ASCII-folded, case-insensitive normalization for track
+// metadata, so "Björk" matches "bjork" in search and duplicate detection
+// instead of requiring an exact diacritic match. The fold table is a
+// compile-time `phf::Map` (codepoint -> ASCII expansion) rather than a
+// runtime `HashMap`, since it's a fixed table baked into the binary and
+// looked up on every character of every search query/track field.
+
+use phf::phf_map;
+
+/// Accented letters map to their ASCII spelling and smart punctuation maps
+/// to the empty string (see below); everything else not in
+/// this table is either already ASCII (kept as-is if alphanumeric/space) or
+/// dropped (e.g. emoji, CJK - nothing useful to fold them to).
+static ASCII_FOLD: phf::Map<char, &'static str> = phf_map! {
+  'à' => "a", 'á' => "a", 'â' => "a", 'ã' => "a", 'ä' => "a", 'å' => "a", 'ā' => "a",
+  'è' => "e", 'é' => "e", 'ê' => "e", 'ë' => "e", 'ē' => "e", 'ė' => "e", 'ę' => "e",
+  'ì' => "i", 'í' => "i", 'î' => "i", 'ï' => "i", 'ī' => "i",
+  'ò' => "o", 'ó' => "o", 'ô' => "o", 'õ' => "o", 'ö' => "o", 'ø' => "o", 'ō' => "o",
+  'ù' => "u", 'ú' => "u", 'û' => "u", 'ü' => "u", 'ū' => "u",
+  'ý' => "y", 'ÿ' => "y",
+  'ñ' => "n", 'ń' => "n",
+  'ç' => "c", 'ć' => "c", 'č' => "c",
+  'ś' => "s", 'š' => "s",
+  'ź' => "z", 'ż' => "z", 'ž' => "z",
+  'ł' => "l",
+  'đ' => "d",
+  'ß' => "ss",
+  'æ' => "ae",
+  'œ' => "oe",
+  'þ' => "th",
+  'ð' => "d",
+  // Smart punctuation from re-encoded/typeset metadata. Folded to the empty
+  // string rather than their ASCII look-alikes (', ", -) so a smart-quoted
+  // and plain-ASCII-quoted copy of the same title normalize identically -
+  // plain ASCII punctuation is already dropped below, not kept.
+  '\u{2018}' => "", '\u{2019}' => "", '\u{201c}' => "", '\u{201d}' => "",
+  '\u{2013}' => "", '\u{2014}' => "",
+  '\u{2026}' => "",
+};
+
+/// Produce an ASCII-folded, lowercased, whitespace-collapsed key for
+/// `value`, suitable for comparing across diacritic/case/punctuation
+/// variants of the "same" artist/title/album - e.g. `normalize_key("Björk")
+/// == normalize_key("BJORK")`. Characters with no fold mapping and no
+/// ASCII-alphanumeric/whitespace meaning (emoji, CJK, etc.) are dropped
+/// rather than kept verbatim, since they'd never match a folded counterpart
+/// anyway.
+pub fn normalize_key(value: &str) -> String {
+  let mut out = String::with_capacity(value.len());
+  let mut last_was_space = false;
+
+  for ch in value.chars() {
+    let lower = ch.to_lowercase().next().unwrap_or(ch);
+
+    let piece: Option<&str> = if let Some(folded) = ASCII_FOLD.get(&lower) {
+      Some(folded)
+    } else if lower.is_ascii_alphanumeric() {
+      None // handled below via push(lower) to avoid an extra allocation
+    } else if lower.is_whitespace() {
+      Some(" ")
+    } else {
+      None
+    };
+
+    match piece {
+      Some(" ") => {
+        if !last_was_space && !out.is_empty() {
+          out.push(' ');
+          last_was_space = true;
+        }
+        continue;
+      }
+      Some(folded) => {
+        out.push_str(folded);
+        last_was_space = false;
+      }
+      None if lower.is_ascii_alphanumeric() => {
+        out.push(lower);
+        last_was_space = false;
+      }
+      None => {
+        // No fold mapping and not ASCII-alphanumeric - drop silently.
+      }
+    }
+  }
+
+  out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_diacritics_fold_to_ascii() {
+    assert_eq!(normalize_key("Björk"), normalize_key("BJORK"));
+    assert_eq!(normalize_key("Björk"), "bjork");
+  }
+
+  #[test]
+  fn test_german_eszett_expands_to_ss() {
+    assert_eq!(normalize_key("Straße"), "strasse");
+  }
+
+  #[test]
+  fn test_whitespace_collapses() {
+    assert_eq!(normalize_key("The   Prodigy"), "the prodigy");
+  }
+
+  #[test]
+  fn test_smart_quotes_normalize_like_ascii_quotes() {
+    assert_eq!(normalize_key("Rock \u{2018}n\u{2019} Roll"), normalize_key("Rock 'n' Roll"));
+  }
+
+  #[test]
+  fn test_unmapped_symbols_are_dropped_not_kept() {
+    assert_eq!(normalize_key("Daft Punk (feat. \u{1F3B5})"), "daft punk feat");
+  }
+}
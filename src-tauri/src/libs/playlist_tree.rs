@@ -0,0 +1,176 @@
+// AIDEV-NOTE: Format-agnostic playlist/folder tree model.
+//
+// `libs::traktor::playlist_sync` originally defined `FolderTreeNode` and
+// `ImportedPlaylist` as Traktor-specific types, but neither actually
+// references anything Traktor-shaped - they're just "a playlist with track
+// paths" and "a folder containing playlists and sub-folders". Pulling them
+// out here lets `libs::rekordbox` and `libs::serato` share the same tree and
+// the same folder-insertion logic instead of reimplementing it per format.
+
+use serde::{Deserialize, Serialize};
+
+use crate::libs::playlist::Playlist;
+
+/// A playlist with track paths, independent of any particular DJ software's
+/// on-disk representation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedPlaylist {
+  pub id: String,
+  pub name: String,
+  /// Track file paths (system format)
+  pub track_paths: Vec<String>,
+  /// Folder path in tree (e.g., "/$ROOT/My Folder")
+  pub folder_path: Option<String>,
+}
+
+/// Folder/playlist tree node for representing hierarchy, shared by every
+/// import/export path (Traktor NML, Rekordbox XML, Serato crates).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderTreeNode {
+  pub name: String,
+  pub is_folder: bool,
+  /// Only for playlists
+  pub playlist: Option<ImportedPlaylist>,
+  /// Child nodes (folders or playlists)
+  pub children: Vec<FolderTreeNode>,
+}
+
+impl FolderTreeNode {
+  pub fn folder(name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      is_folder: true,
+      playlist: None,
+      children: Vec::new(),
+    }
+  }
+}
+
+/// Flatten a folder tree to a list of playlists with folder paths.
+///
+/// AIDEV-NOTE: Recursively walks tree and collects all playlists
+/// - Sets folderPath for each playlist based on parent hierarchy
+/// - Example: "/$ROOT/House Music/Deep House"
+///
+/// # Arguments
+/// * `tree` - Root folder tree node
+/// * `parent_path` - Parent folder path (for recursion)
+///
+/// # Returns
+/// Flat list of playlists with folderPath set
+pub fn flatten_playlist_tree(
+  tree: &FolderTreeNode,
+  parent_path: Option<&str>,
+) -> Vec<ImportedPlaylist> {
+  let current_path = if let Some(parent) = parent_path {
+    format!("{}/{}", parent, tree.name)
+  } else {
+    format!("/{}", tree.name)
+  };
+
+  let mut playlists: Vec<ImportedPlaylist> = Vec::new();
+
+  if !tree.is_folder {
+    if let Some(mut playlist) = tree.playlist.clone() {
+      playlist.folder_path = parent_path.map(|s| s.to_string()).or(Some("/".to_string()));
+      playlists.push(playlist);
+    }
+  }
+
+  for child in &tree.children {
+    playlists.extend(flatten_playlist_tree(child, Some(&current_path)));
+  }
+
+  playlists
+}
+
+/// Insert `playlist` into `root` at `playlist.folder_path`, creating
+/// intermediate folder nodes on demand and reusing them for playlists that
+/// share a path prefix.
+///
+/// AIDEV-NOTE: Inverse of `flatten_playlist_tree`, shared by every export
+/// path that rebuilds a nested folder tree from Harmony's flat
+/// `folder_path` strings (Traktor's `build_traktor_playlists_node`,
+/// Rekordbox's and Serato's playlist exporters).
+pub fn insert_playlist_into_tree(root: &mut FolderTreeNode, playlist: ImportedPlaylist) {
+  let root_name = root.name.clone();
+  let folder_path = playlist.folder_path.clone().unwrap_or_default();
+  let segments: Vec<&str> = folder_path
+    .split('/')
+    .filter(|s| !s.is_empty() && *s != root_name)
+    .collect();
+
+  let mut current = root;
+  for segment in segments {
+    let idx = match current
+      .children
+      .iter()
+      .position(|node| node.is_folder && node.name == segment)
+    {
+      Some(idx) => idx,
+      None => {
+        current.children.push(FolderTreeNode::folder(segment));
+        current.children.len() - 1
+      }
+    };
+    current = &mut current.children[idx];
+  }
+
+  current.children.push(FolderTreeNode {
+    name: playlist.name.clone(),
+    is_folder: false,
+    playlist: Some(playlist),
+    children: Vec::new(),
+  });
+}
+
+/// Convert ImportedPlaylist to Harmony Playlist (for database storage).
+///
+/// AIDEV-NOTE: Final conversion step before saving to database
+/// - Harmony Playlist requires actual Track objects, not paths
+/// - This function creates the playlist metadata only
+/// - Track associations are created separately via PlaylistTrack entries
+///
+/// # Arguments
+/// * `imported` - ImportedPlaylist with track paths
+///
+/// # Returns
+/// Harmony Playlist (without tracks populated)
+pub fn convert_to_harmony_playlist(imported: &ImportedPlaylist) -> Playlist {
+  Playlist {
+    id: imported.id.clone(),
+    name: imported.name.clone(),
+    folder_id: imported.folder_path.clone(),
+    tracks: vec![], // Tracks are linked via PlaylistTrack table
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn leaf(name: &str, folder_path: Option<&str>) -> ImportedPlaylist {
+    ImportedPlaylist {
+      id: format!("id-{name}"),
+      name: name.to_string(),
+      track_paths: vec!["/music/a.mp3".to_string()],
+      folder_path: folder_path.map(|s| s.to_string()),
+    }
+  }
+
+  #[test]
+  fn insert_and_flatten_round_trip() {
+    let mut root = FolderTreeNode::folder("$ROOT");
+    insert_playlist_into_tree(&mut root, leaf("Deep House", Some("/$ROOT/House")));
+    insert_playlist_into_tree(&mut root, leaf("Top 40", None));
+
+    let flattened = flatten_playlist_tree(&root, None);
+    assert_eq!(flattened.len(), 2);
+    assert!(flattened
+      .iter()
+      .any(|p| p.name == "Deep House" && p.folder_path.as_deref() == Some("/$ROOT/House")));
+    assert!(flattened.iter().any(|p| p.name == "Top 40"));
+  }
+}
@@ -0,0 +1,719 @@
+// AIDEV-NOTE: Rekordbox `collection.xml` import/export.
+//
+// Mirrors `libs::traktor::{nml_types, nml_parser, nml_writer}`'s split
+// between type definitions and parse/serialize logic, but Rekordbox's
+// format is small enough that one module covers both. Playlists share the
+// format-agnostic `libs::playlist_tree::FolderTreeNode` with Traktor and
+// Serato, so only the Rekordbox-specific XML shapes and field mapping live
+// here.
+//
+// Reference: https://cdn.rekordbox.com/files/20200410160904/xml_format_list.pdf
+
+use quick_xml::de::from_str;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::libs::cue_point::{CuePoint, CueType};
+use crate::libs::playlist_tree::{insert_playlist_into_tree, FolderTreeNode, ImportedPlaylist};
+use crate::libs::{HarmonyError, Result, Track};
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+
+/// Rekordbox `NODE` folder type: a folder containing other nodes.
+const NODE_TYPE_FOLDER: &str = "0";
+/// Rekordbox `NODE` playlist type: a leaf playlist with `TRACK` references.
+const NODE_TYPE_PLAYLIST: &str = "1";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RekordboxDjPlaylists {
+  #[serde(rename = "@Version")]
+  pub version: String,
+  #[serde(rename = "PRODUCT")]
+  pub product: RekordboxProduct,
+  #[serde(rename = "COLLECTION")]
+  pub collection: RekordboxCollection,
+  #[serde(rename = "PLAYLISTS", skip_serializing_if = "Option::is_none")]
+  pub playlists: Option<RekordboxPlaylists>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RekordboxProduct {
+  #[serde(rename = "@Name")]
+  pub name: String,
+  #[serde(rename = "@Version")]
+  pub version: String,
+  #[serde(rename = "@Company")]
+  pub company: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RekordboxCollection {
+  #[serde(rename = "@Entries")]
+  pub entries: String,
+  #[serde(rename = "TRACK", default)]
+  pub track: Vec<RekordboxTrack>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RekordboxTrack {
+  #[serde(rename = "@TrackID")]
+  pub track_id: String,
+  #[serde(rename = "@Name", skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  #[serde(rename = "@Artist", skip_serializing_if = "Option::is_none")]
+  pub artist: Option<String>,
+  #[serde(rename = "@Album", skip_serializing_if = "Option::is_none")]
+  pub album: Option<String>,
+  #[serde(rename = "@Genre", skip_serializing_if = "Option::is_none")]
+  pub genre: Option<String>,
+  #[serde(rename = "@Year", skip_serializing_if = "Option::is_none")]
+  pub year: Option<String>,
+  #[serde(rename = "@AverageBpm", skip_serializing_if = "Option::is_none")]
+  pub average_bpm: Option<String>,
+  #[serde(rename = "@Tonality", skip_serializing_if = "Option::is_none")]
+  pub tonality: Option<String>,
+  #[serde(rename = "@Label", skip_serializing_if = "Option::is_none")]
+  pub label: Option<String>,
+  #[serde(rename = "@Comments", skip_serializing_if = "Option::is_none")]
+  pub comments: Option<String>,
+  #[serde(rename = "@TotalTime", skip_serializing_if = "Option::is_none")]
+  pub total_time: Option<String>,
+  #[serde(rename = "@Location")]
+  pub location: String,
+  #[serde(rename = "POSITION_MARK", default)]
+  pub position_mark: Vec<RekordboxPositionMark>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RekordboxPlaylists {
+  #[serde(rename = "NODE")]
+  pub root: RekordboxNode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RekordboxNode {
+  #[serde(rename = "@Name")]
+  pub name: String,
+  #[serde(rename = "@Type")]
+  pub node_type: String,
+  #[serde(rename = "@Count", skip_serializing_if = "Option::is_none")]
+  pub count: Option<String>,
+  #[serde(rename = "@KeyType", skip_serializing_if = "Option::is_none")]
+  pub key_type: Option<String>,
+  #[serde(rename = "@Entries", skip_serializing_if = "Option::is_none")]
+  pub entries: Option<String>,
+  #[serde(rename = "NODE", default)]
+  pub nodes: Vec<RekordboxNode>,
+  #[serde(rename = "TRACK", default)]
+  pub tracks: Vec<RekordboxTrackRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RekordboxTrackRef {
+  #[serde(rename = "@Key")]
+  pub key: String,
+}
+
+/// Deterministic numeric-looking TrackID for a track path, since Rekordbox
+/// keys playlist `TRACK/@Key` references to `COLLECTION/TRACK/@TrackID`
+/// rather than by path. Same approach as
+/// [`crate::libs::traktor::playlist_sync`]'s `generate_playlist_id`.
+fn generate_track_id(path: &str) -> String {
+  let mut hasher = DefaultHasher::new();
+  path.hash(&mut hasher);
+  hasher.finish().to_string()
+}
+
+/// Convert a system path to a Rekordbox `Location` file URI.
+///
+/// AIDEV-NOTE: Rekordbox stores `file://localhost/`-prefixed, percent-encoded
+/// paths rather than Traktor's volume+segmented `DIR`/`FILE` attributes, so
+/// this doesn't reuse `traktor::mapper`'s path helpers.
+fn path_to_location(system_path: &str) -> String {
+  let normalized = system_path.replace('\\', "/");
+  let normalized = normalized.strip_prefix('/').unwrap_or(&normalized);
+  let encoded = normalized
+    .split('/')
+    .map(|segment| urlencoding_component(segment))
+    .collect::<Vec<_>>()
+    .join("/");
+  format!("file://localhost/{}", encoded)
+}
+
+/// Inverse of [`path_to_location`].
+fn location_to_path(location: &str) -> String {
+  let stripped = location
+    .strip_prefix("file://localhost/")
+    .or_else(|| location.strip_prefix("file://localhost"))
+    .unwrap_or(location);
+  let decoded = stripped
+    .split('/')
+    .map(urldecoding_component)
+    .collect::<Vec<_>>()
+    .join("/");
+
+  #[cfg(target_os = "windows")]
+  {
+    decoded
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    format!("/{}", decoded)
+  }
+}
+
+/// Minimal percent-encoding for the handful of characters common in music
+/// library paths (spaces, `#`, `%`) - not a general-purpose URI encoder.
+fn urlencoding_component(segment: &str) -> String {
+  segment
+    .chars()
+    .map(|c| match c {
+      ' ' => "%20".to_string(),
+      '#' => "%23".to_string(),
+      '%' => "%25".to_string(),
+      other => other.to_string(),
+    })
+    .collect()
+}
+
+fn urldecoding_component(segment: &str) -> String {
+  segment
+    .replace("%20", " ")
+    .replace("%23", "#")
+    .replace("%25", "%")
+}
+
+fn build_rekordbox_track(track: &Track, track_id: &str, cues: &[CuePoint]) -> RekordboxTrack {
+  RekordboxTrack {
+    track_id: track_id.to_string(),
+    name: Some(track.title.clone()),
+    artist: track.artist.clone(),
+    album: track.album.clone(),
+    genre: track.genre.clone(),
+    year: track.year.map(|y| y.to_string()),
+    average_bpm: track.bpm.map(|bpm| bpm.to_string()),
+    tonality: track.initial_key.clone(),
+    label: track.label.clone(),
+    comments: track.comment.clone(),
+    total_time: Some((track.duration / 1000).to_string()),
+    location: path_to_location(&track.path),
+    position_mark: build_position_marks(cues),
+  }
+}
+
+fn map_rekordbox_track_to_harmony(entry: &RekordboxTrack) -> Track {
+  let path = location_to_path(&entry.location);
+  let title = entry
+    .name
+    .clone()
+    .unwrap_or_else(|| path.rsplit('/').next().unwrap_or(&path).to_string());
+  Track {
+    id: Track::generate_id(&path),
+    path,
+    title,
+    artist: entry.artist.clone(),
+    album: entry.album.clone(),
+    genre: entry.genre.clone(),
+    year: entry.year.as_deref().and_then(|y| y.parse().ok()),
+    duration: entry
+      .total_time
+      .as_deref()
+      .and_then(|t| t.parse::<i64>().ok())
+      .map(|secs| secs * 1000)
+      .unwrap_or(0),
+    bitrate: None,
+    comment: entry.comments.clone(),
+    bpm: entry.average_bpm.as_deref().and_then(|b| b.parse().ok()),
+    initial_key: entry.tonality.clone(),
+    rating: None,
+    label: entry.label.clone(),
+    catalog_number: None,
+    isrc: None,
+    musicbrainz_id: None,
+    release_group_id: None,
+    waveform_peaks: None,
+    added_at: None,
+    url: None,
+    start_ms: None,
+    end_ms: None,
+    chapters: Vec::new(),
+    album_date: None,
+    track_number: None,
+    album_seq: None,
+    artist_sort: None,
+    album_sort: None,
+    title_sort: None,
+    synced_lyrics: Vec::new(),
+  }
+}
+
+/// Build the `PLAYLISTS` NODE tree from a format-agnostic [`FolderTreeNode`].
+fn build_rekordbox_node_tree(tree: &FolderTreeNode, path_to_id: &HashMap<String, String>) -> RekordboxNode {
+  if !tree.is_folder {
+    let imported = tree.playlist.as_ref();
+    let tracks: Vec<RekordboxTrackRef> = imported
+      .map(|p| {
+        p.track_paths
+          .iter()
+          .filter_map(|path| path_to_id.get(path))
+          .map(|key| RekordboxTrackRef { key: key.clone() })
+          .collect()
+      })
+      .unwrap_or_default();
+
+    return RekordboxNode {
+      name: tree.name.clone(),
+      node_type: NODE_TYPE_PLAYLIST.to_string(),
+      count: None,
+      key_type: Some("0".to_string()),
+      entries: Some(tracks.len().to_string()),
+      nodes: Vec::new(),
+      tracks,
+    };
+  }
+
+  let nodes: Vec<RekordboxNode> = tree
+    .children
+    .iter()
+    .map(|child| build_rekordbox_node_tree(child, path_to_id))
+    .collect();
+
+  RekordboxNode {
+    name: tree.name.clone(),
+    node_type: NODE_TYPE_FOLDER.to_string(),
+    count: Some(nodes.len().to_string()),
+    key_type: None,
+    entries: None,
+    nodes,
+    tracks: Vec::new(),
+  }
+}
+
+/// Rebuild a format-agnostic [`FolderTreeNode`] from a parsed Rekordbox
+/// `PLAYLISTS` NODE tree, resolving `TRACK/@Key` references back to system
+/// paths via `id_to_path`.
+fn rekordbox_node_to_folder_tree(node: &RekordboxNode, id_to_path: &HashMap<String, String>) -> FolderTreeNode {
+  if node.node_type == NODE_TYPE_PLAYLIST {
+    let track_paths: Vec<String> = node
+      .tracks
+      .iter()
+      .filter_map(|t| id_to_path.get(&t.key).cloned())
+      .collect();
+
+    return FolderTreeNode {
+      name: node.name.clone(),
+      is_folder: false,
+      playlist: Some(ImportedPlaylist {
+        id: generate_track_id(&node.name),
+        name: node.name.clone(),
+        track_paths,
+        folder_path: None,
+      }),
+      children: Vec::new(),
+    };
+  }
+
+  FolderTreeNode {
+    name: node.name.clone(),
+    is_folder: true,
+    playlist: None,
+    children: node
+      .nodes
+      .iter()
+      .map(|child| rekordbox_node_to_folder_tree(child, id_to_path))
+      .collect(),
+  }
+}
+
+/// Export Harmony's library to a Rekordbox `collection.xml` document.
+///
+/// # Arguments
+/// * `tracks` - All tracks to include in the `COLLECTION`
+/// * `playlists_tree` - Root playlist tree (see
+///   [`crate::libs::playlist_tree::insert_playlist_into_tree`] to build one
+///   from flat Harmony playlists)
+/// * `cues_by_track_id` - Cue points per track, keyed by Harmony `Track::id`,
+///   nested as `POSITION_MARK` elements under each track's `TRACK` entry
+///
+/// # Returns
+/// A complete `collection.xml` string, ready to write to disk.
+pub fn export_rekordbox_xml(
+  tracks: &[Track],
+  playlists_tree: &FolderTreeNode,
+  cues_by_track_id: &HashMap<String, Vec<CuePoint>>,
+) -> Result<String> {
+  let path_to_id: HashMap<String, String> = tracks
+    .iter()
+    .map(|t| (t.path.clone(), generate_track_id(&t.path)))
+    .collect();
+
+  let no_cues: Vec<CuePoint> = Vec::new();
+  let collection = RekordboxCollection {
+    entries: tracks.len().to_string(),
+    track: tracks
+      .iter()
+      .map(|t| {
+        let cues = cues_by_track_id.get(&t.id).unwrap_or(&no_cues);
+        build_rekordbox_track(t, &path_to_id[&t.path], cues)
+      })
+      .collect(),
+  };
+
+  let doc = RekordboxDjPlaylists {
+    version: "1.0.0".to_string(),
+    product: RekordboxProduct {
+      name: "rekordbox".to_string(),
+      version: "6.0.0".to_string(),
+      company: "AlphaTheta".to_string(),
+    },
+    collection,
+    playlists: Some(RekordboxPlaylists {
+      root: build_rekordbox_node_tree(playlists_tree, &path_to_id),
+    }),
+  };
+
+  let body = quick_xml::se::to_string(&doc)
+    .map_err(|e| HarmonyError::Xml(format!("Failed to serialize Rekordbox XML: {}", e)))?;
+
+  Ok(format!("{}{}", XML_DECLARATION, body))
+}
+
+/// Parse a Rekordbox `collection.xml` document into Harmony tracks, a
+/// format-agnostic playlist tree, and each track's cue points (keyed by the
+/// Harmony `Track::id` the same track appears under in the returned `Vec`).
+pub fn parse_rekordbox_xml(xml: &str) -> Result<(Vec<Track>, FolderTreeNode, HashMap<String, Vec<CuePoint>>)> {
+  let doc: RekordboxDjPlaylists =
+    from_str(xml).map_err(|e| HarmonyError::Xml(format!("Failed to parse Rekordbox XML: {}", e)))?;
+
+  let tracks: Vec<Track> = doc
+    .collection
+    .track
+    .iter()
+    .map(map_rekordbox_track_to_harmony)
+    .collect();
+
+  let cues_by_track_id: HashMap<String, Vec<CuePoint>> = doc
+    .collection
+    .track
+    .iter()
+    .zip(tracks.iter())
+    .filter(|(entry, _)| !entry.position_mark.is_empty())
+    .map(|(entry, track)| (track.id.clone(), parse_position_marks(&entry.position_mark, &track.id)))
+    .collect();
+
+  let id_to_path: HashMap<String, String> = doc
+    .collection
+    .track
+    .iter()
+    .map(|t| (t.track_id.clone(), location_to_path(&t.location)))
+    .collect();
+
+  let tree = match &doc.playlists {
+    Some(playlists) => rekordbox_node_to_folder_tree(&playlists.root, &id_to_path),
+    None => FolderTreeNode::folder("ROOT"),
+  };
+
+  Ok((tracks, tree, cues_by_track_id))
+}
+
+/// Insert Harmony playlists (as paired `(ImportedPlaylist,)` track lists) into
+/// a fresh root tree, for callers that only have the flat
+/// `libs::playlist::Playlist` rows rather than an existing tree.
+pub fn build_rekordbox_playlists_tree(playlists: Vec<ImportedPlaylist>) -> FolderTreeNode {
+  let mut root = FolderTreeNode::folder("ROOT");
+  for playlist in playlists {
+    insert_playlist_into_tree(&mut root, playlist);
+  }
+  root
+}
+
+/// Rekordbox `POSITION_MARK` hotcue type: an ordinary cue point.
+const MARK_TYPE_CUE: &str = "0";
+/// Rekordbox `POSITION_MARK` hotcue type: a saved loop (`End` is set).
+const MARK_TYPE_LOOP: &str = "4";
+/// Rekordbox `Num` value meaning "unassigned memory cue" rather than a
+/// numbered hotcue slot.
+const MARK_NUM_UNASSIGNED: i32 = -1;
+
+/// A single Rekordbox hotcue/memory-cue/loop marker, nested inside a
+/// `COLLECTION` `TRACK` entry. See `build_position_marks`/
+/// `parse_position_marks` for the `CuePoint` mapping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RekordboxPositionMark {
+  #[serde(rename = "@Name", skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  #[serde(rename = "@Type")]
+  pub mark_type: String,
+  #[serde(rename = "@Start")]
+  pub start: String,
+  #[serde(rename = "@End", skip_serializing_if = "Option::is_none")]
+  pub end: Option<String>,
+  #[serde(rename = "@Num")]
+  pub num: String,
+  #[serde(rename = "@Red", skip_serializing_if = "Option::is_none")]
+  pub red: Option<String>,
+  #[serde(rename = "@Green", skip_serializing_if = "Option::is_none")]
+  pub green: Option<String>,
+  #[serde(rename = "@Blue", skip_serializing_if = "Option::is_none")]
+  pub blue: Option<String>,
+}
+
+/// Parse a `#rrggbb` `CuePoint::color` into its `(r, g, b)` byte components.
+fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
+  let hex = hex.strip_prefix('#')?;
+  if hex.len() != 6 {
+    return None;
+  }
+  Some((
+    u8::from_str_radix(&hex[0..2], 16).ok()?,
+    u8::from_str_radix(&hex[2..4], 16).ok()?,
+    u8::from_str_radix(&hex[4..6], 16).ok()?,
+  ))
+}
+
+/// Build Rekordbox `POSITION_MARK` elements from a track's cue points, for
+/// nesting inside its `collection.xml` `TRACK` entry.
+///
+/// AIDEV-NOTE: Rekordbox only models hotcues/memory-cues/loops - `CueType`
+/// variants with no Rekordbox equivalent (`FadeIn`, `FadeOut`, `Load`,
+/// `Grid`) are skipped rather than exported as a lossy approximation.
+pub fn build_position_marks(cues: &[CuePoint]) -> Vec<RekordboxPositionMark> {
+  cues
+    .iter()
+    .filter(|c| matches!(c.cue_type, CueType::HotCue | CueType::Loop))
+    .map(|cue| {
+      let (red, green, blue) = match cue.color.as_deref().and_then(parse_hex_color) {
+        Some((r, g, b)) => (Some(r.to_string()), Some(g.to_string()), Some(b.to_string())),
+        None => (None, None, None),
+      };
+
+      RekordboxPositionMark {
+        name: cue.name.clone(),
+        mark_type: if cue.cue_type == CueType::Loop { MARK_TYPE_LOOP } else { MARK_TYPE_CUE }.to_string(),
+        start: (cue.position_ms / 1000.0).to_string(),
+        end: cue.length_ms.map(|len| ((cue.position_ms + len) / 1000.0).to_string()),
+        num: cue
+          .hotcue_slot
+          .map(|slot| slot.to_string())
+          .unwrap_or_else(|| MARK_NUM_UNASSIGNED.to_string()),
+        red,
+        green,
+        blue,
+      }
+    })
+    .collect()
+}
+
+/// Inverse of `build_position_marks`, reconstructing `CuePoint` rows for
+/// `track_id` from a track's parsed `POSITION_MARK` elements.
+pub fn parse_position_marks(marks: &[RekordboxPositionMark], track_id: &str) -> Vec<CuePoint> {
+  marks
+    .iter()
+    .enumerate()
+    .map(|(order, mark)| {
+      let start_secs: f64 = mark.start.parse().unwrap_or(0.0);
+      let end_secs: Option<f64> = mark.end.as_deref().and_then(|s| s.parse().ok());
+      let position_ms = start_secs * 1000.0;
+
+      let color = match (&mark.red, &mark.green, &mark.blue) {
+        (Some(r), Some(g), Some(b)) => {
+          let (r, g, b) = (r.parse::<u8>().ok(), g.parse::<u8>().ok(), b.parse::<u8>().ok());
+          match (r, g, b) {
+            (Some(r), Some(g), Some(b)) => Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+            _ => None,
+          }
+        }
+        _ => None,
+      };
+
+      CuePoint {
+        id: format!("{}-rb-{}", track_id, order),
+        track_id: track_id.to_string(),
+        cue_type: if mark.mark_type == MARK_TYPE_LOOP { CueType::Loop } else { CueType::HotCue },
+        position_ms,
+        length_ms: end_secs.map(|end| end * 1000.0 - position_ms),
+        hotcue_slot: mark.num.parse::<i32>().ok().filter(|&n| n != MARK_NUM_UNASSIGNED),
+        name: mark.name.clone(),
+        color,
+        grid_bpm: None,
+        order: Some(order as i32),
+        updated_at: 0,
+        deleted: false,
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample_track(path: &str, title: &str) -> Track {
+    Track {
+      id: Track::generate_id(path),
+      path: path.to_string(),
+      title: title.to_string(),
+      artist: Some("Test Artist".to_string()),
+      album: None,
+      genre: None,
+      year: Some(2024),
+      duration: 180_000,
+      bitrate: None,
+      comment: None,
+      bpm: Some(128),
+      initial_key: Some("8A".to_string()),
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_path_to_location_round_trip() {
+    let path = "/Users/josev/Music/My Track.mp3";
+    let location = path_to_location(path);
+    assert_eq!(location, "file://localhost/Users/josev/Music/My%20Track.mp3");
+    assert_eq!(location_to_path(&location), path);
+  }
+
+  #[test]
+  fn test_export_then_parse_round_trip() {
+    let tracks = vec![sample_track("/Music/a.mp3", "Track A")];
+    let mut tree = FolderTreeNode::folder("ROOT");
+    insert_playlist_into_tree(
+      &mut tree,
+      ImportedPlaylist {
+        id: "p1".to_string(),
+        name: "My Playlist".to_string(),
+        track_paths: vec!["/Music/a.mp3".to_string()],
+        folder_path: None,
+      },
+    );
+
+    let xml = export_rekordbox_xml(&tracks, &tree, &HashMap::new()).unwrap();
+    assert!(xml.starts_with(XML_DECLARATION));
+
+    let (parsed_tracks, parsed_tree, _cues) = parse_rekordbox_xml(&xml).unwrap();
+    assert_eq!(parsed_tracks.len(), 1);
+    assert_eq!(parsed_tracks[0].title, "Track A");
+
+    let playlists = crate::libs::playlist_tree::flatten_playlist_tree(&parsed_tree, None);
+    assert_eq!(playlists.len(), 1);
+    assert_eq!(playlists[0].track_paths, vec!["/Music/a.mp3".to_string()]);
+  }
+
+  fn sample_cue(cue_type: CueType, position_ms: f64, slot: Option<i32>) -> CuePoint {
+    CuePoint {
+      id: "cue1".to_string(),
+      track_id: "track1".to_string(),
+      cue_type,
+      position_ms,
+      length_ms: None,
+      hotcue_slot: slot,
+      name: Some("Drop".to_string()),
+      color: Some("#ff0000".to_string()),
+      grid_bpm: None,
+      order: Some(0),
+      updated_at: 0,
+      deleted: false,
+    }
+  }
+
+  #[test]
+  fn test_position_marks_round_trip() {
+    let cues = vec![sample_cue(CueType::HotCue, 12_500.0, Some(2))];
+    let marks = build_position_marks(&cues);
+    assert_eq!(marks.len(), 1);
+    assert_eq!(marks[0].num, "2");
+    assert_eq!(marks[0].red, Some("255".to_string()));
+
+    let parsed = parse_position_marks(&marks, "track1");
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].hotcue_slot, Some(2));
+    assert_eq!(parsed[0].color, Some("#ff0000".to_string()));
+    assert!((parsed[0].position_ms - 12_500.0).abs() < 1.0);
+  }
+
+  #[test]
+  fn test_position_marks_skip_unsupported_cue_types() {
+    let cues = vec![sample_cue(CueType::FadeIn, 0.0, None)];
+    assert!(build_position_marks(&cues).is_empty());
+  }
+
+  #[test]
+  fn test_position_marks_unassigned_num_has_no_hotcue_slot() {
+    let cues = vec![sample_cue(CueType::HotCue, 1000.0, None)];
+    let marks = build_position_marks(&cues);
+    assert_eq!(marks[0].num, "-1");
+
+    let parsed = parse_position_marks(&marks, "track1");
+    assert_eq!(parsed[0].hotcue_slot, None);
+  }
+
+  #[test]
+  fn test_export_then_parse_round_trip_preserves_memory_cues() {
+    let track = sample_track("/Music/a.mp3", "Track A");
+    let tree = FolderTreeNode::folder("ROOT");
+    let cues = vec![
+      sample_cue(CueType::Loop, 5_000.0, None),
+      sample_cue(CueType::Loop, 30_000.0, None),
+      sample_cue(CueType::Loop, 90_500.0, None),
+    ];
+    let mut cues_by_track_id = HashMap::new();
+    cues_by_track_id.insert(track.id.clone(), cues);
+
+    let xml = export_rekordbox_xml(&[track.clone()], &tree, &cues_by_track_id).unwrap();
+    let (parsed_tracks, _tree, parsed_cues) = parse_rekordbox_xml(&xml).unwrap();
+
+    assert_eq!(parsed_tracks.len(), 1);
+    let parsed_track_id = parsed_tracks[0].id.clone();
+    let marks = parsed_cues.get(&parsed_track_id).expect("memory cues should round-trip");
+    assert_eq!(marks.len(), 3);
+    for mark in marks {
+      assert_eq!(mark.cue_type, CueType::Loop);
+      assert_eq!(mark.hotcue_slot, None);
+    }
+    let mut positions: Vec<i64> = marks.iter().map(|m| m.position_ms.round() as i64).collect();
+    positions.sort();
+    assert_eq!(positions, vec![5_000, 30_000, 90_500]);
+  }
+
+  #[test]
+  fn test_export_then_parse_round_trip_preserves_hotcue_bank() {
+    let track = sample_track("/Music/a.mp3", "Track A");
+    let tree = FolderTreeNode::folder("ROOT");
+    let cues: Vec<CuePoint> = (0..8).map(|slot| sample_cue(CueType::HotCue, slot as f64 * 1_000.0, Some(slot))).collect();
+    let mut cues_by_track_id = HashMap::new();
+    cues_by_track_id.insert(track.id.clone(), cues);
+
+    let xml = export_rekordbox_xml(&[track.clone()], &tree, &cues_by_track_id).unwrap();
+    let (parsed_tracks, _tree, parsed_cues) = parse_rekordbox_xml(&xml).unwrap();
+
+    assert_eq!(parsed_tracks.len(), 1);
+    let parsed_track_id = parsed_tracks[0].id.clone();
+    let marks = parsed_cues.get(&parsed_track_id).expect("hot-cue bank should round-trip");
+    assert_eq!(marks.len(), 8);
+    let mut slots: Vec<i32> = marks.iter().map(|m| m.hotcue_slot.expect("hotcue slot should be assigned")).collect();
+    slots.sort();
+    assert_eq!(slots, (0..8).collect::<Vec<i32>>());
+    assert!(marks.iter().all(|m| m.cue_type == CueType::HotCue));
+  }
+}
@@ -0,0 +1,420 @@
+// AIDEV-NOTE: Serato DJ `.crate` file import/export.
+//
+// Unlike Traktor's NML and Rekordbox's XML, a Serato crate is a small
+// binary TLV (tag-length-value) format: a flat sequence of 4-byte ASCII tag
+// names followed by a 4-byte big-endian length and a payload, with strings
+// encoded UTF-16BE. There's no serde backend for this, so (unlike
+// `nml_parser`/`nml_writer` and `rekordbox`) this module hand-rolls the
+// encode/decode instead of deriving it.
+//
+// A crate is flat (just a list of track paths) - folder nesting is encoded
+// in the *filename*, with parent and child folder names joined by `%%`
+// (e.g. `House%%Deep House.crate`), one file per leaf playlist. That's why
+// export here produces a `HashMap<String, Vec<u8>>` (crate name -> file
+// bytes) rather than a single document like the Traktor/Rekordbox writers.
+//
+// Reference: https://github.com/Holzhaus/serato-tags (community format notes)
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose, Engine as _};
+
+use crate::libs::cue_point::{CuePoint, CueType};
+use crate::libs::playlist_tree::{flatten_playlist_tree, FolderTreeNode};
+use crate::libs::{HarmonyError, Result};
+
+/// Canonical version string Serato itself writes into every crate's `vrsn` tag.
+const CRATE_VERSION: &str = "1.0/Serato ScratchLive Crate";
+
+fn write_tag(name: &str, payload: &[u8]) -> Vec<u8> {
+  let mut out = Vec::with_capacity(8 + payload.len());
+  out.extend_from_slice(name.as_bytes());
+  out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+  out.extend_from_slice(payload);
+  out
+}
+
+fn encode_utf16be(s: &str) -> Vec<u8> {
+  s.encode_utf16().flat_map(|u| u.to_be_bytes()).collect()
+}
+
+fn decode_utf16be(bytes: &[u8]) -> String {
+  let units: Vec<u16> = bytes
+    .chunks_exact(2)
+    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+    .collect();
+  String::from_utf16_lossy(&units)
+}
+
+/// Serato stores paths relative to the filesystem root, without a leading
+/// slash and always with `/` separators - the inverse of
+/// [`crate_path_to_system_path`].
+fn system_path_to_crate_path(path: &str) -> String {
+  path.replace('\\', "/").trim_start_matches('/').to_string()
+}
+
+fn crate_path_to_system_path(path: &str) -> String {
+  #[cfg(target_os = "windows")]
+  {
+    path.to_string()
+  }
+  #[cfg(not(target_os = "windows"))]
+  {
+    format!("/{}", path)
+  }
+}
+
+/// Serialize one crate's track list to the on-disk `.crate` byte format.
+pub fn build_serato_crate(track_paths: &[String]) -> Vec<u8> {
+  let mut out = write_tag("vrsn", &encode_utf16be(CRATE_VERSION));
+
+  for path in track_paths {
+    let ptrk = write_tag("ptrk", &encode_utf16be(&system_path_to_crate_path(path)));
+    out.extend(write_tag("otrk", &ptrk));
+  }
+
+  out
+}
+
+/// Parse a `.crate` file's bytes back into its ordered list of system track
+/// paths.
+pub fn parse_serato_crate(bytes: &[u8]) -> Result<Vec<String>> {
+  let mut paths = Vec::new();
+  let mut offset = 0;
+
+  while offset + 8 <= bytes.len() {
+    let name = std::str::from_utf8(&bytes[offset..offset + 4])
+      .map_err(|e| HarmonyError::Custom(format!("Invalid Serato tag name: {}", e)))?;
+    let len = u32::from_be_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()) as usize;
+    offset += 8;
+
+    if offset + len > bytes.len() {
+      return Err(HarmonyError::Custom(
+        "Truncated Serato crate: tag length overruns buffer".to_string(),
+      ));
+    }
+    let payload = &bytes[offset..offset + len];
+
+    if name == "otrk" {
+      if let Some(path) = parse_otrk_payload(payload)? {
+        paths.push(crate_path_to_system_path(&path));
+      }
+    }
+
+    offset += len;
+  }
+
+  Ok(paths)
+}
+
+/// An `otrk` (track) tag's payload is itself a nested sequence of tags; the
+/// one we care about is `ptrk` (the track's path).
+fn parse_otrk_payload(bytes: &[u8]) -> Result<Option<String>> {
+  let mut offset = 0;
+  while offset + 8 <= bytes.len() {
+    let name = std::str::from_utf8(&bytes[offset..offset + 4])
+      .map_err(|e| HarmonyError::Custom(format!("Invalid Serato tag name: {}", e)))?;
+    let len = u32::from_be_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()) as usize;
+    offset += 8;
+
+    if offset + len > bytes.len() {
+      return Err(HarmonyError::Custom(
+        "Truncated Serato crate: nested tag length overruns buffer".to_string(),
+      ));
+    }
+    let payload = &bytes[offset..offset + len];
+
+    if name == "ptrk" {
+      return Ok(Some(decode_utf16be(payload)));
+    }
+
+    offset += len;
+  }
+  Ok(None)
+}
+
+/// Version header every `Markers2` GEOB payload begins with, before the
+/// base64-encoded entry list.
+const MARKERS2_VERSION: [u8; 2] = [0x01, 0x01];
+
+/// Default hotcue color Serato falls back to when Harmony has no color set
+/// for a cue (Serato's own default hotcue red).
+const DEFAULT_HOTCUE_COLOR: (u8, u8, u8) = (0xCC, 0x00, 0x00);
+
+/// Write one `Markers2`-internal TLV entry: a null-terminated ASCII name,
+/// a 4-byte big-endian payload length, then the payload. Distinct from the
+/// crate-file `write_tag` above, whose names are a fixed 4 bytes - Markers2
+/// entry names ("CUE", "LOOP", "COLOR", ...) are variable-length.
+fn write_markers2_entry(name: &str, payload: &[u8]) -> Vec<u8> {
+  let mut out = Vec::with_capacity(name.len() + 5 + payload.len());
+  out.extend_from_slice(name.as_bytes());
+  out.push(0);
+  out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+  out.extend_from_slice(payload);
+  out
+}
+
+/// Serialize `cues` into a Serato `Markers2` GEOB tag payload (to be written
+/// into the audio file's own tags, not a crate file - Serato stores hotcues
+/// per-file rather than per-playlist).
+///
+/// AIDEV-NOTE: Layout reverse-engineered from the community serato-tags notes
+/// referenced at the top of this file (2-byte version header + base64 of a
+/// `CUE` TLV per hotcue: reserved byte, slot index, 4-byte BE position in
+/// ms, reserved byte, RGB color, 2 reserved bytes, null-terminated name).
+/// Not verified byte-for-byte against a real Serato-written file - good
+/// enough to round-trip through `parse_markers2_geob`, but a file written by
+/// Serato itself may carry additional entry types (`COLOR`, `BPMLOCK`,
+/// `LOOP`) this doesn't attempt to preserve.
+pub fn build_markers2_geob(cues: &[CuePoint]) -> Vec<u8> {
+  let mut inner = Vec::new();
+
+  for cue in cues.iter().filter(|c| c.cue_type == CueType::HotCue) {
+    let Some(slot) = cue.hotcue_slot else { continue };
+    let (r, g, b) = cue
+      .color
+      .as_deref()
+      .and_then(parse_hex_color)
+      .unwrap_or(DEFAULT_HOTCUE_COLOR);
+
+    let mut entry = vec![0u8, slot.clamp(0, 255) as u8];
+    entry.extend_from_slice(&(cue.position_ms.round() as u32).to_be_bytes());
+    entry.push(0);
+    entry.extend_from_slice(&[r, g, b]);
+    entry.extend_from_slice(&[0, 0]);
+    entry.extend_from_slice(cue.name.as_deref().unwrap_or("").as_bytes());
+    entry.push(0);
+
+    inner.extend(write_markers2_entry("CUE", &entry));
+  }
+
+  let mut out = MARKERS2_VERSION.to_vec();
+  out.extend_from_slice(general_purpose::STANDARD.encode(&inner).as_bytes());
+  out
+}
+
+/// Inverse of `build_markers2_geob`: parse a `Markers2` GEOB payload's `CUE`
+/// entries back into hotcue `CuePoint` rows for `track_id`.
+pub fn parse_markers2_geob(data: &[u8], track_id: &str) -> Result<Vec<CuePoint>> {
+  let body = data
+    .get(2..)
+    .ok_or_else(|| HarmonyError::Custom("Markers2 payload shorter than version header".to_string()))?;
+  let inner = general_purpose::STANDARD
+    .decode(body)
+    .map_err(|e| HarmonyError::Custom(format!("Invalid Markers2 base64: {}", e)))?;
+
+  let mut cues = Vec::new();
+  let mut offset = 0;
+
+  while offset < inner.len() {
+    let Some(name_end) = inner[offset..].iter().position(|&b| b == 0) else {
+      break;
+    };
+    let name = String::from_utf8_lossy(&inner[offset..offset + name_end]).to_string();
+    offset += name_end + 1;
+
+    if offset + 4 > inner.len() {
+      break;
+    }
+    let len = u32::from_be_bytes(inner[offset..offset + 4].try_into().unwrap()) as usize;
+    offset += 4;
+
+    if offset + len > inner.len() {
+      break;
+    }
+    let payload = &inner[offset..offset + len];
+    offset += len;
+
+    if name == "CUE" && payload.len() >= 8 {
+      let slot = payload[1] as i32;
+      let position_ms = u32::from_be_bytes(payload[2..6].try_into().unwrap()) as f64;
+      let color = if payload.len() >= 10 {
+        Some(format!("#{:02x}{:02x}{:02x}", payload[7], payload[8], payload[9]))
+      } else {
+        None
+      };
+      let name = payload
+        .get(12..)
+        .and_then(|rest| rest.iter().position(|&b| b == 0).map(|end| (rest, end)))
+        .map(|(rest, end)| String::from_utf8_lossy(&rest[..end]).to_string())
+        .filter(|s| !s.is_empty());
+
+      cues.push(CuePoint {
+        id: format!("{}-serato-{}", track_id, slot),
+        track_id: track_id.to_string(),
+        cue_type: CueType::HotCue,
+        position_ms,
+        length_ms: None,
+        hotcue_slot: Some(slot),
+        name,
+        color,
+        grid_bpm: None,
+        order: Some(slot),
+        updated_at: 0,
+        deleted: false,
+      });
+    }
+  }
+
+  Ok(cues)
+}
+
+/// Parse a `#rrggbb` `CuePoint::color` into its `(r, g, b)` byte components.
+fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
+  let hex = hex.strip_prefix('#')?;
+  if hex.len() != 6 {
+    return None;
+  }
+  Some((
+    u8::from_str_radix(&hex[0..2], 16).ok()?,
+    u8::from_str_radix(&hex[2..4], 16).ok()?,
+    u8::from_str_radix(&hex[4..6], 16).ok()?,
+  ))
+}
+
+/// Export every playlist in `tree` to its own Serato crate, keyed by the
+/// `%%`-joined crate filename (without the `.crate` extension) Serato uses
+/// to encode folder nesting.
+pub fn build_serato_crates(tree: &FolderTreeNode) -> HashMap<String, Vec<u8>> {
+  let mut crates = HashMap::new();
+  collect_crates(tree, &[], &mut crates);
+  crates
+}
+
+fn collect_crates(node: &FolderTreeNode, path_segments: &[String], crates: &mut HashMap<String, Vec<u8>>) {
+  if !node.is_folder {
+    let crate_name = path_segments.join("%%");
+    let track_paths = node
+      .playlist
+      .as_ref()
+      .map(|p| p.track_paths.clone())
+      .unwrap_or_default();
+    crates.insert(crate_name, build_serato_crate(&track_paths));
+    return;
+  }
+
+  for child in &node.children {
+    let mut segments = path_segments.to_vec();
+    segments.push(child.name.clone());
+    collect_crates(child, &segments, crates);
+  }
+}
+
+/// Rebuild a format-agnostic [`FolderTreeNode`] from a set of parsed crate
+/// files, splitting each `%%`-joined crate name back into its folder path.
+pub fn parse_serato_crates(crate_files: &HashMap<String, Vec<u8>>) -> Result<FolderTreeNode> {
+  use crate::libs::playlist_tree::{insert_playlist_into_tree, ImportedPlaylist};
+
+  let mut root = FolderTreeNode::folder("ROOT");
+  for (crate_name, bytes) in crate_files {
+    let track_paths = parse_serato_crate(bytes)?;
+    let mut segments: Vec<&str> = crate_name.split("%%").collect();
+    let name = segments.pop().unwrap_or(crate_name).to_string();
+    let folder_path = if segments.is_empty() {
+      None
+    } else {
+      Some(format!("/ROOT/{}", segments.join("/")))
+    };
+
+    insert_playlist_into_tree(
+      &mut root,
+      ImportedPlaylist {
+        id: crate_name.clone(),
+        name,
+        track_paths,
+        folder_path,
+      },
+    );
+  }
+
+  Ok(root)
+}
+
+/// Re-flatten a Serato-derived tree (see [`parse_serato_crates`]) back to
+/// plain Harmony playlists, for callers that don't need the tree shape.
+pub fn flatten_serato_tree(tree: &FolderTreeNode) -> Vec<crate::libs::playlist_tree::ImportedPlaylist> {
+  flatten_playlist_tree(tree, None)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_crate_round_trip() {
+    let paths = vec![
+      "/Users/josev/Music/track1.mp3".to_string(),
+      "/Users/josev/Music/track2.mp3".to_string(),
+    ];
+    let bytes = build_serato_crate(&paths);
+    let parsed = parse_serato_crate(&bytes).unwrap();
+    assert_eq!(parsed, paths);
+  }
+
+  #[test]
+  fn test_build_serato_crates_nests_by_percent_percent() {
+    let mut root = FolderTreeNode::folder("ROOT");
+    root.children.push(FolderTreeNode {
+      name: "House".to_string(),
+      is_folder: true,
+      playlist: None,
+      children: vec![FolderTreeNode {
+        name: "Deep House".to_string(),
+        is_folder: false,
+        playlist: Some(crate::libs::playlist_tree::ImportedPlaylist {
+          id: "p1".to_string(),
+          name: "Deep House".to_string(),
+          track_paths: vec!["/Music/a.mp3".to_string()],
+          folder_path: None,
+        }),
+        children: vec![],
+      }],
+    });
+
+    let crates = build_serato_crates(&root);
+    assert!(crates.contains_key("House%%Deep House"));
+
+    let tracks = parse_serato_crate(&crates["House%%Deep House"]).unwrap();
+    assert_eq!(tracks, vec!["/Music/a.mp3".to_string()]);
+  }
+
+  fn sample_hotcue(slot: i32, position_ms: f64) -> CuePoint {
+    CuePoint {
+      id: "cue1".to_string(),
+      track_id: "track1".to_string(),
+      cue_type: CueType::HotCue,
+      position_ms,
+      length_ms: None,
+      hotcue_slot: Some(slot),
+      name: Some("Drop".to_string()),
+      color: Some("#ff0000".to_string()),
+      grid_bpm: None,
+      order: Some(slot),
+      updated_at: 0,
+      deleted: false,
+    }
+  }
+
+  #[test]
+  fn test_markers2_geob_round_trip() {
+    let cues = vec![sample_hotcue(0, 12_500.0), sample_hotcue(1, 34_000.0)];
+    let blob = build_markers2_geob(&cues);
+    assert_eq!(&blob[..2], &MARKERS2_VERSION);
+
+    let parsed = parse_markers2_geob(&blob, "track1").unwrap();
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].hotcue_slot, Some(0));
+    assert_eq!(parsed[0].position_ms, 12_500.0);
+    assert_eq!(parsed[0].color, Some("#ff0000".to_string()));
+    assert_eq!(parsed[0].name, Some("Drop".to_string()));
+    assert_eq!(parsed[1].hotcue_slot, Some(1));
+  }
+
+  #[test]
+  fn test_markers2_geob_skips_non_hotcue_types() {
+    let mut cue = sample_hotcue(0, 0.0);
+    cue.cue_type = CueType::Grid;
+    let blob = build_markers2_geob(&[cue]);
+    assert!(parse_markers2_geob(&blob, "track1").unwrap().is_empty());
+  }
+}
@@ -0,0 +1,475 @@
+// AIDEV-NOTE: Acoustic-similarity analysis module
+// Decodes a track's audio signal (not just its tags) into a fixed-length
+// feature vector - tempo, spectral centroid, zero-crossing rate, and
+// chroma (pitch-class) mean/variance - so tracks can be compared by how
+// they actually sound. Used to walk a "sounds-like" playlist from a seed
+// track (see `generate_similar_playlist`) and to thin out near-duplicate
+// runs (see `dedup_playlist`).
+//
+// Pure Rust, no FFT crate: chroma and spectral centroid are estimated with
+// the Goertzel algorithm evaluated at a handful of target frequencies per
+// analysis frame, which is enough resolution for a similarity heuristic
+// without pulling in a new dependency.
+
+use log::warn;
+use std::fs;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::CODEC_TYPE_NULL;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::libs::{HarmonyError, Result};
+
+/// tempo(1) + spectral centroid(1) + zero-crossing rate(1) + chroma mean(12)
+/// + chroma variance(12).
+pub const FEATURE_DIM: usize = 27;
+
+/// A normalized, fixed-length acoustic fingerprint for one track.
+pub type FeatureVector = [f64; FEATURE_DIM];
+
+/// Number of evenly-spaced analysis frames sampled across the track.
+///
+/// AIDEV-NOTE: Deliberately a fixed sample of frames rather than every
+/// frame in the file - the feature vector only needs to characterize a
+/// track's overall tempo/timbre, not track every transient, and this keeps
+/// analysis time roughly constant regardless of track length.
+const ANALYSIS_FRAMES: usize = 48;
+const FRAME_LEN: usize = 4096;
+
+/// Decode a file to mono f32 PCM samples, for feature extraction.
+fn decode_to_mono_pcm(path: &str) -> Result<(Vec<f32>, u32)> {
+  let file = fs::File::open(path)?;
+  let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+  let mut hint = Hint::new();
+  if let Some(ext) = std::path::Path::new(path).extension().and_then(|e| e.to_str()) {
+    hint.with_extension(ext);
+  }
+
+  let probed = symphonia::default::get_probe()
+    .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+    .map_err(|e| HarmonyError::Custom(format!("Failed to probe {}: {}", path, e)))?;
+
+  let mut format = probed.format;
+  let track = format
+    .tracks()
+    .iter()
+    .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+    .ok_or_else(|| HarmonyError::Custom(format!("No decodable audio track in {}", path)))?;
+
+  let track_id = track.id;
+  let sample_rate = track.codec_params.sample_rate.unwrap_or(44100);
+
+  let mut decoder = symphonia::default::get_codecs()
+    .make(&track.codec_params, &Default::default())
+    .map_err(|e| HarmonyError::Custom(format!("Failed to create decoder for {}: {}", path, e)))?;
+
+  let mut samples = Vec::new();
+
+  loop {
+    let packet = match format.next_packet() {
+      Ok(packet) => packet,
+      Err(SymphoniaError::IoError(_)) => break, // end of stream
+      Err(e) => return Err(HarmonyError::Custom(format!("Decode error in {}: {}", path, e))),
+    };
+
+    if packet.track_id() != track_id {
+      continue;
+    }
+
+    match decoder.decode(&packet) {
+      Ok(decoded) => {
+        let spec = *decoded.spec();
+        let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buf.copy_interleaved_ref(decoded);
+
+        let channels = spec.channels.count().max(1);
+        for frame in buf.samples().chunks_exact(channels) {
+          samples.push(frame.iter().sum::<f32>() / channels as f32);
+        }
+      }
+      Err(SymphoniaError::DecodeError(e)) => {
+        warn!("Skipping corrupt packet in {}: {}", path, e);
+        continue;
+      }
+      Err(e) => return Err(HarmonyError::Custom(format!("Decode error in {}: {}", path, e))),
+    }
+  }
+
+  Ok((samples, sample_rate))
+}
+
+fn zero_crossing_rate(samples: &[f32]) -> f64 {
+  if samples.len() < 2 {
+    return 0.0;
+  }
+  let crossings = samples.windows(2).filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0)).count();
+  crossings as f64 / (samples.len() - 1) as f64
+}
+
+/// Estimate tempo (BPM) from the autocorrelation peak of the track's
+/// energy envelope, the same approach `libs::audio_analysis::detect_bpm`
+/// uses for tag-level BPM detection.
+fn estimate_tempo(samples: &[f32], sample_rate: u32) -> f64 {
+  let frame_size = (sample_rate as usize / 50).max(1); // ~20ms frames
+  let envelope: Vec<f64> = samples
+    .chunks(frame_size)
+    .map(|chunk| chunk.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / chunk.len() as f64)
+    .collect();
+
+  if envelope.len() < 4 {
+    return 0.0;
+  }
+
+  let mean = envelope.iter().sum::<f64>() / envelope.len() as f64;
+  let centered: Vec<f64> = envelope.iter().map(|e| e - mean).collect();
+
+  let frame_rate = sample_rate as f64 / frame_size as f64;
+  let min_lag = ((frame_rate * 60.0 / 200.0) as usize).max(1); // 200 BPM upper bound
+  let max_lag = ((frame_rate * 60.0 / 60.0) as usize).min(centered.len().saturating_sub(1)); // 60 BPM lower bound
+
+  if max_lag <= min_lag {
+    return 0.0;
+  }
+
+  let mut best_lag = min_lag;
+  let mut best_score = f64::MIN;
+  for lag in min_lag..=max_lag {
+    let score: f64 = centered.iter().zip(centered.iter().skip(lag)).map(|(a, b)| a * b).sum();
+    if score > best_score {
+      best_score = score;
+      best_lag = lag;
+    }
+  }
+
+  frame_rate * 60.0 / best_lag as f64
+}
+
+/// Magnitude of `frame` at `target_freq`, via the single-bin Goertzel
+/// algorithm - cheaper than a full DFT when only a handful of frequencies
+/// are needed per frame.
+fn goertzel_magnitude(frame: &[f64], sample_rate: f64, target_freq: f64) -> f64 {
+  let n = frame.len();
+  let k = (0.5 + (n as f64 * target_freq / sample_rate)).floor();
+  let omega = 2.0 * std::f64::consts::PI * k / n as f64;
+  let coeff = 2.0 * omega.cos();
+
+  let (mut s_prev, mut s_prev2) = (0.0, 0.0);
+  for &x in frame {
+    let s = x + coeff * s_prev - s_prev2;
+    s_prev2 = s_prev;
+    s_prev = s;
+  }
+
+  (s_prev2 * s_prev2 + s_prev * s_prev - coeff * s_prev * s_prev2).max(0.0).sqrt()
+}
+
+/// Target frequencies (Hz) for each of the 12 pitch classes across three
+/// octaves (starting at C3), summed per class to build the chroma vector.
+fn chroma_reference_frequencies() -> [[f64; 3]; 12] {
+  const C3_HZ: f64 = 130.8128;
+  let semitone = 2f64.powf(1.0 / 12.0);
+
+  let mut freqs = [[0.0; 3]; 12];
+  for (pitch_class, slot) in freqs.iter_mut().enumerate() {
+    let base = C3_HZ * semitone.powi(pitch_class as i32);
+    *slot = [base, base * 2.0, base * 4.0];
+  }
+  freqs
+}
+
+/// Spectral centroid and per-pitch-class chroma mean/variance, sampled over
+/// `ANALYSIS_FRAMES` evenly-spaced frames.
+fn spectral_profile(samples: &[f32], sample_rate: u32) -> (f64, [f64; 12], [f64; 12]) {
+  let mut chroma_mean = [0.0; 12];
+  let mut chroma_var = [0.0; 12];
+
+  if samples.len() < FRAME_LEN {
+    return (0.0, chroma_mean, chroma_var);
+  }
+
+  let sample_rate_f = sample_rate as f64;
+  let chroma_freqs = chroma_reference_frequencies();
+  // Log-spaced bins approximating the audible spectrum, used to estimate
+  // spectral centroid without a full FFT.
+  let centroid_bins: Vec<f64> = (0..24).map(|i| 80.0 * 2f64.powf(i as f64 / 3.0)).collect();
+
+  let stride = ((samples.len() - FRAME_LEN) / ANALYSIS_FRAMES.max(1)).max(1);
+
+  let mut chroma_sums = [0.0; 12];
+  let mut chroma_sq_sums = [0.0; 12];
+  let mut centroid_num = 0.0;
+  let mut centroid_den = 0.0;
+  let mut frames_used = 0usize;
+
+  for frame_idx in 0..ANALYSIS_FRAMES {
+    let start = frame_idx * stride;
+    if start + FRAME_LEN > samples.len() {
+      break;
+    }
+    let frame: Vec<f64> = samples[start..start + FRAME_LEN].iter().map(|s| *s as f64).collect();
+    frames_used += 1;
+
+    for (pitch_class, target_freqs) in chroma_freqs.iter().enumerate() {
+      let magnitude: f64 = target_freqs.iter().map(|f| goertzel_magnitude(&frame, sample_rate_f, *f)).sum();
+      chroma_sums[pitch_class] += magnitude;
+      chroma_sq_sums[pitch_class] += magnitude * magnitude;
+    }
+
+    for freq in &centroid_bins {
+      let magnitude = goertzel_magnitude(&frame, sample_rate_f, *freq);
+      centroid_num += magnitude * freq;
+      centroid_den += magnitude;
+    }
+  }
+
+  if frames_used == 0 {
+    return (0.0, chroma_mean, chroma_var);
+  }
+
+  for pitch_class in 0..12 {
+    let mean = chroma_sums[pitch_class] / frames_used as f64;
+    let variance = (chroma_sq_sums[pitch_class] / frames_used as f64) - mean * mean;
+    chroma_mean[pitch_class] = mean;
+    chroma_var[pitch_class] = variance.max(0.0);
+  }
+
+  let centroid = if centroid_den > 0.0 { centroid_num / centroid_den } else { 0.0 };
+  (centroid, chroma_mean, chroma_var)
+}
+
+/// Decode `path` and compute its (un-normalized) feature vector. Callers
+/// analyzing a batch should normalize the resulting vectors together with
+/// [`normalize_feature_vectors`] before persisting or comparing them.
+pub fn compute_feature_vector(path: &str) -> Result<FeatureVector> {
+  let (samples, sample_rate) = decode_to_mono_pcm(path)?;
+  if samples.is_empty() {
+    return Err(HarmonyError::Custom(format!("No audio samples decoded from {}", path)));
+  }
+
+  let tempo = estimate_tempo(&samples, sample_rate);
+  let zcr = zero_crossing_rate(&samples);
+  let (centroid, chroma_mean, chroma_var) = spectral_profile(&samples, sample_rate);
+
+  let mut vector = [0.0; FEATURE_DIM];
+  vector[0] = tempo;
+  vector[1] = centroid;
+  vector[2] = zcr;
+  vector[3..15].copy_from_slice(&chroma_mean);
+  vector[15..27].copy_from_slice(&chroma_var);
+  Ok(vector)
+}
+
+/// Min-max normalize each feature dimension across `vectors` in place, so
+/// dimensions with naturally larger magnitudes (e.g. spectral centroid in
+/// Hz vs. zero-crossing rate in [0,1]) don't dominate the distance
+/// calculations below.
+pub fn normalize_feature_vectors(vectors: &mut [FeatureVector]) {
+  if vectors.is_empty() {
+    return;
+  }
+
+  for dim in 0..FEATURE_DIM {
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    for vector in vectors.iter() {
+      min = min.min(vector[dim]);
+      max = max.max(vector[dim]);
+    }
+
+    let range = max - min;
+    for vector in vectors.iter_mut() {
+      vector[dim] = if range > f64::EPSILON { (vector[dim] - min) / range } else { 0.0 };
+    }
+  }
+}
+
+/// Squared Euclidean distance between two (normalized) feature vectors.
+pub fn squared_distance(a: &FeatureVector, b: &FeatureVector) -> f64 {
+  a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+/// Build a "sounds-like" playlist starting from `seed_path`: repeatedly pick
+/// the un-picked track closest (squared Euclidean distance) to the
+/// most-recently-added track, so the playlist drifts smoothly rather than
+/// jumping around the whole library. Stops once `len` tracks are collected
+/// or candidates run out. Returns an empty list if `seed_path` has no
+/// feature vector.
+pub fn generate_similar_playlist(
+  seed_path: &str,
+  vectors: &std::collections::HashMap<String, FeatureVector>,
+  len: usize,
+) -> Vec<String> {
+  let Some(seed) = vectors.get(seed_path) else {
+    return Vec::new();
+  };
+
+  let mut current = *seed;
+  let mut remaining: std::collections::HashSet<&String> =
+    vectors.keys().filter(|path| path.as_str() != seed_path).collect();
+  let mut playlist = vec![seed_path.to_string()];
+
+  while playlist.len() < len {
+    let next = remaining
+      .iter()
+      .map(|path| (*path, squared_distance(&current, &vectors[*path])))
+      .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let Some((path, _)) = next else { break };
+    current = vectors[path];
+    playlist.push(path.clone());
+    remaining.remove(path);
+  }
+
+  playlist
+}
+
+/// Rank `candidate_paths` by acoustic similarity to `seed_path` and return
+/// the `count` closest, nearest-first. Unlike [`generate_similar_playlist`]
+/// (which greedily chains from the most-recently-added track so the
+/// playlist drifts smoothly), this always measures distance from the fixed
+/// seed - for callers that want "more like this track" over a specific
+/// candidate set (e.g. one playlist or crate) rather than a library-wide
+/// walk. Candidates with no feature vector, and the seed itself, are
+/// skipped. Returns an empty list if `seed_path` has no feature vector.
+pub fn build_similarity_playlist(
+  seed_path: &str,
+  candidate_paths: &[String],
+  vectors: &std::collections::HashMap<String, FeatureVector>,
+  count: usize,
+) -> Vec<String> {
+  let Some(seed) = vectors.get(seed_path) else {
+    return Vec::new();
+  };
+
+  let mut ranked: Vec<(&String, f64)> = candidate_paths
+    .iter()
+    .filter(|path| path.as_str() != seed_path)
+    .filter_map(|path| vectors.get(path).map(|vector| (path, squared_distance(seed, vector))))
+    .collect();
+
+  ranked.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+  ranked.into_iter().take(count).map(|(path, _)| path.clone()).collect()
+}
+
+/// Drop any track in `ordered` whose distance to the previously *kept*
+/// track falls below `threshold`, to thin out near-duplicate consecutive
+/// songs. Paths with no feature vector are dropped rather than kept blind.
+pub fn dedup_playlist(
+  ordered: &[String],
+  vectors: &std::collections::HashMap<String, FeatureVector>,
+  threshold: f64,
+) -> Vec<String> {
+  let mut kept: Vec<String> = Vec::with_capacity(ordered.len());
+
+  for path in ordered {
+    let Some(vector) = vectors.get(path) else {
+      continue;
+    };
+
+    let is_near_duplicate = kept
+      .last()
+      .and_then(|prev| vectors.get(prev))
+      .map(|prev_vector| squared_distance(prev_vector, vector) < threshold)
+      .unwrap_or(false);
+
+    if !is_near_duplicate {
+      kept.push(path.clone());
+    }
+  }
+
+  kept
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn vector_with(dim0: f64, dim1: f64) -> FeatureVector {
+    let mut v = [0.0; FEATURE_DIM];
+    v[0] = dim0;
+    v[1] = dim1;
+    v
+  }
+
+  #[test]
+  fn normalize_feature_vectors_scales_each_dimension_to_unit_range() {
+    let mut vectors = vec![vector_with(0.0, 10.0), vector_with(5.0, 20.0), vector_with(10.0, 30.0)];
+    normalize_feature_vectors(&mut vectors);
+
+    assert_eq!(vectors[0][0], 0.0);
+    assert_eq!(vectors[2][0], 1.0);
+    assert!((vectors[1][0] - 0.5).abs() < 1e-9);
+  }
+
+  #[test]
+  fn generate_similar_playlist_walks_to_nearest_unpicked_track() {
+    let mut vectors = std::collections::HashMap::new();
+    vectors.insert("seed".to_string(), vector_with(0.0, 0.0));
+    vectors.insert("near".to_string(), vector_with(1.0, 0.0));
+    vectors.insert("far".to_string(), vector_with(10.0, 0.0));
+
+    let playlist = generate_similar_playlist("seed", &vectors, 3);
+
+    assert_eq!(playlist, vec!["seed".to_string(), "near".to_string(), "far".to_string()]);
+  }
+
+  #[test]
+  fn generate_similar_playlist_returns_empty_for_unknown_seed() {
+    let vectors = std::collections::HashMap::new();
+    assert!(generate_similar_playlist("missing", &vectors, 5).is_empty());
+  }
+
+  #[test]
+  fn squared_distance_of_a_track_to_itself_is_zero() {
+    let v = vector_with(3.7, -1.2);
+    assert_eq!(squared_distance(&v, &v), 0.0);
+  }
+
+  #[test]
+  fn squared_distance_of_identical_signals_is_near_zero() {
+    // Two independently-built vectors carrying the same values should
+    // still compare as near-zero distance, not merely reference-equal.
+    let a = vector_with(4.0, 8.0);
+    let b = vector_with(4.0, 8.0);
+    assert!(squared_distance(&a, &b) < 1e-9);
+  }
+
+  #[test]
+  fn build_similarity_playlist_ranks_candidates_nearest_first() {
+    let mut vectors = std::collections::HashMap::new();
+    vectors.insert("seed".to_string(), vector_with(0.0, 0.0));
+    vectors.insert("near".to_string(), vector_with(1.0, 0.0));
+    vectors.insert("mid".to_string(), vector_with(5.0, 0.0));
+    vectors.insert("far".to_string(), vector_with(10.0, 0.0));
+
+    let candidates = vec!["far".to_string(), "near".to_string(), "mid".to_string()];
+    let playlist = build_similarity_playlist("seed", &candidates, &vectors, 2);
+
+    assert_eq!(playlist, vec!["near".to_string(), "mid".to_string()]);
+  }
+
+  #[test]
+  fn build_similarity_playlist_returns_empty_for_unknown_seed() {
+    let vectors = std::collections::HashMap::new();
+    let candidates = vec!["a".to_string()];
+    assert!(build_similarity_playlist("missing", &candidates, &vectors, 5).is_empty());
+  }
+
+  #[test]
+  fn dedup_playlist_drops_near_duplicates_of_the_previously_kept_track() {
+    let mut vectors = std::collections::HashMap::new();
+    vectors.insert("a".to_string(), vector_with(0.0, 0.0));
+    vectors.insert("b".to_string(), vector_with(0.1, 0.0)); // near-duplicate of a
+    vectors.insert("c".to_string(), vector_with(10.0, 0.0));
+
+    let ordered = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    let kept = dedup_playlist(&ordered, &vectors, 1.0);
+
+    assert_eq!(kept, vec!["a".to_string(), "c".to_string()]);
+  }
+}
@@ -0,0 +1,232 @@
+// AIDEV-NOTE: Backend-agnostic storage trait for the Harmony library.
+// Lets Tauri commands (and the Traktor sync subsystem in particular) depend
+// on an interface instead of the concrete `Database` type, so the sync
+// algorithm can be unit-tested against an in-memory store and a different
+// embedded engine could be swapped in behind the same `State<Box<dyn
+// LibraryStore>>` without touching command code.
+
+use std::collections::HashMap;
+
+use crate::libs::cue_point::CuePoint;
+use crate::libs::database::TraktorSyncWrite;
+use crate::libs::field_clock::FieldClock;
+use crate::libs::folder::Folder;
+use crate::libs::playlist::Playlist;
+use crate::libs::track::Track;
+use crate::libs::Result;
+
+/// The subset of `Database` operations the Tauri commands and the Traktor
+/// sync subsystem actually depend on. Implemented by [`crate::libs::Database`]
+/// (SQLite-backed); any other type implementing it can stand in wherever a
+/// command or the sync algorithm takes `&dyn LibraryStore`.
+pub trait LibraryStore: Send + Sync {
+  fn get_all_tracks(&self) -> Result<Vec<Track>>;
+  fn get_track_by_id(&self, track_id: &str) -> Result<Option<Track>>;
+  fn insert_tracks(&self, tracks: &[Track]) -> Result<()>;
+  fn update_track(&self, track: &Track) -> Result<()>;
+  fn delete_tracks(&self, track_ids: &[String]) -> Result<()>;
+
+  fn get_all_playlists(&self) -> Result<Vec<Playlist>>;
+  fn get_playlist_by_id(&self, playlist_id: &str) -> Result<Option<Playlist>>;
+  fn create_playlist(&self, playlist: &Playlist) -> Result<()>;
+  fn update_playlist(&self, playlist: &Playlist) -> Result<()>;
+  fn delete_playlist(&self, playlist_id: &str) -> Result<()>;
+  fn set_playlist_tracks(&self, playlist_id: &str, track_ids: &[String]) -> Result<()>;
+
+  fn get_all_folders(&self) -> Result<Vec<Folder>>;
+  fn create_folder(&self, folder: &Folder) -> Result<()>;
+  fn update_folder(&self, folder: &Folder) -> Result<()>;
+  fn delete_folder(&self, folder_id: &str) -> Result<()>;
+
+  fn get_cue_points_for_track(&self, track_id: &str) -> Result<Vec<CuePoint>>;
+  fn get_cue_points_for_tracks(&self, track_ids: &[String]) -> Result<Vec<CuePoint>>;
+  fn save_cue_points(&self, cue_points: &[CuePoint]) -> Result<()>;
+  fn replace_cue_points_for_track(&self, track_id: &str, cue_points: &[CuePoint]) -> Result<()>;
+
+  fn get_field_clock(&self, track_id: &str) -> Result<FieldClock>;
+  fn save_field_clock(&self, track_id: &str, clock: &FieldClock) -> Result<()>;
+
+  fn get_traktor_sync_hashes(&self) -> Result<HashMap<String, String>>;
+  fn save_traktor_sync_hashes(&self, hashes: &[(String, String)], synced_at: i64) -> Result<()>;
+  fn delete_traktor_sync_hashes(&self, paths: &[String]) -> Result<()>;
+  fn apply_traktor_sync_batch(&self, writes: &[TraktorSyncWrite]) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::sync::Mutex;
+
+  /// Minimal in-memory `LibraryStore` used to unit-test code written against
+  /// the trait (e.g. the Traktor sync algorithm) without a real DB file.
+  /// Only implements what the tests below exercise - reaching for a fuller
+  /// fake is premature until something needs it.
+  #[derive(Default)]
+  struct MemoryStore {
+    tracks: Mutex<HashMap<String, Track>>,
+  }
+
+  impl LibraryStore for MemoryStore {
+    fn get_all_tracks(&self) -> Result<Vec<Track>> {
+      Ok(self.tracks.lock().unwrap().values().cloned().collect())
+    }
+    fn get_track_by_id(&self, track_id: &str) -> Result<Option<Track>> {
+      Ok(self.tracks.lock().unwrap().get(track_id).cloned())
+    }
+    fn insert_tracks(&self, tracks: &[Track]) -> Result<()> {
+      let mut store = self.tracks.lock().unwrap();
+      for track in tracks {
+        store.insert(track.id.clone(), track.clone());
+      }
+      Ok(())
+    }
+    fn update_track(&self, track: &Track) -> Result<()> {
+      self.tracks.lock().unwrap().insert(track.id.clone(), track.clone());
+      Ok(())
+    }
+    fn delete_tracks(&self, track_ids: &[String]) -> Result<()> {
+      let mut store = self.tracks.lock().unwrap();
+      for id in track_ids {
+        store.remove(id);
+      }
+      Ok(())
+    }
+
+    fn get_all_playlists(&self) -> Result<Vec<Playlist>> {
+      Ok(Vec::new())
+    }
+    fn get_playlist_by_id(&self, _playlist_id: &str) -> Result<Option<Playlist>> {
+      Ok(None)
+    }
+    fn create_playlist(&self, _playlist: &Playlist) -> Result<()> {
+      Ok(())
+    }
+    fn update_playlist(&self, _playlist: &Playlist) -> Result<()> {
+      Ok(())
+    }
+    fn delete_playlist(&self, _playlist_id: &str) -> Result<()> {
+      Ok(())
+    }
+    fn set_playlist_tracks(&self, _playlist_id: &str, _track_ids: &[String]) -> Result<()> {
+      Ok(())
+    }
+
+    fn get_all_folders(&self) -> Result<Vec<Folder>> {
+      Ok(Vec::new())
+    }
+    fn create_folder(&self, _folder: &Folder) -> Result<()> {
+      Ok(())
+    }
+    fn update_folder(&self, _folder: &Folder) -> Result<()> {
+      Ok(())
+    }
+    fn delete_folder(&self, _folder_id: &str) -> Result<()> {
+      Ok(())
+    }
+
+    fn get_cue_points_for_track(&self, _track_id: &str) -> Result<Vec<CuePoint>> {
+      Ok(Vec::new())
+    }
+    fn get_cue_points_for_tracks(&self, _track_ids: &[String]) -> Result<Vec<CuePoint>> {
+      Ok(Vec::new())
+    }
+    fn save_cue_points(&self, _cue_points: &[CuePoint]) -> Result<()> {
+      Ok(())
+    }
+    fn replace_cue_points_for_track(&self, _track_id: &str, _cue_points: &[CuePoint]) -> Result<()> {
+      Ok(())
+    }
+
+    fn get_field_clock(&self, _track_id: &str) -> Result<FieldClock> {
+      Ok(FieldClock::default())
+    }
+    fn save_field_clock(&self, _track_id: &str, _clock: &FieldClock) -> Result<()> {
+      Ok(())
+    }
+
+    fn get_traktor_sync_hashes(&self) -> Result<HashMap<String, String>> {
+      Ok(HashMap::new())
+    }
+    fn save_traktor_sync_hashes(&self, _hashes: &[(String, String)], _synced_at: i64) -> Result<()> {
+      Ok(())
+    }
+    fn delete_traktor_sync_hashes(&self, _paths: &[String]) -> Result<()> {
+      Ok(())
+    }
+    fn apply_traktor_sync_batch(&self, writes: &[TraktorSyncWrite]) -> Result<()> {
+      let mut store = self.tracks.lock().unwrap();
+      for write in writes {
+        store.insert(write.track.id.clone(), write.track.clone());
+      }
+      Ok(())
+    }
+  }
+
+  fn sample_track(id: &str, path: &str) -> Track {
+    Track {
+      id: id.to_string(),
+      path: path.to_string(),
+      title: "Untitled".to_string(),
+      artist: None,
+      album: None,
+      genre: None,
+      year: None,
+      duration: 0,
+      bitrate: None,
+      comment: None,
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn trait_object_round_trips_tracks() {
+    let store: Box<dyn LibraryStore> = Box::new(MemoryStore::default());
+
+    store.insert_tracks(&[sample_track("t1", "/music/a.mp3")]).unwrap();
+    assert_eq!(store.get_all_tracks().unwrap().len(), 1);
+
+    let mut track = store.get_track_by_id("t1").unwrap().unwrap();
+    track.path = "/music/renamed.mp3".to_string();
+    store.update_track(&track).unwrap();
+
+    assert_eq!(store.get_track_by_id("t1").unwrap().unwrap().path, "/music/renamed.mp3");
+
+    store.delete_tracks(&["t1".to_string()]).unwrap();
+    assert!(store.get_all_tracks().unwrap().is_empty());
+  }
+
+  #[test]
+  fn apply_traktor_sync_batch_upserts_through_the_trait() {
+    let store: Box<dyn LibraryStore> = Box::new(MemoryStore::default());
+
+    store
+      .apply_traktor_sync_batch(&[TraktorSyncWrite {
+        track: sample_track("t1", "/music/a.mp3"),
+        field_clock: None,
+        cues: None,
+      }])
+      .unwrap();
+
+    assert_eq!(store.get_all_tracks().unwrap().len(), 1);
+  }
+}
@@ -0,0 +1,177 @@
+// AIDEV-NOTE: Pluggable tag-reading/writing backends
+// `lofty` covers the vast majority of formats and is always tried first.
+// `ffprobe` is a read-only fallback for streams lofty can't fully parse
+// (exotic webm/ogv/3gp containers, odd AAC variants) — it shells out to the
+// ffprobe CLI and maps its JSON output onto a best-effort Track. This gives
+// the metadata and cover modules a single dispatch point instead of each
+// calling `lofty::read_from_path` directly.
+
+use log::{info, warn};
+use std::path::Path;
+use std::process::Command;
+
+use crate::libs::cover::CoverArt;
+use crate::libs::{HarmonyError, Result, Track};
+
+/// A metadata backend capable of reading (and, for the default backend,
+/// writing) tags from an audio file.
+pub trait TagHandler {
+  /// Read whatever tag fields this backend can extract into a Track.
+  fn read(&self, path: &Path) -> Result<Track>;
+
+  /// Write track metadata back to the file. Read-only backends return
+  /// `HarmonyError::Custom`.
+  fn write(&self, path: &Path, track: &Track) -> Result<()>;
+
+  /// Read embedded cover art, if any.
+  fn read_cover(&self, path: &Path) -> Result<Option<CoverArt>>;
+}
+
+/// Default backend, built on the `lofty` crate.
+pub struct LoftyTagHandler;
+
+impl TagHandler for LoftyTagHandler {
+  fn read(&self, path: &Path) -> Result<Track> {
+    crate::libs::audio_metadata::read_with_lofty(path)
+  }
+
+  fn write(&self, path: &Path, track: &Track) -> Result<()> {
+    crate::libs::audio_metadata::write_with_lofty(
+      path,
+      track,
+      &crate::libs::audio_metadata::WriteMetadataOptions::default(),
+    )
+  }
+
+  fn read_cover(&self, path: &Path) -> Result<Option<CoverArt>> {
+    crate::libs::cover::read_cover_with_lofty(path)
+  }
+}
+
+/// Read-only fallback backend for files `lofty` can't fully parse.
+/// Shells out to an external `ffprobe` binary (from the ffmpeg suite; audio
+/// analysis decodes with Symphonia and does not need it) and maps its
+/// container-level tags onto a Track.
+pub struct FfprobeTagHandler;
+
+impl TagHandler for FfprobeTagHandler {
+  fn read(&self, path: &Path) -> Result<Track> {
+    let path_str = path.to_string_lossy().to_string();
+    info!("Reading tags via ffprobe fallback: {}", path_str);
+
+    let output = Command::new("ffprobe")
+      .args([
+        "-v",
+        "quiet",
+        "-print_format",
+        "json",
+        "-show_format",
+      ])
+      .arg(&path_str)
+      .output()
+      .map_err(|e| HarmonyError::Custom(format!("failed to run ffprobe: {}", e)))?;
+
+    if !output.status.success() {
+      return Err(HarmonyError::Custom(format!(
+        "ffprobe failed for {}: {}",
+        path_str,
+        String::from_utf8_lossy(&output.stderr)
+      )));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let format = &json["format"];
+    let tags = &format["tags"];
+
+    let tag_str = |keys: &[&str]| -> Option<String> {
+      keys
+        .iter()
+        .find_map(|key| tags[key].as_str().or_else(|| tags[&key.to_uppercase()].as_str()))
+        .map(|s| s.to_string())
+    };
+
+    let title = tag_str(&["title"]).unwrap_or_else(|| {
+      path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Unknown")
+        .to_string()
+    });
+
+    let duration_ms = format["duration"]
+      .as_str()
+      .and_then(|s| s.parse::<f64>().ok())
+      .map(|secs| (secs * 1000.0) as i64)
+      .unwrap_or(0);
+
+    let bitrate = format["bit_rate"]
+      .as_str()
+      .and_then(|s| s.parse::<i64>().ok())
+      .map(|bps| (bps / 1000) as i32);
+
+    Ok(Track {
+      id: Track::generate_id(&path_str),
+      path: path_str,
+      title,
+      artist: tag_str(&["artist"]),
+      album: tag_str(&["album"]),
+      genre: tag_str(&["genre"]),
+      year: tag_str(&["date"]).and_then(|d| d.get(0..4).and_then(|y| y.parse::<i32>().ok())),
+      duration: duration_ms,
+      bitrate,
+      comment: tag_str(&["comment"]),
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: Some(chrono::Utc::now().timestamp_millis()),
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    })
+  }
+
+  fn write(&self, path: &Path, _track: &Track) -> Result<()> {
+    Err(HarmonyError::Custom(format!(
+      "ffprobe backend is read-only, cannot write tags to {}",
+      path.display()
+    )))
+  }
+
+  fn read_cover(&self, _path: &Path) -> Result<Option<CoverArt>> {
+    // AIDEV-NOTE: ffprobe can report an attached-picture stream but extracting
+    // the bytes needs a second ffmpeg invocation; not worth it for a fallback
+    // path that only exists for containers lofty can't parse. Directory-cover
+    // search in `fetch_cover` still applies regardless of which tag backend
+    // handled the file.
+    Ok(None)
+  }
+}
+
+/// Read a track with the default backend, falling back to ffprobe when
+/// lofty can't parse the container at all.
+pub fn read_track(path: &Path) -> Result<Track> {
+  match LoftyTagHandler.read(path) {
+    Ok(track) => Ok(track),
+    Err(lofty_err) => {
+      warn!(
+        "lofty failed to read {:?} ({}), falling back to ffprobe",
+        path, lofty_err
+      );
+      FfprobeTagHandler.read(path).map_err(|_| lofty_err)
+    }
+  }
+}
@@ -11,6 +11,61 @@ pub struct TrackRating {
   pub rating: i32,
 }
 
+/// A chapter marker (audiobook/podcast/continuous-mix navigation point).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Chapter {
+  pub start_ms: i64,
+  pub end_ms: i64,
+  pub title: String,
+}
+
+/// A release date parsed from tags, kept as granular as the source allows
+/// rather than collapsed to a bare year. `year` is always present; `month`
+/// and `day` are only set when the tag carried that much precision (e.g. an
+/// ID3v2.4 `TDRC` frame can be year-only, year-month, or a full date). See
+/// `libs::album_order::parse_album_date` for how this is produced, and
+/// `libs::album_order::order_tracks_by_album` for how it's used to sort.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct AlbumDate {
+  pub year: i32,
+  pub month: Option<u32>,
+  pub day: Option<u32>,
+}
+
+// AIDEV-NOTE: Hand-rolled Deserialize (instead of deriving it) so old data
+// saved before this struct existed - a bare year integer - still loads as a
+// year-only AlbumDate instead of failing to deserialize.
+impl<'de> Deserialize<'de> for AlbumDate {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: serde::Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+      BareYear(i32),
+      Full { year: i32, month: Option<u32>, day: Option<u32> },
+    }
+
+    match Repr::deserialize(deserializer)? {
+      Repr::BareYear(year) => Ok(AlbumDate { year, month: None, day: None }),
+      Repr::Full { year, month, day } => Ok(AlbumDate { year, month, day }),
+    }
+  }
+}
+
+/// A manual tie-breaker for `album_date`: when two releases by the same
+/// artist share an identical (possibly partial) date - a remaster and the
+/// original, or two EPs dropped the same day - `album_date`/`track_number`
+/// alone can't order them. `album_seq` lets a user pin one ahead of the
+/// other; `None` sorts before any explicit value, matching the existing
+/// "less precision sorts first" behavior of `AlbumDate`. Harmony-only - no
+/// Traktor NML field corresponds to it, see `traktor::mapper`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AlbumSeq(pub u8);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Track {
@@ -28,9 +83,61 @@ pub struct Track {
   pub initial_key: Option<String>,
   pub rating: Option<TrackRating>,
   pub label: Option<String>,
+  // AIDEV-NOTE: Populated by `libs::musicbrainz` enrichment (catalog number
+  // from the matched release, ISRC from the matched recording). Also
+  // round-tripped through tags where the format supports it.
+  pub catalog_number: Option<String>,
+  pub isrc: Option<String>,
+  // AIDEV-NOTE: Resolved via AcoustID fingerprint + MusicBrainz lookup (see
+  // `libs::musicbrainz::identify_track`/`apply_fingerprint_match`, driven by
+  // the `enrich_track_metadata` command), not the artist-name search
+  // above - set once a track is confidently matched to a Recording, so later
+  // enrichment runs can skip re-querying it.
+  pub musicbrainz_id: Option<String>,
+  pub release_group_id: Option<String>,
   pub waveform_peaks: Option<Vec<f64>>, // ~300 normalized values (0-1)
   pub added_at: Option<i64>,            // Unix timestamp in milliseconds
   pub url: Option<String>,
+  // AIDEV-NOTE: Present when this Track is a virtual track carved out of a
+  // longer file by a CUE sheet (continuous mix, album rip). `path` still
+  // points at the parent file; playback/waveform rendering must seek to
+  // `start_ms` and stop at `end_ms` (None means "play to the end of file").
+  pub start_ms: Option<i64>,
+  pub end_ms: Option<i64>,
+  // AIDEV-NOTE: Chapter markers and synchronised (timed) lyrics, round-tripped
+  // through a custom ID3v2 TXXX/Vorbis comment frame as JSON (see
+  // `libs::tag_handler`). Not native CHAP/SYLT frames, so other players won't
+  // see them, but Harmony reading a file it wrote is lossless.
+  #[serde(default)]
+  pub chapters: Vec<Chapter>,
+  // AIDEV-NOTE: Structured release date + per-album track position, used by
+  // `libs::album_order::order_tracks_by_album` to give `import_library` a
+  // deterministic chronological ordering instead of collapsing same-year
+  // releases by one artist together. `album_date` falls back gracefully
+  // (year-only, year+month, full date) depending on what the tag carried.
+  #[serde(default)]
+  pub album_date: Option<AlbumDate>,
+  #[serde(default)]
+  pub track_number: Option<i32>,
+  // AIDEV-NOTE: Manual same-date tie-breaker - see `AlbumSeq`. Only ever set
+  // by the user (no importer derives it), so it defaults to `None` and is
+  // left untouched by filesystem/Traktor import.
+  #[serde(default)]
+  pub album_seq: Option<AlbumSeq>,
+  // AIDEV-NOTE: Manual sort-key overrides for `artist`/`album`/`title`. When
+  // `None`, `libs::database::derive_sort_name` fills in a default (ASCII-
+  // folded, lowercased, leading-article-stripped) at write time; a `Some`
+  // here - set by the user via `update_track` - is stored verbatim instead
+  // and wins until explicitly cleared back to `None`. Used by
+  // `get_all_tracks_sorted`'s `ORDER BY` so e.g. "The Prodigy" sorts under P.
+  #[serde(default)]
+  pub artist_sort: Option<String>,
+  #[serde(default)]
+  pub album_sort: Option<String>,
+  #[serde(default)]
+  pub title_sort: Option<String>,
+  #[serde(default)]
+  pub synced_lyrics: Vec<(i64, String)>,
 }
 
 impl Track {
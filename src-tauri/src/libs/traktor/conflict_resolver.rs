@@ -2,20 +2,102 @@
 //
 // Handles merge strategies when syncing Traktor data with Harmony.
 //
-// Three strategies:
-// 1. SMART_MERGE (default): Traktor fills empty Harmony fields, Harmony wins on conflicts
-// 2. TRAKTOR_WINS: Traktor data overwrites Harmony (except id/path/duration/waveform)
-// 3. HARMONY_WINS: Keep all Harmony data, ignore Traktor completely
+// SMART_MERGE is a CRDT: every mergeable field is a last-writer-wins (LWW)
+// register backed by a `FieldStamp` (timestamp + source). The value with the
+// strictly greater timestamp wins; exact ties are broken deterministically by
+// source priority (see `libs::field_clock`). Because the comparison is pure
+// (same two stamps always produce the same winner), merging the same NML
+// twice - or merging Traktor and Harmony edits made independently between
+// syncs - is commutative, associative, and idempotent: it converges to a
+// fixed point instead of flip-flopping.
+//
+// TRAKTOR_WINS / HARMONY_WINS remain as forceful overrides for users who
+// don't want field-level reconciliation at all.
 //
 // Identity fields (id, path, duration, waveform_peaks) always stay with Harmony.
 //
 // Reference: src/main/lib/traktor/sync/conflict-resolver.ts
 
+use std::collections::{HashMap, HashSet};
+
 use log::debug;
 use serde::{Deserialize, Serialize};
 
-use crate::libs::cue_point::CuePoint;
-use crate::libs::track::Track;
+use crate::libs::cue_point::{CueKey, CuePoint};
+use crate::libs::field_clock::{FieldClock, FieldStamp, SourcePriority};
+use crate::libs::track::{AlbumDate, Track};
+
+/// Names of every field `merge_track` treats as mergeable, in merge order.
+/// Shared with `Database::update_track` so local edits can be diffed against
+/// the same field list used by the CRDT merge.
+pub const MERGEABLE_FIELDS: &[&str] = &[
+  "title",
+  "artist",
+  "album",
+  "genre",
+  "year",
+  "bpm",
+  "initial_key",
+  "rating",
+  "comment",
+  "bitrate",
+  "label",
+  "catalog_number",
+  "isrc",
+  "album_date",
+];
+
+/// Which of `MERGEABLE_FIELDS` differ between `old` and `new`. Used to bump
+/// `FieldClock` entries to "now, Harmony" whenever a track is edited locally,
+/// so a later Traktor sync knows the Harmony value is newer.
+pub fn changed_mergeable_fields(old: &Track, new: &Track) -> Vec<&'static str> {
+  let mut changed = Vec::new();
+
+  if old.title != new.title {
+    changed.push("title");
+  }
+  if old.artist != new.artist {
+    changed.push("artist");
+  }
+  if old.album != new.album {
+    changed.push("album");
+  }
+  if old.genre != new.genre {
+    changed.push("genre");
+  }
+  if old.year != new.year {
+    changed.push("year");
+  }
+  if old.bpm != new.bpm {
+    changed.push("bpm");
+  }
+  if old.initial_key != new.initial_key {
+    changed.push("initial_key");
+  }
+  if old.rating != new.rating {
+    changed.push("rating");
+  }
+  if old.comment != new.comment {
+    changed.push("comment");
+  }
+  if old.bitrate != new.bitrate {
+    changed.push("bitrate");
+  }
+  if old.label != new.label {
+    changed.push("label");
+  }
+  if old.catalog_number != new.catalog_number {
+    changed.push("catalog_number");
+  }
+  if old.isrc != new.isrc {
+    changed.push("isrc");
+  }
+  if old.album_date != new.album_date {
+    changed.push("album_date");
+  }
+
+  changed
+}
 
 /// Available merge strategies for track data
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -45,6 +127,29 @@ pub struct MergeResult {
   pub has_changes: bool,
   /// List of field names that were updated
   pub fields_updated: Vec<String>,
+  /// Fields where Harmony and Traktor both changed the value since `base`,
+  /// but disagree on the new value - only ever populated by
+  /// [`merge_track_3way`] under `MergeStrategy::SmartMerge`.
+  #[serde(default)]
+  pub conflicts: Vec<FieldConflict>,
+  /// The merged field clock, to persist via `Database::save_field_clock` so
+  /// the next sync starts from the same state this one ended on.
+  #[serde(skip)]
+  pub merged_clock: FieldClock,
+}
+
+/// A single field where Harmony and Traktor each changed a value away from
+/// the last-synced `base` snapshot, but landed on different results. Carries
+/// all three values (Debug-formatted, since mergeable fields span `String`,
+/// `Option<String>`, `Option<i32>` and `Option<TrackRating>`) so a UI can
+/// show the user exactly what's in dispute.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldConflict {
+  pub field: String,
+  pub base: String,
+  pub harmony: String,
+  pub traktor: String,
 }
 
 /// Available merge strategies for cue points
@@ -55,6 +160,10 @@ pub enum CueMergeStrategy {
   SmartMerge,
   /// Always replace Harmony cue points with Traktor's
   Replace,
+  /// Reconcile the two sets cue-by-cue instead of an all-or-nothing choice:
+  /// matched cues are field-merged, unmatched Traktor cues are appended, and
+  /// Harmony-only cues are retained. See [`merge_cue_points`].
+  Combine,
 }
 
 impl Default for CueMergeStrategy {
@@ -75,6 +184,10 @@ pub struct CueMergeResult {
   pub added: usize,
   /// Number of cue points removed
   pub removed: usize,
+  /// Number of matched cues reconciled field-by-field in place (only ever
+  /// populated by `CueMergeStrategy::Combine`).
+  #[serde(default)]
+  pub updated: usize,
 }
 
 /// Check if a string value is considered "empty"
@@ -95,23 +208,94 @@ fn is_int_empty(value: &Option<i32>) -> bool {
   }
 }
 
+/// Check if an optional album date is empty (None)
+fn is_album_date_empty(value: &Option<AlbumDate>) -> bool {
+  value.is_none()
+}
+
+/// How precisely a date pins down a release: year-only, year+month, or a
+/// full date. Higher is more specific.
+fn album_date_specificity(date: &AlbumDate) -> u8 {
+  match (date.month, date.day) {
+    (Some(_), Some(_)) => 2,
+    (Some(_), None) => 1,
+    (None, _) => 0,
+  }
+}
+
+/// Whether `candidate` should replace `current` purely because it's a more
+/// specific date for the *same* release year - e.g. Harmony only has
+/// "2020" and Traktor has "2020-05-14". This isn't a "newer edit" signal
+/// (it doesn't mean Traktor's date is more *recently set*), so it's checked
+/// independently of the LWW clock.
+fn is_more_specific_same_year(current: &AlbumDate, candidate: &AlbumDate) -> bool {
+  current.year == candidate.year && album_date_specificity(candidate) > album_date_specificity(current)
+}
+
+/// Decide whether Traktor's `album_date` should win over Harmony's under
+/// `strategy`. `wins_over_clock` is the result of the normal CRDT timestamp
+/// comparison used for every other field, for when neither the "Traktor
+/// fills an empty field" nor "same year, Traktor is more specific" shortcuts
+/// apply. Returns `(traktor_wins, is_specificity_fill)` - the latter is true
+/// when Traktor only won by being more specific, which shouldn't bump the
+/// persisted clock since it's not a genuine newer-edit signal.
+fn resolve_album_date_merge(
+  harmony: Option<AlbumDate>,
+  traktor: Option<AlbumDate>,
+  strategy: MergeStrategy,
+  wins_over_clock: bool,
+) -> (bool, bool) {
+  if is_album_date_empty(&traktor) || harmony == traktor {
+    return (false, false);
+  }
+  if strategy == MergeStrategy::TraktorWins {
+    return (true, false);
+  }
+  if let (Some(h), Some(t)) = (harmony, traktor) {
+    if is_more_specific_same_year(&h, &t) {
+      return (true, true);
+    }
+  }
+  (wins_over_clock, false)
+}
+
 /// Merge a Traktor track into a Harmony track.
 ///
 /// AIDEV-NOTE: Core merge logic for track data
 /// - Always preserves: id, path, duration, waveform_peaks, url, added_at
-/// - Mergeable fields: title, artist, album, genre, year, bpm, initial_key, rating, comment, bitrate, label
+/// - Mergeable fields: see [`MERGEABLE_FIELDS`]
+///
+/// SMART_MERGE runs a genuine CRDT merge: each field is decided by comparing
+/// `harmony_clock`'s stamp for that field against `traktor_stamp` (one stamp
+/// for the whole Traktor entry, since NML only records one modification time
+/// per track). An empty Traktor value never wins regardless of timestamp -
+/// Traktor not knowing a field isn't a newer edit, it's no information at
+/// all - so fields Traktor can't represent (e.g. catalog_number, isrc) are
+/// never clobbered by a sync.
+///
+/// TRAKTOR_WINS / HARMONY_WINS bypass the clock entirely and keep their
+/// original "force overwrite" / "ignore Traktor" semantics.
 ///
 /// # Arguments
 /// * `harmony` - The existing Harmony track
+/// * `harmony_clock` - Persisted LWW clock for `harmony`'s fields (from `Database::get_field_clock`)
 /// * `traktor` - The Traktor track data to merge
+/// * `traktor_stamp` - LWW stamp for all of `traktor`'s fields (derive from the NML entry's MODIFIED_DATE/TIME)
 /// * `strategy` - Merge strategy (default: SMART_MERGE)
 ///
 /// # Returns
-/// MergeResult with merged track and change info
-pub fn merge_track(harmony: &Track, traktor: &Track, strategy: MergeStrategy) -> MergeResult {
+/// MergeResult with merged track, change info, and the clock to persist
+pub fn merge_track(
+  harmony: &Track,
+  harmony_clock: &FieldClock,
+  traktor: &Track,
+  traktor_stamp: FieldStamp,
+  strategy: MergeStrategy,
+) -> MergeResult {
   // Start with a copy of Harmony track (preserve identity fields)
   let mut merged = harmony.clone();
   let mut fields_updated: Vec<String> = Vec::new();
+  let mut merged_clock = harmony_clock.clone();
 
   // HARMONY_WINS: No changes, just return Harmony as-is
   if strategy == MergeStrategy::HarmonyWins {
@@ -119,136 +303,371 @@ pub fn merge_track(harmony: &Track, traktor: &Track, strategy: MergeStrategy) ->
       merged,
       has_changes: false,
       fields_updated,
+      conflicts: Vec::new(),
+      merged_clock,
     };
   }
 
-  // Process each mergeable field
-  // AIDEV-NOTE: Field-by-field merge logic
-
-  // Title
-  if strategy == MergeStrategy::TraktorWins {
-    if !traktor.title.trim().is_empty() && harmony.title != traktor.title {
-      merged.title = traktor.title.clone();
-      fields_updated.push("title".to_string());
-    }
-  } else if harmony.title.trim().is_empty() && !traktor.title.trim().is_empty() {
-    merged.title = traktor.title.clone();
-    fields_updated.push("title".to_string());
+  macro_rules! merge_field {
+    ($field:ident, $name:literal, $is_empty:expr) => {
+      let traktor_is_empty = $is_empty(&traktor.$field);
+      let differs = harmony.$field != traktor.$field;
+
+      let traktor_wins = if strategy == MergeStrategy::TraktorWins {
+        !traktor_is_empty && differs
+      } else {
+        // SMART_MERGE: genuine CRDT comparison. An empty Traktor value never
+        // wins - it carries no information, not a "delete" signal.
+        !traktor_is_empty
+          && differs
+          && traktor_stamp.wins_over(
+            &merged_clock
+              .get($name)
+              .copied()
+              .unwrap_or(FieldStamp { updated_at: 0, source: SourcePriority::Harmony }),
+          )
+      };
+
+      if traktor_wins {
+        merged.$field = traktor.$field.clone();
+        merged_clock.insert($name.to_string(), traktor_stamp);
+        fields_updated.push($name.to_string());
+      } else if !merged_clock.contains_key($name) {
+        // Bootstrap: first time this field's clock is persisted, anchor it
+        // to Harmony so a later, genuinely newer Traktor edit can still win.
+        merged_clock.insert(
+          $name.to_string(),
+          FieldStamp { updated_at: 0, source: SourcePriority::Harmony },
+        );
+      }
+    };
   }
 
-  // Artist
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_string_empty(&traktor.artist) && harmony.artist != traktor.artist {
-      merged.artist = traktor.artist.clone();
-      fields_updated.push("artist".to_string());
+  merge_field!(title, "title", |v: &String| v.trim().is_empty());
+  merge_field!(artist, "artist", is_string_empty);
+  merge_field!(album, "album", is_string_empty);
+  merge_field!(genre, "genre", is_string_empty);
+  merge_field!(year, "year", is_int_empty);
+  merge_field!(bpm, "bpm", is_int_empty);
+  merge_field!(initial_key, "initial_key", is_string_empty);
+  merge_field!(rating, "rating", |v: &Option<crate::libs::track::TrackRating>| v.is_none());
+  merge_field!(comment, "comment", is_string_empty);
+  merge_field!(bitrate, "bitrate", is_int_empty);
+  merge_field!(label, "label", is_string_empty);
+  merge_field!(catalog_number, "catalog_number", is_string_empty);
+  merge_field!(isrc, "isrc", is_string_empty);
+
+  // album_date doesn't fit the generic merge_field! macro: under SmartMerge
+  // a same-year, more-specific Traktor date should fill in Harmony's gaps
+  // regardless of the clock - see `resolve_album_date_merge`.
+  {
+    let wins_over_clock = !is_album_date_empty(&traktor.album_date)
+      && harmony.album_date != traktor.album_date
+      && traktor_stamp.wins_over(
+        &merged_clock
+          .get("album_date")
+          .copied()
+          .unwrap_or(FieldStamp { updated_at: 0, source: SourcePriority::Harmony }),
+      );
+    let (album_date_wins, is_specificity_fill) =
+      resolve_album_date_merge(harmony.album_date, traktor.album_date, strategy, wins_over_clock);
+
+    if album_date_wins {
+      merged.album_date = traktor.album_date;
+      if !is_specificity_fill {
+        merged_clock.insert("album_date".to_string(), traktor_stamp);
+      }
+      fields_updated.push("album_date".to_string());
+    } else if !merged_clock.contains_key("album_date") {
+      merged_clock.insert(
+        "album_date".to_string(),
+        FieldStamp { updated_at: 0, source: SourcePriority::Harmony },
+      );
     }
-  } else if is_string_empty(&harmony.artist) && !is_string_empty(&traktor.artist) {
-    merged.artist = traktor.artist.clone();
-    fields_updated.push("artist".to_string());
   }
 
-  // Album
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_string_empty(&traktor.album) && harmony.album != traktor.album {
-      merged.album = traktor.album.clone();
-      fields_updated.push("album".to_string());
-    }
-  } else if is_string_empty(&harmony.album) && !is_string_empty(&traktor.album) {
-    merged.album = traktor.album.clone();
-    fields_updated.push("album".to_string());
-  }
+  debug!(
+    "Track merge complete: {} fields updated with {:?} strategy",
+    fields_updated.len(),
+    strategy
+  );
 
-  // Genre
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_string_empty(&traktor.genre) && harmony.genre != traktor.genre {
-      merged.genre = traktor.genre.clone();
-      fields_updated.push("genre".to_string());
-    }
-  } else if is_string_empty(&harmony.genre) && !is_string_empty(&traktor.genre) {
-    merged.genre = traktor.genre.clone();
-    fields_updated.push("genre".to_string());
+  MergeResult {
+    merged,
+    has_changes: !fields_updated.is_empty(),
+    fields_updated,
+    conflicts: Vec::new(),
+    merged_clock,
   }
+}
 
-  // Year
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_int_empty(&traktor.year) && harmony.year != traktor.year {
-      merged.year = traktor.year;
-      fields_updated.push("year".to_string());
-    }
-  } else if is_int_empty(&harmony.year) && !is_int_empty(&traktor.year) {
-    merged.year = traktor.year;
-    fields_updated.push("year".to_string());
-  }
+/// Per-field override of [`MergeStrategy`], keyed by the field names in
+/// [`MERGEABLE_FIELDS`]. Fields absent from the map fall back to
+/// `MergeStrategy::SmartMerge`. Lets the Tauri layer persist preferences like
+/// "BPM: TraktorWins, Rating: HarmonyWins, everything else: SmartMerge"
+/// without new global strategy variants.
+pub type FieldMergePolicy = HashMap<String, MergeStrategy>;
+
+/// Expand one of the three global [`MergeStrategy`] presets into a full
+/// [`FieldMergePolicy`] that applies it to every mergeable field.
+pub fn expand_strategy_to_policy(strategy: MergeStrategy) -> FieldMergePolicy {
+  MERGEABLE_FIELDS
+    .iter()
+    .map(|&field| (field.to_string(), strategy))
+    .collect()
+}
 
-  // BPM
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_int_empty(&traktor.bpm) && harmony.bpm != traktor.bpm {
-      merged.bpm = traktor.bpm;
-      fields_updated.push("bpm".to_string());
-    }
-  } else if is_int_empty(&harmony.bpm) && !is_int_empty(&traktor.bpm) {
-    merged.bpm = traktor.bpm;
-    fields_updated.push("bpm".to_string());
+/// Merge a Traktor track into a Harmony track using a per-field
+/// [`FieldMergePolicy`] instead of one global [`MergeStrategy`].
+///
+/// Each mergeable field is resolved independently by the strategy the policy
+/// assigns it (defaulting to `SmartMerge` if the field is absent from the
+/// map), using the same CRDT/force-overwrite/keep-Harmony semantics as
+/// [`merge_track`] - see its docs for the rules each strategy applies to a
+/// single field. `merge_track(harmony, harmony_clock, traktor, traktor_stamp,
+/// strategy)` is equivalent to calling this with
+/// `expand_strategy_to_policy(strategy)`.
+pub fn merge_track_with_policy(
+  harmony: &Track,
+  harmony_clock: &FieldClock,
+  traktor: &Track,
+  traktor_stamp: FieldStamp,
+  policy: &FieldMergePolicy,
+) -> MergeResult {
+  let mut merged = harmony.clone();
+  let mut fields_updated: Vec<String> = Vec::new();
+  let mut merged_clock = harmony_clock.clone();
+
+  macro_rules! merge_field_policy {
+    ($field:ident, $name:literal, $is_empty:expr) => {
+      let strategy = policy.get($name).copied().unwrap_or_default();
+
+      if strategy == MergeStrategy::HarmonyWins {
+        if !merged_clock.contains_key($name) {
+          merged_clock.insert(
+            $name.to_string(),
+            FieldStamp { updated_at: 0, source: SourcePriority::Harmony },
+          );
+        }
+      } else {
+        let traktor_is_empty = $is_empty(&traktor.$field);
+        let differs = harmony.$field != traktor.$field;
+
+        let traktor_wins = if strategy == MergeStrategy::TraktorWins {
+          !traktor_is_empty && differs
+        } else {
+          // SMART_MERGE: genuine CRDT comparison. An empty Traktor value
+          // never wins - it carries no information, not a "delete" signal.
+          !traktor_is_empty
+            && differs
+            && traktor_stamp.wins_over(
+              &merged_clock
+                .get($name)
+                .copied()
+                .unwrap_or(FieldStamp { updated_at: 0, source: SourcePriority::Harmony }),
+            )
+        };
+
+        if traktor_wins {
+          merged.$field = traktor.$field.clone();
+          merged_clock.insert($name.to_string(), traktor_stamp);
+          fields_updated.push($name.to_string());
+        } else if !merged_clock.contains_key($name) {
+          // Bootstrap: first time this field's clock is persisted, anchor it
+          // to Harmony so a later, genuinely newer Traktor edit can still win.
+          merged_clock.insert(
+            $name.to_string(),
+            FieldStamp { updated_at: 0, source: SourcePriority::Harmony },
+          );
+        }
+      }
+    };
   }
 
-  // Initial Key
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_string_empty(&traktor.initial_key) && harmony.initial_key != traktor.initial_key {
-      merged.initial_key = traktor.initial_key.clone();
-      fields_updated.push("initial_key".to_string());
+  merge_field_policy!(title, "title", |v: &String| v.trim().is_empty());
+  merge_field_policy!(artist, "artist", is_string_empty);
+  merge_field_policy!(album, "album", is_string_empty);
+  merge_field_policy!(genre, "genre", is_string_empty);
+  merge_field_policy!(year, "year", is_int_empty);
+  merge_field_policy!(bpm, "bpm", is_int_empty);
+  merge_field_policy!(initial_key, "initial_key", is_string_empty);
+  merge_field_policy!(rating, "rating", |v: &Option<crate::libs::track::TrackRating>| v.is_none());
+  merge_field_policy!(comment, "comment", is_string_empty);
+  merge_field_policy!(bitrate, "bitrate", is_int_empty);
+  merge_field_policy!(label, "label", is_string_empty);
+  merge_field_policy!(catalog_number, "catalog_number", is_string_empty);
+  merge_field_policy!(isrc, "isrc", is_string_empty);
+
+  // See the matching block in `merge_track` for why album_date isn't a
+  // `merge_field_policy!` invocation.
+  {
+    let album_date_strategy = policy.get("album_date").copied().unwrap_or_default();
+
+    if album_date_strategy == MergeStrategy::HarmonyWins {
+      if !merged_clock.contains_key("album_date") {
+        merged_clock.insert(
+          "album_date".to_string(),
+          FieldStamp { updated_at: 0, source: SourcePriority::Harmony },
+        );
+      }
+    } else {
+      let wins_over_clock = !is_album_date_empty(&traktor.album_date)
+        && harmony.album_date != traktor.album_date
+        && traktor_stamp.wins_over(
+          &merged_clock
+            .get("album_date")
+            .copied()
+            .unwrap_or(FieldStamp { updated_at: 0, source: SourcePriority::Harmony }),
+        );
+      let (album_date_wins, is_specificity_fill) = resolve_album_date_merge(
+        harmony.album_date,
+        traktor.album_date,
+        album_date_strategy,
+        wins_over_clock,
+      );
+
+      if album_date_wins {
+        merged.album_date = traktor.album_date;
+        if !is_specificity_fill {
+          merged_clock.insert("album_date".to_string(), traktor_stamp);
+        }
+        fields_updated.push("album_date".to_string());
+      } else if !merged_clock.contains_key("album_date") {
+        merged_clock.insert(
+          "album_date".to_string(),
+          FieldStamp { updated_at: 0, source: SourcePriority::Harmony },
+        );
+      }
     }
-  } else if is_string_empty(&harmony.initial_key) && !is_string_empty(&traktor.initial_key) {
-    merged.initial_key = traktor.initial_key.clone();
-    fields_updated.push("initial_key".to_string());
   }
 
-  // Rating
-  if strategy == MergeStrategy::TraktorWins {
-    if traktor.rating.is_some() && harmony.rating != traktor.rating {
-      merged.rating = traktor.rating.clone();
-      fields_updated.push("rating".to_string());
-    }
-  } else if harmony.rating.is_none() && traktor.rating.is_some() {
-    merged.rating = traktor.rating.clone();
-    fields_updated.push("rating".to_string());
-  }
+  debug!(
+    "Track policy merge complete: {} fields updated across {} policy entries",
+    fields_updated.len(),
+    policy.len()
+  );
 
-  // Comment
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_string_empty(&traktor.comment) && harmony.comment != traktor.comment {
-      merged.comment = traktor.comment.clone();
-      fields_updated.push("comment".to_string());
-    }
-  } else if is_string_empty(&harmony.comment) && !is_string_empty(&traktor.comment) {
-    merged.comment = traktor.comment.clone();
-    fields_updated.push("comment".to_string());
+  MergeResult {
+    merged,
+    has_changes: !fields_updated.is_empty(),
+    fields_updated,
+    conflicts: Vec::new(),
+    merged_clock,
   }
+}
 
-  // Bitrate
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_int_empty(&traktor.bitrate) && harmony.bitrate != traktor.bitrate {
-      merged.bitrate = traktor.bitrate;
-      fields_updated.push("bitrate".to_string());
-    }
-  } else if is_int_empty(&harmony.bitrate) && !is_int_empty(&traktor.bitrate) {
-    merged.bitrate = traktor.bitrate;
-    fields_updated.push("bitrate".to_string());
+/// Three-way merge of a Traktor track against a Harmony track, using the
+/// `base` Track captured at the previous sync to tell genuine edits apart
+/// from values that simply never changed.
+///
+/// Unlike [`merge_track`] (which decides each field by its LWW clock under
+/// `SmartMerge`, or blindly overwrites under `TraktorWins`), this compares
+/// each mergeable field against `base` on both sides:
+/// - only Traktor changed it -> take Traktor
+/// - only Harmony changed it -> keep Harmony
+/// - neither changed it -> keep as-is
+/// - both changed it to the *same* value -> keep it, no conflict
+/// - both changed it to *different* values -> a true conflict, resolved by
+///   `strategy`: `SmartMerge` keeps Harmony and records a [`FieldConflict`],
+///   `TraktorWins` takes Traktor, `HarmonyWins` keeps Harmony.
+///
+/// As with `merge_track`, an empty Traktor value is never treated as a
+/// change - Traktor not carrying a field (e.g. catalog_number, isrc) isn't a
+/// deliberate clear, it's no information at all.
+pub fn merge_track_3way(
+  harmony: &Track,
+  traktor: &Track,
+  base: &Track,
+  strategy: MergeStrategy,
+) -> MergeResult {
+  let mut merged = harmony.clone();
+  let mut fields_updated: Vec<String> = Vec::new();
+  let mut conflicts: Vec<FieldConflict> = Vec::new();
+
+  macro_rules! merge_field_3way {
+    ($field:ident, $name:literal, $is_empty:expr) => {
+      let traktor_changed = !$is_empty(&traktor.$field) && traktor.$field != base.$field;
+      let harmony_changed = harmony.$field != base.$field;
+
+      if traktor_changed && !harmony_changed {
+        merged.$field = traktor.$field.clone();
+        fields_updated.push($name.to_string());
+      } else if traktor_changed && harmony_changed && harmony.$field != traktor.$field {
+        match strategy {
+          MergeStrategy::TraktorWins => {
+            merged.$field = traktor.$field.clone();
+            fields_updated.push($name.to_string());
+          }
+          MergeStrategy::SmartMerge => {
+            conflicts.push(FieldConflict {
+              field: $name.to_string(),
+              base: format!("{:?}", base.$field),
+              harmony: format!("{:?}", harmony.$field),
+              traktor: format!("{:?}", traktor.$field),
+            });
+          }
+          MergeStrategy::HarmonyWins => {
+            // Keep Harmony, no conflict recorded - the caller opted out of
+            // reconciliation entirely.
+          }
+        }
+      }
+      // Otherwise: only Harmony changed, neither changed, or both changed to
+      // the same value - `merged` already holds the right value.
+    };
   }
 
-  // Label
-  if strategy == MergeStrategy::TraktorWins {
-    if !is_string_empty(&traktor.label) && harmony.label != traktor.label {
-      merged.label = traktor.label.clone();
-      fields_updated.push("label".to_string());
+  merge_field_3way!(title, "title", |v: &String| v.trim().is_empty());
+  merge_field_3way!(artist, "artist", is_string_empty);
+  merge_field_3way!(album, "album", is_string_empty);
+  merge_field_3way!(genre, "genre", is_string_empty);
+  merge_field_3way!(year, "year", is_int_empty);
+  merge_field_3way!(bpm, "bpm", is_int_empty);
+  merge_field_3way!(initial_key, "initial_key", is_string_empty);
+  merge_field_3way!(rating, "rating", |v: &Option<crate::libs::track::TrackRating>| v.is_none());
+  merge_field_3way!(comment, "comment", is_string_empty);
+  merge_field_3way!(bitrate, "bitrate", is_int_empty);
+  merge_field_3way!(label, "label", is_string_empty);
+  merge_field_3way!(catalog_number, "catalog_number", is_string_empty);
+  merge_field_3way!(isrc, "isrc", is_string_empty);
+
+  // album_date doesn't fit the generic merge_field_3way! macro - see the
+  // matching block in `merge_track` for why.
+  {
+    let traktor_changed = !is_album_date_empty(&traktor.album_date) && traktor.album_date != base.album_date;
+    let harmony_changed = harmony.album_date != base.album_date;
+
+    if traktor_changed && !harmony_changed {
+      merged.album_date = traktor.album_date;
+      fields_updated.push("album_date".to_string());
+    } else if traktor_changed && harmony_changed && harmony.album_date != traktor.album_date {
+      match strategy {
+        MergeStrategy::TraktorWins => {
+          merged.album_date = traktor.album_date;
+          fields_updated.push("album_date".to_string());
+        }
+        MergeStrategy::SmartMerge => {
+          conflicts.push(FieldConflict {
+            field: "album_date".to_string(),
+            base: format!("{:?}", base.album_date),
+            harmony: format!("{:?}", harmony.album_date),
+            traktor: format!("{:?}", traktor.album_date),
+          });
+        }
+        MergeStrategy::HarmonyWins => {
+          // Keep Harmony, no conflict recorded - the caller opted out of
+          // reconciliation entirely.
+        }
+      }
     }
-  } else if is_string_empty(&harmony.label) && !is_string_empty(&traktor.label) {
-    merged.label = traktor.label.clone();
-    fields_updated.push("label".to_string());
+    // Otherwise: only Harmony changed, neither changed, or both changed to
+    // the same value - `merged` already holds the right value.
   }
 
   debug!(
-    "Track merge complete: {} fields updated with {:?} strategy",
+    "Track 3-way merge complete: {} fields updated, {} conflicts with {:?} strategy",
     fields_updated.len(),
+    conflicts.len(),
     strategy
   );
 
@@ -256,14 +675,24 @@ pub fn merge_track(harmony: &Track, traktor: &Track, strategy: MergeStrategy) ->
     merged,
     has_changes: !fields_updated.is_empty(),
     fields_updated,
+    conflicts,
+    merged_clock: FieldClock::new(),
   }
 }
 
 /// Merge cue points from Traktor into Harmony.
 ///
 /// AIDEV-NOTE: Cue point merge logic
-/// - SMART_MERGE: If Harmony has cues, keep them (no changes); otherwise use Traktor's
-/// - REPLACE: Always replace Harmony cue points with Traktor's
+/// - SMART_MERGE: LWW-map keyed by [`CuePoint::lww_key`]. Each cue carries its
+///   own `updated_at`, so a cue Traktor hasn't touched since the last sync
+///   never overwrites a Harmony edit, and re-running the same sync twice is a
+///   no-op (the second pass always sees `candidate.updated_at <=
+///   existing.updated_at` and changes nothing). Deletions are tombstones
+///   (`deleted: true`) rather than omissions, so an intentional removal on
+///   one side propagates instead of looking like "the other side just
+///   doesn't know about this cue yet". Exact-tie timestamps keep the
+///   Harmony cue, mirroring `merge_track`'s Harmony-anchored bootstrap.
+/// - REPLACE: Always replace Harmony cue points with Traktor's, unconditionally
 ///
 /// # Arguments
 /// * `harmony_cues` - Existing Harmony cue points
@@ -279,44 +708,18 @@ pub fn merge_cue_points(
   track_id: &str,
   strategy: CueMergeStrategy,
 ) -> CueMergeResult {
-  // SMART_MERGE: Keep Harmony cues if any exist
-  if strategy == CueMergeStrategy::SmartMerge && !harmony_cues.is_empty() {
-    debug!(
-      "Keeping {} existing Harmony cue points (SMART_MERGE)",
-      harmony_cues.len()
-    );
-    return CueMergeResult {
-      merged: harmony_cues.to_vec(),
-      has_changes: false,
-      added: 0,
-      removed: 0,
-    };
-  }
-
-  // If Harmony has no cues or REPLACE strategy, use Traktor cues
-  if strategy == CueMergeStrategy::Replace || harmony_cues.is_empty() {
-    // Assign correct trackId to all cue points
+  // REPLACE: blunt, non-CRDT override - always take Traktor's cues as-is
+  if strategy == CueMergeStrategy::Replace {
     let merged: Vec<CuePoint> = traktor_cues
       .iter()
       .map(|cue| CuePoint {
-        id: cue.id.clone(),
         track_id: track_id.to_string(),
-        cue_type: cue.cue_type,
-        position_ms: cue.position_ms,
-        length_ms: cue.length_ms,
-        hotcue_slot: cue.hotcue_slot,
-        name: cue.name.clone(),
-        color: cue.color.clone(),
-        order: cue.order,
+        ..cue.clone()
       })
       .collect();
 
     let added = traktor_cues.len();
-    let removed = if strategy == CueMergeStrategy::Replace {
-      harmony_cues.len()
-    } else {
-      0
-    };
+    let removed = harmony_cues.len();
     let has_changes = added > 0 || removed > 0;
 
     debug!(
@@ -329,15 +732,184 @@ pub fn merge_cue_points(
       has_changes,
       added,
       removed,
+      updated: 0,
     };
   }
 
-  // Fallback (shouldn't reach here)
+  if strategy == CueMergeStrategy::Combine {
+    return combine_cue_points(harmony_cues, traktor_cues, track_id);
+  }
+
+  // SMART_MERGE: per-cue LWW, keyed by stable cue identity
+  let mut harmony_by_key: HashMap<CueKey, CuePoint> = HashMap::new();
+  for cue in harmony_cues {
+    harmony_by_key.insert(cue.lww_key(), cue.clone());
+  }
+
+  let mut merged_by_key = harmony_by_key.clone();
+
+  for traktor_cue in traktor_cues {
+    let key = traktor_cue.lww_key();
+    let candidate = CuePoint {
+      track_id: track_id.to_string(),
+      ..traktor_cue.clone()
+    };
+
+    match merged_by_key.get(&key) {
+      Some(existing) if candidate.updated_at <= existing.updated_at => {
+        // Harmony's cue is at least as new - keep it untouched.
+      }
+      _ => {
+        merged_by_key.insert(key, candidate);
+      }
+    }
+  }
+
+  let was_active: HashSet<CueKey> = harmony_by_key
+    .iter()
+    .filter(|(_, cue)| !cue.deleted)
+    .map(|(key, _)| *key)
+    .collect();
+  let is_active: HashSet<CueKey> = merged_by_key
+    .iter()
+    .filter(|(_, cue)| !cue.deleted)
+    .map(|(key, _)| *key)
+    .collect();
+
+  let added = is_active.difference(&was_active).count();
+  let removed = was_active.difference(&is_active).count();
+  let has_changes = added > 0 || removed > 0;
+
+  let merged: Vec<CuePoint> = merged_by_key
+    .into_values()
+    .filter(|cue| !cue.deleted)
+    .collect();
+
+  debug!(
+    "Cue merge: {} added, {} removed (strategy: {:?})",
+    added, removed, strategy
+  );
+
+  CueMergeResult {
+    merged,
+    has_changes,
+    added,
+    removed,
+    updated: 0,
+  }
+}
+
+/// Maximum distance (milliseconds) between two slot-less cues' positions for
+/// them to be considered the same cue during a [`CueMergeStrategy::Combine`] merge.
+const CUE_COMBINE_POSITION_TOLERANCE_MS: f64 = 50.0;
+
+/// Whether `harmony` and `traktor` cues of the same type should be treated as
+/// the same cue: by `hotcue_slot` when both have one, otherwise by
+/// `position_ms` within [`CUE_COMBINE_POSITION_TOLERANCE_MS`].
+fn cues_match(harmony: &CuePoint, traktor: &CuePoint) -> bool {
+  if harmony.cue_type != traktor.cue_type {
+    return false;
+  }
+  match (harmony.hotcue_slot, traktor.hotcue_slot) {
+    (Some(a), Some(b)) => a == b,
+    _ => (harmony.position_ms - traktor.position_ms).abs() <= CUE_COMBINE_POSITION_TOLERANCE_MS,
+  }
+}
+
+/// `CueMergeStrategy::Combine`: reconcile Harmony and Traktor cue sets
+/// cue-by-cue instead of choosing one set wholesale.
+///
+/// Every Harmony cue is kept. A Harmony cue matched to a Traktor cue (see
+/// [`cues_match`]) has its empty `name`/`color` filled from Traktor, but a
+/// Harmony value already present is never overwritten. Traktor cues that
+/// match nothing are appended, moved into a free hotcue slot if their own
+/// slot is already taken. The result is re-sorted by `position_ms` and
+/// `order` is reassigned sequentially.
+fn combine_cue_points(harmony_cues: &[CuePoint], traktor_cues: &[CuePoint], track_id: &str) -> CueMergeResult {
+  let mut traktor_matched = vec![false; traktor_cues.len()];
+  let mut merged: Vec<CuePoint> = Vec::new();
+  let mut updated = 0usize;
+
+  for harmony_cue in harmony_cues {
+    let match_idx = traktor_cues
+      .iter()
+      .enumerate()
+      .position(|(i, t)| !traktor_matched[i] && cues_match(harmony_cue, t));
+
+    let mut reconciled = CuePoint {
+      track_id: track_id.to_string(),
+      ..harmony_cue.clone()
+    };
+
+    if let Some(i) = match_idx {
+      traktor_matched[i] = true;
+      let traktor_cue = &traktor_cues[i];
+      let mut changed = false;
+
+      if reconciled.name.is_none() && traktor_cue.name.is_some() {
+        reconciled.name = traktor_cue.name.clone();
+        changed = true;
+      }
+      if reconciled.color.is_none() && traktor_cue.color.is_some() {
+        reconciled.color = traktor_cue.color.clone();
+        changed = true;
+      }
+
+      if changed {
+        updated += 1;
+      }
+    }
+
+    merged.push(reconciled);
+  }
+
+  let mut used_slots: HashSet<i32> = merged.iter().filter_map(|c| c.hotcue_slot).collect();
+  let mut added = 0usize;
+
+  for (i, traktor_cue) in traktor_cues.iter().enumerate() {
+    if traktor_matched[i] {
+      continue;
+    }
+
+    let mut appended = CuePoint {
+      track_id: track_id.to_string(),
+      ..traktor_cue.clone()
+    };
+
+    if let Some(slot) = appended.hotcue_slot {
+      if used_slots.contains(&slot) {
+        let free_slot = (0..).find(|s| !used_slots.contains(s)).unwrap();
+        appended.hotcue_slot = Some(free_slot);
+      }
+      used_slots.insert(appended.hotcue_slot.unwrap());
+    }
+
+    added += 1;
+    merged.push(appended);
+  }
+
+  merged.sort_by(|a, b| {
+    a.position_ms
+      .partial_cmp(&b.position_ms)
+      .unwrap_or(std::cmp::Ordering::Equal)
+  });
+  for (i, cue) in merged.iter_mut().enumerate() {
+    cue.order = Some(i as i32 + 1);
+  }
+
+  let has_changes = added > 0 || updated > 0;
+
+  debug!(
+    "Cue merge: {} added, {} updated, 0 removed (strategy: Combine)",
+    added, updated
+  );
+
   CueMergeResult {
-    merged: harmony_cues.to_vec(),
-    has_changes: false,
-    added: 0,
+    merged,
+    has_changes,
+    added,
     removed: 0,
+    updated,
   }
 }
 
@@ -364,9 +936,23 @@ mod tests {
       initial_key: None,
       rating: None,
       label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
       waveform_peaks: None,
       added_at: Some(1234567890),
       url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
     };
 
     let traktor = Track {
@@ -387,12 +973,46 @@ mod tests {
         rating: 5,
       }),
       label: Some("Test Label".to_string()),
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
       waveform_peaks: Some(vec![0.5, 0.8]), // Ignored
       added_at: None,
       url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
     };
 
-    let result = merge_track(&harmony, &traktor, MergeStrategy::SmartMerge);
+    // genre/bitrate were edited in Harmony after Traktor's stamp; the
+    // empty fields have no clock entry yet.
+    let mut harmony_clock = FieldClock::new();
+    for field in ["genre", "bitrate"] {
+      harmony_clock.insert(
+        field.to_string(),
+        FieldStamp { updated_at: 5_000, source: SourcePriority::Harmony },
+      );
+    }
+    let traktor_stamp = FieldStamp {
+      updated_at: 1_000,
+      source: SourcePriority::Traktor,
+    };
+
+    let result = merge_track(
+      &harmony,
+      &harmony_clock,
+      &traktor,
+      traktor_stamp,
+      MergeStrategy::SmartMerge,
+    );
 
     // Identity fields preserved
     assert_eq!(result.merged.id, "track-1");
@@ -412,6 +1032,114 @@ mod tests {
     assert!(result.has_changes);
     assert!(result.fields_updated.contains(&"artist".to_string()));
     assert!(result.fields_updated.contains(&"bpm".to_string()));
+
+    // Re-running the exact same merge against the clock it just produced
+    // changes nothing further - this is the CRDT fixed-point guarantee.
+    let second = merge_track(
+      &result.merged,
+      &result.merged_clock,
+      &traktor,
+      traktor_stamp,
+      MergeStrategy::SmartMerge,
+    );
+    assert!(!second.has_changes);
+  }
+
+  #[test]
+  fn test_merge_strategy_smart_merge_older_traktor_edit_does_not_win() {
+    let mut harmony_clock = FieldClock::new();
+    harmony_clock.insert(
+      "genre".to_string(),
+      FieldStamp {
+        updated_at: 5_000,
+        source: SourcePriority::Harmony,
+      },
+    );
+
+    let harmony = Track {
+      id: "track-1".to_string(),
+      path: "/music/test.mp3".to_string(),
+      title: "Test Track".to_string(),
+      artist: None,
+      album: None,
+      genre: Some("House".to_string()),
+      year: None,
+      duration: 180000,
+      bitrate: None,
+      comment: None,
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: Some(1234567890),
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    };
+
+    let traktor = Track {
+      id: "ignored".to_string(),
+      path: "/ignored".to_string(),
+      title: "Test Track".to_string(),
+      artist: None,
+      album: None,
+      genre: Some("Techno".to_string()), // Differs, but edit predates the Harmony one
+      year: None,
+      duration: 999999,
+      bitrate: None,
+      comment: None,
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    };
+
+    // Traktor's stamp is older than the Harmony edit already on record.
+    let traktor_stamp = FieldStamp {
+      updated_at: 1_000,
+      source: SourcePriority::Traktor,
+    };
+
+    let result = merge_track(
+      &harmony,
+      &harmony_clock,
+      &traktor,
+      traktor_stamp,
+      MergeStrategy::SmartMerge,
+    );
+
+    assert_eq!(result.merged.genre, Some("House".to_string()));
+    assert!(!result.fields_updated.contains(&"genre".to_string()));
   }
 
   #[test]
@@ -431,9 +1159,23 @@ mod tests {
       initial_key: Some("C".to_string()),
       rating: None,
       label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
       waveform_peaks: None,
       added_at: Some(1234567890),
       url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
     };
 
     let traktor = Track {
@@ -454,12 +1196,37 @@ mod tests {
         rating: 4,
       }),
       label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
       waveform_peaks: None,
       added_at: None,
       url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
     };
 
-    let result = merge_track(&harmony, &traktor, MergeStrategy::TraktorWins);
+    let harmony_clock = FieldClock::new();
+    let traktor_stamp = FieldStamp {
+      updated_at: 1_000,
+      source: SourcePriority::Traktor,
+    };
+    let result = merge_track(
+      &harmony,
+      &harmony_clock,
+      &traktor,
+      traktor_stamp,
+      MergeStrategy::TraktorWins,
+    );
 
     // Identity preserved
     assert_eq!(result.merged.id, "track-1");
@@ -491,9 +1258,23 @@ mod tests {
       initial_key: None,
       rating: None,
       label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
       waveform_peaks: None,
       added_at: Some(1234567890),
       url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
     };
 
     let traktor = Track {
@@ -511,12 +1292,37 @@ mod tests {
       initial_key: Some("Am".to_string()),
       rating: None,
       label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
       waveform_peaks: None,
       added_at: None,
       url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
     };
 
-    let result = merge_track(&harmony, &traktor, MergeStrategy::HarmonyWins);
+    let harmony_clock = FieldClock::new();
+    let traktor_stamp = FieldStamp {
+      updated_at: 1_000,
+      source: SourcePriority::Traktor,
+    };
+    let result = merge_track(
+      &harmony,
+      &harmony_clock,
+      &traktor,
+      traktor_stamp,
+      MergeStrategy::HarmonyWins,
+    );
 
     // Everything from harmony preserved
     assert_eq!(result.merged.title, "Keep This");
@@ -528,44 +1334,27 @@ mod tests {
     assert!(result.fields_updated.is_empty());
   }
 
-  #[test]
-  fn test_merge_cue_points_smart_keeps_existing() {
-    let harmony_cues = vec![
-      CuePoint {
-        id: "cue-1".to_string(),
-        track_id: "track-1".to_string(),
-        cue_type: CueType::HotCue,
-        position_ms: 1000.0,
-        length_ms: None,
-        hotcue_slot: Some(0),
-        name: Some("Drop".to_string()),
-        color: None,
-        order: None,
-      },
-      CuePoint {
-        id: "cue-2".to_string(),
-        track_id: "track-1".to_string(),
-        cue_type: CueType::Loop,
-        position_ms: 30000.0,
-        length_ms: Some(8000.0),
-        hotcue_slot: None,
-        name: Some("Outro".to_string()),
-        color: None,
-        order: None,
-      },
-    ];
-
-    let traktor_cues = vec![CuePoint {
-      id: "traktor-cue-1".to_string(),
+  fn hotcue(id: &str, slot: i32, name: &str, updated_at: i64) -> CuePoint {
+    CuePoint {
+      id: id.to_string(),
       track_id: "track-1".to_string(),
       cue_type: CueType::HotCue,
-      position_ms: 2000.0,
+      position_ms: (slot as f64) * 1000.0,
       length_ms: None,
-      hotcue_slot: Some(1),
-      name: Some("Build".to_string()),
+      hotcue_slot: Some(slot),
+      name: Some(name.to_string()),
       color: None,
+      grid_bpm: None,
       order: None,
-    }];
+      updated_at,
+      deleted: false,
+    }
+  }
+
+  #[test]
+  fn test_merge_cue_points_smart_adds_unseen_cue() {
+    let harmony_cues = vec![hotcue("cue-1", 0, "Drop", 5_000)];
+    let traktor_cues = vec![hotcue("traktor-cue-1", 1, "Build", 1_000)];
 
     let result = merge_cue_points(
       &harmony_cues,
@@ -574,56 +1363,92 @@ mod tests {
       CueMergeStrategy::SmartMerge,
     );
 
-    assert_eq!(result.merged.len(), 2); // Keep Harmony's 2 cues
+    assert_eq!(result.merged.len(), 2);
+    assert!(result.has_changes);
+    assert_eq!(result.added, 1);
+    assert_eq!(result.removed, 0);
+  }
+
+  #[test]
+  fn test_merge_cue_points_smart_older_traktor_edit_does_not_win() {
+    let harmony_cues = vec![hotcue("cue-1", 0, "Drop", 5_000)];
+    // Same slot (same lww_key), but this Traktor edit predates the Harmony one.
+    let traktor_cues = vec![hotcue("traktor-cue-1", 0, "ReplacedDrop", 1_000)];
+
+    let result = merge_cue_points(
+      &harmony_cues,
+      &traktor_cues,
+      "track-1",
+      CueMergeStrategy::SmartMerge,
+    );
+
+    assert_eq!(result.merged.len(), 1);
+    assert_eq!(result.merged[0].name, Some("Drop".to_string()));
     assert!(!result.has_changes);
     assert_eq!(result.added, 0);
     assert_eq!(result.removed, 0);
   }
 
   #[test]
-  fn test_merge_cue_points_smart_uses_traktor_when_empty() {
-    let harmony_cues: Vec<CuePoint> = vec![];
-
-    let traktor_cues = vec![
-      CuePoint {
-        id: "traktor-cue-1".to_string(),
-        track_id: "wrong-id".to_string(), // Should be replaced
-        cue_type: CueType::HotCue,
-        position_ms: 1500.0,
-        length_ms: None,
-        hotcue_slot: Some(0),
-        name: Some("Intro".to_string()),
-        color: None,
-        order: None,
-      },
-      CuePoint {
-        id: "traktor-cue-2".to_string(),
-        track_id: "wrong-id".to_string(),
-        cue_type: CueType::Loop,
-        position_ms: 45000.0,
-        length_ms: Some(16000.0),
-        hotcue_slot: None,
-        name: None,
-        color: None,
-        order: None,
-      },
-    ];
+  fn test_merge_cue_points_smart_newer_traktor_edit_wins() {
+    let harmony_cues = vec![hotcue("cue-1", 0, "Drop", 1_000)];
+    let traktor_cues = vec![hotcue("traktor-cue-1", 0, "ReplacedDrop", 9_000)];
 
     let result = merge_cue_points(
       &harmony_cues,
       &traktor_cues,
-      "correct-track-id",
+      "track-1",
       CueMergeStrategy::SmartMerge,
     );
 
-    assert_eq!(result.merged.len(), 2);
+    assert_eq!(result.merged.len(), 1);
+    assert_eq!(result.merged[0].name, Some("ReplacedDrop".to_string()));
+    assert_eq!(result.merged[0].track_id, "track-1");
+    // Same key, not a new one - nothing was added or removed, just updated.
+    assert!(!result.has_changes);
+  }
+
+  #[test]
+  fn test_merge_cue_points_smart_tombstone_removes_cue() {
+    let harmony_cues = vec![hotcue("cue-1", 0, "Drop", 1_000)];
+    let mut deleted_cue = hotcue("traktor-cue-1", 0, "Drop", 9_000);
+    deleted_cue.deleted = true;
+
+    let result = merge_cue_points(
+      &harmony_cues,
+      &[deleted_cue],
+      "track-1",
+      CueMergeStrategy::SmartMerge,
+    );
+
+    assert!(result.merged.is_empty());
     assert!(result.has_changes);
-    assert_eq!(result.added, 2);
-    assert_eq!(result.removed, 0);
+    assert_eq!(result.added, 0);
+    assert_eq!(result.removed, 1);
+  }
+
+  #[test]
+  fn test_merge_cue_points_smart_merge_is_idempotent() {
+    let harmony_cues = vec![hotcue("cue-1", 0, "Drop", 1_000)];
+    let traktor_cues = vec![hotcue("traktor-cue-1", 1, "Build", 2_000)];
+
+    let first = merge_cue_points(
+      &harmony_cues,
+      &traktor_cues,
+      "track-1",
+      CueMergeStrategy::SmartMerge,
+    );
+    assert!(first.has_changes);
 
-    // Check trackId was corrected
-    assert_eq!(result.merged[0].track_id, "correct-track-id");
-    assert_eq!(result.merged[1].track_id, "correct-track-id");
+    // Re-running the same sync against its own prior output is a no-op.
+    let second = merge_cue_points(
+      &first.merged,
+      &traktor_cues,
+      "track-1",
+      CueMergeStrategy::SmartMerge,
+    );
+    assert!(!second.has_changes);
+    assert_eq!(second.merged.len(), first.merged.len());
   }
 
   #[test]
@@ -637,7 +1462,10 @@ mod tests {
       hotcue_slot: None,
       name: None,
       color: None,
+      grid_bpm: None,
       order: None,
+      updated_at: 0,
+      deleted: false,
     }];
 
     let traktor_cues = vec![
@@ -650,7 +1478,10 @@ mod tests {
         hotcue_slot: Some(0),
         name: Some("Drop".to_string()),
         color: None,
+        grid_bpm: None,
         order: None,
+        updated_at: 0,
+        deleted: false,
       },
       CuePoint {
         id: "new-cue-2".to_string(),
@@ -661,7 +1492,10 @@ mod tests {
         hotcue_slot: None,
         name: None,
         color: None,
+        grid_bpm: None,
         order: None,
+        updated_at: 0,
+        deleted: false,
       },
     ];
 
@@ -680,4 +1514,430 @@ mod tests {
     // All cues should have correct trackId
     assert!(result.merged.iter().all(|c| c.track_id == "track-1"));
   }
+
+  fn base_track() -> Track {
+    Track {
+      id: "track-1".to_string(),
+      path: "/music/test.mp3".to_string(),
+      title: "Original Title".to_string(),
+      artist: Some("Original Artist".to_string()),
+      album: None,
+      genre: Some("House".to_string()),
+      year: Some(2020),
+      duration: 180000,
+      bitrate: Some(320),
+      comment: None,
+      bpm: Some(120),
+      initial_key: Some("8A".to_string()),
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: Some(1234567890),
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_merge_track_3way_only_traktor_changed_takes_traktor() {
+    let base = base_track();
+    let harmony = base_track(); // Untouched since last sync
+    let mut traktor = base_track();
+    traktor.genre = Some("Techno".to_string());
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.genre, Some("Techno".to_string()));
+    assert!(result.fields_updated.contains(&"genre".to_string()));
+    assert!(result.conflicts.is_empty());
+    assert!(result.has_changes);
+  }
+
+  #[test]
+  fn test_merge_track_3way_only_harmony_changed_keeps_harmony() {
+    let base = base_track();
+    let mut harmony = base_track();
+    harmony.title = "User Edited Title".to_string();
+    let traktor = base_track(); // Traktor never re-scanned, still at base
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.title, "User Edited Title");
+    assert!(!result.fields_updated.contains(&"title".to_string()));
+    assert!(result.conflicts.is_empty());
+  }
+
+  #[test]
+  fn test_merge_track_3way_neither_changed_keeps_as_is() {
+    let base = base_track();
+    let harmony = base_track();
+    let traktor = base_track();
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::SmartMerge);
+
+    assert!(!result.has_changes);
+    assert!(result.conflicts.is_empty());
+    assert_eq!(result.merged.title, base.title);
+  }
+
+  #[test]
+  fn test_merge_track_3way_both_changed_same_value_no_conflict() {
+    let base = base_track();
+    let mut harmony = base_track();
+    harmony.bpm = Some(128);
+    let mut traktor = base_track();
+    traktor.bpm = Some(128);
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.bpm, Some(128));
+    assert!(result.conflicts.is_empty());
+  }
+
+  #[test]
+  fn test_merge_track_3way_true_conflict_smart_merge_keeps_harmony_and_records_conflict() {
+    let base = base_track();
+    let mut harmony = base_track();
+    harmony.initial_key = Some("9A".to_string());
+    let mut traktor = base_track();
+    traktor.initial_key = Some("10A".to_string());
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.initial_key, Some("9A".to_string()));
+    assert!(!result.fields_updated.contains(&"initial_key".to_string()));
+    assert_eq!(result.conflicts.len(), 1);
+    let conflict = &result.conflicts[0];
+    assert_eq!(conflict.field, "initial_key");
+    assert!(conflict.base.contains("8A"));
+    assert!(conflict.harmony.contains("9A"));
+    assert!(conflict.traktor.contains("10A"));
+  }
+
+  #[test]
+  fn test_merge_track_3way_true_conflict_traktor_wins() {
+    let base = base_track();
+    let mut harmony = base_track();
+    harmony.comment = Some("Harmony note".to_string());
+    let mut traktor = base_track();
+    traktor.comment = Some("Traktor note".to_string());
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::TraktorWins);
+
+    assert_eq!(result.merged.comment, Some("Traktor note".to_string()));
+    assert!(result.fields_updated.contains(&"comment".to_string()));
+    assert!(result.conflicts.is_empty());
+  }
+
+  #[test]
+  fn test_merge_track_3way_true_conflict_harmony_wins() {
+    let base = base_track();
+    let mut harmony = base_track();
+    harmony.label = Some("Harmony Label".to_string());
+    let mut traktor = base_track();
+    traktor.label = Some("Traktor Label".to_string());
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::HarmonyWins);
+
+    assert_eq!(result.merged.label, Some("Harmony Label".to_string()));
+    assert!(result.fields_updated.is_empty());
+    assert!(result.conflicts.is_empty());
+  }
+
+  #[test]
+  fn test_merge_track_3way_empty_traktor_value_never_counts_as_a_change() {
+    let base = base_track();
+    let harmony = base_track();
+    let mut traktor = base_track();
+    traktor.catalog_number = None; // Traktor never carries this field
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::SmartMerge);
+
+    assert!(!result.has_changes);
+    assert!(result.conflicts.is_empty());
+  }
+
+  #[test]
+  fn test_merge_track_3way_only_traktor_changed_album_date_takes_traktor() {
+    let base = base_track();
+    let harmony = base_track(); // Untouched since last sync
+    let mut traktor = base_track();
+    traktor.album_date = Some(AlbumDate { year: 2021, month: Some(5), day: Some(14) });
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.album_date, traktor.album_date);
+    assert!(result.fields_updated.contains(&"album_date".to_string()));
+    assert!(result.conflicts.is_empty());
+  }
+
+  #[test]
+  fn test_merge_track_3way_album_date_true_conflict_smart_merge_keeps_harmony_and_records_conflict() {
+    let base = base_track();
+    let mut harmony = base_track();
+    harmony.album_date = Some(AlbumDate { year: 2019, month: None, day: None });
+    let mut traktor = base_track();
+    traktor.album_date = Some(AlbumDate { year: 2021, month: None, day: None });
+
+    let result = merge_track_3way(&harmony, &traktor, &base, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.album_date, harmony.album_date);
+    assert!(!result.fields_updated.contains(&"album_date".to_string()));
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].field, "album_date");
+  }
+
+  #[test]
+  fn test_merge_track_with_policy_mixed_strategies_per_field() {
+    let harmony = Track {
+      rating: Some(TrackRating { source: Some("harmony".to_string()), rating: 3 }),
+      comment: Some("Harmony note".to_string()),
+      ..base_track()
+    };
+    let traktor = Track {
+      bpm: Some(140),
+      initial_key: Some("10A".to_string()),
+      rating: Some(TrackRating { source: Some("traktor".to_string()), rating: 5 }),
+      comment: Some("Traktor note".to_string()),
+      genre: Some("Techno".to_string()),
+      ..base_track()
+    };
+
+    let mut policy: FieldMergePolicy = HashMap::new();
+    policy.insert("bpm".to_string(), MergeStrategy::TraktorWins);
+    policy.insert("initial_key".to_string(), MergeStrategy::TraktorWins);
+    policy.insert("rating".to_string(), MergeStrategy::HarmonyWins);
+    policy.insert("comment".to_string(), MergeStrategy::HarmonyWins);
+    // "genre" deliberately absent -> defaults to SmartMerge, and since
+    // Harmony's genre clock is newer than Traktor's stamp it should NOT be
+    // overwritten (TraktorWins would overwrite it).
+
+    let mut harmony_clock = FieldClock::new();
+    harmony_clock.insert(
+      "genre".to_string(),
+      FieldStamp { updated_at: 5_000, source: SourcePriority::Harmony },
+    );
+    let traktor_stamp = FieldStamp { updated_at: 1_000, source: SourcePriority::Traktor };
+
+    let result = merge_track_with_policy(&harmony, &harmony_clock, &traktor, traktor_stamp, &policy);
+
+    assert_eq!(result.merged.bpm, Some(140));
+    assert_eq!(result.merged.initial_key, Some("10A".to_string()));
+    assert_eq!(result.merged.rating.unwrap().rating, 3);
+    assert_eq!(result.merged.comment, Some("Harmony note".to_string()));
+    assert_eq!(result.merged.genre, base_track().genre);
+
+    assert!(result.fields_updated.contains(&"bpm".to_string()));
+    assert!(result.fields_updated.contains(&"initial_key".to_string()));
+    assert!(!result.fields_updated.contains(&"rating".to_string()));
+    assert!(!result.fields_updated.contains(&"comment".to_string()));
+  }
+
+  #[test]
+  fn test_merge_track_with_policy_equivalent_to_global_strategy_presets() {
+    let harmony = base_track();
+    let mut traktor = base_track();
+    traktor.genre = Some("Techno".to_string());
+    traktor.bpm = Some(140);
+
+    let harmony_clock = FieldClock::new();
+    let traktor_stamp = FieldStamp { updated_at: 1_000, source: SourcePriority::Traktor };
+
+    let via_global = merge_track(&harmony, &harmony_clock, &traktor, traktor_stamp, MergeStrategy::TraktorWins);
+    let via_policy = merge_track_with_policy(
+      &harmony,
+      &harmony_clock,
+      &traktor,
+      traktor_stamp,
+      &expand_strategy_to_policy(MergeStrategy::TraktorWins),
+    );
+
+    assert_eq!(via_global.merged.genre, via_policy.merged.genre);
+    assert_eq!(via_global.merged.bpm, via_policy.merged.bpm);
+    assert_eq!(via_global.fields_updated.len(), via_policy.fields_updated.len());
+  }
+
+  fn cue_at(id: &str, cue_type: CueType, position_ms: f64, name: Option<&str>, color: Option<&str>) -> CuePoint {
+    CuePoint {
+      id: id.to_string(),
+      track_id: "track-1".to_string(),
+      cue_type,
+      position_ms,
+      length_ms: None,
+      hotcue_slot: None,
+      name: name.map(|s| s.to_string()),
+      color: color.map(|s| s.to_string()),
+      grid_bpm: None,
+      order: None,
+      updated_at: 0,
+      deleted: false,
+    }
+  }
+
+  #[test]
+  fn test_combine_matches_by_slot_and_fills_empty_name() {
+    let mut harmony_cues = vec![hotcue("h1", 1, "placeholder", 0)];
+    harmony_cues[0].name = None;
+    let traktor_cues = vec![hotcue("t1", 1, "Drop", 0)];
+
+    let result = merge_cue_points(&harmony_cues, &traktor_cues, "track-1", CueMergeStrategy::Combine);
+
+    assert_eq!(result.merged.len(), 1);
+    assert_eq!(result.merged[0].name, Some("Drop".to_string()));
+    assert_eq!(result.updated, 1);
+    assert_eq!(result.added, 0);
+    assert_eq!(result.removed, 0);
+  }
+
+  #[test]
+  fn test_combine_never_overwrites_existing_harmony_name() {
+    let harmony_cues = vec![hotcue("h1", 1, "Mine", 0)];
+    let traktor_cues = vec![hotcue("t1", 1, "Theirs", 0)];
+
+    let result = merge_cue_points(&harmony_cues, &traktor_cues, "track-1", CueMergeStrategy::Combine);
+
+    assert_eq!(result.merged[0].name, Some("Mine".to_string()));
+    assert_eq!(result.updated, 0);
+  }
+
+  #[test]
+  fn test_combine_matches_slot_less_cues_by_position_tolerance() {
+    let harmony_cues = vec![cue_at("h1", CueType::FadeIn, 1000.0, None, None)];
+    let traktor_cues = vec![cue_at("t1", CueType::FadeIn, 1030.0, None, Some("#ff0000"))];
+
+    let result = merge_cue_points(&harmony_cues, &traktor_cues, "track-1", CueMergeStrategy::Combine);
+
+    assert_eq!(result.merged.len(), 1);
+    assert_eq!(result.merged[0].color, Some("#ff0000".to_string()));
+    assert_eq!(result.updated, 1);
+  }
+
+  #[test]
+  fn test_combine_appends_unmatched_traktor_cue_and_resorts_by_position() {
+    let harmony_cues = vec![hotcue("h1", 1, "Intro", 0)];
+    let traktor_cues = vec![cue_at("t1", CueType::FadeOut, 99_000.0, Some("Outro"), None)];
+
+    let result = merge_cue_points(&harmony_cues, &traktor_cues, "track-1", CueMergeStrategy::Combine);
+
+    assert_eq!(result.merged.len(), 2);
+    assert_eq!(result.added, 1);
+    assert_eq!(result.removed, 0);
+    // Re-sorted by position, Outro (99_000ms) should come after Intro (1000ms)
+    assert_eq!(result.merged.last().unwrap().name, Some("Outro".to_string()));
+    assert_eq!(result.merged.iter().map(|c| c.order).collect::<Vec<_>>(), vec![Some(1), Some(2)]);
+  }
+
+  #[test]
+  fn test_combine_moves_unmatched_traktor_hotcue_into_free_slot_on_conflict() {
+    let harmony_cues = vec![hotcue("h1", 1, "Intro", 0)];
+    // Different position so it doesn't match h1's slot-1 cue, but still
+    // wants slot 1 for itself.
+    let mut conflicting = hotcue("t1", 1, "Also Slot 1", 0);
+    conflicting.position_ms = 50_000.0;
+    let traktor_cues = vec![conflicting];
+
+    let result = merge_cue_points(&harmony_cues, &traktor_cues, "track-1", CueMergeStrategy::Combine);
+
+    assert_eq!(result.added, 1);
+    let appended = result.merged.iter().find(|c| c.name.as_deref() == Some("Also Slot 1")).unwrap();
+    assert_ne!(appended.hotcue_slot, Some(1));
+  }
+
+  #[test]
+  fn test_merge_track_album_date_fills_completely_empty_harmony_date() {
+    let harmony = base_track();
+    let mut traktor = base_track();
+    traktor.album_date = Some(AlbumDate { year: 2021, month: Some(6), day: None });
+
+    let harmony_clock = FieldClock::new();
+    let traktor_stamp = FieldStamp { updated_at: 1_000, source: SourcePriority::Traktor };
+
+    let result = merge_track(&harmony, &harmony_clock, &traktor, traktor_stamp, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.album_date, traktor.album_date);
+    assert!(result.fields_updated.contains(&"album_date".to_string()));
+  }
+
+  #[test]
+  fn test_merge_track_album_date_fills_in_month_and_day_for_same_year() {
+    let mut harmony = base_track();
+    harmony.album_date = Some(AlbumDate { year: 2021, month: None, day: None });
+    let mut traktor = base_track();
+    traktor.album_date = Some(AlbumDate { year: 2021, month: Some(6), day: Some(12) });
+
+    // A clock that would normally keep Harmony's value (Traktor's stamp is
+    // not newer) - specificity still wins because it's not a timestamp race.
+    let mut harmony_clock = FieldClock::new();
+    harmony_clock.insert(
+      "album_date".to_string(),
+      FieldStamp { updated_at: 10_000, source: SourcePriority::Harmony },
+    );
+    let traktor_stamp = FieldStamp { updated_at: 1_000, source: SourcePriority::Traktor };
+
+    let result = merge_track(&harmony, &harmony_clock, &traktor, traktor_stamp, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.album_date, traktor.album_date);
+    assert!(result.fields_updated.contains(&"album_date".to_string()));
+
+    // The specificity fill didn't bump the clock - a genuinely newer but
+    // less specific Traktor edit still can't clobber it afterwards.
+    assert_eq!(
+      result.merged_clock.get("album_date").copied().map(|s| s.updated_at),
+      Some(10_000)
+    );
+  }
+
+  #[test]
+  fn test_merge_track_album_date_different_years_is_a_genuine_conflict_resolved_by_clock() {
+    let mut harmony = base_track();
+    harmony.album_date = Some(AlbumDate { year: 2020, month: None, day: None });
+    let mut traktor = base_track();
+    traktor.album_date = Some(AlbumDate { year: 2021, month: Some(6), day: Some(12) });
+
+    // Different years aren't "more specific" - it's a genuine conflict,
+    // resolved by the normal LWW clock rather than the specificity shortcut.
+    // A Harmony edit newer than Traktor's stamp keeps Harmony's date.
+    let mut harmony_clock = FieldClock::new();
+    harmony_clock.insert(
+      "album_date".to_string(),
+      FieldStamp { updated_at: 5_000, source: SourcePriority::Harmony },
+    );
+    let traktor_stamp = FieldStamp { updated_at: 1_000, source: SourcePriority::Traktor };
+
+    let result = merge_track(&harmony, &harmony_clock, &traktor, traktor_stamp, MergeStrategy::SmartMerge);
+
+    assert_eq!(result.merged.album_date, harmony.album_date);
+  }
+
+  #[test]
+  fn test_merge_track_with_policy_album_date_respects_harmony_wins() {
+    let mut harmony = base_track();
+    harmony.album_date = Some(AlbumDate { year: 2021, month: None, day: None });
+    let mut traktor = base_track();
+    traktor.album_date = Some(AlbumDate { year: 2021, month: Some(6), day: Some(12) });
+
+    let mut policy: FieldMergePolicy = HashMap::new();
+    policy.insert("album_date".to_string(), MergeStrategy::HarmonyWins);
+
+    let harmony_clock = FieldClock::new();
+    let traktor_stamp = FieldStamp { updated_at: 1_000, source: SourcePriority::Traktor };
+
+    let result = merge_track_with_policy(&harmony, &harmony_clock, &traktor, traktor_stamp, &policy);
+
+    assert_eq!(result.merged.album_date, harmony.album_date);
+    assert!(!result.fields_updated.contains(&"album_date".to_string()));
+  }
 }
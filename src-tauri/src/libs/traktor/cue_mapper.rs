@@ -53,7 +53,6 @@ pub fn map_traktor_cue_type(type_str: &str) -> CueType {
 ///
 /// # Returns
 /// Traktor TYPE value
-#[allow(dead_code)]
 pub fn map_harmony_cue_type(cue_type: CueType) -> String {
   match cue_type {
     CueType::HotCue => "0".to_string(),
@@ -67,14 +66,16 @@ pub fn map_harmony_cue_type(cue_type: CueType) -> String {
 
 /// Generate a deterministic unique ID for a cue point.
 ///
-/// AIDEV-NOTE: Uses hash of trackId + position + type + hotcueSlot
-/// This ensures uniqueness even when multiple cues exist at the same position
+/// AIDEV-NOTE: Uses hash of trackId + position + type + hotcueSlot + gridBpm
+/// This ensures uniqueness even when multiple cues exist at the same position,
+/// including two grid markers at the same position with different tempos.
 ///
 /// # Arguments
 /// * `track_id` - Parent track ID
 /// * `position_ms` - Position in milliseconds
 /// * `cue_type` - Cue type
 /// * `hotcue_slot` - Optional hotcue slot number
+/// * `grid_bpm` - Optional beatgrid tempo (only meaningful for `CueType::Grid`)
 ///
 /// # Returns
 /// Unique cue ID (format: "cue-{hash}")
@@ -83,6 +84,7 @@ pub fn generate_cue_id(
   position_ms: f64,
   cue_type: CueType,
   hotcue_slot: Option<i32>,
+  grid_bpm: Option<f64>,
 ) -> String {
   let mut hasher = DefaultHasher::new();
 
@@ -97,6 +99,12 @@ pub fn generate_cue_id(
     "none".hash(&mut hasher);
   }
 
+  if let Some(bpm) = grid_bpm {
+    bpm.to_bits().hash(&mut hasher);
+  } else {
+    "no-grid-bpm".hash(&mut hasher);
+  }
+
   let hash = hasher.finish();
   format!("cue-{:x}", hash) // Hexadecimal hash
 }
@@ -113,10 +121,16 @@ pub fn generate_cue_id(
 /// # Arguments
 /// * `traktor_cue` - Traktor cue data
 /// * `track_id` - Parent track ID
+/// * `modified_at` - Traktor entry's own modification timestamp (ms), used as
+///   this cue's LWW stamp since CUE_V2 carries no per-cue timestamp of its own
 ///
 /// # Returns
 /// Harmony CuePoint
-pub fn map_traktor_cue_to_harmony(traktor_cue: &TraktorCue, track_id: &str) -> CuePoint {
+pub fn map_traktor_cue_to_harmony(
+  traktor_cue: &TraktorCue,
+  track_id: &str,
+  modified_at: i64,
+) -> CuePoint {
   let cue_type = map_traktor_cue_type(&traktor_cue.cue_type);
 
   // Parse position (start field is in milliseconds as string)
@@ -136,8 +150,14 @@ pub fn map_traktor_cue_to_harmony(traktor_cue: &TraktorCue, track_id: &str) -> C
     })
   });
 
+  // Parse grid BPM early for ID generation (only present for TYPE=4)
+  let grid_bpm = traktor_cue
+    .grid
+    .as_ref()
+    .and_then(|grid| grid.bpm.parse::<f64>().ok());
+
   // Generate unique ID
-  let id = generate_cue_id(track_id, position_ms, cue_type, hotcue_slot);
+  let id = generate_cue_id(track_id, position_ms, cue_type, hotcue_slot, grid_bpm);
 
   let mut cue = CuePoint {
     id,
@@ -148,7 +168,10 @@ pub fn map_traktor_cue_to_harmony(traktor_cue: &TraktorCue, track_id: &str) -> C
     hotcue_slot,
     name: None,
     color: None,
+    grid_bpm,
     order: None,
+    updated_at: modified_at,
+    deleted: false,
   };
 
   // Optional: Name (skip "n.n." which is Traktor's placeholder)
@@ -174,17 +197,6 @@ pub fn map_traktor_cue_to_harmony(traktor_cue: &TraktorCue, track_id: &str) -> C
     }
   }
 
-  // CRITICAL: Preserve GRID.BPM for grid cues (TYPE=4)
-  // This is essential for beatgrid precision in Traktor round-trips
-  // Note: Harmony CuePoint doesn't have a gridBpm field yet, so we store it in name
-  // TODO: Add gridBpm field to CuePoint struct if needed
-  if cue_type == CueType::Grid {
-    if let Some(grid) = &traktor_cue.grid {
-      // Store BPM in name for now (format: "Grid {bpm}")
-      cue.name = Some(format!("Grid {}", grid.bpm));
-    }
-  }
-
   cue
 }
 
@@ -193,18 +205,21 @@ pub fn map_traktor_cue_to_harmony(traktor_cue: &TraktorCue, track_id: &str) -> C
 /// # Arguments
 /// * `cue_data` - Traktor CUE_V2 (single cue, array, or None)
 /// * `track_id` - Parent track ID
+/// * `modified_at` - Traktor entry's own modification timestamp (ms), applied
+///   to every cue produced (see [`map_traktor_cue_to_harmony`])
 ///
 /// # Returns
 /// Vec of Harmony CuePoints
 pub fn map_traktor_cues_to_harmony(
   cue_data: Option<&Vec<TraktorCue>>,
   track_id: &str,
+  modified_at: i64,
 ) -> Vec<CuePoint> {
   match cue_data {
     None => vec![],
     Some(cues) => cues
       .iter()
-      .map(|cue| map_traktor_cue_to_harmony(cue, track_id))
+      .map(|cue| map_traktor_cue_to_harmony(cue, track_id, modified_at))
       .collect(),
   }
 }
@@ -213,19 +228,28 @@ pub fn map_traktor_cues_to_harmony(
 ///
 /// AIDEV-NOTE: Reverse conversion from Harmony to Traktor
 /// - Formats position with 6 decimal places (Traktor format)
-/// - Preserves grid BPM if stored in name (format: "Grid {bpm}")
+/// - Restores the GRID element from `grid_bpm` for grid cues (TYPE=4)
 ///
 /// # Arguments
 /// * `cue` - Harmony CuePoint
 ///
 /// # Returns
 /// Traktor CUE_V2 data
-#[allow(dead_code)]
 pub fn map_harmony_cue_to_traktor(cue: &CuePoint) -> TraktorCue {
   // Format position with 6 decimal places to match Traktor format
   let format_position = |ms: f64| -> String { format!("{:.6}", ms) };
 
-  let mut traktor = TraktorCue {
+  let grid = if cue.cue_type == CueType::Grid {
+    cue
+      .grid_bpm
+      .map(|bpm| crate::libs::traktor::nml_types::TraktorGrid {
+        bpm: format!("{:.2}", bpm),
+      })
+  } else {
+    None
+  };
+
+  TraktorCue {
     cue_type: map_harmony_cue_type(cue.cue_type),
     start: format_position(cue.position_ms),
     len: cue.length_ms.map(format_position),
@@ -233,22 +257,28 @@ pub fn map_harmony_cue_to_traktor(cue: &CuePoint) -> TraktorCue {
     hotcue: cue.hotcue_slot.map(|s| s.to_string()),
     name: cue.name.clone(),
     displ_order: cue.order.map(|o| o.to_string()),
-    grid: None,
-  };
-
-  // Restore GRID element for grid cues (TYPE=4)
-  // Extract BPM from name if present (format: "Grid {bpm}")
-  if cue.cue_type == CueType::Grid {
-    if let Some(name) = &cue.name {
-      if let Some(bpm_str) = name.strip_prefix("Grid ") {
-        traktor.grid = Some(crate::libs::traktor::nml_types::TraktorGrid {
-          bpm: bpm_str.to_string(),
-        });
-      }
-    }
+    grid,
   }
+}
 
-  traktor
+/// Map a track's Harmony CuePoints to Traktor CUE_V2 entries for write-back.
+///
+/// AIDEV-NOTE: Used by `export_traktor_nml`. Tombstoned cues (`deleted: true`)
+/// are dropped rather than serialized - the tombstone only matters for the
+/// LWW merge in `conflict_resolver`, Traktor itself has no concept of a
+/// "deleted" cue.
+///
+/// # Arguments
+/// * `cues` - Harmony cue points for one track
+///
+/// # Returns
+/// Vec of Traktor CUE_V2 entries
+pub fn map_harmony_cues_to_traktor(cues: &[CuePoint]) -> Vec<TraktorCue> {
+  cues
+    .iter()
+    .filter(|cue| !cue.deleted)
+    .map(map_harmony_cue_to_traktor)
+    .collect()
 }
 
 #[cfg(test)]
@@ -278,11 +308,11 @@ mod tests {
 
   #[test]
   fn test_generate_cue_id_deterministic() {
-    let id1 = generate_cue_id("track-1", 1000.0, CueType::HotCue, Some(0));
-    let id2 = generate_cue_id("track-1", 1000.0, CueType::HotCue, Some(0));
+    let id1 = generate_cue_id("track-1", 1000.0, CueType::HotCue, Some(0), None);
+    let id2 = generate_cue_id("track-1", 1000.0, CueType::HotCue, Some(0), None);
     assert_eq!(id1, id2); // Same inputs = same ID
 
-    let id3 = generate_cue_id("track-1", 1000.0, CueType::HotCue, Some(1));
+    let id3 = generate_cue_id("track-1", 1000.0, CueType::HotCue, Some(1), None);
     assert_ne!(id1, id3); // Different hotcue slot = different ID
   }
 
@@ -299,7 +329,7 @@ mod tests {
       grid: None,
     };
 
-    let harmony_cue = map_traktor_cue_to_harmony(&traktor_cue, "track-123");
+    let harmony_cue = map_traktor_cue_to_harmony(&traktor_cue, "track-123", 0);
 
     assert_eq!(harmony_cue.track_id, "track-123");
     assert_eq!(harmony_cue.cue_type, CueType::HotCue);
@@ -323,7 +353,7 @@ mod tests {
       grid: None,
     };
 
-    let harmony_cue = map_traktor_cue_to_harmony(&traktor_cue, "track-456");
+    let harmony_cue = map_traktor_cue_to_harmony(&traktor_cue, "track-456", 0);
 
     assert_eq!(harmony_cue.cue_type, CueType::Loop);
     assert_eq!(harmony_cue.position_ms, 30000.0);
@@ -346,17 +376,25 @@ mod tests {
       }),
     };
 
-    let harmony_cue = map_traktor_cue_to_harmony(&traktor_cue, "track-789");
+    let harmony_cue = map_traktor_cue_to_harmony(&traktor_cue, "track-789", 0);
 
     assert_eq!(harmony_cue.cue_type, CueType::Grid);
     assert_eq!(harmony_cue.position_ms, 0.0);
-    // BPM stored in name
-    assert_eq!(harmony_cue.name, Some("Grid 128.00".to_string()));
+    assert_eq!(harmony_cue.grid_bpm, Some(128.0));
+    // Name is independent of grid BPM
+    assert_eq!(harmony_cue.name, Some("AutoGrid".to_string()));
+  }
+
+  #[test]
+  fn test_generate_cue_id_distinguishes_grid_bpm() {
+    let id1 = generate_cue_id("track-1", 0.0, CueType::Grid, None, Some(128.0));
+    let id2 = generate_cue_id("track-1", 0.0, CueType::Grid, None, Some(140.0));
+    assert_ne!(id1, id2); // Same position, different grid BPM = different ID
   }
 
   #[test]
   fn test_map_traktor_cues_to_harmony_empty() {
-    let cues = map_traktor_cues_to_harmony(None, "track-1");
+    let cues = map_traktor_cues_to_harmony(None, "track-1", 0);
     assert_eq!(cues.len(), 0);
   }
 
@@ -385,7 +423,7 @@ mod tests {
       },
     ];
 
-    let harmony_cues = map_traktor_cues_to_harmony(Some(&traktor_cues), "track-xyz");
+    let harmony_cues = map_traktor_cues_to_harmony(Some(&traktor_cues), "track-xyz", 0);
 
     assert_eq!(harmony_cues.len(), 2);
     assert_eq!(harmony_cues[0].cue_type, CueType::HotCue);
@@ -405,7 +443,10 @@ mod tests {
       hotcue_slot: Some(1),
       name: Some("Build".to_string()),
       color: None,
+      grid_bpm: None,
       order: Some(2),
+      updated_at: 0,
+      deleted: false,
     };
 
     let traktor_cue = map_harmony_cue_to_traktor(&harmony_cue);
@@ -428,7 +469,10 @@ mod tests {
       hotcue_slot: None,
       name: Some("Breakdown".to_string()),
       color: None,
+      grid_bpm: None,
       order: None,
+      updated_at: 0,
+      deleted: false,
     };
 
     let traktor_cue = map_harmony_cue_to_traktor(&harmony_cue);
@@ -448,9 +492,12 @@ mod tests {
       position_ms: 0.0,
       length_ms: None,
       hotcue_slot: None,
-      name: Some("Grid 130.50".to_string()), // BPM encoded in name
+      name: Some("AutoGrid".to_string()),
       color: None,
+      grid_bpm: Some(130.50),
       order: None,
+      updated_at: 0,
+      deleted: false,
     };
 
     let traktor_cue = map_harmony_cue_to_traktor(&harmony_cue);
@@ -475,7 +522,7 @@ mod tests {
       grid: None,
     };
 
-    let harmony = map_traktor_cue_to_harmony(&original_traktor, "track-roundtrip");
+    let harmony = map_traktor_cue_to_harmony(&original_traktor, "track-roundtrip", 0);
     let back_to_traktor = map_harmony_cue_to_traktor(&harmony);
 
     assert_eq!(back_to_traktor.cue_type, "0");
@@ -487,4 +534,28 @@ mod tests {
     let roundtrip_pos: f64 = back_to_traktor.start.parse().unwrap();
     assert!((original_pos - roundtrip_pos).abs() < 0.001);
   }
+
+  #[test]
+  fn test_map_harmony_cues_to_traktor_drops_tombstones() {
+    let live = CuePoint {
+      id: "cue-1".to_string(),
+      track_id: "track-1".to_string(),
+      cue_type: CueType::HotCue,
+      position_ms: 1000.0,
+      length_ms: None,
+      hotcue_slot: Some(0),
+      name: Some("Drop".to_string()),
+      color: None,
+      grid_bpm: None,
+      order: None,
+      updated_at: 0,
+      deleted: false,
+    };
+    let mut tombstoned = live.clone();
+    tombstoned.deleted = true;
+
+    let traktor_cues = map_harmony_cues_to_traktor(&[live, tombstoned]);
+
+    assert_eq!(traktor_cues.len(), 1);
+  }
 }
@@ -0,0 +1,212 @@
+// AIDEV-NOTE: Traktor musical-key notation converter
+// `INFO KEY` in Traktor's NML can hold any of three representations of the
+// same 24 musical keys: plain musical notation ("Am", "F#"), Open Key
+// ("1m".."12m"/"1d".."12d"), or a numeric index (0-23). `mapper::
+// map_traktor_entry_to_track` used to copy the raw string through verbatim,
+// so a key imported from NML rarely matched the same key written by the
+// filesystem scanner (which always sees plain/Camelot notation - see
+// `audio_metadata::is_valid_key_notation`). This module normalizes all three
+// input forms to a single semitone + major/minor pair, then formats that
+// pair in a caller-chosen canonical notation.
+
+use phf::phf_map;
+
+/// The notation `map_traktor_key_as` renders its output in. Camelot is the
+/// default (matches the notation `is_valid_key_notation` documents as
+/// Harmony's own canonical form, e.g. "8A").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyNotation {
+  Camelot,
+  OpenKey,
+  Classical,
+}
+
+/// Note-name aliases (including common enharmonic spellings) to a semitone
+/// index, 0 = C .. 11 = B, ascending chromatically.
+static NOTE_TO_SEMITONE: phf::Map<&'static str, u8> = phf_map! {
+  "C" => 0,
+  "C#" => 1, "DB" => 1,
+  "D" => 2,
+  "D#" => 3, "EB" => 3,
+  "E" => 4,
+  "F" => 5,
+  "F#" => 6, "GB" => 6,
+  "G" => 7,
+  "G#" => 8, "AB" => 8,
+  "A" => 9,
+  "A#" => 10, "BB" => 10,
+  "B" => 11,
+};
+
+/// Canonical "Classical" output spelling for a major key, by semitone -
+/// flats preferred except `F#` (matches the enharmonic convention the
+/// Camelot wheel itself uses for majors).
+const MAJOR_NAMES: [&str; 12] = ["C", "Db", "D", "Eb", "E", "F", "F#", "G", "Ab", "A", "Bb", "B"];
+
+/// Canonical "Classical" output spelling for a minor key, by semitone (its
+/// root note) - sharps preferred except `Bb` (matches the Camelot wheel's
+/// convention for minors, e.g. camelot 11A = "F#m" but camelot 3A = "Bbm").
+const MINOR_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "Bb", "B"];
+
+/// Open Key wheel position (1-12) for the major key rooted at `semitone`,
+/// derived from the circle-of-fifths ordering: 7 is its own inverse mod 12,
+/// so this formula is symmetric with `wheel_number_to_major_semitone`.
+fn major_semitone_to_wheel_number(semitone: u8) -> u8 {
+  (semitone as u32 * 7 % 12) as u8 + 1
+}
+
+/// Inverse of `major_semitone_to_wheel_number`.
+fn wheel_number_to_major_semitone(number: u8) -> u8 {
+  ((number as u32 - 1) * 7 % 12) as u8
+}
+
+/// Camelot wheel position (1-12) for the major key rooted at `semitone`.
+/// Camelot numbers are Open Key numbers rotated by a constant +7 (mod 12) -
+/// e.g. Open Key "1d" (C major) is Camelot "8B", not "1B".
+fn major_semitone_to_camelot_number(semitone: u8) -> u8 {
+  let open_key_number = major_semitone_to_wheel_number(semitone);
+  ((open_key_number as u32 + 6) % 12) as u8 + 1
+}
+
+/// Root semitone of a minor key's relative major (a minor third above).
+fn relative_major_semitone(minor_semitone: u8) -> u8 {
+  (minor_semitone + 3) % 12
+}
+
+/// Root semitone of a major key's relative minor (a minor third below).
+fn relative_minor_semitone(major_semitone: u8) -> u8 {
+  (major_semitone + 12 - 3) % 12
+}
+
+/// Parse a raw Traktor `INFO KEY` string into (root semitone, is_minor).
+fn parse_traktor_key(raw: &str) -> Option<(u8, bool)> {
+  let raw = raw.trim();
+  if raw.is_empty() {
+    return None;
+  }
+
+  if let Ok(index) = raw.parse::<u32>() {
+    return (index <= 23).then(|| ((index / 2) as u8, index % 2 == 1));
+  }
+
+  let upper = raw.to_uppercase();
+  if let Some(suffix) = upper.strip_suffix('D').or_else(|| upper.strip_suffix('M')) {
+    if let Ok(number) = suffix.parse::<u8>() {
+      if (1..=12).contains(&number) {
+        let is_minor = upper.ends_with('M');
+        let major_semitone = wheel_number_to_major_semitone(number);
+        let semitone = if is_minor { relative_minor_semitone(major_semitone) } else { major_semitone };
+        return Some((semitone, is_minor));
+      }
+    }
+  }
+
+  let (note_part, is_minor) = match upper.strip_suffix('M') {
+    Some(rest) if !rest.is_empty() => (rest, true),
+    _ => (upper.as_str(), false),
+  };
+  let semitone = *NOTE_TO_SEMITONE.get(note_part)?;
+  Some((semitone, is_minor))
+}
+
+/// Format (root semitone, is_minor) in `notation`.
+fn format_key(semitone: u8, is_minor: bool, notation: KeyNotation) -> String {
+  match notation {
+    KeyNotation::Camelot => {
+      let major_semitone = if is_minor { relative_major_semitone(semitone) } else { semitone };
+      let number = major_semitone_to_camelot_number(major_semitone);
+      format!("{}{}", number, if is_minor { "A" } else { "B" })
+    }
+    KeyNotation::OpenKey => {
+      let major_semitone = if is_minor { relative_major_semitone(semitone) } else { semitone };
+      let number = major_semitone_to_wheel_number(major_semitone);
+      format!("{}{}", number, if is_minor { "m" } else { "d" })
+    }
+    KeyNotation::Classical => {
+      if is_minor {
+        format!("{}m", MINOR_NAMES[semitone as usize])
+      } else {
+        MAJOR_NAMES[semitone as usize].to_string()
+      }
+    }
+  }
+}
+
+/// Convert a Traktor `INFO KEY` value (plain musical notation, Open Key, or
+/// numeric 0-23) into `notation`. Returns `None` if `key` is absent or
+/// doesn't match any recognized form, so callers can fall back to leaving
+/// the field unset rather than writing a garbage string.
+pub fn map_traktor_key_as(key: Option<&str>, notation: KeyNotation) -> Option<String> {
+  let (semitone, is_minor) = parse_traktor_key(key?)?;
+  Some(format_key(semitone, is_minor, notation))
+}
+
+/// Convert a Traktor `INFO KEY` value to Harmony's canonical Camelot
+/// notation - see `map_traktor_key_as` to target a different notation.
+pub fn map_traktor_key(key: Option<&str>) -> Option<String> {
+  map_traktor_key_as(key, KeyNotation::Camelot)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn musical_notation_maps_to_camelot() {
+    assert_eq!(map_traktor_key(Some("Am")), Some("8A".to_string()));
+    assert_eq!(map_traktor_key(Some("C")), Some("8B".to_string()));
+    assert_eq!(map_traktor_key(Some("F#")), Some("2B".to_string()));
+  }
+
+  #[test]
+  fn open_key_maps_to_camelot() {
+    assert_eq!(map_traktor_key(Some("1m")), Some("8A".to_string()));
+    assert_eq!(map_traktor_key(Some("1d")), Some("8B".to_string()));
+    assert_eq!(map_traktor_key(Some("8m")), Some("3A".to_string()));
+  }
+
+  #[test]
+  fn numeric_index_maps_to_camelot() {
+    // 0 = C major, 1 = C minor, ... even = major, odd = minor.
+    assert_eq!(map_traktor_key(Some("0")), Some("8B".to_string()));
+    assert_eq!(map_traktor_key(Some("1")), Some("5A".to_string()));
+    assert_eq!(map_traktor_key(Some("23")), Some("10A".to_string()));
+  }
+
+  #[test]
+  fn enharmonic_edge_cases_round_trip() {
+    // Db and C# are the same pitch and must normalize identically.
+    assert_eq!(map_traktor_key(Some("Db")), map_traktor_key(Some("C#")));
+    assert_eq!(map_traktor_key(Some("Gb")), map_traktor_key(Some("F#")));
+    assert_eq!(map_traktor_key_as(Some("C#"), KeyNotation::Classical), Some("Db".to_string()));
+  }
+
+  #[test]
+  fn all_three_representations_of_the_same_key_agree() {
+    // Camelot 3A = Bb minor = Open Key "8m" = numeric index for Bb minor.
+    let via_musical = map_traktor_key(Some("Bbm"));
+    let via_open_key = map_traktor_key(Some("8m"));
+    assert_eq!(via_musical, via_open_key);
+    assert_eq!(via_musical, Some("3A".to_string()));
+  }
+
+  #[test]
+  fn classical_notation_round_trips_through_open_key() {
+    for semitone in 0..12u8 {
+      for is_minor in [false, true] {
+        let open_key = format_key(semitone, is_minor, KeyNotation::OpenKey);
+        let camelot = map_traktor_key_as(Some(&open_key), KeyNotation::Camelot).unwrap();
+        let classical = map_traktor_key_as(Some(&open_key), KeyNotation::Classical).unwrap();
+        let classical_as_camelot = map_traktor_key_as(Some(&classical), KeyNotation::Camelot).unwrap();
+        assert_eq!(camelot, classical_as_camelot);
+      }
+    }
+  }
+
+  #[test]
+  fn unrecognized_or_missing_key_returns_none() {
+    assert_eq!(map_traktor_key(None), None);
+    assert_eq!(map_traktor_key(Some("")), None);
+    assert_eq!(map_traktor_key(Some("not a key")), None);
+  }
+}
@@ -0,0 +1,167 @@
+// AIDEV-NOTE: `DjLibrary` implementor for Traktor NML.
+// Wraps an already-parsed `TraktorNML` tree so callers that only care about
+// "the tracks/playlists in this library" (or writing a new track list back
+// out) can work through `DjLibrary` instead of reaching into
+// `TraktorNMLParser`/`mapper`/`playlist_sync` directly. Those lower-level
+// pieces are unchanged and still used directly by `commands::traktor` for
+// the richer sync/merge flows that need more than the trait exposes.
+
+use std::path::{Path, PathBuf};
+
+use crate::libs::dj_library::DjLibrary;
+use crate::libs::playlist::Playlist;
+use crate::libs::track::Track;
+use crate::libs::Result;
+
+use super::mapper::{map_traktor_entry_to_track, map_track_to_traktor_entry};
+use super::nml_types::{TraktorEntry, TraktorNML};
+use super::playlist_sync::{convert_to_harmony_playlist, extract_playlists_from_traktor};
+use super::nml_parser::TraktorNMLParser;
+use super::nml_writer::TraktorNMLWriter;
+
+/// A parsed Traktor `collection.nml`, normalized through [`DjLibrary`].
+pub struct TraktorLibrary {
+  nml: TraktorNML,
+  source_path: PathBuf,
+}
+
+impl TraktorLibrary {
+  /// Parse a `collection.nml` file into a `TraktorLibrary`. `export` writes
+  /// back to this same path.
+  pub fn load<P: AsRef<Path>>(file_path: P) -> Result<Self> {
+    let nml = TraktorNMLParser::new().parse(&file_path)?;
+    Ok(Self { nml, source_path: file_path.as_ref().to_path_buf() })
+  }
+}
+
+impl DjLibrary for TraktorLibrary {
+  fn tracks(&self) -> Vec<Track> {
+    self.nml.nml.collection.entry.iter().map(map_traktor_entry_to_track).collect()
+  }
+
+  fn playlists(&self) -> Vec<Playlist> {
+    match &self.nml.nml.playlists {
+      Some(playlists) => extract_playlists_from_traktor(&playlists.node)
+        .iter()
+        .map(convert_to_harmony_playlist)
+        .collect(),
+      None => Vec::new(),
+    }
+  }
+
+  /// Rebuild `COLLECTION/ENTRY` from `tracks` and write the result back to
+  /// the path this library was loaded from.
+  ///
+  /// AIDEV-NOTE: Matches each track to its previous entry by path (if any)
+  /// so fields Harmony doesn't model (audio fingerprint, loudness analysis,
+  /// unknown attributes, ...) survive the round trip - same approach as
+  /// `commands::traktor::export_traktor_nml`, just without that command's
+  /// separate `source_nml_path`/cue-point/playlist plumbing.
+  fn export(&self, tracks: &[Track]) -> Result<()> {
+    let seed_by_path: std::collections::HashMap<String, &TraktorEntry> = self
+      .nml
+      .nml
+      .collection
+      .entry
+      .iter()
+      .map(|entry| (map_traktor_entry_to_track(entry).path, entry))
+      .collect();
+
+    let exported_at_ms = chrono::Utc::now().timestamp_millis();
+    let entries: Vec<TraktorEntry> = tracks
+      .iter()
+      .map(|track| map_track_to_traktor_entry(track, seed_by_path.get(&track.path).copied(), exported_at_ms))
+      .collect();
+
+    let mut nml = self.nml.clone();
+    nml.nml.collection.entry = entries;
+
+    TraktorNMLWriter::new().write(&nml, &self.source_path)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::libs::traktor::nml_types::{TraktorCollection, TraktorHead, TraktorLocation, NML};
+
+  fn sample_entry(title: &str, file: &str) -> TraktorEntry {
+    TraktorEntry {
+      modified_date: None,
+      modified_time: None,
+      audio_id: None,
+      title: Some(title.to_string()),
+      artist: None,
+      location: TraktorLocation {
+        dir: "/:Music/:".to_string(),
+        file: file.to_string(),
+        volume: None,
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: None,
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    }
+  }
+
+  fn sample_nml(entries: Vec<TraktorEntry>) -> TraktorNML {
+    TraktorNML {
+      nml: NML {
+        version: "19".to_string(),
+        head: TraktorHead {
+          company: "www.native-instruments.com".to_string(),
+          program: "Traktor".to_string(),
+        },
+        collection: TraktorCollection { entries: entries.len().to_string(), entry: entries },
+        playlists: None,
+        indexing: None,
+      },
+    }
+  }
+
+  #[test]
+  fn tracks_maps_every_collection_entry() {
+    let library = TraktorLibrary {
+      nml: sample_nml(vec![sample_entry("One", "one.mp3"), sample_entry("Two", "two.mp3")]),
+      source_path: PathBuf::from("/tmp/collection.nml"),
+    };
+
+    let tracks = library.tracks();
+    assert_eq!(tracks.len(), 2);
+    assert_eq!(tracks[0].title, "One");
+    assert_eq!(tracks[1].title, "Two");
+  }
+
+  #[test]
+  fn playlists_is_empty_when_nml_has_no_playlists_node() {
+    let library = TraktorLibrary { nml: sample_nml(vec![]), source_path: PathBuf::from("/tmp/collection.nml") };
+
+    assert!(library.playlists().is_empty());
+  }
+
+  #[test]
+  fn export_preserves_unmodeled_fields_from_the_matching_source_entry() {
+    let mut entry = sample_entry("One", "one.mp3");
+    entry.audio_id = Some("fingerprint-123".to_string());
+
+    let dir = tempfile::tempdir().unwrap();
+    let source_path = dir.path().join("collection.nml");
+    let library = TraktorLibrary { nml: sample_nml(vec![entry]), source_path: source_path.clone() };
+
+    let mut track = library.tracks().into_iter().next().unwrap();
+    track.title = "One (Renamed)".to_string();
+
+    library.export(&[track]).unwrap();
+
+    let reparsed = TraktorNMLParser::new().parse(&source_path).unwrap();
+    assert_eq!(reparsed.nml.collection.entry.len(), 1);
+    assert_eq!(reparsed.nml.collection.entry[0].title, Some("One (Renamed)".to_string()));
+    assert_eq!(reparsed.nml.collection.entry[0].audio_id, Some("fingerprint-123".to_string()));
+  }
+}
@@ -8,11 +8,12 @@
 // - BPM: Traktor stores float, we use rounded integer
 // - Duration: Traktor stores seconds as string, we use milliseconds
 
-use crate::libs::track::{Track, TrackRating};
-use chrono::NaiveDate;
+use crate::libs::track::{AlbumDate, Track, TrackRating};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use std::path::{Path, PathBuf};
 
-use super::nml_types::TraktorEntry;
+use super::key_notation::map_traktor_key;
+use super::nml_types::{TraktorAlbum, TraktorEntry, TraktorInfo, TraktorLocation, TraktorTempo};
 
 /// Convert Traktor path format to system path
 ///
@@ -45,7 +46,6 @@ pub fn map_traktor_path_to_system(dir: &str, file: &str, volume: Option<&str>) -
 ///
 /// Handles both Unix-style and Windows-style paths
 /// Extracts Windows drive letter (e.g., "C:") to VOLUME attribute
-#[allow(dead_code)]
 pub fn map_system_path_to_traktor(system_path: &str) -> (String, String, String) {
   let path = Path::new(system_path);
 
@@ -89,7 +89,6 @@ pub fn map_traktor_rating(ranking: Option<&str>) -> i32 {
 }
 
 /// Convert Harmony rating (0-5) to Traktor rating (0-255)
-#[allow(dead_code)]
 pub fn map_harmony_rating_to_traktor(stars: i32) -> String {
   (stars * 51).to_string()
 }
@@ -118,6 +117,49 @@ pub fn parse_traktor_date(date_str: Option<&str>) -> Option<i64> {
   })
 }
 
+/// Parse a Traktor `@RELEASE_DATE` value into an [`AlbumDate`], keeping
+/// whatever precision the tag actually carried (`"YYYY"`, `"YYYY/M"`, or
+/// `"YYYY/M/D"`) instead of collapsing straight to a bare year. Same
+/// graceful-fallback shape as `album_order::parse_album_date` - a malformed
+/// month or day is dropped rather than rejecting the date outright - just
+/// split on `/` to match Traktor's format instead of the hyphen-separated
+/// one tag readers use.
+pub fn parse_traktor_release_date(date_str: Option<&str>) -> Option<AlbumDate> {
+  let raw = date_str?.trim();
+  if raw.is_empty() {
+    return None;
+  }
+
+  let mut parts = raw.splitn(3, '/');
+  let year = parts.next()?.parse::<i32>().ok()?;
+
+  let month = parts.next().and_then(|m| m.parse::<u32>().ok()).filter(|m| (1..=12).contains(m));
+
+  let day = month.and_then(|_| parts.next().and_then(|d| d.parse::<u32>().ok()).filter(|d| (1..=31).contains(d)));
+
+  Some(AlbumDate { year, month, day })
+}
+
+/// Combine a Traktor entry's `@MODIFIED_DATE` (YYYY/M/D) and `@MODIFIED_TIME`
+/// (seconds since midnight) into a single Unix timestamp in milliseconds.
+///
+/// Used as the Traktor-side `FieldStamp`/cue `updated_at` during a sync, so
+/// the CRDT merge in `conflict_resolver` can compare it against Harmony's
+/// own edit timestamps. Falls back to midnight (time 0) if `MODIFIED_TIME`
+/// is missing or unparsable - a coarser stamp is still correct for LWW, just
+/// less precise within the same day.
+pub fn map_traktor_modified_at(entry: &TraktorEntry) -> Option<i64> {
+  let date_ms = parse_traktor_date(entry.modified_date.as_deref())?;
+
+  let seconds_since_midnight = entry
+    .modified_time
+    .as_deref()
+    .and_then(|t| t.parse::<i64>().ok())
+    .unwrap_or(0);
+
+  Some(date_ms + seconds_since_midnight * 1000)
+}
+
 /// Map a Traktor NML entry to a Harmony Track object
 ///
 /// Uses Track::generate_id() for deterministic ID generation.
@@ -149,15 +191,11 @@ pub fn map_traktor_entry_to_track(entry: &TraktorEntry) -> Track {
     })
     .flatten();
 
-  // Extract year from RELEASE_DATE
-  let year = info
-    .and_then(|i| i.release_date.as_deref())
-    .and_then(|date_str| {
-      date_str
-        .split('/')
-        .next()
-        .and_then(|y| y.parse::<i32>().ok())
-    });
+  // RELEASE_DATE carries as much precision as Traktor recorded it with;
+  // `year` is kept in sync with `album_date.year` for callers still matching
+  // on the bare field (search, fuzzy-duplicate criteria, export formats).
+  let album_date = parse_traktor_release_date(info.and_then(|i| i.release_date.as_deref()));
+  let year = album_date.as_ref().map(|d| d.year);
 
   // Convert bitrate from bps to kbps
   let bitrate = info
@@ -195,15 +233,191 @@ pub fn map_traktor_entry_to_track(entry: &TraktorEntry) -> Track {
     bitrate,
     comment: info.and_then(|i| i.comment.clone()),
     bpm: tempo.and_then(|t| map_traktor_bpm(Some(&t.bpm))),
-    initial_key: info.and_then(|i| i.key.clone()), // TODO: Convert from Traktor key notation
+    initial_key: map_traktor_key(info.and_then(|i| i.key.as_deref())),
     rating,
     label: info.and_then(|i| i.label.clone()),
+    catalog_number: None, // Not stored in Traktor NML
+    isrc: None,           // Not stored in Traktor NML
+    musicbrainz_id: None,
+    release_group_id: None,
     waveform_peaks: None, // Not stored in NML
     added_at,
     url: None,
+    start_ms: None,
+    end_ms: None,
+    chapters: Vec::new(),
+    album_date,
+    track_number: None,
+    album_seq: None, // Harmony-only, no Traktor equivalent
+    artist_sort: None,
+    album_sort: None,
+    title_sort: None,
+    synced_lyrics: Vec::new(),
   }
 }
 
+/// Compute a stable content hash for a Traktor entry, covering the fields
+/// Harmony actually syncs (the `map_traktor_entry_to_track` output) plus its
+/// raw `CUE_V2` list.
+///
+/// AIDEV-NOTE: Used by delta-mode `sync_traktor_nml` to skip merging entries
+/// that haven't changed since the last sync (see `trackSyncHash` in
+/// `libs::database`). Deliberately hashes the *mapped* `Track` fields rather
+/// than the raw entry XML, so Traktor re-writing the file with different
+/// attribute ordering/whitespace (which happens on every save) doesn't look
+/// like a content change. Cues are hashed from the raw `CUE_V2` fields
+/// instead of mapped `CuePoint`s, since mapping assigns a fresh deterministic
+/// id per cue that's already derived from these same fields - hashing the
+/// mapped form would just be a slower way to hash the same inputs.
+pub fn compute_entry_content_hash(entry: &TraktorEntry) -> String {
+  use std::collections::hash_map::DefaultHasher;
+  use std::hash::{Hash, Hasher};
+
+  let track = map_traktor_entry_to_track(entry);
+  let mut hasher = DefaultHasher::new();
+
+  track.title.hash(&mut hasher);
+  track.artist.hash(&mut hasher);
+  track.album.hash(&mut hasher);
+  track.genre.hash(&mut hasher);
+  track.year.hash(&mut hasher);
+  track.duration.hash(&mut hasher);
+  track.bitrate.hash(&mut hasher);
+  track.comment.hash(&mut hasher);
+  track.bpm.hash(&mut hasher);
+  track.initial_key.hash(&mut hasher);
+  track.rating.as_ref().map(|r| (r.rating, r.source.clone())).hash(&mut hasher);
+  track.label.hash(&mut hasher);
+  track.album_date.hash(&mut hasher);
+
+  for cue in &entry.cue_v2 {
+    cue.name.hash(&mut hasher);
+    cue.cue_type.hash(&mut hasher);
+    cue.start.hash(&mut hasher);
+    cue.len.hash(&mut hasher);
+    cue.hotcue.hash(&mut hasher);
+  }
+
+  format!("{:x}", hasher.finish())
+}
+
+/// Format a Unix timestamp (ms) as Traktor's `@MODIFIED_DATE`/`@MODIFIED_TIME`
+/// pair. Inverse of [`map_traktor_modified_at`].
+pub fn format_traktor_modified_at(timestamp_ms: i64) -> (String, String) {
+  let datetime = DateTime::<Utc>::from_timestamp_millis(timestamp_ms).unwrap_or_default();
+  let date = format!(
+    "{}/{}/{}",
+    datetime.year(),
+    datetime.month(),
+    datetime.day()
+  );
+
+  let midnight = datetime.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+  let seconds_since_midnight = (datetime - midnight).num_seconds();
+
+  (date, seconds_since_midnight.to_string())
+}
+
+/// Build a Traktor NML entry from a Harmony track, for [`TraktorNMLWriter`](super::nml_writer::TraktorNMLWriter).
+///
+/// AIDEV-NOTE: Inverse of `map_traktor_entry_to_track`, but not a strict one:
+/// `base` is the entry this same track (matched by path) last had in the
+/// source NML, if any. When present, its unmodeled attributes and fields
+/// Harmony doesn't own (`audio_id`, `loudness`, `musical_key`, `extra_attrs`,
+/// ...) are carried over untouched and only the fields Harmony does own get
+/// overwritten, so round-tripping a file through Harmony without editing it
+/// doesn't lose data Harmony never parsed. `base: None` (no Traktor history
+/// for this track, e.g. it was scanned from disk) builds a fresh entry.
+///
+/// `exported_at_ms` becomes the entry's `MODIFIED_DATE`/`MODIFIED_TIME`, so a
+/// later re-import sees this export as newer than whatever it's based on.
+pub fn map_track_to_traktor_entry(
+  track: &Track,
+  base: Option<&TraktorEntry>,
+  exported_at_ms: i64,
+) -> TraktorEntry {
+  let (dir, file, volume) = map_system_path_to_traktor(&track.path);
+  let mut entry = base.cloned().unwrap_or(TraktorEntry {
+    modified_date: None,
+    modified_time: None,
+    audio_id: None,
+    title: None,
+    artist: None,
+    location: TraktorLocation {
+      dir: dir.clone(),
+      file: file.clone(),
+      volume: None,
+      volumeid: None,
+    },
+    album: None,
+    modification_info: None,
+    info: None,
+    tempo: None,
+    loudness: None,
+    musical_key: None,
+    cue_v2: Vec::new(),
+    primarykey: None,
+    extra_attrs: Default::default(),
+  });
+
+  let (modified_date, modified_time) = format_traktor_modified_at(exported_at_ms);
+  entry.modified_date = Some(modified_date);
+  entry.modified_time = Some(modified_time);
+
+  entry.location = TraktorLocation {
+    dir,
+    file,
+    volume: if volume.is_empty() { None } else { Some(volume) },
+    volumeid: entry.location.volumeid.clone(),
+  };
+
+  entry.title = Some(track.title.clone());
+  entry.artist = track.artist.clone();
+  entry.album = track.album.as_ref().map(|title| TraktorAlbum {
+    title: Some(title.clone()),
+    track: entry.album.as_ref().and_then(|a| a.track.clone()),
+    of_tracks: entry.album.as_ref().and_then(|a| a.of_tracks.clone()),
+  });
+
+  let mut info = entry.info.clone().unwrap_or(TraktorInfo {
+    bitrate: None,
+    genre: None,
+    label: None,
+    comment: None,
+    coverartid: None,
+    key: None,
+    playtime: None,
+    playtime_float: None,
+    ranking: None,
+    import_date: None,
+    release_date: None,
+    last_played: None,
+    playcount: None,
+    flags: None,
+    filesize: None,
+    color: None,
+  });
+  info.genre = track.genre.clone();
+  info.label = track.label.clone();
+  info.comment = track.comment.clone();
+  info.key = track.initial_key.clone();
+  info.bitrate = track.bitrate.map(|kbps| (kbps * 1000).to_string());
+  info.playtime = Some((track.duration / 1000).to_string());
+  info.playtime_float = Some(format!("{:.6}", track.duration as f64 / 1000.0));
+  info.ranking = track
+    .rating
+    .as_ref()
+    .map(|r| map_harmony_rating_to_traktor(r.rating));
+  entry.info = Some(info);
+
+  entry.tempo = track.bpm.map(|bpm| TraktorTempo {
+    bpm: format!("{:.6}", bpm as f64),
+    bpm_quality: entry.tempo.as_ref().and_then(|t| t.bpm_quality.clone()),
+  });
+
+  entry
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -261,6 +475,181 @@ mod tests {
     assert!(ts > 0);
   }
 
+  #[test]
+  fn test_parse_traktor_release_date_keeps_available_precision() {
+    assert_eq!(
+      parse_traktor_release_date(Some("2026/1/15")),
+      Some(AlbumDate { year: 2026, month: Some(1), day: Some(15) })
+    );
+    assert_eq!(
+      parse_traktor_release_date(Some("2026/1")),
+      Some(AlbumDate { year: 2026, month: Some(1), day: None })
+    );
+    assert_eq!(
+      parse_traktor_release_date(Some("2026")),
+      Some(AlbumDate { year: 2026, month: None, day: None })
+    );
+    assert_eq!(parse_traktor_release_date(Some("")), None);
+    assert_eq!(parse_traktor_release_date(None), None);
+  }
+
+  #[test]
+  fn test_map_traktor_entry_to_track_populates_album_date_from_release_date() {
+    let entry = TraktorEntry {
+      modified_date: None,
+      modified_time: None,
+      audio_id: None,
+      title: Some("Test Track".to_string()),
+      artist: Some("Test Artist".to_string()),
+      location: TraktorLocation {
+        dir: "/:Music/:".to_string(),
+        file: "test.mp3".to_string(),
+        volume: None,
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: Some(TraktorInfo {
+        bitrate: None,
+        genre: None,
+        label: None,
+        comment: None,
+        coverartid: None,
+        key: None,
+        playtime: None,
+        playtime_float: None,
+        ranking: None,
+        import_date: None,
+        release_date: Some("2014/9/22".to_string()),
+        last_played: None,
+        playcount: None,
+        flags: None,
+        filesize: None,
+        color: None,
+      }),
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    };
+
+    let track = map_traktor_entry_to_track(&entry);
+
+    assert_eq!(track.album_date, Some(AlbumDate { year: 2014, month: Some(9), day: Some(22) }));
+    assert_eq!(track.year, Some(2014));
+  }
+
+  #[test]
+  fn test_map_traktor_modified_at_combines_date_and_time() {
+    let entry = TraktorEntry {
+      modified_date: Some("2026/1/15".to_string()),
+      modified_time: Some("3600".to_string()), // 1 hour past midnight
+      audio_id: None,
+      title: None,
+      artist: None,
+      location: TraktorLocation {
+        dir: "/:Music/:".to_string(),
+        file: "test.mp3".to_string(),
+        volume: None,
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: None,
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    };
+
+    let date_only = parse_traktor_date(Some("2026/1/15")).unwrap();
+    let modified_at = map_traktor_modified_at(&entry).unwrap();
+
+    assert_eq!(modified_at, date_only + 3600 * 1000);
+  }
+
+  #[test]
+  fn test_map_traktor_modified_at_missing_time_falls_back_to_midnight() {
+    let entry = TraktorEntry {
+      modified_date: Some("2026/1/15".to_string()),
+      modified_time: None,
+      audio_id: None,
+      title: None,
+      artist: None,
+      location: TraktorLocation {
+        dir: "/:Music/:".to_string(),
+        file: "test.mp3".to_string(),
+        volume: None,
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: None,
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    };
+
+    let modified_at = map_traktor_modified_at(&entry).unwrap();
+    assert_eq!(modified_at, parse_traktor_date(Some("2026/1/15")).unwrap());
+  }
+
+  #[test]
+  fn test_map_traktor_entry_to_track_converts_key_notation() {
+    let entry = TraktorEntry {
+      modified_date: None,
+      modified_time: None,
+      audio_id: None,
+      title: Some("Test Track".to_string()),
+      artist: Some("Test Artist".to_string()),
+      location: TraktorLocation {
+        dir: "/:Music/:".to_string(),
+        file: "test.mp3".to_string(),
+        volume: None,
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: Some(TraktorInfo {
+        bitrate: None,
+        genre: None,
+        label: None,
+        comment: None,
+        coverartid: None,
+        key: Some("Am".to_string()),
+        playtime: None,
+        playtime_float: None,
+        ranking: None,
+        import_date: None,
+        release_date: None,
+        last_played: None,
+        playcount: None,
+        flags: None,
+        filesize: None,
+        color: None,
+      }),
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    };
+
+    let track = map_traktor_entry_to_track(&entry);
+
+    // "Am" (plain musical notation) and Harmony's Camelot notation must
+    // agree with what the filesystem scanner would write for the same key.
+    assert_eq!(track.initial_key, Some("8A".to_string()));
+  }
+
   #[test]
   fn test_map_system_path_to_traktor() {
     let (dir, file, volume) = map_system_path_to_traktor("/Users/josev/Music/test.mp3");
@@ -269,4 +658,41 @@ mod tests {
     assert!(dir.starts_with("/:"));
     assert!(dir.ends_with("/:"));
   }
+
+  #[test]
+  fn test_export_then_reimport_round_trip_preserves_generate_id() {
+    // map_track_to_traktor_entry is the inverse of map_traktor_entry_to_track;
+    // an exported entry re-imported through the same mapper must resolve to
+    // the same system path and therefore the same Track::generate_id, or
+    // export-then-reimport would silently duplicate every track.
+    let entry = TraktorEntry {
+      modified_date: None,
+      modified_time: None,
+      audio_id: None,
+      title: Some("Test Track".to_string()),
+      artist: Some("Test Artist".to_string()),
+      location: TraktorLocation {
+        dir: "/:Users/:josev/:Music/:".to_string(),
+        file: "test.mp3".to_string(),
+        volume: None,
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: None,
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    };
+
+    let imported = map_traktor_entry_to_track(&entry);
+    let exported = map_track_to_traktor_entry(&imported, Some(&entry), 0);
+    let reimported = map_traktor_entry_to_track(&exported);
+
+    assert_eq!(reimported.id, imported.id);
+    assert_eq!(reimported.path, imported.path);
+  }
 }
@@ -7,14 +7,34 @@
 // - mapper: Track conversion (Traktor <-> Harmony)
 // - conflict_resolver: Merge strategies for sync (Phase 4.5)
 // - cue_mapper: Cue point conversion (Traktor <-> Harmony) (Phase 4.5)
+// - key_notation: Musical key notation conversion (plain/Open Key/numeric -> canonical)
 // - playlist_sync: Playlist extraction and conversion (Phase 4.5)
+// - nml_writer: XML serialization for bidirectional write-back
+// - track_matcher: Fuzzy Harmony<->Traktor track pairing when paths drift (Phase 4.5)
+// - library: `DjLibrary` implementation wrapping a parsed NML tree
+// - musicbrainz_enrich: Fills parsed entries' genre/label/release date/album
+//   from MusicBrainz, writing back into the NML shape directly
+// - nml_merge: Merges two parsed NML collections (e.g. from two machines)
+//   into one, deduplicating entries and reconciling field conflicts
 
 pub mod conflict_resolver;
 pub mod cue_mapper;
+pub mod key_notation;
+pub mod library;
 pub mod mapper;
+pub mod musicbrainz_enrich;
+pub mod nml_merge;
 pub mod nml_parser;
 pub mod nml_types;
+pub mod nml_writer;
 pub mod playlist_sync;
+pub mod track_matcher;
 
-pub use mapper::map_traktor_entry_to_track;
-pub use nml_parser::TraktorNMLParser;
+pub use key_notation::{map_traktor_key, map_traktor_key_as, KeyNotation};
+pub use library::TraktorLibrary;
+pub use mapper::{compute_entry_content_hash, map_traktor_entry_to_track, map_traktor_modified_at};
+pub use musicbrainz_enrich::enrich_traktor_entries;
+pub use nml_merge::{merge as merge_nml, MergeConflict, MergeReport};
+pub use nml_parser::{TraktorEntryStream, TraktorNMLParser};
+pub use nml_writer::TraktorNMLWriter;
+pub use track_matcher::{match_tracks, TrackMatch, DEFAULT_MATCH_THRESHOLD};
@@ -0,0 +1,262 @@
+// AIDEV-NOTE: MusicBrainz enrichment for parsed Traktor entries.
+//
+// Unlike `libs::musicbrainz::enrich_tracks` (which enriches the Harmony
+// `Track` model with candidates for the user to review), this writes
+// straight back into the NML's own `INFO @GENRE`/`@LABEL`/`@RELEASE_DATE`
+// and `ALBUM @TITLE` attributes, so a re-exported `collection.nml` carries
+// the enrichment even for entries never imported into Harmony. It reuses
+// `libs::musicbrainz`'s artist-search + Browse-recordings pipeline (one
+// search + one paged browse per distinct artist, behind the same 1 req/sec
+// `RateLimiter`) rather than re-implementing MusicBrainz access - only the
+// match-and-write-back step here is Traktor-specific.
+
+use std::collections::HashMap;
+
+use crate::libs::musicbrainz::{
+  browse_recordings_by_artist, http_client, score_title_duration, search_artist_mbid, to_candidate,
+  EnrichmentCandidate,
+};
+use crate::libs::Result;
+
+use super::nml_types::{TraktorAlbum, TraktorEntry, TraktorInfo};
+
+/// A MusicBrainz recording within this many milliseconds of `INFO
+/// @PLAYTIME_FLOAT` is considered a duration match (the "within ±2s" the
+/// matching strategy is built around).
+const MAX_DURATION_DIFF_MS: f64 = 2000.0;
+/// Below this confidence, leave the entry's fields untouched rather than
+/// risk writing in a wrong genre/label/release date.
+const MIN_APPLY_CONFIDENCE: f64 = 0.6;
+
+/// An entry is worth enriching if any of the fields this module can fill
+/// (`@GENRE`, `@LABEL`, `@RELEASE_DATE`, `ALBUM @TITLE`) is still empty.
+fn needs_enrichment(entry: &TraktorEntry) -> bool {
+  let info = entry.info.as_ref();
+  info.and_then(|i| i.genre.as_deref()).unwrap_or("").trim().is_empty()
+    || info.and_then(|i| i.label.as_deref()).unwrap_or("").trim().is_empty()
+    || info.and_then(|i| i.release_date.as_deref()).unwrap_or("").trim().is_empty()
+    || entry.album.as_ref().and_then(|a| a.title.as_deref()).unwrap_or("").trim().is_empty()
+}
+
+fn entry_duration_ms(entry: &TraktorEntry) -> Option<i64> {
+  let secs: f64 = entry.info.as_ref()?.playtime_float.as_deref()?.parse().ok()?;
+  Some((secs * 1000.0).round() as i64)
+}
+
+/// Enrich `entries` in place using MusicBrainz. Returns the number of
+/// entries that had at least one field written.
+///
+/// AIDEV-NOTE: Entries are grouped by `@ARTIST` first (same "one search +
+/// one browse per distinct artist" batching `enrich_tracks` uses) so the
+/// MBID lookup and recording browse are cached per-artist rather than
+/// repeated per-entry. `on_progress` is called once per distinct artist.
+pub fn enrich_traktor_entries(
+  entries: &mut [TraktorEntry],
+  mut on_progress: impl FnMut(usize, usize, &str),
+) -> Result<usize> {
+  let client = http_client()?;
+  let limiter = crate::libs::musicbrainz::RateLimiter::new();
+
+  let mut indices_by_artist: HashMap<String, Vec<usize>> = HashMap::new();
+  for (idx, entry) in entries.iter().enumerate() {
+    let Some(artist) = entry.artist.as_deref() else {
+      continue;
+    };
+    if artist.trim().is_empty() || entry.title.as_deref().unwrap_or("").trim().is_empty() || !needs_enrichment(entry)
+    {
+      continue;
+    }
+    indices_by_artist.entry(artist.to_string()).or_default().push(idx);
+  }
+
+  let total_artists = indices_by_artist.len();
+  let mut enriched_count = 0;
+
+  for (artist_idx, (artist_name, indices)) in indices_by_artist.into_iter().enumerate() {
+    on_progress(artist_idx + 1, total_artists, &artist_name);
+
+    let artist_mbid = match search_artist_mbid(&client, &limiter, &artist_name) {
+      Ok(Some(mbid)) => mbid,
+      Ok(None) => continue,
+      Err(_) => continue,
+    };
+
+    let recordings = match browse_recordings_by_artist(&client, &limiter, &artist_mbid) {
+      Ok(recordings) => recordings,
+      Err(_) => continue,
+    };
+
+    for idx in indices {
+      let title = entries[idx].title.clone().unwrap_or_default();
+      let duration_ms = entry_duration_ms(&entries[idx]);
+
+      let best = recordings
+        .iter()
+        .filter(|recording| within_duration(duration_ms, recording))
+        .map(|recording| to_candidate(recording, score_title_duration(&title, duration_ms, recording)))
+        .filter(|candidate| candidate.confidence >= MIN_APPLY_CONFIDENCE)
+        .max_by(|a, b| a.confidence.total_cmp(&b.confidence));
+
+      if let Some(candidate) = best {
+        if apply_candidate_to_entry(&mut entries[idx], &candidate) {
+          enriched_count += 1;
+        }
+      }
+    }
+  }
+
+  Ok(enriched_count)
+}
+
+fn within_duration(entry_duration_ms: Option<i64>, recording: &crate::libs::musicbrainz::MbRecording) -> bool {
+  match (entry_duration_ms, recording.length_ms()) {
+    (Some(entry_ms), Some(recording_ms)) => (entry_ms - recording_ms).unsigned_abs() as f64 <= MAX_DURATION_DIFF_MS,
+    // Missing duration on either side: let title similarity alone decide.
+    _ => true,
+  }
+}
+
+/// Write `candidate`'s genre/label/release-date/album title onto `entry`,
+/// filling only fields that are currently empty - same "never clobber an
+/// existing value" rule as `musicbrainz::apply_fingerprint_match`. Returns
+/// whether anything was actually written.
+fn apply_candidate_to_entry(entry: &mut TraktorEntry, candidate: &EnrichmentCandidate) -> bool {
+  let mut changed = false;
+
+  if candidate.genre.is_some() || candidate.label.is_some() || candidate.release_date.is_some() {
+    let info = entry.info.get_or_insert_with(TraktorInfo::default);
+
+    if info.genre.as_deref().unwrap_or("").trim().is_empty() {
+      if let Some(genre) = &candidate.genre {
+        info.genre = Some(genre.clone());
+        changed = true;
+      }
+    }
+    if info.label.as_deref().unwrap_or("").trim().is_empty() {
+      if let Some(label) = &candidate.label {
+        info.label = Some(label.clone());
+        changed = true;
+      }
+    }
+    if info.release_date.as_deref().unwrap_or("").trim().is_empty() {
+      if let Some(release_date) = &candidate.release_date {
+        info.release_date = Some(release_date.clone());
+        changed = true;
+      }
+    }
+  }
+
+  if let Some(album_title) = &candidate.album {
+    let album = entry.album.get_or_insert_with(TraktorAlbum::default);
+    if album.title.as_deref().unwrap_or("").trim().is_empty() {
+      album.title = Some(album_title.clone());
+      changed = true;
+    }
+  }
+
+  changed
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::libs::traktor::nml_types::TraktorLocation;
+
+  fn sample_entry(title: &str, artist: &str, playtime_float: &str) -> TraktorEntry {
+    TraktorEntry {
+      modified_date: None,
+      modified_time: None,
+      audio_id: None,
+      title: Some(title.to_string()),
+      artist: Some(artist.to_string()),
+      location: TraktorLocation {
+        dir: "/:Music/:".to_string(),
+        file: "track.mp3".to_string(),
+        volume: None,
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: Some(TraktorInfo { playtime_float: Some(playtime_float.to_string()), ..Default::default() }),
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    }
+  }
+
+  #[test]
+  fn needs_enrichment_is_true_when_every_fillable_field_is_empty() {
+    let entry = sample_entry("Strobe", "deadmau5", "450.0");
+    assert!(needs_enrichment(&entry));
+  }
+
+  #[test]
+  fn needs_enrichment_is_false_once_every_fillable_field_is_set() {
+    let mut entry = sample_entry("Strobe", "deadmau5", "450.0");
+    entry.info = Some(TraktorInfo {
+      genre: Some("Progressive House".to_string()),
+      label: Some("Mau5trap".to_string()),
+      release_date: Some("2009-06-01".to_string()),
+      ..Default::default()
+    });
+    entry.album = Some(TraktorAlbum { title: Some("For Lack of a Better Name".to_string()), ..Default::default() });
+
+    assert!(!needs_enrichment(&entry));
+  }
+
+  #[test]
+  fn entry_duration_ms_converts_playtime_float_seconds_to_milliseconds() {
+    let entry = sample_entry("Strobe", "deadmau5", "450.5");
+    assert_eq!(entry_duration_ms(&entry), Some(450_500));
+  }
+
+  #[test]
+  fn apply_candidate_to_entry_fills_empty_fields_only() {
+    let mut entry = sample_entry("Strobe", "deadmau5", "450.0");
+    entry.info = Some(TraktorInfo { genre: Some("Existing Genre".to_string()), ..Default::default() });
+
+    let candidate = EnrichmentCandidate {
+      recording_mbid: "mbid-1".to_string(),
+      artist: Some("deadmau5".to_string()),
+      album: Some("For Lack of a Better Name".to_string()),
+      year: Some(2009),
+      catalog_number: None,
+      isrc: None,
+      genre: Some("Progressive House".to_string()),
+      label: Some("Mau5trap".to_string()),
+      release_date: Some("2009-06-01".to_string()),
+      confidence: 0.9,
+    };
+
+    let changed = apply_candidate_to_entry(&mut entry, &candidate);
+
+    assert!(changed);
+    let info = entry.info.as_ref().unwrap();
+    assert_eq!(info.genre, Some("Existing Genre".to_string()));
+    assert_eq!(info.label, Some("Mau5trap".to_string()));
+    assert_eq!(info.release_date, Some("2009-06-01".to_string()));
+    assert_eq!(entry.album.as_ref().unwrap().title, Some("For Lack of a Better Name".to_string()));
+  }
+
+  #[test]
+  fn apply_candidate_to_entry_reports_no_change_when_nothing_to_fill() {
+    let mut entry = sample_entry("Strobe", "deadmau5", "450.0");
+    let candidate = EnrichmentCandidate {
+      recording_mbid: "mbid-1".to_string(),
+      artist: None,
+      album: None,
+      year: None,
+      catalog_number: None,
+      isrc: None,
+      genre: None,
+      label: None,
+      release_date: None,
+      confidence: 0.9,
+    };
+
+    assert!(!apply_candidate_to_entry(&mut entry, &candidate));
+  }
+}
@@ -0,0 +1,502 @@
+// AIDEV-NOTE: Merge two parsed TraktorNML collections - e.g. a DJ's laptop
+// collection.nml and a USB/controller-side copy that drifted apart - into
+// one. Operates directly on the NML shape; unlike `conflict_resolver`/
+// `cue_merge`, which reconcile Harmony `Track`/`CuePoint` data during a
+// Traktor<->Harmony sync, this never touches the Harmony model at all, so
+// it's useful even for users who don't import into Harmony.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::mapper::{map_traktor_modified_at, map_traktor_path_to_system};
+use super::nml_types::{
+  TraktorAlbum, TraktorCollection, TraktorCue, TraktorEntry, TraktorInfo, TraktorNML, TraktorNode,
+  TraktorPlaylistData, TraktorPlaylistEntry, TraktorPlaylists, TraktorSubnodes, NML,
+};
+
+/// A field where both sides carried a different non-empty value for the
+/// same entry; the newer side (by `MODIFIED_DATE`/`MODIFIED_TIME`) won.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeConflict {
+  /// The entry's identity key (`@AUDIO_ID` if present, else its path).
+  pub identity: String,
+  pub field: String,
+  pub kept: String,
+  pub dropped: String,
+}
+
+/// Outcome of [`merge`].
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeReport {
+  /// Entries that only `a` had.
+  pub entries_from_a_only: usize,
+  /// Entries that only `b` had.
+  pub entries_from_b_only: usize,
+  /// Entries present in both, reconciled field-by-field.
+  pub entries_merged: usize,
+  /// Total entries in the merged collection.
+  pub entries_total: usize,
+  /// Cue points that existed on only one side of a merged entry and were
+  /// carried over from it.
+  pub cues_added: usize,
+  /// Fields that disagreed and had to be resolved by recency.
+  pub conflicts: Vec<MergeConflict>,
+}
+
+/// Merge two parsed NML collections into one.
+///
+/// Entries are matched by identity - `@AUDIO_ID` when an entry has one,
+/// otherwise the reconstructed filesystem path from `LOCATION` - so the same
+/// track seen from two machines (possibly re-analyzed, re-tagged, or with a
+/// drifted path) still lines up. Matched entries are field-merged (see
+/// [`merge_entry`]); everything unmatched from either side is carried over
+/// unchanged. Playlist `NODE` trees are merged the same way, by folder/
+/// playlist name at each level, concatenating `PRIMARYKEY` entries while
+/// dropping duplicates.
+pub fn merge(a: TraktorNML, b: TraktorNML) -> (TraktorNML, MergeReport) {
+  let mut report = MergeReport::default();
+
+  let entries_a = a.nml.collection.entry;
+  let entries_b = b.nml.collection.entry;
+  let total_a = entries_a.len();
+
+  let mut by_identity: HashMap<String, TraktorEntry> = HashMap::new();
+  let mut order: Vec<String> = Vec::new();
+
+  for entry in entries_a {
+    let key = entry_identity(&entry);
+    order.push(key.clone());
+    by_identity.insert(key, entry);
+  }
+
+  for entry in entries_b {
+    let key = entry_identity(&entry);
+    match by_identity.remove(&key) {
+      Some(existing) => {
+        let (merged_entry, cues_added) = merge_entry(&key, existing, entry, &mut report.conflicts);
+        report.cues_added += cues_added;
+        report.entries_merged += 1;
+        by_identity.insert(key, merged_entry);
+      }
+      None => {
+        order.push(key.clone());
+        by_identity.insert(key, entry);
+        report.entries_from_b_only += 1;
+      }
+    }
+  }
+
+  report.entries_from_a_only = total_a - report.entries_merged;
+
+  let merged_entries: Vec<TraktorEntry> = order.into_iter().filter_map(|key| by_identity.remove(&key)).collect();
+  report.entries_total = merged_entries.len();
+
+  let playlists = match (a.nml.playlists, b.nml.playlists) {
+    (Some(pa), Some(pb)) => Some(TraktorPlaylists { node: merge_node(pa.node, pb.node) }),
+    (Some(p), None) | (None, Some(p)) => Some(p),
+    (None, None) => None,
+  };
+
+  let merged = TraktorNML {
+    nml: NML {
+      version: a.nml.version,
+      head: a.nml.head,
+      collection: TraktorCollection { entries: merged_entries.len().to_string(), entry: merged_entries },
+      playlists,
+      indexing: a.nml.indexing.or(b.nml.indexing),
+    },
+  };
+
+  (merged, report)
+}
+
+fn entry_identity(entry: &TraktorEntry) -> String {
+  match entry.audio_id.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+    Some(audio_id) => format!("audio_id:{}", audio_id),
+    None => format!(
+      "path:{}",
+      map_traktor_path_to_system(&entry.location.dir, &entry.location.file, entry.location.volume.as_deref())
+    ),
+  }
+}
+
+/// Field-merge two entries that share an identity. The side with the newer
+/// `MODIFIED_DATE`/`MODIFIED_TIME` stamp (ties going to `b`) wins each
+/// non-empty field; the older side only fills in what the newer side left
+/// blank. `CUE_V2` is unioned by `HOTCUE`/`START` instead of picked from one
+/// side, so hand-set cues from both sources survive. Returns the merged
+/// entry and the number of cues that only the older side had.
+fn merge_entry(identity: &str, a: TraktorEntry, b: TraktorEntry, conflicts: &mut Vec<MergeConflict>) -> (TraktorEntry, usize) {
+  let a_ts = map_traktor_modified_at(&a).unwrap_or(0);
+  let b_ts = map_traktor_modified_at(&b).unwrap_or(0);
+  let (newer, older) = if b_ts >= a_ts { (b, a) } else { (a, b) };
+
+  let (cue_v2, cues_added) = merge_cues(newer.cue_v2, older.cue_v2);
+
+  let mut extra_attrs = older.extra_attrs;
+  extra_attrs.extend(newer.extra_attrs);
+
+  let merged = TraktorEntry {
+    modified_date: newer.modified_date.or(older.modified_date),
+    modified_time: newer.modified_time.or(older.modified_time),
+    audio_id: newer.audio_id.or(older.audio_id),
+    title: merge_field(identity, "title", newer.title, older.title, conflicts),
+    artist: merge_field(identity, "artist", newer.artist, older.artist, conflicts),
+    location: newer.location,
+    album: merge_album(identity, newer.album, older.album, conflicts),
+    modification_info: newer.modification_info.or(older.modification_info),
+    info: merge_info(identity, newer.info, older.info, conflicts),
+    tempo: newer.tempo.or(older.tempo),
+    loudness: newer.loudness.or(older.loudness),
+    musical_key: newer.musical_key.or(older.musical_key),
+    cue_v2,
+    primarykey: newer.primarykey.or(older.primarykey),
+    extra_attrs,
+  };
+
+  (merged, cues_added)
+}
+
+/// Prefer `newer` when it's non-empty, recording a conflict if `older` was
+/// also non-empty and disagreed; otherwise fall back to `older`.
+fn merge_field(
+  identity: &str,
+  field: &str,
+  newer: Option<String>,
+  older: Option<String>,
+  conflicts: &mut Vec<MergeConflict>,
+) -> Option<String> {
+  if !is_empty(&newer) {
+    if !is_empty(&older) && older != newer {
+      conflicts.push(MergeConflict {
+        identity: identity.to_string(),
+        field: field.to_string(),
+        kept: newer.clone().unwrap_or_default(),
+        dropped: older.unwrap_or_default(),
+      });
+    }
+    newer
+  } else {
+    older
+  }
+}
+
+fn is_empty(value: &Option<String>) -> bool {
+  value.as_deref().map(str::trim).unwrap_or("").is_empty()
+}
+
+fn merge_album(
+  identity: &str,
+  newer: Option<TraktorAlbum>,
+  older: Option<TraktorAlbum>,
+  conflicts: &mut Vec<MergeConflict>,
+) -> Option<TraktorAlbum> {
+  if newer.is_none() && older.is_none() {
+    return None;
+  }
+  let newer = newer.unwrap_or_default();
+  let older = older.unwrap_or_default();
+
+  Some(TraktorAlbum {
+    title: merge_field(identity, "album.title", newer.title, older.title, conflicts),
+    track: merge_field(identity, "album.track", newer.track, older.track, conflicts),
+    of_tracks: merge_field(identity, "album.of_tracks", newer.of_tracks, older.of_tracks, conflicts),
+  })
+}
+
+fn merge_info(
+  identity: &str,
+  newer: Option<TraktorInfo>,
+  older: Option<TraktorInfo>,
+  conflicts: &mut Vec<MergeConflict>,
+) -> Option<TraktorInfo> {
+  if newer.is_none() && older.is_none() {
+    return None;
+  }
+  let newer = newer.unwrap_or_default();
+  let older = older.unwrap_or_default();
+
+  let playcount = max_playcount(&newer.playcount, &older.playcount);
+
+  Some(TraktorInfo {
+    bitrate: merge_field(identity, "info.bitrate", newer.bitrate, older.bitrate, conflicts),
+    genre: merge_field(identity, "info.genre", newer.genre, older.genre, conflicts),
+    label: merge_field(identity, "info.label", newer.label, older.label, conflicts),
+    comment: merge_field(identity, "info.comment", newer.comment, older.comment, conflicts),
+    coverartid: merge_field(identity, "info.coverartid", newer.coverartid, older.coverartid, conflicts),
+    key: merge_field(identity, "info.key", newer.key, older.key, conflicts),
+    playtime: merge_field(identity, "info.playtime", newer.playtime, older.playtime, conflicts),
+    playtime_float: merge_field(
+      identity,
+      "info.playtime_float",
+      newer.playtime_float,
+      older.playtime_float,
+      conflicts,
+    ),
+    ranking: merge_field(identity, "info.ranking", newer.ranking, older.ranking, conflicts),
+    import_date: merge_field(identity, "info.import_date", newer.import_date, older.import_date, conflicts),
+    release_date: merge_field(identity, "info.release_date", newer.release_date, older.release_date, conflicts),
+    last_played: merge_field(identity, "info.last_played", newer.last_played, older.last_played, conflicts),
+    playcount,
+    flags: merge_field(identity, "info.flags", newer.flags, older.flags, conflicts),
+    filesize: merge_field(identity, "info.filesize", newer.filesize, older.filesize, conflicts),
+    color: merge_field(identity, "info.color", newer.color, older.color, conflicts),
+  })
+}
+
+/// Max of two `@PLAYCOUNT` strings - neither machine's play history should
+/// regress the other's.
+fn max_playcount(a: &Option<String>, b: &Option<String>) -> Option<String> {
+  let parsed_a = a.as_deref().and_then(|s| s.parse::<i64>().ok());
+  let parsed_b = b.as_deref().and_then(|s| s.parse::<i64>().ok());
+  match (parsed_a, parsed_b) {
+    (Some(pa), Some(pb)) => Some(pa.max(pb).to_string()),
+    (Some(_), None) => a.clone(),
+    (None, Some(_)) => b.clone(),
+    (None, None) => None,
+  }
+}
+
+/// Identity of a cue within an entry: its hot-cue slot if it has one, else
+/// its rounded start position - two cues at the same slot/position are
+/// considered "the same cue" for union purposes.
+fn cue_key(cue: &TraktorCue) -> (Option<i64>, i64) {
+  let hotcue = cue.hotcue.as_deref().and_then(|s| s.parse::<i64>().ok());
+  let start = cue.start.parse::<f64>().unwrap_or(0.0).round() as i64;
+  (hotcue, start)
+}
+
+/// Union `newer` and `older`'s cue points by [`cue_key`], keeping `newer`'s
+/// copy when both sides have one at the same key. Returns the merged set
+/// plus how many cues came only from `older`.
+fn merge_cues(newer: Vec<TraktorCue>, older: Vec<TraktorCue>) -> (Vec<TraktorCue>, usize) {
+  let mut by_key: HashMap<(Option<i64>, i64), TraktorCue> = HashMap::new();
+  let mut order: Vec<(Option<i64>, i64)> = Vec::new();
+
+  for cue in newer {
+    let key = cue_key(&cue);
+    if !by_key.contains_key(&key) {
+      order.push(key);
+    }
+    by_key.insert(key, cue);
+  }
+
+  let mut added_from_older = 0;
+  for cue in older {
+    let key = cue_key(&cue);
+    if !by_key.contains_key(&key) {
+      order.push(key);
+      by_key.insert(key, cue);
+      added_from_older += 1;
+    }
+  }
+
+  let merged = order.into_iter().filter_map(|key| by_key.remove(&key)).collect();
+  (merged, added_from_older)
+}
+
+/// Merge two playlist-tree nodes assumed to represent the same folder/
+/// playlist (same position in the tree). Children are matched by `@NAME`;
+/// unmatched children from either side are appended.
+fn merge_node(a: TraktorNode, b: TraktorNode) -> TraktorNode {
+  let subnodes = match (a.subnodes, b.subnodes) {
+    (Some(sa), Some(sb)) => Some(merge_subnodes(sa, sb)),
+    (Some(s), None) | (None, Some(s)) => Some(s),
+    (None, None) => None,
+  };
+
+  let playlist = match (a.playlist, b.playlist) {
+    (Some(pa), Some(pb)) => Some(merge_playlist_data(pa, pb)),
+    (Some(p), None) | (None, Some(p)) => Some(p),
+    (None, None) => None,
+  };
+
+  TraktorNode { node_type: a.node_type, name: a.name, subnodes, playlist }
+}
+
+fn merge_subnodes(a: TraktorSubnodes, b: TraktorSubnodes) -> TraktorSubnodes {
+  let mut by_name: HashMap<String, TraktorNode> = HashMap::new();
+  let mut order: Vec<String> = Vec::new();
+
+  for node in a.nodes {
+    order.push(node.name.clone());
+    by_name.insert(node.name.clone(), node);
+  }
+
+  for node in b.nodes {
+    if let Some(existing) = by_name.remove(&node.name) {
+      let name = node.name.clone();
+      by_name.insert(name, merge_node(existing, node));
+    } else {
+      order.push(node.name.clone());
+      by_name.insert(node.name.clone(), node);
+    }
+  }
+
+  let nodes: Vec<TraktorNode> = order.into_iter().filter_map(|name| by_name.remove(&name)).collect();
+  TraktorSubnodes { count: Some(nodes.len().to_string()), nodes }
+}
+
+/// Concatenate both playlists' entries, dropping `PRIMARYKEY`s already seen
+/// (matched on `@TYPE`+`@KEY`) so a track referenced on both sides isn't
+/// duplicated in the merged playlist.
+fn merge_playlist_data(a: TraktorPlaylistData, b: TraktorPlaylistData) -> TraktorPlaylistData {
+  let mut seen: HashSet<(Option<String>, Option<String>)> = HashSet::new();
+  let mut entry: Vec<TraktorPlaylistEntry> = Vec::new();
+
+  for e in a.entry.into_iter().chain(b.entry) {
+    let key = (e.primarykey.key_type.clone(), e.primarykey.key.clone());
+    if seen.insert(key) {
+      entry.push(e);
+    }
+  }
+
+  TraktorPlaylistData { entries: entry.len().to_string(), playlist_type: a.playlist_type, uuid: a.uuid, entry }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::libs::traktor::nml_types::{TraktorHead, TraktorLocation};
+
+  fn sample_head() -> TraktorHead {
+    TraktorHead { company: "Native Instruments".to_string(), program: "Traktor".to_string() }
+  }
+
+  fn sample_entry(audio_id: Option<&str>, file: &str, modified: Option<(&str, &str)>) -> TraktorEntry {
+    TraktorEntry {
+      modified_date: modified.map(|(d, _)| d.to_string()),
+      modified_time: modified.map(|(_, t)| t.to_string()),
+      audio_id: audio_id.map(str::to_string),
+      title: Some("Strobe".to_string()),
+      artist: Some("deadmau5".to_string()),
+      location: TraktorLocation {
+        dir: "/:Music/:".to_string(),
+        file: file.to_string(),
+        volume: None,
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: Some(TraktorInfo { playcount: Some("1".to_string()), ..Default::default() }),
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    }
+  }
+
+  fn sample_cue(hotcue: Option<&str>, start: &str) -> TraktorCue {
+    TraktorCue {
+      name: None,
+      displ_order: None,
+      cue_type: "0".to_string(),
+      start: start.to_string(),
+      len: None,
+      repeats: None,
+      hotcue: hotcue.map(str::to_string),
+      grid: None,
+    }
+  }
+
+  fn sample_nml(entries: Vec<TraktorEntry>) -> TraktorNML {
+    TraktorNML {
+      nml: NML {
+        version: "19".to_string(),
+        head: sample_head(),
+        collection: TraktorCollection { entries: entries.len().to_string(), entry: entries },
+        playlists: None,
+        indexing: None,
+      },
+    }
+  }
+
+  #[test]
+  fn merge_dedupes_entries_with_the_same_audio_id() {
+    let a = sample_nml(vec![sample_entry(Some("abc123"), "track.mp3", Some(("2024/1/1", "0")))]);
+    let b = sample_nml(vec![sample_entry(Some("abc123"), "track.mp3", Some(("2024/6/1", "0")))]);
+
+    let (merged, report) = merge(a, b);
+
+    assert_eq!(merged.nml.collection.entry.len(), 1);
+    assert_eq!(report.entries_total, 1);
+    assert_eq!(report.entries_merged, 1);
+    assert_eq!(report.entries_from_a_only, 0);
+    assert_eq!(report.entries_from_b_only, 0);
+  }
+
+  #[test]
+  fn merge_keeps_entries_unique_to_either_side() {
+    let a = sample_nml(vec![sample_entry(Some("a"), "a.mp3", None)]);
+    let b = sample_nml(vec![sample_entry(Some("b"), "b.mp3", None)]);
+
+    let (merged, report) = merge(a, b);
+
+    assert_eq!(merged.nml.collection.entry.len(), 2);
+    assert_eq!(report.entries_from_a_only, 1);
+    assert_eq!(report.entries_from_b_only, 1);
+    assert_eq!(report.entries_merged, 0);
+  }
+
+  #[test]
+  fn merge_prefers_the_newer_entrys_non_empty_fields() {
+    let mut old_entry = sample_entry(Some("abc"), "track.mp3", Some(("2024/1/1", "0")));
+    old_entry.info = Some(TraktorInfo { genre: Some("Old Genre".to_string()), playcount: Some("3".to_string()), ..Default::default() });
+    let mut new_entry = sample_entry(Some("abc"), "track.mp3", Some(("2024/6/1", "0")));
+    new_entry.info = Some(TraktorInfo { playcount: Some("1".to_string()), ..Default::default() });
+
+    let (merged, report) = merge(sample_nml(vec![old_entry]), sample_nml(vec![new_entry]));
+
+    let info = merged.nml.collection.entry[0].info.as_ref().unwrap();
+    assert_eq!(info.genre, Some("Old Genre".to_string()), "newer side's empty genre should fall back to the older one");
+    assert_eq!(info.playcount, Some("3".to_string()), "playcount should be the max of both sides");
+    assert!(report.conflicts.is_empty());
+  }
+
+  #[test]
+  fn merge_reports_conflicts_when_both_sides_changed_a_field() {
+    let mut a_entry = sample_entry(Some("abc"), "track.mp3", Some(("2024/1/1", "0")));
+    a_entry.info = Some(TraktorInfo { genre: Some("House".to_string()), ..Default::default() });
+    let mut b_entry = sample_entry(Some("abc"), "track.mp3", Some(("2024/6/1", "0")));
+    b_entry.info = Some(TraktorInfo { genre: Some("Techno".to_string()), ..Default::default() });
+
+    let (merged, report) = merge(sample_nml(vec![a_entry]), sample_nml(vec![b_entry]));
+
+    let info = merged.nml.collection.entry[0].info.as_ref().unwrap();
+    assert_eq!(info.genre, Some("Techno".to_string()));
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].field, "info.genre");
+    assert_eq!(report.conflicts[0].kept, "Techno");
+    assert_eq!(report.conflicts[0].dropped, "House");
+  }
+
+  #[test]
+  fn merge_unions_hot_cues_from_both_sides() {
+    let mut a_entry = sample_entry(Some("abc"), "track.mp3", None);
+    a_entry.cue_v2 = vec![sample_cue(Some("0"), "1000.0"), sample_cue(Some("1"), "5000.0")];
+    let mut b_entry = sample_entry(Some("abc"), "track.mp3", None);
+    b_entry.cue_v2 = vec![sample_cue(Some("1"), "5000.0"), sample_cue(Some("2"), "9000.0")];
+
+    let (merged, report) = merge(sample_nml(vec![a_entry]), sample_nml(vec![b_entry]));
+
+    let cues = &merged.nml.collection.entry[0].cue_v2;
+    assert_eq!(cues.len(), 3, "cue at the same slot should dedupe, unique slots should union");
+    assert_eq!(report.cues_added, 1);
+  }
+
+  #[test]
+  fn merge_falls_back_to_path_identity_without_an_audio_id() {
+    let a = sample_nml(vec![sample_entry(None, "track.mp3", None)]);
+    let b = sample_nml(vec![sample_entry(None, "track.mp3", None)]);
+
+    let (merged, report) = merge(a, b);
+
+    assert_eq!(merged.nml.collection.entry.len(), 1);
+    assert_eq!(report.entries_merged, 1);
+  }
+}
@@ -4,14 +4,39 @@
 // Uses quick-xml with serde for efficient XML deserialization
 // Handles NML files from Traktor Pro 3.x (VERSION="19")
 
-use log::info;
+use log::{info, warn};
 use quick_xml::de::from_str;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use quick_xml::writer::Writer;
+use serde::Deserialize;
 use std::fs;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor};
 use std::path::Path;
 
-use crate::libs::Result;
+use crate::libs::{HarmonyError, Result};
 
-use super::nml_types::TraktorNML;
+use super::nml_types::{NmlSchema, NmlV15, TraktorEntry, TraktorNML, TraktorPlaylists, NML};
+
+/// Lowest `NML @VERSION` still deserialized against the V19 (Traktor Pro 3.x)
+/// schema. Traktor 2.x collections below this use the legacy `CUE`-based
+/// shape instead.
+const MIN_V19_VERSION: u32 = 19;
+
+/// Just enough of the NML root to read `@VERSION` before committing to a
+/// full schema-specific deserialization pass.
+#[derive(Debug, Deserialize)]
+struct NmlVersionProbe {
+  #[serde(rename = "NML")]
+  nml: NmlVersionProbeRoot,
+}
+
+#[derive(Debug, Deserialize)]
+struct NmlVersionProbeRoot {
+  #[serde(rename = "@VERSION")]
+  version: String,
+}
 
 /// Parser for Traktor NML (collection.nml) files
 ///
@@ -58,16 +83,108 @@ impl TraktorNMLParser {
   pub fn parse_xml(&self, xml_content: &str) -> Result<TraktorNML> {
     info!("Deserializing NML XML ({} bytes)", xml_content.len());
 
-    // AIDEV-NOTE: quick-xml with serde handles attribute parsing automatically
-    // The @-prefix in our struct definitions tells serde to look for XML attributes
-    let nml: TraktorNML = from_str(xml_content)
-      .map_err(|e| crate::libs::HarmonyError::Xml(format!("Failed to parse NML XML: {}", e)))?;
+    let nml = Self::parse_schema(xml_content)?.into_canonical();
 
     let entry_count = nml.nml.collection.entry.len();
     info!("Successfully parsed NML with {} tracks", entry_count);
 
     Ok(nml)
   }
+
+  /// Read `NML @VERSION` and deserialize against the matching schema.
+  ///
+  /// AIDEV-NOTE: Traktor Pro 3.x writes `VERSION="19"`; legacy Traktor 2.x
+  /// collections use an older, `CUE`-based schema. Versions newer than 19
+  /// (future Traktor releases) are assumed backwards-compatible with V19 and
+  /// parsed best-effort, with a warning. A non-numeric `@VERSION`, or a
+  /// document that won't deserialize against the one shape its version
+  /// selects, is a hard error; the other shape is not tried.
+  fn parse_schema(xml_content: &str) -> Result<NmlSchema> {
+    let probe: NmlVersionProbe = from_str(xml_content)
+      .map_err(|e| HarmonyError::Xml(format!("Failed to read NML @VERSION: {}", e)))?;
+    let version = probe.nml.version;
+
+    // AIDEV-NOTE: quick-xml with serde handles attribute parsing automatically
+    // The @-prefix in our struct definitions tells serde to look for XML attributes
+    match version.parse::<u32>() {
+      Ok(v) if v >= MIN_V19_VERSION => {
+        if v > MIN_V19_VERSION {
+          warn!("NML @VERSION={} is newer than the known V19 schema; parsing best-effort", v);
+        }
+        let nml: NML = from_str::<TraktorNML>(xml_content)
+          .map_err(|e| HarmonyError::Xml(format!("Failed to parse NML XML (VERSION={}): {}", v, e)))?
+          .nml;
+        Ok(NmlSchema::V19(nml))
+      }
+      Ok(_) => {
+        let legacy: NmlV15 = from_str(xml_content)
+          .map_err(|e| HarmonyError::Xml(format!("Failed to parse legacy NML XML (VERSION={}): {}", version, e)))?;
+        Ok(NmlSchema::V15(legacy))
+      }
+      Err(_) => Err(HarmonyError::UnsupportedNmlVersion(version)),
+    }
+  }
+
+  /// Open an NML file and stream its `<ENTRY>` elements one at a time.
+  ///
+  /// AIDEV-NOTE: `parse`/`parse_xml` deserialize the whole document (and
+  /// every `TraktorEntry`) into memory up front, which is fine for a few
+  /// thousand tracks but scales poorly past ~50k. This pulls entries from a
+  /// `quick_xml::Reader` as the caller consumes the iterator, so
+  /// `sync_traktor_nml` can match/merge/write in bounded batches instead of
+  /// holding the full collection. `PLAYLISTS`/`INDEXING` are not visited by
+  /// this path - callers that need them should use `parse`/`parse_xml`.
+  ///
+  /// # Errors
+  /// Returns error if the file cannot be opened
+  pub fn stream_entries<P: AsRef<Path>>(&self, file_path: P) -> Result<TraktorEntryStream<BufReader<File>>> {
+    let path = file_path.as_ref();
+    info!("Streaming Traktor NML file: {:?}", path);
+
+    let file = File::open(path)?;
+    Ok(TraktorEntryStream::new(BufReader::new(file)))
+  }
+
+  /// Stream `<ENTRY>` elements from an in-memory XML string (mainly for tests).
+  pub fn stream_entries_str(&self, xml_content: &str) -> TraktorEntryStream<Cursor<Vec<u8>>> {
+    TraktorEntryStream::new(Cursor::new(xml_content.as_bytes().to_vec()))
+  }
+
+  /// Parse just the `<PLAYLISTS>` subtree of an NML file, skipping over
+  /// `<COLLECTION>` without deserializing any entries.
+  ///
+  /// AIDEV-NOTE: Companion to `stream_entries` - a sync that streams tracks
+  /// still needs playlists, but shouldn't pay for materializing every
+  /// `TraktorEntry` a second time just to reach the `PLAYLISTS` node near
+  /// the end of the file.
+  ///
+  /// # Errors
+  /// Returns error if the file cannot be opened or the subtree fails to parse
+  pub fn parse_playlists<P: AsRef<Path>>(&self, file_path: P) -> Result<Option<TraktorPlaylists>> {
+    let path = file_path.as_ref();
+    info!("Scanning for PLAYLISTS in Traktor NML file: {:?}", path);
+
+    let file = File::open(path)?;
+    let mut reader = Reader::from_reader(BufReader::new(file));
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+
+    loop {
+      buf.clear();
+      match reader.read_event_into(&mut buf).map_err(xml_err)? {
+        Event::Eof => return Ok(None),
+        Event::Start(e) if e.name().as_ref() == b"PLAYLISTS" => {
+          let xml = capture_subtree(&mut reader, e.into_owned())?;
+          let playlists: TraktorPlaylists = from_str(&xml)
+            .map_err(|e| HarmonyError::Xml(format!("Failed to parse PLAYLISTS: {}", e)))?;
+          return Ok(Some(playlists));
+        }
+        // An empty PLAYLISTS element (`<PLAYLISTS/>`) has no NODE - nothing to import
+        Event::Empty(e) if e.name().as_ref() == b"PLAYLISTS" => return Ok(None),
+        _ => {}
+      }
+    }
+  }
 }
 
 impl Default for TraktorNMLParser {
@@ -76,6 +193,153 @@ impl Default for TraktorNMLParser {
   }
 }
 
+/// Pull iterator over the `<ENTRY>` elements of a Traktor NML document.
+///
+/// Produced by [`TraktorNMLParser::stream_entries`]. Each call to `next()`
+/// advances the underlying `quick_xml::Reader` only as far as the next
+/// complete `<ENTRY>...</ENTRY>` (or self-closing `<ENTRY .../>`), re-emits
+/// just that fragment, and deserializes it on its own - the rest of the
+/// document is never buffered.
+pub struct TraktorEntryStream<R: BufRead> {
+  reader: Reader<R>,
+  buf: Vec<u8>,
+  done: bool,
+  /// `COLLECTION@ENTRIES` once the stream has read past the `<COLLECTION>`
+  /// start tag - lets callers show sync progress without materializing the
+  /// collection to count it themselves.
+  total_entries: Option<usize>,
+}
+
+impl<R: BufRead> TraktorEntryStream<R> {
+  fn new(source: R) -> Self {
+    let mut reader = Reader::from_reader(source);
+    reader.trim_text(true);
+
+    Self { reader, buf: Vec::new(), done: false, total_entries: None }
+  }
+
+  /// The `COLLECTION@ENTRIES` count declared by the document, once the
+  /// stream has read past the `<COLLECTION>` start tag. `None` before then
+  /// (or if the attribute is missing/unparsable).
+  pub fn total_entries(&self) -> Option<usize> {
+    self.total_entries
+  }
+}
+
+impl<R: BufRead> Iterator for TraktorEntryStream<R> {
+  type Item = Result<TraktorEntry>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+
+    loop {
+      self.buf.clear();
+      let event = match self.reader.read_event_into(&mut self.buf) {
+        Ok(event) => event,
+        Err(e) => {
+          self.done = true;
+          return Some(Err(xml_err(e)));
+        }
+      };
+
+      match event {
+        Event::Eof => {
+          self.done = true;
+          return None;
+        }
+        Event::Start(ref e) if e.name().as_ref() == b"COLLECTION" => {
+          self.total_entries = e
+            .attributes()
+            .flatten()
+            .find(|attr| attr.key.as_ref() == b"ENTRIES")
+            .and_then(|attr| attr.unescape_value().ok())
+            .and_then(|v| v.parse::<usize>().ok());
+        }
+        Event::Start(e) => {
+          if e.name().as_ref() != b"ENTRY" {
+            continue;
+          }
+          let xml = match capture_subtree(&mut self.reader, e.into_owned()) {
+            Ok(xml) => xml,
+            Err(e) => {
+              self.done = true;
+              return Some(Err(e));
+            }
+          };
+          return Some(
+            from_str::<TraktorEntry>(&xml)
+              .map_err(|e| HarmonyError::Xml(format!("Failed to parse NML entry: {}", e))),
+          );
+        }
+        Event::Empty(e) if e.name().as_ref() == b"ENTRY" => {
+          let mut writer = Writer::new(Cursor::new(Vec::new()));
+          if let Err(e) = writer.write_event(Event::Empty(e.into_owned())) {
+            self.done = true;
+            return Some(Err(xml_err(e)));
+          }
+          let xml = match String::from_utf8(writer.into_inner().into_inner()) {
+            Ok(xml) => xml,
+            Err(e) => {
+              self.done = true;
+              return Some(Err(HarmonyError::Xml(format!(
+                "Entry fragment was not valid UTF-8: {}",
+                e
+              ))));
+            }
+          };
+          return Some(
+            from_str::<TraktorEntry>(&xml)
+              .map_err(|e| HarmonyError::Xml(format!("Failed to parse NML entry: {}", e))),
+          );
+        }
+        _ => continue,
+      }
+    }
+  }
+}
+
+/// Re-serialize one subtree (captured between a `Start` event and its
+/// matching `End`) into a standalone XML fragment that can be handed to
+/// `quick_xml::de::from_str` on its own, without buffering the rest of the
+/// document.
+fn capture_subtree<R: BufRead>(reader: &mut Reader<R>, start: BytesStart<'static>) -> Result<String> {
+  let mut writer = Writer::new(Cursor::new(Vec::new()));
+  writer.write_event(Event::Start(start)).map_err(xml_err)?;
+
+  let mut buf = Vec::new();
+  let mut depth = 1u32;
+  loop {
+    buf.clear();
+    match reader.read_event_into(&mut buf).map_err(xml_err)? {
+      Event::Start(e) => {
+        depth += 1;
+        writer.write_event(Event::Start(e)).map_err(xml_err)?;
+      }
+      Event::End(e) => {
+        depth -= 1;
+        writer.write_event(Event::End(e.clone())).map_err(xml_err)?;
+        if depth == 0 {
+          break;
+        }
+      }
+      Event::Empty(e) => writer.write_event(Event::Empty(e)).map_err(xml_err)?,
+      Event::Text(e) => writer.write_event(Event::Text(e)).map_err(xml_err)?,
+      Event::CData(e) => writer.write_event(Event::CData(e)).map_err(xml_err)?,
+      Event::Eof => break,
+      _ => {}
+    }
+  }
+
+  String::from_utf8(writer.into_inner().into_inner())
+    .map_err(|e| HarmonyError::Xml(format!("Captured subtree was not valid UTF-8: {}", e)))
+}
+
+fn xml_err(e: impl std::fmt::Display) -> HarmonyError {
+  HarmonyError::Xml(e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -136,4 +400,137 @@ mod tests {
     assert_eq!(entry.cue_v2[1].cue_type, "5");
     assert_eq!(entry.cue_v2[1].len, Some("16000.0".to_string()));
   }
+
+  #[test]
+  fn test_stream_entries_matches_full_parse() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<NML VERSION="19">
+  <HEAD COMPANY="www.native-instruments.com" PROGRAM="Traktor"/>
+  <COLLECTION ENTRIES="2">
+    <ENTRY TITLE="First" ARTIST="Artist A">
+      <LOCATION DIR="/:Music/:" FILE="a.mp3" VOLUME="C:"/>
+      <CUE_V2 NAME="Intro" TYPE="0" START="5000.0" HOTCUE="0"/>
+    </ENTRY>
+    <ENTRY TITLE="Second" ARTIST="Artist B">
+      <LOCATION DIR="/:Music/:" FILE="b.mp3" VOLUME="C:"/>
+    </ENTRY>
+  </COLLECTION>
+</NML>"#;
+
+    let parser = TraktorNMLParser::new();
+    let entries: Vec<TraktorEntry> = parser
+      .stream_entries_str(xml)
+      .collect::<Result<Vec<_>>>()
+      .unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].title, Some("First".to_string()));
+    assert_eq!(entries[0].cue_v2.len(), 1);
+    assert_eq!(entries[0].location.file, "a.mp3");
+    assert_eq!(entries[1].title, Some("Second".to_string()));
+    assert_eq!(entries[1].location.file, "b.mp3");
+  }
+
+  #[test]
+  fn test_stream_entries_is_lazy() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<NML VERSION="19">
+  <HEAD COMPANY="www.native-instruments.com" PROGRAM="Traktor"/>
+  <COLLECTION ENTRIES="1">
+    <ENTRY TITLE="Only" ARTIST="Artist">
+      <LOCATION DIR="/:Music/:" FILE="only.mp3" VOLUME="C:"/>
+    </ENTRY>
+  </COLLECTION>
+</NML>"#;
+
+    let parser = TraktorNMLParser::new();
+    let mut stream = parser.stream_entries_str(xml);
+
+    let first = stream.next().unwrap().unwrap();
+    assert_eq!(first.title, Some("Only".to_string()));
+    assert!(stream.next().is_none());
+  }
+
+  #[test]
+  fn test_parse_legacy_v15_cue_element_maps_to_cue_v2() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<NML VERSION="15">
+  <HEAD COMPANY="www.native-instruments.com" PROGRAM="Traktor"/>
+  <COLLECTION ENTRIES="1">
+    <ENTRY TITLE="Legacy Track" ARTIST="Legacy Artist">
+      <LOCATION DIR="/:Music/:" FILE="legacy.mp3" VOLUME="C:"/>
+      <CUE NAME="Intro" TYPE="0" START="1000.0" HOTCUE="0"/>
+    </ENTRY>
+  </COLLECTION>
+</NML>"#;
+
+    let parser = TraktorNMLParser::new();
+    let nml = parser.parse_xml(xml).unwrap();
+
+    assert_eq!(nml.nml.version, "15");
+    let entry = &nml.nml.collection.entry[0];
+    assert_eq!(entry.title, Some("Legacy Track".to_string()));
+    assert_eq!(entry.cue_v2.len(), 1);
+    assert_eq!(entry.cue_v2[0].name, Some("Intro".to_string()));
+    assert!(entry.loudness.is_none());
+  }
+
+  #[test]
+  fn test_parse_unknown_future_version_falls_back_to_v19_best_effort() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<NML VERSION="20">
+  <HEAD COMPANY="www.native-instruments.com" PROGRAM="Traktor"/>
+  <COLLECTION ENTRIES="1">
+    <ENTRY TITLE="Future Track" ARTIST="Future Artist">
+      <LOCATION DIR="/:Music/:" FILE="future.mp3" VOLUME="C:"/>
+      <CUE_V2 NAME="Intro" TYPE="0" START="1000.0" HOTCUE="0"/>
+    </ENTRY>
+  </COLLECTION>
+</NML>"#;
+
+    let parser = TraktorNMLParser::new();
+    let nml = parser.parse_xml(xml).unwrap();
+
+    assert_eq!(nml.nml.version, "20");
+    assert_eq!(nml.nml.collection.entry[0].title, Some("Future Track".to_string()));
+  }
+
+  #[test]
+  fn test_parse_non_numeric_version_is_a_structured_error() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<NML VERSION="beta">
+  <HEAD COMPANY="www.native-instruments.com" PROGRAM="Traktor"/>
+  <COLLECTION ENTRIES="0"></COLLECTION>
+</NML>"#;
+
+    let parser = TraktorNMLParser::new();
+    let err = parser.parse_xml(xml).unwrap_err();
+    match err {
+      HarmonyError::UnsupportedNmlVersion(v) => assert_eq!(v, "beta"),
+      other => panic!("expected UnsupportedNmlVersion, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_stream_entries_exposes_total_entries_after_collection_tag() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<NML VERSION="19">
+  <HEAD COMPANY="www.native-instruments.com" PROGRAM="Traktor"/>
+  <COLLECTION ENTRIES="2">
+    <ENTRY TITLE="First" ARTIST="Artist A">
+      <LOCATION DIR="/:Music/:" FILE="a.mp3" VOLUME="C:"/>
+    </ENTRY>
+    <ENTRY TITLE="Second" ARTIST="Artist B">
+      <LOCATION DIR="/:Music/:" FILE="b.mp3" VOLUME="C:"/>
+    </ENTRY>
+  </COLLECTION>
+</NML>"#;
+
+    let parser = TraktorNMLParser::new();
+    let mut stream = parser.stream_entries_str(xml);
+
+    assert_eq!(stream.total_entries(), None);
+    stream.next();
+    assert_eq!(stream.total_entries(), Some(2));
+  }
 }
@@ -99,6 +99,14 @@ pub struct TraktorEntry {
 
   #[serde(rename = "PRIMARYKEY")]
   pub primarykey: Option<TraktorPrimaryKey>,
+
+  // AIDEV-NOTE: Catches ENTRY attributes this struct doesn't model by name
+  // (future Traktor versions, vendor-specific extensions). `TraktorNMLWriter`
+  // re-emits these verbatim so `export_traktor_nml` doesn't silently drop
+  // data it never parsed in the first place. Always empty for entries built
+  // fresh from a Harmony-only track.
+  #[serde(flatten)]
+  pub extra_attrs: std::collections::BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,7 +124,7 @@ pub struct TraktorLocation {
   pub volumeid: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TraktorAlbum {
   #[serde(rename = "@TITLE")]
   pub title: Option<String>,
@@ -134,7 +142,7 @@ pub struct TraktorModificationInfo {
   pub author_type: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TraktorInfo {
   #[serde(rename = "@BITRATE")]
   pub bitrate: Option<String>,
@@ -318,6 +326,158 @@ pub struct TraktorPlaylistEntry {
   pub primarykey: TraktorPrimaryKey,
 }
 
+// ============================================================================
+// Versioned schema dispatch
+// ============================================================================
+
+/// Traktor NML schema, tagged by the `NML @VERSION` that produced it.
+///
+/// AIDEV-NOTE: Traktor Pro 3.x writes `VERSION="19"`, but legacy Traktor 2.x
+/// collections use an older schema (`CUE` instead of `CUE_V2`, no
+/// `LOUDNESS`). `TraktorNMLParser::parse_xml` reads `@VERSION` first and
+/// deserializes against whichever shape matches, then converts into the
+/// canonical `TraktorNML` used by the rest of `libs::traktor` - so a V15
+/// file and a V19 file look identical to every caller downstream of parsing.
+#[derive(Debug, Clone)]
+pub enum NmlSchema {
+  /// Traktor Pro 3.x (`VERSION="19"`) - the schema this module is modeled on.
+  V19(NML),
+  /// Legacy Traktor 2.x (`VERSION` < 19) - `CUE` instead of `CUE_V2`, no `LOUDNESS`.
+  V15(NmlV15),
+}
+
+impl NmlSchema {
+  /// Convert into the canonical `TraktorNML` shape used everywhere else in
+  /// `libs::traktor`.
+  pub fn into_canonical(self) -> TraktorNML {
+    match self {
+      NmlSchema::V19(nml) => TraktorNML { nml },
+      NmlSchema::V15(legacy) => legacy.into(),
+    }
+  }
+}
+
+/// Legacy Traktor 2.x NML root (`CUE` cue points, no `LOUDNESS`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NmlV15 {
+  #[serde(rename = "NML")]
+  pub nml: NmlV15Root,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NmlV15Root {
+  #[serde(rename = "@VERSION")]
+  pub version: String,
+
+  #[serde(rename = "HEAD")]
+  pub head: TraktorHead,
+
+  #[serde(rename = "COLLECTION")]
+  pub collection: NmlV15Collection,
+
+  #[serde(rename = "PLAYLISTS")]
+  pub playlists: Option<TraktorPlaylists>,
+
+  #[serde(rename = "INDEXING")]
+  pub indexing: Option<TraktorIndexing>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NmlV15Collection {
+  #[serde(rename = "@ENTRIES")]
+  pub entries: String,
+
+  #[serde(rename = "ENTRY", default)]
+  pub entry: Vec<NmlV15Entry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NmlV15Entry {
+  #[serde(rename = "@MODIFIED_DATE")]
+  pub modified_date: Option<String>,
+
+  #[serde(rename = "@MODIFIED_TIME")]
+  pub modified_time: Option<String>,
+
+  #[serde(rename = "@AUDIO_ID")]
+  pub audio_id: Option<String>,
+
+  #[serde(rename = "@TITLE")]
+  pub title: Option<String>,
+
+  #[serde(rename = "@ARTIST")]
+  pub artist: Option<String>,
+
+  #[serde(rename = "LOCATION")]
+  pub location: TraktorLocation,
+
+  #[serde(rename = "ALBUM")]
+  pub album: Option<TraktorAlbum>,
+
+  #[serde(rename = "MODIFICATION_INFO")]
+  pub modification_info: Option<TraktorModificationInfo>,
+
+  #[serde(rename = "INFO")]
+  pub info: Option<TraktorInfo>,
+
+  #[serde(rename = "TEMPO")]
+  pub tempo: Option<TraktorTempo>,
+
+  #[serde(rename = "MUSICAL_KEY")]
+  pub musical_key: Option<TraktorMusicalKey>,
+
+  // AIDEV-NOTE: Legacy Traktor 2.x names cue points `CUE`, not `CUE_V2`, but
+  // the attribute shape is unchanged - reuse `TraktorCue` rather than
+  // duplicating it.
+  #[serde(rename = "CUE", default)]
+  pub cue: Vec<TraktorCue>,
+
+  #[serde(rename = "PRIMARYKEY")]
+  pub primarykey: Option<TraktorPrimaryKey>,
+
+  #[serde(flatten)]
+  pub extra_attrs: std::collections::BTreeMap<String, String>,
+}
+
+impl From<NmlV15> for TraktorNML {
+  fn from(legacy: NmlV15) -> Self {
+    TraktorNML {
+      nml: NML {
+        version: legacy.nml.version,
+        head: legacy.nml.head,
+        collection: TraktorCollection {
+          entries: legacy.nml.collection.entries,
+          entry: legacy.nml.collection.entry.into_iter().map(TraktorEntry::from).collect(),
+        },
+        playlists: legacy.nml.playlists,
+        indexing: legacy.nml.indexing,
+      },
+    }
+  }
+}
+
+impl From<NmlV15Entry> for TraktorEntry {
+  fn from(legacy: NmlV15Entry) -> Self {
+    TraktorEntry {
+      modified_date: legacy.modified_date,
+      modified_time: legacy.modified_time,
+      audio_id: legacy.audio_id,
+      title: legacy.title,
+      artist: legacy.artist,
+      location: legacy.location,
+      album: legacy.album,
+      modification_info: legacy.modification_info,
+      info: legacy.info,
+      tempo: legacy.tempo,
+      loudness: None,
+      musical_key: legacy.musical_key,
+      cue_v2: legacy.cue,
+      primarykey: legacy.primarykey,
+      extra_attrs: legacy.extra_attrs,
+    }
+  }
+}
+
 // ============================================================================
 // INDEXING (Sorting Information)
 // ============================================================================
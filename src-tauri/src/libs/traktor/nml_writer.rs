@@ -0,0 +1,300 @@
+// AIDEV-NOTE: Traktor NML Writer
+// Serializes Rust structs back into Traktor's collection.nml XML format
+//
+// Inverse of `TraktorNMLParser`: uses quick-xml with serde for efficient XML
+// serialization. Callers assemble a `TraktorNML` tree (see `mapper`,
+// `cue_mapper`, `playlist_sync` for the Harmony -> Traktor conversions) and
+// hand it to this writer, which only owns the XML framing.
+
+use log::info;
+use std::fs;
+use std::path::Path;
+
+use crate::libs::{HarmonyError, Result};
+
+use super::nml_types::{TraktorNML, TraktorNode};
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\" ?>\n";
+
+/// Writer for Traktor NML (collection.nml) files
+///
+/// Usage:
+/// ```rust
+/// let writer = TraktorNMLWriter::new();
+/// writer.write(&nml, "/path/to/collection.nml")?;
+/// ```
+pub struct TraktorNMLWriter;
+
+impl TraktorNMLWriter {
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Serialize an NML structure to an XML string.
+  ///
+  /// Traktor refuses to load a `collection.nml` whose `@ENTRIES` counts don't
+  /// match their element lists, so this re-derives `COLLECTION/@ENTRIES` and
+  /// every `PLAYLIST/@ENTRIES` from the actual `entry.len()` before
+  /// serializing rather than trusting whatever the caller left in those
+  /// fields (easy to get stale after pushing/removing entries in place).
+  ///
+  /// # Errors
+  /// Returns error if the structure cannot be serialized
+  pub fn write_xml(&self, nml: &TraktorNML) -> Result<String> {
+    let mut nml = nml.clone();
+    nml.nml.collection.entries = nml.nml.collection.entry.len().to_string();
+    if let Some(playlists) = nml.nml.playlists.as_mut() {
+      fix_node_entries_counts(&mut playlists.node);
+    }
+
+    let body = quick_xml::se::to_string(&nml)
+      .map_err(|e| HarmonyError::Xml(format!("Failed to serialize NML XML: {}", e)))?;
+
+    Ok(format!("{}{}", XML_DECLARATION, body))
+  }
+
+  /// Serialize an NML structure and write it to disk.
+  ///
+  /// # Arguments
+  /// * `nml` - NML structure to serialize
+  /// * `file_path` - Absolute path to write the collection.nml file
+  ///
+  /// # Errors
+  /// Returns error if the structure cannot be serialized or the file cannot be written
+  pub fn write<P: AsRef<Path>>(&self, nml: &TraktorNML, file_path: P) -> Result<()> {
+    let path = file_path.as_ref();
+    let xml = self.write_xml(nml)?;
+
+    info!("Writing Traktor NML file: {:?} ({} bytes)", path, xml.len());
+    fs::write(path, xml)?;
+
+    Ok(())
+  }
+}
+
+impl Default for TraktorNMLWriter {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Recompute `@ENTRIES` on this node's `PLAYLIST` (if any) and recurse into
+/// `SUBNODES`, so a nested folder structure gets every playlist's count
+/// fixed up, not just the top-level one.
+fn fix_node_entries_counts(node: &mut TraktorNode) {
+  if let Some(playlist) = node.playlist.as_mut() {
+    playlist.entries = playlist.entry.len().to_string();
+  }
+  if let Some(subnodes) = node.subnodes.as_mut() {
+    subnodes.count = Some(subnodes.nodes.len().to_string());
+    for child in subnodes.nodes.iter_mut() {
+      fix_node_entries_counts(child);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::libs::traktor::nml_parser::TraktorNMLParser;
+  use crate::libs::traktor::nml_types::{
+    TraktorCollection, TraktorEntry, TraktorHead, TraktorLocation, TraktorNode, TraktorPlaylistData,
+    TraktorPlaylistEntry, TraktorPlaylists, TraktorPrimaryKey, TraktorSubnodes, NML,
+  };
+
+  fn minimal_entry() -> TraktorEntry {
+    TraktorEntry {
+      modified_date: Some("2026/1/15".to_string()),
+      modified_time: Some("3600".to_string()),
+      audio_id: Some("test123".to_string()),
+      title: Some("Test Track".to_string()),
+      artist: Some("Test Artist".to_string()),
+      location: TraktorLocation {
+        dir: "/:Music/:".to_string(),
+        file: "test.mp3".to_string(),
+        volume: Some("C:".to_string()),
+        volumeid: None,
+      },
+      album: None,
+      modification_info: None,
+      info: None,
+      tempo: None,
+      loudness: None,
+      musical_key: None,
+      cue_v2: Vec::new(),
+      primarykey: None,
+      extra_attrs: Default::default(),
+    }
+  }
+
+  #[test]
+  fn test_write_xml_starts_with_declaration() {
+    let nml = TraktorNML {
+      nml: NML {
+        version: "19".to_string(),
+        head: TraktorHead {
+          company: "www.native-instruments.com".to_string(),
+          program: "Traktor".to_string(),
+        },
+        collection: TraktorCollection {
+          entries: "1".to_string(),
+          entry: vec![minimal_entry()],
+        },
+        playlists: None,
+        indexing: None,
+      },
+    };
+
+    let writer = TraktorNMLWriter::new();
+    let xml = writer.write_xml(&nml).unwrap();
+
+    assert!(xml.starts_with(XML_DECLARATION));
+    assert!(xml.contains("test.mp3"));
+  }
+
+  #[test]
+  fn test_write_then_parse_round_trip() {
+    let nml = TraktorNML {
+      nml: NML {
+        version: "19".to_string(),
+        head: TraktorHead {
+          company: "www.native-instruments.com".to_string(),
+          program: "Traktor".to_string(),
+        },
+        collection: TraktorCollection {
+          entries: "1".to_string(),
+          entry: vec![minimal_entry()],
+        },
+        playlists: None,
+        indexing: None,
+      },
+    };
+
+    let writer = TraktorNMLWriter::new();
+    let xml = writer.write_xml(&nml).unwrap();
+
+    let parser = TraktorNMLParser::new();
+    let reparsed = parser.parse_xml(&xml).unwrap();
+
+    assert_eq!(reparsed.nml.collection.entry.len(), 1);
+    let entry = &reparsed.nml.collection.entry[0];
+    assert_eq!(entry.title, Some("Test Track".to_string()));
+    assert_eq!(entry.location.file, "test.mp3");
+    assert_eq!(reparsed.nml.collection.entries, "1");
+  }
+
+  #[test]
+  fn test_write_xml_recomputes_stale_collection_entries_count() {
+    let nml = TraktorNML {
+      nml: NML {
+        version: "19".to_string(),
+        head: TraktorHead {
+          company: "www.native-instruments.com".to_string(),
+          program: "Traktor".to_string(),
+        },
+        collection: TraktorCollection {
+          // Deliberately wrong - must be overwritten with the real count (2).
+          entries: "999".to_string(),
+          entry: vec![minimal_entry(), minimal_entry()],
+        },
+        playlists: None,
+        indexing: None,
+      },
+    };
+
+    let writer = TraktorNMLWriter::new();
+    let xml = writer.write_xml(&nml).unwrap();
+
+    let parser = TraktorNMLParser::new();
+    let reparsed = parser.parse_xml(&xml).unwrap();
+
+    assert_eq!(reparsed.nml.collection.entries, "2");
+    assert_eq!(reparsed.nml.collection.entry.len(), 2);
+  }
+
+  #[test]
+  fn test_write_xml_recomputes_stale_playlist_entries_count() {
+    let nml = TraktorNML {
+      nml: NML {
+        version: "19".to_string(),
+        head: TraktorHead {
+          company: "www.native-instruments.com".to_string(),
+          program: "Traktor".to_string(),
+        },
+        collection: TraktorCollection {
+          entries: "1".to_string(),
+          entry: vec![minimal_entry()],
+        },
+        playlists: Some(TraktorPlaylists {
+          node: TraktorNode {
+            node_type: "FOLDER".to_string(),
+            name: "$ROOT".to_string(),
+            subnodes: Some(TraktorSubnodes {
+              count: Some("1".to_string()),
+              nodes: vec![TraktorNode {
+                node_type: "PLAYLIST".to_string(),
+                name: "My Playlist".to_string(),
+                subnodes: None,
+                playlist: Some(TraktorPlaylistData {
+                  // Deliberately wrong - must be overwritten with the real count (1).
+                  entries: "0".to_string(),
+                  playlist_type: "LIST".to_string(),
+                  uuid: "abc123".to_string(),
+                  entry: vec![TraktorPlaylistEntry {
+                    primarykey: TraktorPrimaryKey {
+                      key_type: Some("TRACK".to_string()),
+                      key: Some("/:Music/:test.mp3".to_string()),
+                    },
+                  }],
+                }),
+              }],
+            }),
+            playlist: None,
+          },
+        }),
+        indexing: None,
+      },
+    };
+
+    let writer = TraktorNMLWriter::new();
+    let xml = writer.write_xml(&nml).unwrap();
+
+    let parser = TraktorNMLParser::new();
+    let reparsed = parser.parse_xml(&xml).unwrap();
+
+    let playlist_node = &reparsed.nml.playlists.unwrap().node.subnodes.unwrap().nodes[0];
+    assert_eq!(playlist_node.playlist.as_ref().unwrap().entries, "1");
+  }
+
+  #[test]
+  fn test_write_xml_escapes_ampersand_in_attribute_values() {
+    let mut entry = minimal_entry();
+    entry.title = Some("Rock & Roll".to_string());
+
+    let nml = TraktorNML {
+      nml: NML {
+        version: "19".to_string(),
+        head: TraktorHead {
+          company: "www.native-instruments.com".to_string(),
+          program: "Traktor".to_string(),
+        },
+        collection: TraktorCollection {
+          entries: "1".to_string(),
+          entry: vec![entry],
+        },
+        playlists: None,
+        indexing: None,
+      },
+    };
+
+    let writer = TraktorNMLWriter::new();
+    let xml = writer.write_xml(&nml).unwrap();
+
+    assert!(xml.contains("Rock &amp; Roll"));
+    assert!(!xml.contains("Rock & Roll"));
+
+    let parser = TraktorNMLParser::new();
+    let reparsed = parser.parse_xml(&xml).unwrap();
+    assert_eq!(reparsed.nml.collection.entry[0].title, Some("Rock & Roll".to_string()));
+  }
+}
@@ -13,37 +13,23 @@
 // Reference: src/main/lib/traktor/mappers/playlist-mapper.ts
 
 use log::{debug, warn};
-use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
 use crate::libs::playlist::Playlist;
-use crate::libs::traktor::mapper::map_traktor_path_to_system;
-use crate::libs::traktor::nml_types::TraktorNode;
-
-/// Harmony playlist with track paths for import/export
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ImportedPlaylist {
-  pub id: String,
-  pub name: String,
-  /// Track file paths (system format)
-  pub track_paths: Vec<String>,
-  /// Folder path in tree (e.g., "/$ROOT/My Folder")
-  pub folder_path: Option<String>,
-}
-
-/// Folder/playlist tree node for representing hierarchy
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct FolderTreeNode {
-  pub name: String,
-  pub is_folder: bool,
-  /// Only for playlists
-  pub playlist: Option<ImportedPlaylist>,
-  /// Child nodes (folders or playlists)
-  pub children: Vec<FolderTreeNode>,
-}
+use crate::libs::playlist_tree::{flatten_playlist_tree, FolderTreeNode, ImportedPlaylist};
+use crate::libs::traktor::mapper::{map_system_path_to_traktor, map_traktor_path_to_system};
+use crate::libs::traktor::nml_types::{
+  TraktorNode, TraktorPlaylistData, TraktorPlaylistEntry, TraktorPrimaryKey, TraktorSubnodes,
+};
+
+// AIDEV-NOTE: `FolderTreeNode`/`ImportedPlaylist` and `flatten_playlist_tree`
+// live in `libs::playlist_tree` now, shared with `libs::rekordbox` and
+// `libs::serato`. Only `convert_to_harmony_playlist` and
+// `insert_playlist_into_tree` are re-exported here, so existing
+// `playlist_sync::{...}` imports of them (see `commands::traktor`) don't
+// need to change.
+pub use crate::libs::playlist_tree::{convert_to_harmony_playlist, insert_playlist_into_tree};
 
 /// Convert Traktor PRIMARYKEY path to system path.
 ///
@@ -195,46 +181,6 @@ pub fn map_traktor_node_to_folder_tree(node: &TraktorNode) -> FolderTreeNode {
   }
 }
 
-/// Flatten a folder tree to a list of playlists with folder paths.
-///
-/// AIDEV-NOTE: Recursively walks tree and collects all playlists
-/// - Sets folderPath for each playlist based on parent hierarchy
-/// - Example: "/$ROOT/House Music/Deep House"
-///
-/// # Arguments
-/// * `tree` - Root folder tree node
-/// * `parent_path` - Parent folder path (for recursion)
-///
-/// # Returns
-/// Flat list of playlists with folderPath set
-pub fn flatten_playlist_tree(
-  tree: &FolderTreeNode,
-  parent_path: Option<&str>,
-) -> Vec<ImportedPlaylist> {
-  let current_path = if let Some(parent) = parent_path {
-    format!("{}/{}", parent, tree.name)
-  } else {
-    format!("/{}", tree.name)
-  };
-
-  let mut playlists: Vec<ImportedPlaylist> = Vec::new();
-
-  if !tree.is_folder {
-    // It's a playlist node
-    if let Some(mut playlist) = tree.playlist.clone() {
-      playlist.folder_path = parent_path.map(|s| s.to_string()).or(Some("/".to_string()));
-      playlists.push(playlist);
-    }
-  }
-
-  // Process children recursively
-  for child in &tree.children {
-    playlists.extend(flatten_playlist_tree(child, Some(&current_path)));
-  }
-
-  playlists
-}
-
 /// Extract all playlists from Traktor NML PLAYLISTS structure.
 ///
 /// AIDEV-NOTE: Main entry point for playlist extraction
@@ -260,24 +206,128 @@ pub fn extract_playlists_from_traktor(root_node: &TraktorNode) -> Vec<ImportedPl
   playlists
 }
 
-/// Convert ImportedPlaylist to Harmony Playlist (for database storage).
+/// Convert a system path to a Traktor PRIMARYKEY.KEY value.
+///
+/// AIDEV-NOTE: Inverse of `map_traktor_playlist_key_to_path`.
+///
+/// # Arguments
+/// * `system_path` - OS-native track path
+///
+/// # Returns
+/// Traktor PRIMARYKEY.KEY value (e.g. "C:/:Users/:josev/:Music/:track.mp3")
+pub fn map_path_to_traktor_playlist_key(system_path: &str) -> String {
+  let (dir, file, volume) = map_system_path_to_traktor(system_path);
+  format!("{}{}{}", volume, dir, file)
+}
+
+/// Build the Traktor PLAYLISTS node tree from Harmony playlists, for
+/// [`export_traktor_nml`](crate::commands::traktor::export_traktor_nml).
 ///
-/// AIDEV-NOTE: Final conversion step before saving to database
-/// - Harmony Playlist requires actual Track objects, not paths
-/// - This function creates the playlist metadata only
-/// - Track associations are created separately via PlaylistTrack entries
+/// AIDEV-NOTE: Inverse of `extract_playlists_from_traktor` /
+/// `flatten_playlist_tree`. Harmony only records a playlist's folder as a
+/// flat path string (`Playlist::folder_id`, set from `folder_path` in
+/// `convert_to_harmony_playlist`), so this rebuilds the nested FOLDER/PLAYLIST
+/// tree by walking each path's segments, creating folder nodes on demand and
+/// reusing them for playlists that share a path prefix.
 ///
 /// # Arguments
-/// * `imported` - ImportedPlaylist with track paths
+/// * `playlists` - Harmony playlists paired with their tracks' Traktor-format PRIMARYKEY.KEY values, in playlist order
 ///
 /// # Returns
-/// Harmony Playlist (without tracks populated)
-pub fn convert_to_harmony_playlist(imported: &ImportedPlaylist) -> Playlist {
-  Playlist {
-    id: imported.id.clone(),
-    name: imported.name.clone(),
-    folder_id: imported.folder_path.clone(),
-    tracks: vec![], // Tracks are linked via PlaylistTrack table
+/// Root `$ROOT` FOLDER node, ready for `TraktorPlaylists.node`
+pub fn build_traktor_playlists_node(playlists: &[(Playlist, Vec<String>)]) -> TraktorNode {
+  let mut root = TraktorNode {
+    node_type: "FOLDER".to_string(),
+    name: "$ROOT".to_string(),
+    subnodes: Some(TraktorSubnodes {
+      count: Some("0".to_string()),
+      nodes: vec![],
+    }),
+    playlist: None,
+  };
+
+  for (playlist, track_keys) in playlists {
+    let folder_path = playlist.folder_id.as_deref().unwrap_or("/$ROOT");
+    let segments = folder_path
+      .split('/')
+      .filter(|s| !s.is_empty() && *s != "$ROOT");
+
+    let mut current = &mut root;
+    for segment in segments {
+      let subnodes = current
+        .subnodes
+        .get_or_insert_with(|| TraktorSubnodes { count: None, nodes: vec![] });
+
+      let idx = match subnodes
+        .nodes
+        .iter()
+        .position(|node| node.node_type == "FOLDER" && node.name == segment)
+      {
+        Some(idx) => idx,
+        None => {
+          subnodes.nodes.push(TraktorNode {
+            node_type: "FOLDER".to_string(),
+            name: segment.to_string(),
+            subnodes: Some(TraktorSubnodes { count: None, nodes: vec![] }),
+            playlist: None,
+          });
+          subnodes.nodes.len() - 1
+        }
+      };
+      current = &mut subnodes.nodes[idx];
+    }
+
+    let subnodes = current
+      .subnodes
+      .get_or_insert_with(|| TraktorSubnodes { count: None, nodes: vec![] });
+    subnodes
+      .nodes
+      .push(convert_harmony_playlist_to_traktor_node(playlist, track_keys));
+  }
+
+  fix_subnode_counts(&mut root);
+  root
+}
+
+/// Convert a single Harmony playlist (with its tracks already resolved to
+/// Traktor-format PRIMARYKEY.KEY values) into a standalone `TYPE="PLAYLIST"`
+/// `TraktorNode` - the leaf unit [`build_traktor_playlists_node`] positions
+/// within the folder tree it rebuilds from each playlist's `folder_id`.
+pub fn convert_harmony_playlist_to_traktor_node(
+  playlist: &Playlist,
+  track_keys: &[String],
+) -> TraktorNode {
+  let entries: Vec<TraktorPlaylistEntry> = track_keys
+    .iter()
+    .map(|key| TraktorPlaylistEntry {
+      primarykey: TraktorPrimaryKey {
+        key_type: Some("TRACK".to_string()),
+        key: Some(key.clone()),
+      },
+    })
+    .collect();
+
+  TraktorNode {
+    node_type: "PLAYLIST".to_string(),
+    name: playlist.name.clone(),
+    subnodes: None,
+    playlist: Some(TraktorPlaylistData {
+      entries: entries.len().to_string(),
+      playlist_type: "LIST".to_string(),
+      uuid: playlist.id.clone(),
+      entry: entries,
+    }),
+  }
+}
+
+/// Recompute `SUBNODES.COUNT` attributes bottom-up after building a tree
+/// incrementally in [`build_traktor_playlists_node`].
+fn fix_subnode_counts(node: &mut TraktorNode) {
+  if let Some(subnodes) = &mut node.subnodes {
+    subnodes.count = Some(subnodes.nodes.len().to_string());
+    for child in &mut subnodes.nodes {
+      fix_subnode_counts(child);
+    }
   }
 }
 
@@ -523,4 +573,68 @@ mod tests {
     assert_eq!(harmony.folder_id, Some("/$ROOT/House".to_string()));
     assert_eq!(harmony.tracks.len(), 0); // Tracks linked separately
   }
+
+  #[test]
+  fn test_map_path_to_traktor_playlist_key_round_trips() {
+    let key = "C:/:Users/:josev/:Music/:track.mp3";
+    let path = map_traktor_playlist_key_to_path(key);
+    let back = map_path_to_traktor_playlist_key(&path);
+
+    // Re-converting should land back on the same Traktor-format key.
+    assert_eq!(map_traktor_playlist_key_to_path(&back), path);
+  }
+
+  #[test]
+  fn test_build_traktor_playlists_node_nests_by_folder_path() {
+    let playlists = vec![
+      (
+        Playlist {
+          id: "uuid-a".to_string(),
+          name: "Deep House".to_string(),
+          folder_id: Some("/$ROOT/House".to_string()),
+          tracks: vec![],
+        },
+        vec!["/:music/:track1.mp3".to_string()],
+      ),
+      (
+        Playlist {
+          id: "uuid-b".to_string(),
+          name: "Techno".to_string(),
+          folder_id: Some("/$ROOT".to_string()),
+          tracks: vec![],
+        },
+        vec![],
+      ),
+    ];
+
+    let root = build_traktor_playlists_node(&playlists);
+
+    assert_eq!(root.node_type, "FOLDER");
+    assert_eq!(root.name, "$ROOT");
+
+    let subnodes = root.subnodes.as_ref().unwrap();
+    assert_eq!(subnodes.nodes.len(), 2);
+
+    let house = subnodes
+      .nodes
+      .iter()
+      .find(|n| n.name == "House")
+      .expect("House folder should exist");
+    assert_eq!(house.node_type, "FOLDER");
+    let house_children = &house.subnodes.as_ref().unwrap().nodes;
+    assert_eq!(house_children.len(), 1);
+    assert_eq!(house_children[0].name, "Deep House");
+    assert_eq!(
+      house_children[0].playlist.as_ref().unwrap().uuid,
+      "uuid-a"
+    );
+
+    let techno = subnodes
+      .nodes
+      .iter()
+      .find(|n| n.name == "Techno")
+      .expect("Techno playlist should exist at root");
+    assert_eq!(techno.node_type, "PLAYLIST");
+    assert_eq!(techno.playlist.as_ref().unwrap().entry.len(), 0);
+  }
 }
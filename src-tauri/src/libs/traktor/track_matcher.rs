@@ -0,0 +1,294 @@
+// AIDEV-NOTE: Fuzzy Harmony<->Traktor track-identity matcher (Phase 4.5)
+//
+// `merge_track`/`merge_track_3way` assume the caller already knows which
+// Harmony Track corresponds to which Traktor Track. That's true when syncing
+// by path, but Traktor exports often drift from Harmony's library after
+// files are moved or renamed. This module scores candidate pairs on title,
+// artist, duration and a couple of exact-match bonuses, then resolves a
+// one-to-one assignment greedily by descending score.
+
+use crate::libs::track::Track;
+
+/// Weight given to each signal in the composite match score. Title and
+/// artist dominate since they're the only signals that survive a file move;
+/// duration and the exact-match bonuses just disambiguate between
+/// same-named tracks.
+const TITLE_WEIGHT: f64 = 0.4;
+const ARTIST_WEIGHT: f64 = 0.35;
+const DURATION_WEIGHT: f64 = 0.15;
+const BITRATE_WEIGHT: f64 = 0.05;
+const YEAR_WEIGHT: f64 = 0.05;
+
+/// A pair whose durations differ by at most this many milliseconds gets
+/// `DURATION_WEIGHT` added to its score; a larger difference only forgoes
+/// that weight and does not exclude the pair.
+const DURATION_TOLERANCE_MS: i64 = 2000;
+
+/// Minimum composite score for a pair to be accepted as a match.
+pub const DEFAULT_MATCH_THRESHOLD: f64 = 0.85;
+
+/// A proposed pairing between a Harmony track and a Traktor track.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackMatch {
+  /// Index into the `harmony` slice passed to `match_tracks`.
+  pub harmony_index: usize,
+  /// Index into the `traktor` slice passed to `match_tracks`.
+  pub traktor_index: usize,
+  /// Composite similarity score in 0.0..=1.0.
+  pub score: f64,
+  /// Names of the signals that agreed closely enough to contribute their
+  /// full weight to `score` - lets a caller explain *why* a pair matched.
+  pub agreed_fields: Vec<&'static str>,
+}
+
+/// Lowercase `s`, strip punctuation and bracketed "feat."/remaster-style
+/// tags, and collapse whitespace - the normalization both title and artist
+/// go through before being compared.
+fn normalize(s: &str) -> String {
+  let mut out = String::with_capacity(s.len());
+  let mut in_brackets = false;
+
+  for ch in s.chars() {
+    match ch {
+      '(' | '[' => in_brackets = true,
+      ')' | ']' => in_brackets = false,
+      c if in_brackets => {
+        let _ = c;
+      }
+      c if c.is_alphanumeric() => out.push(c.to_ascii_lowercase()),
+      c if c.is_whitespace() => out.push(' '),
+      _ => {}
+    }
+  }
+
+  out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Levenshtein edit distance between two strings, in characters.
+fn levenshtein(a: &str, b: &str) -> usize {
+  let a: Vec<char> = a.chars().collect();
+  let b: Vec<char> = b.chars().collect();
+
+  if a.is_empty() {
+    return b.len();
+  }
+  if b.is_empty() {
+    return a.len();
+  }
+
+  let mut prev: Vec<usize> = (0..=b.len()).collect();
+  let mut curr = vec![0usize; b.len() + 1];
+
+  for (i, &ca) in a.iter().enumerate() {
+    curr[0] = i + 1;
+    for (j, &cb) in b.iter().enumerate() {
+      let cost = if ca == cb { 0 } else { 1 };
+      curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+    }
+    std::mem::swap(&mut prev, &mut curr);
+  }
+
+  prev[b.len()]
+}
+
+/// Normalized similarity ratio of two strings: `1.0 - edit_distance / max_len`,
+/// in 0.0..=1.0. Two empty strings are considered a perfect (1.0) match -
+/// there's no signal either way, so this shouldn't veto a pair on its own.
+fn similarity_ratio(a: &str, b: &str) -> f64 {
+  let norm_a = normalize(a);
+  let norm_b = normalize(b);
+  let max_len = norm_a.chars().count().max(norm_b.chars().count());
+
+  if max_len == 0 {
+    return 1.0;
+  }
+
+  1.0 - (levenshtein(&norm_a, &norm_b) as f64 / max_len as f64)
+}
+
+/// Score one candidate (harmony, traktor) pair. Returns the composite score
+/// and the list of fields that agreed strongly enough to count as a match
+/// signal (title/artist ratio >= 0.8, duration within tolerance, exact
+/// bitrate/year equality).
+fn score_pair(harmony: &Track, traktor: &Track) -> (f64, Vec<&'static str>) {
+  let mut score = 0.0;
+  let mut agreed = Vec::new();
+
+  let title_ratio = similarity_ratio(&harmony.title, &traktor.title);
+  score += title_ratio * TITLE_WEIGHT;
+  if title_ratio >= 0.8 {
+    agreed.push("title");
+  }
+
+  let artist_ratio = similarity_ratio(
+    harmony.artist.as_deref().unwrap_or(""),
+    traktor.artist.as_deref().unwrap_or(""),
+  );
+  score += artist_ratio * ARTIST_WEIGHT;
+  if artist_ratio >= 0.8 {
+    agreed.push("artist");
+  }
+
+  if (harmony.duration - traktor.duration).abs() <= DURATION_TOLERANCE_MS {
+    score += DURATION_WEIGHT;
+    agreed.push("duration");
+  }
+
+  if harmony.bitrate.is_some() && harmony.bitrate == traktor.bitrate {
+    score += BITRATE_WEIGHT;
+    agreed.push("bitrate");
+  }
+
+  if harmony.year.is_some() && harmony.year == traktor.year {
+    score += YEAR_WEIGHT;
+    agreed.push("year");
+  }
+
+  (score, agreed)
+}
+
+/// Score every (harmony, traktor) pair and greedily resolve a one-to-one
+/// assignment by descending score, accepting only pairs scoring at least
+/// `threshold`. A Harmony or Traktor track is matched at most once; ties are
+/// broken by the order candidates were scored (harmony index, then traktor
+/// index), so the result is deterministic.
+pub fn match_tracks(harmony: &[Track], traktor: &[Track], threshold: f64) -> Vec<TrackMatch> {
+  let mut candidates: Vec<TrackMatch> = Vec::new();
+
+  for (h_idx, h) in harmony.iter().enumerate() {
+    for (t_idx, t) in traktor.iter().enumerate() {
+      let (score, agreed_fields) = score_pair(h, t);
+      if score >= threshold {
+        candidates.push(TrackMatch { harmony_index: h_idx, traktor_index: t_idx, score, agreed_fields });
+      }
+    }
+  }
+
+  candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+  let mut harmony_taken = vec![false; harmony.len()];
+  let mut traktor_taken = vec![false; traktor.len()];
+  let mut matches = Vec::new();
+
+  for candidate in candidates {
+    if harmony_taken[candidate.harmony_index] || traktor_taken[candidate.traktor_index] {
+      continue;
+    }
+    harmony_taken[candidate.harmony_index] = true;
+    traktor_taken[candidate.traktor_index] = true;
+    matches.push(candidate);
+  }
+
+  matches
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn track(id: &str, title: &str, artist: &str, duration: i64) -> Track {
+    Track {
+      id: id.to_string(),
+      path: format!("/music/{}.mp3", id),
+      title: title.to_string(),
+      artist: Some(artist.to_string()),
+      album: None,
+      genre: None,
+      year: None,
+      duration,
+      bitrate: None,
+      comment: None,
+      bpm: None,
+      initial_key: None,
+      rating: None,
+      label: None,
+      catalog_number: None,
+      isrc: None,
+      musicbrainz_id: None,
+      release_group_id: None,
+      waveform_peaks: None,
+      added_at: None,
+      url: None,
+      start_ms: None,
+      end_ms: None,
+      chapters: Vec::new(),
+      album_date: None,
+      track_number: None,
+      album_seq: None,
+      artist_sort: None,
+      album_sort: None,
+      title_sort: None,
+      synced_lyrics: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_normalize_strips_brackets_and_punctuation() {
+    assert_eq!(normalize("Song Title (feat. Someone) [Remaster 2020]"), "song title");
+    assert_eq!(normalize("  Multi   Space  "), "multi space");
+  }
+
+  #[test]
+  fn test_match_tracks_exact_pair() {
+    let harmony = vec![track("h1", "Around the World", "Daft Punk", 300_000)];
+    let traktor = vec![track("t1", "Around the World", "Daft Punk", 300_500)];
+
+    let matches = match_tracks(&harmony, &traktor, DEFAULT_MATCH_THRESHOLD);
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].harmony_index, 0);
+    assert_eq!(matches[0].traktor_index, 0);
+    assert!(matches[0].agreed_fields.contains(&"title"));
+    assert!(matches[0].agreed_fields.contains(&"artist"));
+    assert!(matches[0].agreed_fields.contains(&"duration"));
+  }
+
+  #[test]
+  fn test_match_tracks_survives_path_drift_and_tag_variants() {
+    let harmony = vec![track("h1", "One More Time", "Daft Punk", 320_000)];
+    let traktor = vec![track("t1", "One More Time (Remaster)", "Daft Punk", 320_800)];
+
+    let matches = match_tracks(&harmony, &traktor, DEFAULT_MATCH_THRESHOLD);
+
+    assert_eq!(matches.len(), 1);
+  }
+
+  #[test]
+  fn test_match_tracks_rejects_unrelated_pair() {
+    let harmony = vec![track("h1", "Around the World", "Daft Punk", 300_000)];
+    let traktor = vec![track("t1", "Thunderstruck", "AC/DC", 292_000)];
+
+    let matches = match_tracks(&harmony, &traktor, DEFAULT_MATCH_THRESHOLD);
+
+    assert!(matches.is_empty());
+  }
+
+  #[test]
+  fn test_match_tracks_never_matches_traktor_track_twice() {
+    let harmony = vec![
+      track("h1", "Song A", "Artist", 200_000),
+      track("h2", "Song A", "Artist", 200_100),
+    ];
+    let traktor = vec![track("t1", "Song A", "Artist", 200_050)];
+
+    let matches = match_tracks(&harmony, &traktor, DEFAULT_MATCH_THRESHOLD);
+
+    assert_eq!(matches.len(), 1);
+    let traktor_indices: Vec<usize> = matches.iter().map(|m| m.traktor_index).collect();
+    assert_eq!(traktor_indices, vec![0]);
+  }
+
+  #[test]
+  fn test_match_tracks_greedy_assignment_prefers_best_score() {
+    let harmony = vec![
+      track("h1", "Strobe", "Deadmau5", 600_000),
+      track("h2", "Strobe (Radio Edit)", "Deadmau5", 210_000),
+    ];
+    let traktor = vec![track("t1", "Strobe", "Deadmau5", 600_200)];
+
+    let matches = match_tracks(&harmony, &traktor, DEFAULT_MATCH_THRESHOLD);
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].harmony_index, 0);
+  }
+}